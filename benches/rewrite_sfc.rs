@@ -0,0 +1,24 @@
+//! Conversion time of generated components of increasing size, `cargo bench` to run
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use vue_options_to_composition::{rewrite_sfc, RewriteOptions};
+
+#[path = "../tests/common/mod.rs"]
+mod common;
+
+fn rewrite_sfc_benchmark(c: &mut Criterion) {
+  let mut group = c.benchmark_group("rewrite_sfc");
+  group.sample_size(10);
+
+  for (name, members) in [("small", 2), ("medium", 20), ("huge", 120)] {
+    let sfc = common::component(members);
+    group.throughput(Throughput::Bytes(sfc.len() as u64));
+    group.bench_with_input(BenchmarkId::from_parameter(name), &sfc, |b, sfc| {
+      b.iter(|| rewrite_sfc(sfc, Some(RewriteOptions::default())).unwrap())
+    });
+  }
+
+  group.finish();
+}
+
+criterion_group!(benches, rewrite_sfc_benchmark);
+criterion_main!(benches);
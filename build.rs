@@ -0,0 +1,5 @@
+fn main() {
+  // Node.js addons resolve the N-API symbols when they are loaded
+  #[cfg(feature = "napi")]
+  napi_build::setup();
+}
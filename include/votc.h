@@ -0,0 +1,36 @@
+/*
+ * C API of vue-options-to-composition, built with the `ffi` feature:
+ *
+ *   cargo rustc --release --lib --crate-type cdylib --features ffi
+ *
+ * Strings are NUL-terminated UTF-8. Every string returned through an out parameter must be
+ * released with votc_free_string.
+ */
+#ifndef VOTC_H
+#define VOTC_H
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* The SFC was converted, `out` holds the result */
+#define VOTC_OK 0
+/* The SFC or the options are invalid, `err` holds the reason */
+#define VOTC_ERROR 1
+/* A pointer argument is NULL or a string is not UTF-8, nothing is written to `out` or `err` */
+#define VOTC_INVALID_ARGUMENT 2
+
+/*
+ * Convert an SFC. `options` is the content of a TOML configuration file, or NULL for the
+ * defaults. On success the converted SFC is written to `out`, on failure the message to `err`.
+ */
+int votc_rewrite_sfc(const char *sfc, const char *options, char **out, char **err);
+
+/* Release a string returned by this library. NULL is ignored. */
+void votc_free_string(char *string);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* VOTC_H */
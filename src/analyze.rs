@@ -0,0 +1,293 @@
+//! Dry-run inventory of the Options API features a component uses.
+//!
+//! Nothing is rewritten: the component is parsed and converted in memory to find out which
+//! features it relies on, which config entries it still needs (mixins without a composable,
+//! plugin injections no transformer knows) and how much manual work the conversion leaves. Teams
+//! use it to scope a migration before running the conversion.
+
+use crate::{parse_component, rewrite_sfc_with_report, DiagnosticSeverity, RewriteOptions};
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::Serialize;
+use tree_sitter::Node;
+
+lazy_static! {
+  static ref CLASS_COMPONENT_PATTERN: Regex =
+    Regex::new(r"@Component\b|\bclass\s+\w+\s+extends\s+(?:Vue|Mixins\s*\()").unwrap();
+  static ref UNRESOLVED_PATTERN: Regex = Regex::new(r"/\* FIXME: ([\w$]+) \*/").unwrap();
+}
+
+/// Instance properties each reported as a feature of their own
+const INSTANCE_FEATURES: [(&str, &str); 8] = [
+  ("$store", "vuex"),
+  ("$refs", "refs"),
+  ("$route", "router"),
+  ("$router", "router"),
+  ("$t(", "i18n"),
+  ("$axios", "axios"),
+  ("$nuxt", "nuxt"),
+  ("$on(", "event-bus"),
+];
+
+/// Options reported as a feature of their own
+const OPTION_FEATURES: [&str; 9] = [
+  "mixins",
+  "extends",
+  "filters",
+  "provide",
+  "inject",
+  "asyncData",
+  "fetch",
+  "head",
+  "render",
+];
+
+/// Estimated effort to finish a conversion
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Difficulty {
+  /// Converts without follow-up
+  Easy,
+  /// Converts, but leaves a few findings to check
+  Medium,
+  /// Needs manual work or config entries first
+  Hard,
+}
+
+/// Inventory of a single component
+#[derive(Debug, Clone, Serialize)]
+pub struct ComponentInventory {
+  /// Options API features the component uses (e.g. "vuex", "filters", "asyncData")
+  pub features: Vec<String>,
+  /// Mixins listed in the `mixins` option
+  pub mixins: Vec<String>,
+  /// Mixins without an entry in the `mixins` config, by config key
+  pub unconfigured_mixins: Vec<String>,
+  /// Keys of the `inject` option
+  pub injections: Vec<String>,
+  /// Instance properties (e.g. `this.$auth`) no transformer or config entry resolves, usually
+  /// plugin injections
+  pub unknown_injections: Vec<String>,
+  /// Identifiers the conversion leaves with a `FIXME` comment
+  pub unresolved: usize,
+  /// Warnings reported by the conversion
+  pub warnings: usize,
+  pub difficulty: Difficulty,
+}
+
+/// Build the inventory of an SFC, converting it in memory with the given options
+pub fn analyze_component(
+  sfc: &str,
+  options: Option<RewriteOptions>,
+) -> Result<ComponentInventory, Box<dyn std::error::Error>> {
+  let context = parse_component(sfc)?;
+  let configured_mixins: Vec<String> = options
+    .as_ref()
+    .and_then(|options| options.mixins.as_ref())
+    .map(|mixins| mixins.keys().cloned().collect())
+    .unwrap_or_default();
+  let report = rewrite_sfc_with_report(sfc, options)?;
+
+  let script = context.sfc_sections.script_content.as_deref().unwrap_or("");
+  let template = context
+    .sfc_sections
+    .template_content
+    .as_deref()
+    .unwrap_or("");
+  let declared = component_options(script);
+
+  let mut features: Vec<String> = Vec::new();
+  let mut add_feature = |feature: &str| {
+    if !features.iter().any(|existing| existing == feature) {
+      features.push(feature.to_string());
+    }
+  };
+
+  if CLASS_COMPONENT_PATTERN.is_match(script) {
+    add_feature("class-component");
+  }
+  for (pattern, feature) in INSTANCE_FEATURES {
+    if script.contains(pattern) || template.contains(pattern) {
+      add_feature(feature);
+    }
+  }
+  if ["mapState", "mapGetters", "mapActions", "mapMutations"]
+    .iter()
+    .any(|map_function| script.contains(map_function))
+  {
+    add_feature("vuex");
+  }
+  if !context.script_state.watchers.is_empty() {
+    add_feature("watch");
+  }
+  for option in OPTION_FEATURES {
+    if declared.iter().any(|(key, _)| key == option) {
+      add_feature(option);
+    }
+  }
+  if !context.template_state.filters.is_empty() {
+    add_feature("filters");
+  }
+
+  // Mixins are configured by the last segment of their import path (`@/mixins/price` -> `price`)
+  let mixins = option_names(&declared, "mixins");
+  let unconfigured_mixins: Vec<String> = mixins
+    .iter()
+    .map(|mixin| {
+      context
+        .script_state
+        .imports
+        .iter()
+        .find(|import| {
+          import
+            .imports
+            .iter()
+            .any(|item| item.alias.as_deref().unwrap_or(&item.name) == mixin)
+        })
+        .and_then(|import| import.source.split('/').next_back())
+        .unwrap_or(mixin)
+        .to_string()
+    })
+    .filter(|key| !configured_mixins.contains(key))
+    .collect();
+
+  let mut unknown_injections: Vec<String> = Vec::new();
+  let mut unresolved = 0;
+  for caps in UNRESOLVED_PATTERN.captures_iter(&report.code) {
+    unresolved += 1;
+    if caps[1].starts_with('$') && !unknown_injections.iter().any(|name| name == &caps[1]) {
+      unknown_injections.push(caps[1].to_string());
+    }
+  }
+
+  let warnings = report
+    .diagnostics
+    .iter()
+    .filter(|diagnostic| diagnostic.severity == DiagnosticSeverity::Warning)
+    .count();
+
+  let findings = unresolved + warnings + unconfigured_mixins.len();
+  let difficulty = if features.iter().any(|feature| feature == "class-component")
+    || !unconfigured_mixins.is_empty()
+    || findings > 3
+  {
+    Difficulty::Hard
+  } else if findings > 0 {
+    Difficulty::Medium
+  } else {
+    Difficulty::Easy
+  };
+
+  Ok(ComponentInventory {
+    features,
+    mixins,
+    unconfigured_mixins,
+    injections: option_names(&declared, "inject"),
+    unknown_injections,
+    unresolved,
+    warnings,
+    difficulty,
+  })
+}
+
+/// Top-level options of the `export default { ... }` object with their value node text
+fn component_options(script: &str) -> Vec<(String, String)> {
+  let Some(tree) = crate::javascript::parse(script) else {
+    return Vec::new();
+  };
+
+  let root = tree.root_node();
+  let mut cursor = root.walk();
+  let Some(object) = root
+    .children(&mut cursor)
+    .filter(|node| node.kind() == "export_statement")
+    .find_map(|node| node.child_by_field_name("value"))
+    .filter(|value| value.kind() == "object")
+  else {
+    return Vec::new();
+  };
+
+  let mut options = Vec::new();
+  let mut cursor = object.walk();
+  for member in object.named_children(&mut cursor) {
+    let (key, value) = match member.kind() {
+      "pair" => (
+        member.child_by_field_name("key"),
+        member.child_by_field_name("value"),
+      ),
+      "method_definition" => (member.child_by_field_name("name"), Some(member)),
+      _ => continue,
+    };
+    if let (Some(key), Some(value)) = (key, value) {
+      options.push((
+        node_text(&key, script)
+          .trim_matches(['\'', '"'])
+          .to_string(),
+        node_text(&value, script),
+      ));
+    }
+  }
+
+  options
+}
+
+/// Names listed by an option: array elements (`['a', b]`) or object keys (`{ a: ... }`)
+fn option_names(options: &[(String, String)], option: &str) -> Vec<String> {
+  let Some((_, value)) = options.iter().find(|(key, _)| key == option) else {
+    return Vec::new();
+  };
+
+  let source = format!("({})", value);
+  let Some(tree) = crate::javascript::parse(&source) else {
+    return Vec::new();
+  };
+
+  let mut names = Vec::new();
+  collect_names(tree.root_node(), &source, &mut names);
+  names
+}
+
+/// Collect the names of the first array or object below `node`
+fn collect_names(node: Node, source: &str, names: &mut Vec<String>) -> bool {
+  let mut cursor = node.walk();
+  match node.kind() {
+    "array" => {
+      for element in node.named_children(&mut cursor) {
+        if matches!(element.kind(), "identifier" | "string") {
+          names.push(
+            node_text(&element, source)
+              .trim_matches(['\'', '"'])
+              .to_string(),
+          );
+        }
+      }
+      true
+    }
+    "object" => {
+      for member in node.named_children(&mut cursor) {
+        let key = match member.kind() {
+          "pair" => member.child_by_field_name("key"),
+          "shorthand_property_identifier" => Some(member),
+          _ => None,
+        };
+        if let Some(key) = key {
+          names.push(
+            node_text(&key, source)
+              .trim_matches(['\'', '"'])
+              .to_string(),
+          );
+        }
+      }
+      true
+    }
+    _ => node
+      .named_children(&mut cursor)
+      .collect::<Vec<_>>()
+      .into_iter()
+      .any(|child| collect_names(child, source, names)),
+  }
+}
+
+fn node_text(node: &Node, source: &str) -> String {
+  source[node.start_byte()..node.end_byte()].to_string()
+}
@@ -0,0 +1,106 @@
+//! Cache of previous runs for `--cache <DIR>`, to skip files that did not change.
+//!
+//! A file is skipped when its output still has the content written by the last run and the input
+//! is either the one converted then or, when converting in place, that output. The whole cache is
+//! dropped when the effective options change or the version of the tool does. The options are
+//! those of the configuration (with its environment variables substituted and its import paths
+//! resolved) and of the CLI flags that change the conversion (`--expose-refs`). Per-file
+//! `<!-- votc: ... -->` overrides are part of the input, so editing one converts that file again.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use tokio::fs as async_fs;
+
+const CACHE_FILE: &str = "cache.json";
+
+/// What the last run converted for a file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheEntry {
+  /// Hash of the input that was converted
+  pub input: String,
+  /// Hash of the output at the end of the run
+  pub output: String,
+  /// Emitted events renamed by the conversion, for `--project`
+  pub event_renames: Vec<(String, String)>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Cache {
+  version: String,
+  config: String,
+  /// Entries by canonical input path
+  files: BTreeMap<PathBuf, CacheEntry>,
+  #[serde(skip)]
+  dir: PathBuf,
+}
+
+impl Cache {
+  /// Load the cache of `dir`, empty when it does not exist yet, can not be read or was written by
+  /// another version or with other options
+  pub async fn load(dir: &Path, config: &str) -> Self {
+    let empty = Self {
+      version: env!("CARGO_PKG_VERSION").to_string(),
+      config: hash(config.as_bytes()),
+      files: BTreeMap::new(),
+      dir: dir.to_path_buf(),
+    };
+
+    let Ok(content) = async_fs::read_to_string(dir.join(CACHE_FILE)).await else {
+      return empty;
+    };
+    match serde_json::from_str::<Self>(&content) {
+      Ok(cache) if cache.version == empty.version && cache.config == empty.config => Self {
+        files: cache.files,
+        ..empty
+      },
+      _ => empty,
+    }
+  }
+
+  /// The entry of `input` if neither `content` nor the output at `output_path` changed since
+  pub async fn fresh(
+    &self,
+    input: &Path,
+    content: &str,
+    output_path: &Path,
+  ) -> Option<&CacheEntry> {
+    let entry = self.files.get(input)?;
+    let content_hash = hash(content.as_bytes());
+    if content_hash != entry.input && content_hash != entry.output {
+      return None;
+    }
+    let output = async_fs::read(output_path).await.ok()?;
+    (hash(&output) == entry.output).then_some(entry)
+  }
+
+  /// Write the cache with the files of this run added, files that failed have no entry and are
+  /// converted again next time
+  pub async fn save(&self, files: BTreeMap<PathBuf, CacheEntry>) -> Result<()> {
+    let mut cache = Self {
+      version: self.version.clone(),
+      config: self.config.clone(),
+      files: self.files.clone(),
+      dir: self.dir.clone(),
+    };
+    cache.files.extend(files);
+
+    async_fs::create_dir_all(&self.dir)
+      .await
+      .with_context(|| format!("Failed to create cache directory: {}", self.dir.display()))?;
+    let path = self.dir.join(CACHE_FILE);
+    async_fs::write(&path, serde_json::to_string(&cache)?)
+      .await
+      .with_context(|| format!("Failed to write cache: {}", path.display()))
+  }
+}
+
+/// Hex SHA-256 of some content
+pub fn hash(content: &[u8]) -> String {
+  Sha256::digest(content)
+    .iter()
+    .map(|byte| format!("{:02x}", byte))
+    .collect()
+}
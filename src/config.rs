@@ -0,0 +1,800 @@
+//! The configuration file format (`config.example.toml`) and its conversion to [`RewriteOptions`].
+//!
+//! The command line reads it from TOML, the bindings accept the same structure as a JavaScript or
+//! JSON object.
+
+use crate::{
+  AdditionalImport, AssetMode, CustomOptionConfig, DatePluginConfig, Diagnostic, EmitMappingConfig,
+  EventBusConfig, ExposeConfig, HttpConfig, ImportOrderConfig, ImportRewrite,
+  InstancePropertyStyle, MixinConfig, NuxtErrorConfig, NuxtI18nStyle, RequireComponentStyle,
+  RewriteOptions, ScriptStyle, SelfReferenceStyle, SetLocaleStyle, Target, TemplateRefStyle,
+  VTDirectiveStyle,
+};
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+
+lazy_static! {
+  static ref ENV_VAR_PATTERN: Regex = Regex::new(r"\$\{([A-Za-z_][A-Za-z0-9_]*)\}").unwrap();
+}
+
+/// Parse the content of a TOML configuration file, replacing `${VAR}` in its strings with the
+/// value of the environment variable
+pub fn parse_config(content: &str) -> Result<RewriteOptions, toml::de::Error> {
+  let mut value = toml::from_str::<toml::Value>(content)?;
+  interpolate_env(&mut value)?;
+  value.try_into::<ConfigFile>().map(Into::into)
+}
+
+/// Parse a configuration file read from `config_dir`, resolving its relative import paths
+/// (`./composables/useHttp`) against that directory
+pub fn parse_config_file(
+  content: &str,
+  config_dir: &Path,
+) -> Result<RewriteOptions, toml::de::Error> {
+  let mut options = parse_config(content)?;
+  for import_path in import_paths(&mut options) {
+    if import_path.starts_with("./") || import_path.starts_with("../") {
+      *import_path = normalize(&config_dir.join(&*import_path))
+        .to_string_lossy()
+        .to_string();
+    }
+  }
+  Ok(options)
+}
+
+/// Make the import paths resolved by [`parse_config_file`] relative to the directory of the
+/// converted file (`../composables/useHttp`)
+pub fn relativize_import_paths(options: &mut RewriteOptions, file_dir: &Path) {
+  for import_path in import_paths(options) {
+    if Path::new(import_path.as_str()).is_absolute() {
+      let relative = relative_path(file_dir, Path::new(import_path.as_str()));
+      let relative = relative.to_string_lossy().replace('\\', "/");
+      *import_path = if relative.starts_with("../") {
+        relative
+      } else {
+        format!("./{}", relative)
+      };
+    }
+  }
+}
+
+/// Check the configuration against the import sources of the components it converts: mixins and
+/// `imports_rewrite` sources no component imports, and keeplist entries that are probably typos of
+/// an import
+pub fn check_config(options: &RewriteOptions, import_sources: &[String]) -> Vec<Diagnostic> {
+  let mut diagnostics = Vec::new();
+
+  let mut mixins: Vec<&String> = options.mixins.iter().flat_map(|m| m.keys()).collect();
+  mixins.sort();
+  for mixin in mixins {
+    if !import_sources
+      .iter()
+      .any(|source| source.contains(mixin.as_str()))
+    {
+      diagnostics.push(Diagnostic::warning(
+        "config-unused-mixin",
+        format!(
+          "`[mixins.{}]` matches no mixin imported by the components",
+          mixin
+        ),
+      ));
+    }
+  }
+
+  let mut rewrites: Vec<&String> = options
+    .imports_rewrite
+    .iter()
+    .flat_map(|rewrites| rewrites.keys())
+    .collect();
+  rewrites.sort();
+  for source in rewrites {
+    if !import_sources.contains(source) {
+      diagnostics.push(Diagnostic::warning(
+        "config-unused-import-rewrite",
+        format!(
+          "`[imports_rewrite.{}]` is never imported by the components",
+          source
+        ),
+      ));
+    }
+  }
+
+  // Generated imports (`vue`) are kept as well, only entries close to an import are typos
+  for kept in options.import_keeplist.iter().flatten() {
+    if import_sources.contains(kept) {
+      continue;
+    }
+    let closest = import_sources
+      .iter()
+      .map(|source| (edit_distance(kept, source), source))
+      .filter(|(distance, _)| *distance <= 2)
+      .min();
+    if let Some((_, source)) = closest {
+      diagnostics.push(Diagnostic::warning(
+        "config-keeplist-typo",
+        format!(
+          "`{}` in `import_keeplist` matches no import of the components, did you mean `{}`?",
+          kept, source
+        ),
+      ));
+    }
+  }
+
+  diagnostics
+}
+
+/// Number of single character insertions, deletions and substitutions turning `a` into `b`
+fn edit_distance(a: &str, b: &str) -> usize {
+  let b: Vec<char> = b.chars().collect();
+  let mut previous: Vec<usize> = (0..=b.len()).collect();
+  for (i, a_char) in a.chars().enumerate() {
+    let mut current = vec![i + 1];
+    for (j, b_char) in b.iter().enumerate() {
+      let substitution = previous[j] + usize::from(a_char != *b_char);
+      current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+    }
+    previous = current;
+  }
+  previous[b.len()]
+}
+
+/// Replace the environment variables in the strings of a parsed configuration
+fn interpolate_env(value: &mut toml::Value) -> Result<(), toml::de::Error> {
+  match value {
+    toml::Value::String(string) => {
+      if let Some(name) = ENV_VAR_PATTERN
+        .captures_iter(string)
+        .map(|captures| captures[1].to_string())
+        .find(|name| std::env::var(name).is_err())
+      {
+        return Err(serde::de::Error::custom(format!(
+          "environment variable `{}` used in `{}` is not set",
+          name, string
+        )));
+      }
+      *string = ENV_VAR_PATTERN
+        .replace_all(string, |captures: &regex::Captures| {
+          std::env::var(&captures[1]).unwrap_or_default()
+        })
+        .to_string();
+    }
+    toml::Value::Array(values) => values.iter_mut().try_for_each(interpolate_env)?,
+    toml::Value::Table(table) => table
+      .iter_mut()
+      .try_for_each(|(_, value)| interpolate_env(value))?,
+    _ => {}
+  }
+  Ok(())
+}
+
+/// Import paths of the configuration that can point to files
+fn import_paths(options: &mut RewriteOptions) -> Vec<&mut String> {
+  let mut paths = Vec::new();
+  if let Some(imports) = &mut options.additional_imports {
+    paths.extend(
+      imports
+        .values_mut()
+        .filter_map(|import| import.import_path.as_mut()),
+    );
+  }
+  if let Some(event_bus) = &mut options.event_bus {
+    paths.push(&mut event_bus.import_path);
+  }
+  if let Some(date_plugin) = &mut options.date_plugin {
+    paths.push(&mut date_plugin.import_path);
+  }
+  if let Some(http) = &mut options.http {
+    paths.push(&mut http.import_path);
+  }
+  if let Some(custom_options) = &mut options.custom_options {
+    paths.extend(
+      custom_options
+        .values_mut()
+        .filter_map(|custom_option| custom_option.import_path.as_mut()),
+    );
+  }
+  if let Some(import_path) = options
+    .nuxt_error
+    .as_mut()
+    .and_then(|nuxt_error| nuxt_error.import_path.as_mut())
+  {
+    paths.push(import_path);
+  }
+  paths
+}
+
+/// Remove the `.` and `..` components of a path without touching the file system
+fn normalize(path: &Path) -> PathBuf {
+  let mut normalized = PathBuf::new();
+  for component in path.components() {
+    match component {
+      Component::CurDir => {}
+      Component::ParentDir => {
+        normalized.pop();
+      }
+      component => normalized.push(component),
+    }
+  }
+  normalized
+}
+
+/// Path of `target` relative to the directory `base`, both absolute
+fn relative_path(base: &Path, target: &Path) -> PathBuf {
+  let base = normalize(base);
+  let target = normalize(target);
+  let common = base
+    .components()
+    .zip(target.components())
+    .take_while(|(a, b)| a == b)
+    .count();
+
+  let mut relative = PathBuf::new();
+  base
+    .components()
+    .skip(common)
+    .for_each(|_| relative.push(".."));
+  target
+    .components()
+    .skip(common)
+    .for_each(|component| relative.push(component));
+  relative
+}
+
+/// Split the comment overriding the options of a single file (`<!-- votc: disable=vuex -->`) off
+/// the top of an SFC, returning the rest of the SFC and the comment's entries
+pub fn split_file_overrides(sfc: &str) -> (&str, Option<&str>) {
+  let comment = sfc
+    .trim_start()
+    .strip_prefix("<!--")
+    .map(str::trim_start)
+    .and_then(|comment| comment.strip_prefix("votc:"))
+    .and_then(|comment| comment.split_once("-->"));
+
+  match comment {
+    Some((entries, rest)) => (rest.trim_start(), Some(entries.trim())),
+    None => (sfc, None),
+  }
+}
+
+/// Apply the `key=value` entries of a file override comment to the options. Entries take the
+/// values of the configuration file, `disable` takes transformer names until the next entry
+/// (`disable=vuex,i18n,typescript=true`). Entries that can not be applied are reported.
+pub fn apply_file_overrides(
+  mut options: RewriteOptions,
+  overrides: &str,
+) -> (RewriteOptions, Vec<Diagnostic>) {
+  let mut diagnostics = Vec::new();
+
+  let mut entries: Vec<(&str, Vec<&str>)> = Vec::new();
+  for part in overrides
+    .split(',')
+    .map(str::trim)
+    .filter(|part| !part.is_empty())
+  {
+    match (part.split_once('='), entries.last_mut()) {
+      (Some((key, value)), _) => entries.push((key.trim(), vec![value.trim()])),
+      (None, Some((_, values))) => values.push(part),
+      (None, None) => entries.push((part, Vec::new())),
+    }
+  }
+
+  for (key, values) in entries {
+    let value = match values.as_slice() {
+      _ if key == "disable" => format!(
+        "[{}]",
+        values
+          .iter()
+          .map(|value| format!("{:?}", value))
+          .collect::<Vec<_>>()
+          .join(", ")
+      ),
+      [value] if *value == "true" || *value == "false" => value.to_string(),
+      [value] => format!("{:?}", value),
+      _ => {
+        diagnostics.push(Diagnostic::warning(
+          "file-override",
+          format!(
+            "`{}` in the votc comment takes a single value, it was ignored",
+            key
+          ),
+        ));
+        continue;
+      }
+    };
+
+    let file_options: RewriteOptions =
+      match toml::from_str::<ConfigFile>(&format!("{} = {}", key, value)) {
+        Ok(config_file) => config_file.into(),
+        Err(error) => {
+          diagnostics.push(Diagnostic::warning(
+            "file-override",
+            format!(
+              "`{}` in the votc comment was ignored: {}",
+              key,
+              error.message()
+            ),
+          ));
+          continue;
+        }
+      };
+
+    match key {
+      "disable" => options.disable = file_options.disable,
+      "enable_transition_transforms" => {
+        options.enable_transition_transforms = file_options.enable_transition_transforms
+      }
+      "enable_style_transforms" => {
+        options.enable_style_transforms = file_options.enable_style_transforms
+      }
+      "instance_properties" => options.instance_properties = file_options.instance_properties,
+      "asset_mode" => options.asset_mode = file_options.asset_mode,
+      "v_t_directive" => options.v_t_directive = file_options.v_t_directive,
+      "set_locale" => options.set_locale = file_options.set_locale,
+      "nuxt_i18n" => options.nuxt_i18n = file_options.nuxt_i18n,
+      "axios_data_helper" => options.axios_data_helper = file_options.axios_data_helper,
+      "target" => options.target = file_options.target,
+      "ssr_target" => options.ssr_target = file_options.ssr_target,
+      "define_page_meta" => options.define_page_meta = file_options.define_page_meta,
+      "typescript" => options.typescript = file_options.typescript,
+      "prefer_define_model" => options.prefer_define_model = file_options.prefer_define_model,
+      "self_reference" => options.self_reference = file_options.self_reference,
+      "script_style" => options.script_style = file_options.script_style,
+      "template_ref_style" => options.template_ref_style = file_options.template_ref_style,
+      "require_components" => options.require_components = file_options.require_components,
+      _ => diagnostics.push(Diagnostic::warning(
+        "file-override",
+        format!(
+          "`{}` can not be overridden in the votc comment, configure it in the configuration file",
+          key
+        ),
+      )),
+    }
+  }
+
+  (options, diagnostics)
+}
+
+/// A configuration file as written, see `config.example.toml`
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ConfigFile {
+  mixins: Option<HashMap<String, FileMixinConfig>>,
+  imports_rewrite: Option<HashMap<String, FileImportRewrite>>,
+  additional_imports: Option<HashMap<String, FileAdditionalImport>>,
+  import_keeplist: Option<Vec<String>>,
+  event_bus: Option<FileEventBusConfig>,
+  global_properties: Option<Vec<String>>,
+  instance_properties: Option<FileInstancePropertyStyle>,
+  date_plugin: Option<FileDatePluginConfig>,
+  enable_transition_transforms: Option<bool>,
+  enable_style_transforms: Option<bool>,
+  emit_mapping: Option<FileEmitMappingConfig>,
+  expose: Option<FileExposeConfig>,
+  imports: Option<FileImportOrderConfig>,
+  aliases: Option<HashMap<String, String>>,
+  asset_mode: Option<FileAssetMode>,
+  v_t_directive: Option<FileVTDirectiveStyle>,
+  set_locale: Option<FileSetLocaleStyle>,
+  nuxt_i18n: Option<FileNuxtI18nStyle>,
+  http: Option<FileHttpConfig>,
+  axios_data_helper: Option<String>,
+  target: Option<FileTarget>,
+  ssr_target: Option<bool>,
+  nuxt_error: Option<FileNuxtErrorConfig>,
+  define_page_meta: Option<bool>,
+  typescript: Option<bool>,
+  prefer_define_model: Option<bool>,
+  self_reference: Option<FileSelfReferenceStyle>,
+  disable: Option<Vec<String>>,
+  script_style: Option<FileScriptStyle>,
+  template_ref_style: Option<FileTemplateRefStyle>,
+  require_components: Option<FileRequireComponentStyle>,
+  custom_options: Option<HashMap<String, FileCustomOptionConfig>>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct FileMixinConfig {
+  name: String,
+  imports: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct FileCustomOptionConfig {
+  emit: String,
+  import_path: Option<String>,
+  imports: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct FileImportRewrite {
+  name: String,
+  component_rewrite: Option<HashMap<String, String>>,
+  directives: Option<HashMap<String, String>>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct FileAdditionalImport {
+  import_path: Option<String>,
+  rewrite_to: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct FileEventBusConfig {
+  import_path: Option<String>,
+  composable: Option<String>,
+  variable: Option<String>,
+  globals: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct FileDatePluginConfig {
+  property: Option<String>,
+  filter: Option<String>,
+  import_path: Option<String>,
+  name: Option<String>,
+  composable: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct FileImportOrderConfig {
+  groups: Vec<String>,
+  separate_groups: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct FileEmitMappingConfig {
+  model_prop: Option<String>,
+  #[serde(default)]
+  components: HashMap<String, FileEmitMappingConfig>,
+  /// Remaining keys are event renames, e.g. `input = "update:modelValue"`
+  #[serde(flatten)]
+  events: HashMap<String, String>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct FileExposeConfig {
+  auto: Option<bool>,
+  /// Remaining keys are component patterns, e.g. `"*Modal" = ["open", "close"]`
+  #[serde(flatten)]
+  components: HashMap<String, Vec<String>>,
+}
+
+impl From<FileEmitMappingConfig> for EmitMappingConfig {
+  fn from(emit_mapping: FileEmitMappingConfig) -> Self {
+    EmitMappingConfig {
+      events: emit_mapping.events,
+      model_prop: emit_mapping.model_prop,
+      components: emit_mapping
+        .components
+        .into_iter()
+        .map(|(name, component)| (name, component.into()))
+        .collect(),
+    }
+  }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum FileInstancePropertyStyle {
+  Let,
+  Ref,
+}
+
+impl From<FileInstancePropertyStyle> for InstancePropertyStyle {
+  fn from(style: FileInstancePropertyStyle) -> Self {
+    match style {
+      FileInstancePropertyStyle::Let => InstancePropertyStyle::Let,
+      FileInstancePropertyStyle::Ref => InstancePropertyStyle::Ref,
+    }
+  }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum FileAssetMode {
+  Path,
+  Import,
+  Url,
+}
+
+impl From<FileAssetMode> for AssetMode {
+  fn from(mode: FileAssetMode) -> Self {
+    match mode {
+      FileAssetMode::Path => AssetMode::Path,
+      FileAssetMode::Import => AssetMode::Import,
+      FileAssetMode::Url => AssetMode::Url,
+    }
+  }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum FileVTDirectiveStyle {
+  Interpolate,
+  Keep,
+}
+
+impl From<FileVTDirectiveStyle> for VTDirectiveStyle {
+  fn from(style: FileVTDirectiveStyle) -> Self {
+    match style {
+      FileVTDirectiveStyle::Interpolate => VTDirectiveStyle::Interpolate,
+      FileVTDirectiveStyle::Keep => VTDirectiveStyle::Keep,
+    }
+  }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum FileSetLocaleStyle {
+  Utils,
+  Assign,
+}
+
+impl From<FileSetLocaleStyle> for SetLocaleStyle {
+  fn from(style: FileSetLocaleStyle) -> Self {
+    match style {
+      FileSetLocaleStyle::Utils => SetLocaleStyle::Utils,
+      FileSetLocaleStyle::Assign => SetLocaleStyle::Assign,
+    }
+  }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+enum FileNuxtI18nStyle {
+  Export,
+  DefineI18nRoute,
+}
+
+impl From<FileNuxtI18nStyle> for NuxtI18nStyle {
+  fn from(style: FileNuxtI18nStyle) -> Self {
+    match style {
+      FileNuxtI18nStyle::Export => NuxtI18nStyle::Export,
+      FileNuxtI18nStyle::DefineI18nRoute => NuxtI18nStyle::DefineI18nRoute,
+    }
+  }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+enum FileSelfReferenceStyle {
+  DefineOptions,
+  FileName,
+}
+
+impl From<FileSelfReferenceStyle> for SelfReferenceStyle {
+  fn from(style: FileSelfReferenceStyle) -> Self {
+    match style {
+      FileSelfReferenceStyle::DefineOptions => SelfReferenceStyle::DefineOptions,
+      FileSelfReferenceStyle::FileName => SelfReferenceStyle::FileName,
+    }
+  }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+enum FileScriptStyle {
+  SetupSugar,
+  SetupFunction,
+}
+
+impl From<FileScriptStyle> for ScriptStyle {
+  fn from(style: FileScriptStyle) -> Self {
+    match style {
+      FileScriptStyle::SetupSugar => ScriptStyle::SetupSugar,
+      FileScriptStyle::SetupFunction => ScriptStyle::SetupFunction,
+    }
+  }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+enum FileTemplateRefStyle {
+  #[serde(rename = "useTemplateRef")]
+  UseTemplateRef,
+  #[serde(rename = "ref")]
+  Ref,
+}
+
+impl From<FileTemplateRefStyle> for TemplateRefStyle {
+  fn from(style: FileTemplateRefStyle) -> Self {
+    match style {
+      FileTemplateRefStyle::UseTemplateRef => TemplateRefStyle::UseTemplateRef,
+      FileTemplateRefStyle::Ref => TemplateRefStyle::Ref,
+    }
+  }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+enum FileRequireComponentStyle {
+  Import,
+  Async,
+}
+
+impl From<FileRequireComponentStyle> for RequireComponentStyle {
+  fn from(style: FileRequireComponentStyle) -> Self {
+    match style {
+      FileRequireComponentStyle::Import => RequireComponentStyle::Import,
+      FileRequireComponentStyle::Async => RequireComponentStyle::Async,
+    }
+  }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+enum FileTarget {
+  Vue3Vite,
+  Nuxt3,
+  #[serde(rename = "vue2.7")]
+  Vue27,
+}
+
+impl From<FileTarget> for Target {
+  fn from(target: FileTarget) -> Self {
+    match target {
+      FileTarget::Vue3Vite => Target::Vue3Vite,
+      FileTarget::Nuxt3 => Target::Nuxt3,
+      FileTarget::Vue27 => Target::Vue27,
+    }
+  }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct FileNuxtErrorConfig {
+  name: Option<String>,
+  import_path: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+enum FileHttpMode {
+  Composable,
+  KeepAxios,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct FileHttpConfig {
+  mode: Option<FileHttpMode>,
+  import_path: Option<String>,
+  name: Option<String>,
+  composable: Option<String>,
+}
+
+impl From<FileHttpConfig> for HttpConfig {
+  fn from(http: FileHttpConfig) -> Self {
+    let defaults = match http.mode {
+      Some(FileHttpMode::KeepAxios) => HttpConfig::keep_axios(),
+      Some(FileHttpMode::Composable) | None => HttpConfig::default(),
+    };
+    HttpConfig {
+      import_path: http.import_path.unwrap_or(defaults.import_path),
+      name: http.name.unwrap_or(defaults.name),
+      composable: http.composable.or(defaults.composable),
+    }
+  }
+}
+
+impl From<ConfigFile> for RewriteOptions {
+  fn from(config_file: ConfigFile) -> Self {
+    RewriteOptions {
+      mixins: config_file.mixins.map(|mixins| {
+        mixins
+          .into_iter()
+          .map(|(k, v)| {
+            (
+              k,
+              MixinConfig {
+                name: v.name,
+                imports: v.imports,
+              },
+            )
+          })
+          .collect()
+      }),
+      imports_rewrite: config_file.imports_rewrite.map(|imports| {
+        imports
+          .into_iter()
+          .map(|(k, v)| {
+            (
+              k,
+              ImportRewrite {
+                name: v.name,
+                component_rewrite: v.component_rewrite,
+                directives: v.directives,
+              },
+            )
+          })
+          .collect()
+      }),
+      additional_imports: config_file.additional_imports.map(|imports| {
+        imports
+          .into_iter()
+          .map(|(k, v)| {
+            (
+              k,
+              AdditionalImport {
+                import_path: v.import_path,
+                rewrite_to: v.rewrite_to,
+              },
+            )
+          })
+          .collect()
+      }),
+      import_keeplist: config_file.import_keeplist,
+      event_bus: config_file.event_bus.map(|event_bus| {
+        let defaults = EventBusConfig::default();
+        EventBusConfig {
+          import_path: event_bus.import_path.unwrap_or(defaults.import_path),
+          composable: event_bus.composable.unwrap_or(defaults.composable),
+          variable: event_bus.variable.unwrap_or(defaults.variable),
+          globals: event_bus.globals.unwrap_or(defaults.globals),
+        }
+      }),
+      global_properties: config_file.global_properties,
+      instance_properties: config_file.instance_properties.map(Into::into),
+      date_plugin: config_file.date_plugin.map(|date_plugin| {
+        let defaults = DatePluginConfig::default();
+        DatePluginConfig {
+          property: date_plugin.property.unwrap_or(defaults.property),
+          filter: date_plugin.filter.unwrap_or(defaults.filter),
+          import_path: date_plugin.import_path.unwrap_or(defaults.import_path),
+          name: date_plugin.name.unwrap_or(defaults.name),
+          composable: date_plugin.composable,
+        }
+      }),
+      enable_transition_transforms: config_file.enable_transition_transforms.unwrap_or(false),
+      enable_style_transforms: config_file.enable_style_transforms,
+      emit_mapping: config_file.emit_mapping.map(Into::into),
+      expose: config_file.expose.map(|expose| ExposeConfig {
+        components: expose.components,
+        auto: expose.auto.unwrap_or(false),
+        members: Vec::new(),
+      }),
+      import_order: config_file.imports.map(|imports| ImportOrderConfig {
+        groups: imports.groups,
+        separate_groups: imports.separate_groups.unwrap_or(true),
+      }),
+      aliases: config_file.aliases,
+      asset_mode: config_file.asset_mode.map(Into::into),
+      v_t_directive: config_file.v_t_directive.map(Into::into),
+      set_locale: config_file.set_locale.map(Into::into),
+      nuxt_i18n: config_file.nuxt_i18n.map(Into::into),
+      http: config_file.http.map(Into::into),
+      axios_data_helper: config_file.axios_data_helper,
+      target: config_file.target.map(Into::into),
+      ssr_target: config_file.ssr_target.unwrap_or(false),
+      nuxt_error: config_file.nuxt_error.map(|nuxt_error| NuxtErrorConfig {
+        name: nuxt_error
+          .name
+          .unwrap_or_else(|| NuxtErrorConfig::default().name),
+        import_path: nuxt_error.import_path,
+      }),
+      define_page_meta: config_file.define_page_meta.unwrap_or(false),
+      typescript: config_file.typescript.unwrap_or(false),
+      prefer_define_model: config_file.prefer_define_model.unwrap_or(false),
+      self_reference: config_file.self_reference.map(Into::into),
+      file_name: None,
+      disable: config_file.disable,
+      script_style: config_file.script_style.map(Into::into),
+      template_ref_style: config_file.template_ref_style.map(Into::into),
+      require_components: config_file.require_components.map(Into::into),
+      custom_options: config_file.custom_options.map(|custom_options| {
+        custom_options
+          .into_iter()
+          .map(|(option, custom_option)| {
+            (
+              option,
+              CustomOptionConfig {
+                emit: custom_option.emit,
+                import_path: custom_option.import_path,
+                imports: custom_option.imports.unwrap_or_default(),
+              },
+            )
+          })
+          .collect()
+      }),
+    }
+  }
+}
@@ -0,0 +1,91 @@
+//! C ABI for embedding the converter in other languages, enabled by the `ffi` feature.
+//!
+//! Build the shared library with `cargo rustc --release --lib --crate-type cdylib --features ffi`,
+//! the declarations are in `include/votc.h`. Strings are NUL-terminated UTF-8, and every string
+//! returned through an out parameter must be released with [`votc_free_string`].
+
+use crate::config::parse_config;
+use std::ffi::{c_char, c_int, CStr, CString};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::ptr;
+
+/// The SFC was converted, `out` holds the result
+pub const VOTC_OK: c_int = 0;
+/// The SFC or the options are invalid, `err` holds the reason
+pub const VOTC_ERROR: c_int = 1;
+/// A pointer argument is NULL or a string is not UTF-8, nothing is written to `out` or `err`
+pub const VOTC_INVALID_ARGUMENT: c_int = 2;
+
+/// Convert an SFC. `options` is the content of a TOML configuration file, or NULL for the
+/// defaults. On success the converted SFC is written to `out`, on failure the message to `err`.
+///
+/// # Safety
+///
+/// `sfc` and `options` (unless NULL) must be valid NUL-terminated strings, `out` and `err` must be
+/// valid pointers to write a string pointer to.
+#[no_mangle]
+pub unsafe extern "C" fn votc_rewrite_sfc(
+  sfc: *const c_char,
+  options: *const c_char,
+  out: *mut *mut c_char,
+  err: *mut *mut c_char,
+) -> c_int {
+  if sfc.is_null() || out.is_null() || err.is_null() {
+    return VOTC_INVALID_ARGUMENT;
+  }
+  *out = ptr::null_mut();
+  *err = ptr::null_mut();
+
+  let Ok(sfc) = CStr::from_ptr(sfc).to_str() else {
+    return VOTC_INVALID_ARGUMENT;
+  };
+  let options = if options.is_null() {
+    None
+  } else {
+    match CStr::from_ptr(options).to_str() {
+      Ok(options) => Some(options),
+      Err(_) => return VOTC_INVALID_ARGUMENT,
+    }
+  };
+
+  // Panics must not unwind into the host
+  let result = catch_unwind(AssertUnwindSafe(|| {
+    let options = options
+      .map(parse_config)
+      .transpose()
+      .map_err(|e| format!("Invalid options: {}", e))?;
+    crate::rewrite_sfc(sfc, options).map_err(|e| e.to_string())
+  }))
+  .unwrap_or_else(|_| Err("The converter panicked".to_string()));
+
+  match result {
+    Ok(code) => {
+      *out = into_c_string(code);
+      VOTC_OK
+    }
+    Err(message) => {
+      *err = into_c_string(message);
+      VOTC_ERROR
+    }
+  }
+}
+
+/// Release a string returned by this library. NULL is ignored.
+///
+/// # Safety
+///
+/// `string` must be NULL or a pointer returned through an out parameter of this library that was
+/// not released yet.
+#[no_mangle]
+pub unsafe extern "C" fn votc_free_string(string: *mut c_char) {
+  if !string.is_null() {
+    drop(CString::from_raw(string));
+  }
+}
+
+/// Hand a string to the caller, NUL bytes (which C strings can not hold) are dropped
+fn into_c_string(string: String) -> *mut c_char {
+  CString::new(string.replace('\0', ""))
+    .unwrap_or_default()
+    .into_raw()
+}
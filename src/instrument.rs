@@ -0,0 +1,29 @@
+//! Optional instrumentation with the `tracing` crate, enabled by the `tracing` feature.
+//!
+//! Parsing, each transformer and the generation of the output run in their own span, so a
+//! subscriber gets the time spent per phase; events report what the transformers produced. Without
+//! the feature the macros expand to nothing and nothing is evaluated.
+
+/// Enter a debug span until the end of the enclosing block:
+/// `let _span = trace_span!("transformer", name = transformer.name());`
+macro_rules! trace_span {
+  ($name:literal $(, $($fields:tt)*)?) => {{
+    #[cfg(feature = "tracing")]
+    let span = tracing::debug_span!($name $(, $($fields)*)?).entered();
+    #[cfg(not(feature = "tracing"))]
+    let span = $crate::instrument::NoSpan;
+    span
+  }};
+}
+
+/// Emit an event at the given level: `trace_event!(DEBUG, count = 2, "message")`
+macro_rules! trace_event {
+  ($level:ident, $($arguments:tt)*) => {
+    #[cfg(feature = "tracing")]
+    tracing::event!(tracing::Level::$level, $($arguments)*);
+  };
+}
+
+/// Stands in for an entered span without the `tracing` feature
+#[cfg(not(feature = "tracing"))]
+pub(crate) struct NoSpan;
@@ -0,0 +1,47 @@
+//! Shared tree-sitter JavaScript parser.
+//!
+//! A conversion parses many small snippets (method bodies, template expressions, option values), so
+//! rather than building a `Parser` and loading the grammar for each of them, every thread keeps one
+//! parser with the grammar loaded and reuses it.
+
+use std::cell::RefCell;
+use tree_sitter::{Parser, Tree};
+
+thread_local! {
+  static PARSER: RefCell<Option<Parser>> = const { RefCell::new(None) };
+}
+
+/// Parse `source` as JavaScript with this thread's parser; `None` when the grammar cannot be
+/// loaded or parsing is cancelled
+pub(crate) fn parse(source: &str) -> Option<Tree> {
+  PARSER.with(|parser| {
+    let mut parser = parser.borrow_mut();
+    if parser.is_none() {
+      let mut new_parser = Parser::new();
+      new_parser
+        .set_language(&tree_sitter_javascript::LANGUAGE.into())
+        .ok()?;
+      *parser = Some(new_parser);
+    }
+    parser.as_mut()?.parse(source, None)
+  })
+}
+
+/// Parse a code fragment that may be a program, a function body (with `return` or `await`) or a
+/// lone expression such as an object literal, with the offset of `code` in the parsed source. The
+/// first wrapping that parses without errors is used, the plain program otherwise.
+pub(crate) fn parse_fragment(code: &str) -> Option<(Tree, usize)> {
+  let program = parse(code)?;
+  if !program.root_node().has_error() {
+    return Some((program, 0));
+  }
+
+  for (prefix, suffix) in [("async function __body__() {\n", "\n}"), ("(\n", "\n)")] {
+    let tree = parse(&format!("{}{}{}", prefix, code, suffix))?;
+    if !tree.root_node().has_error() {
+      return Some((tree, prefix.len()));
+    }
+  }
+
+  Some((program, 0))
+}
@@ -1,48 +1,532 @@
 use lazy_static::lazy_static;
 use regex::Regex;
-use std::collections::HashMap;
-use tree_sitter::{Node, Parser};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+use tree_sitter::Node;
+use template_expression::{is_slot_declaration, VueExpression};
 
 lazy_static! {
   static ref MUSTACHE_PATTERN: Regex = Regex::new(r"(?s)\{\{(.*?)\}\}").unwrap();
+  static ref IMPORT_STATEMENT_PATTERN: Regex =
+    Regex::new(r#"(?s)^import\s+(.+?)\s+from\s+(['"])([^'"]+)['"];?\s*$"#).unwrap();
+  static ref SOURCE_IMPORT_PATTERN: Regex =
+    Regex::new(r#"(?m)^\s*import\s+(?:[^;'"]*?\s+from\s+)?['"][^'"]+['"];?"#).unwrap();
+  static ref TEMPLATE_EXPRESSION_PATTERN: Regex = Regex::new(
+    r#"(?s)<!--.*?-->|\{\{(.*?)\}\}|\s(?:v-[\w:.\[\]-]+|[:@#][\w:.\[\]-]*)\s*=\s*(?:"([^"]*)"|'([^']*)')"#
+  )
+  .unwrap();
+  static ref SIDE_EFFECT_IMPORT_PATTERN: Regex =
+    Regex::new(r#"^import\s*['"]([^'"]+)['"];?\s*$"#).unwrap();
+  static ref IMPORT_SOURCE_PATTERN: Regex = Regex::new(r#"['"]([^'"]+)['"];?$"#).unwrap();
+  static ref SCRIPT_LANG_PATTERN: Regex = Regex::new(r#"\blang\s*=\s*["']([^"']+)["']"#).unwrap();
+  static ref ROOT_BLOCK_PATTERN: Regex = Regex::new(r"(?m)^<([a-zA-Z][\w-]*)[\s>/]").unwrap();
+  static ref PROP_TYPE_CAST_PATTERN: Regex =
+    Regex::new(r"^(?:\[[^\]\n]*\]|[^\n,{}\[])*?\s+as\s+PropType\s*<").unwrap();
+  static ref IDENTIFIER_PATTERN: Regex = Regex::new(r"[A-Za-z_$][\w$]*").unwrap();
+  static ref MACRO_CALL_PATTERN: Regex =
+    Regex::new(r"(?m)^(?:const\s+([\w$]+)\s*=\s*)?(defineProps|defineEmits)\(").unwrap();
+  static ref TOP_LEVEL_DECLARATION_PATTERN: Regex = Regex::new(
+    r"(?m)^(?:(?:async\s+)?function\*?|class|const|let|var)\s+([\w$]+)|^(?:const|let|var)\s*[{\[]([^=;]*)[}\]]\s*="
+  )
+  .unwrap();
 }
 
+#[macro_use]
+mod instrument;
+mod javascript;
+
 // Re-export transformers module
+pub mod analyze;
+pub mod config;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "napi")]
+pub mod node;
+pub mod project;
+pub mod server;
+pub mod store_modules;
+pub mod template_expression;
+pub mod test_files;
 pub mod transformers;
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct RewriteOptions {
   pub mixins: Option<HashMap<String, MixinConfig>>,
   pub imports_rewrite: Option<HashMap<String, ImportRewrite>>,
   pub additional_imports: Option<HashMap<String, AdditionalImport>>,
   pub import_keeplist: Option<Vec<String>>,
+  pub event_bus: Option<EventBusConfig>,
+  /// Instance properties (e.g. "$filters") kept as `app.config.globalProperties` and accessed
+  /// through `getCurrentInstance().proxy` instead of being converted
+  pub global_properties: Option<Vec<String>>,
+  /// Declare `this.x = ...` assignments to properties missing from `data` in setup (opt-in,
+  /// timer handles are always declared, with `let` unless this is `Ref`)
+  pub instance_properties: Option<InstancePropertyStyle>,
+  pub date_plugin: Option<DatePluginConfig>,
+  /// Rename Vue 2 transition classes (`.fade-enter` -> `.fade-enter-from`) in `<style>` and
+  /// `<transition>` class props, and report `<transition-group>` without a `tag`
+  pub enable_transition_transforms: bool,
+  /// Rewrite `/deep/`, `>>>`, `::v-deep` and `::v-slotted` selectors to `:deep()` / `:slotted()`
+  /// (enabled when unset)
+  pub enable_style_transforms: Option<bool>,
+  pub emit_mapping: Option<EmitMappingConfig>,
+  pub expose: Option<ExposeConfig>,
+  pub import_order: Option<ImportOrderConfig>,
+  /// Path alias rewrites (e.g. "~/" -> "@/") for imports, dynamic imports and asset URLs. Nuxt's
+  /// "~/" becomes "@/" when unset, an empty map disables the rewrites.
+  pub aliases: Option<HashMap<String, String>>,
+  /// Conversion of webpack's `require()` for assets (`path` when unset)
+  pub asset_mode: Option<AssetMode>,
+  /// Conversion of the vue-i18n `v-t` directive (`interpolate` when unset)
+  pub v_t_directive: Option<VTDirectiveStyle>,
+  /// Conversion of Nuxt i18n's `$i18n.setLocale()` (`utils` when unset)
+  pub set_locale: Option<SetLocaleStyle>,
+  /// Output of the `nuxtI18n` page options (`export` when unset)
+  pub nuxt_i18n: Option<NuxtI18nStyle>,
+  /// HTTP client `$axios` calls are converted to (`useHttp()` from "@/composables/useHttp" when
+  /// unset)
+  pub http: Option<HttpConfig>,
+  /// Helper imported from the HTTP client's import path to unwrap responses of the Nuxt axios
+  /// shortcuts (`$get`, `$post`, ...), `.then((response) => response.data)` is appended when unset
+  pub axios_data_helper: Option<String>,
+  /// Framework the output targets, adjusting which composables are imported (`vue3-vite` when
+  /// unset)
+  pub target: Option<Target>,
+  /// Load the Nuxt `fetch()` and `asyncData` data in `onServerPrefetch`, with an `onMounted`
+  /// fallback, instead of during setup
+  pub ssr_target: bool,
+  /// Function `this.$nuxt.error()` is converted to (`showError` from `useNuxtCompat()` when unset)
+  pub nuxt_error: Option<NuxtErrorConfig>,
+  /// Pass the Nuxt `key` page option to Nuxt 3's `definePageMeta()` instead of reporting it
+  pub define_page_meta: bool,
+  /// Emit `<script setup lang="ts">` with props, emits and refs typed from their usage
+  pub typescript: bool,
+  /// Replace a `value`/`modelValue` prop, its update event and the computed passing them through
+  /// with Vue 3.4's `defineModel()`
+  pub prefer_define_model: bool,
+  /// How components rendering themselves by their `name` option refer to themselves
+  /// (`define-options` when unset)
+  pub self_reference: Option<SelfReferenceStyle>,
+  /// File name of the component (e.g. `TreeItem.vue`), which `<script setup>` components refer
+  /// to themselves by
+  pub file_name: Option<String>,
+  /// Transformers to skip, by name (e.g. "vuex")
+  pub disable: Option<Vec<String>>,
+  /// Form of the generated script (`setup-sugar` when unset)
+  pub script_style: Option<ScriptStyle>,
+  /// How template refs are declared (`useTemplateRef` when unset)
+  pub template_ref_style: Option<TemplateRefStyle>,
+  /// How components registered with `require()` are loaded (`import` when unset)
+  pub require_components: Option<RequireComponentStyle>,
+  /// Conversions of in-house component options (e.g. `analytics`), by option name
+  pub custom_options: Option<HashMap<String, CustomOptionConfig>>,
 }
 
-#[derive(Debug, Clone)]
+/// Form of the generated Composition API script
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub enum ScriptStyle {
+  /// `<script setup>`
+  #[default]
+  SetupSugar,
+  /// `export default defineComponent({ setup() { ... } })`, returning the bindings the template
+  /// uses and keeping the `name` and `components` options
+  SetupFunction,
+}
+
+/// Declaration of the template refs used through `this.$refs`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub enum TemplateRefStyle {
+  /// `const titleRef = useTemplateRef('title')` (Vue 3.5)
+  #[default]
+  UseTemplateRef,
+  /// `const titleRef = ref(null)`, with the `ref` attribute renamed to `titleRef` since the ref is
+  /// bound by its variable name (Vue 3.2 to 3.4)
+  Ref,
+}
+
+/// Loading of the components registered with `require('./Foo.vue').default`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub enum RequireComponentStyle {
+  /// `import Foo from './Foo.vue'`
+  #[default]
+  Import,
+  /// `const Foo = defineAsyncComponent(() => import('./Foo.vue'))`
+  Async,
+}
+
+/// How a recursive component (a tree item rendering its children with its own `name`) refers to
+/// itself once the `name` option is gone
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub enum SelfReferenceStyle {
+  /// Keep the name with `defineOptions({ name: 'TreeItem' })` (Vue 3.3)
+  #[default]
+  DefineOptions,
+  /// Rename the self-references in the template to the file name, falling back to
+  /// `defineOptions()` when the file name is unknown
+  FileName,
+}
+
+/// Conversion of the vue-i18n `v-t="'key'"` directive
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub enum VTDirectiveStyle {
+  /// The element content becomes `{{ t('key') }}`
+  #[default]
+  Interpolate,
+  /// The directive is kept, vue-i18n v9 still registers it globally
+  Keep,
+}
+
+/// Conversion of Nuxt i18n's `this.$i18n.setLocale('en')`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub enum SetLocaleStyle {
+  /// `setLocale('en')` from the `useI18nUtils()` composable
+  #[default]
+  Utils,
+  /// `locale.value = 'en'` with the `locale` of `useI18n()`
+  Assign,
+}
+
+/// Output of the `nuxtI18n` page options (`paths`, `seo`, `locales`, ...)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub enum NuxtI18nStyle {
+  /// `export const i18n = { ... };` in a separate `<script>`
+  #[default]
+  Export,
+  /// `defineI18nRoute({ ... });` in `<script setup>`, as used by `@nuxtjs/i18n` for Nuxt 3
+  DefineI18nRoute,
+}
+
+/// Conversion of `require('~/assets/logo.png')` asset references
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub enum AssetMode {
+  /// `:src="require(...)"` becomes a static `src` attribute resolved by the template compiler,
+  /// other requires are reported
+  #[default]
+  Path,
+  /// Hoisted `import asset1 from '@/assets/logo.png';` declarations
+  Import,
+  /// Hoisted `const asset1 = new URL('./logo.png', import.meta.url).href;` declarations
+  Url,
+}
+
+/// Framework the converted components run on
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub enum Target {
+  /// Vue 3 with Vite, composables are imported from their packages
+  #[default]
+  Vue3Vite,
+  /// Nuxt 3, where `useRoute`, `useRouter`, `useHead` and `useRuntimeConfig` are auto-imported
+  Nuxt3,
+  /// Vue 2.7, which runs `<script setup>` and the Composition API on the Vue 2 runtime: Vue 2
+  /// template syntax and components are kept, and Vue 3-only APIs are avoided
+  Vue27,
+}
+
+impl Target {
+  /// Module `useRoute()` and `useRouter()` are imported from, `None` where they are auto-imported
+  pub fn router_module(self) -> Option<&'static str> {
+    match self {
+      Target::Vue3Vite => Some("vue-router"),
+      Target::Nuxt3 => None,
+      // vue-router 3.6 ships its composables in a separate entry point
+      Target::Vue27 => Some("vue-router/composables"),
+    }
+  }
+}
+
+/// Declaration style for instance properties that are assigned but never declared in `data`
+/// (e.g. `this.timer = setInterval(...)`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub enum InstancePropertyStyle {
+  /// `let timer;`
+  #[default]
+  Let,
+  /// `const timer = ref(null);` for timer handles, `const flag = ref(undefined);` otherwise
+  Ref,
+}
+
+#[derive(Debug, Clone, Serialize)]
 pub struct MixinConfig {
   pub name: String,
   pub imports: Vec<String>,
 }
 
-#[derive(Debug, Clone)]
+/// Conversion of an in-house component option (e.g. `analytics: { page: 'cart' }`) to a call in
+/// the script
+#[derive(Debug, Clone, Serialize)]
+pub struct CustomOptionConfig {
+  /// Code the option is converted to, with `{body}` replaced by the value of the option (e.g.
+  /// "useAnalytics({body})")
+  pub emit: String,
+  /// Import path of the functions the code calls (e.g. "@/composables/useAnalytics")
+  pub import_path: Option<String>,
+  /// Names imported from `import_path` (e.g. "useAnalytics")
+  pub imports: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
 pub struct ImportRewrite {
   pub name: String,
   pub component_rewrite: Option<HashMap<String, String>>,
   pub directives: Option<HashMap<String, String>>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct AdditionalImport {
   pub import_path: Option<String>,
   pub rewrite_to: Option<String>,
 }
 
+/// Configuration for converting event bus usage to a mitt-based composable.
+#[derive(Debug, Clone, Serialize)]
+pub struct EventBusConfig {
+  /// Import path of the composable (e.g. "@/composables/useEventBus")
+  pub import_path: String,
+  /// Name of the composable function (e.g. "useEventBus")
+  pub composable: String,
+  /// Variable the bus is bound to in setup (e.g. "bus")
+  pub variable: String,
+  /// Instance properties used as a bus, e.g. "$root" or "$bus" from `Vue.prototype.$bus`
+  pub globals: Vec<String>,
+}
+
+impl Default for EventBusConfig {
+  fn default() -> Self {
+    Self {
+      import_path: "@/composables/useEventBus".to_string(),
+      composable: "useEventBus".to_string(),
+      variable: "bus".to_string(),
+      globals: vec![
+        "$root".to_string(),
+        "$bus".to_string(),
+        "$eventBus".to_string(),
+      ],
+    }
+  }
+}
+
+/// Configuration for converting a date plugin installed on the prototype (e.g. vue-moment).
+#[derive(Debug, Clone, Serialize)]
+pub struct DatePluginConfig {
+  /// Instance property of the plugin (e.g. "$moment")
+  pub property: String,
+  /// Template filter registered by the plugin (e.g. "moment" for `{{ date | moment('LL') }}`)
+  pub filter: String,
+  /// Import path of the library or composable (e.g. "dayjs")
+  pub import_path: String,
+  /// Function the plugin calls are converted to (e.g. "dayjs")
+  pub name: String,
+  /// Composable returning the function; the library's default export is imported when unset
+  pub composable: Option<String>,
+}
+
+impl Default for DatePluginConfig {
+  fn default() -> Self {
+    Self {
+      property: "$moment".to_string(),
+      filter: "moment".to_string(),
+      import_path: "dayjs".to_string(),
+      name: "dayjs".to_string(),
+      composable: None,
+    }
+  }
+}
+
+/// Configuration for the HTTP client `$axios` calls are converted to.
+#[derive(Debug, Clone, Serialize)]
+pub struct HttpConfig {
+  /// Import path of the composable or the shared client (e.g. "@/composables/useHttp")
+  pub import_path: String,
+  /// Variable the `$axios` calls are converted to (e.g. "http")
+  pub name: String,
+  /// Composable returning the client; the default export of `import_path` is imported when unset
+  pub composable: Option<String>,
+}
+
+impl HttpConfig {
+  /// Keep axios, importing its default export (or a shared instance) as `axios`
+  pub fn keep_axios() -> Self {
+    Self {
+      import_path: "axios".to_string(),
+      name: "axios".to_string(),
+      composable: None,
+    }
+  }
+}
+
+impl Default for HttpConfig {
+  fn default() -> Self {
+    Self {
+      import_path: "@/composables/useHttp".to_string(),
+      name: "http".to_string(),
+      composable: Some("useHttp".to_string()),
+    }
+  }
+}
+
+/// Configuration for converting `this.$nuxt.error()`.
+#[derive(Debug, Clone, Serialize)]
+pub struct NuxtErrorConfig {
+  /// Function the calls are converted to (e.g. "showError")
+  pub name: String,
+  /// Import path of the function; it is destructured from `useNuxtCompat()` when unset
+  pub import_path: Option<String>,
+}
+
+impl Default for NuxtErrorConfig {
+  fn default() -> Self {
+    Self {
+      name: "showError".to_string(),
+      import_path: None,
+    }
+  }
+}
+
+/// Configuration for mapping Vue 2 event names to their Vue 3 equivalents in `$emit` calls.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct EmitMappingConfig {
+  /// Event renames (e.g. "input" -> "update:modelValue"), added to the default
+  /// "input" -> "update:value" mapping
+  pub events: HashMap<String, String>,
+  /// Name the `value` prop is renamed to (e.g. "modelValue"), kept as `value` when unset
+  pub model_prop: Option<String>,
+  /// Overrides for single components, by their `name` option
+  pub components: HashMap<String, EmitMappingConfig>,
+}
+
+/// Members of components exposed to their parents with `defineExpose()`. `<script setup>`
+/// components are closed by default, so methods parents call through template refs
+/// (`this.$refs.modal.open()`) have to be exposed.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ExposeConfig {
+  /// Members to expose by pattern of the component's `name` option, `*` matching any characters
+  /// (e.g. "*Modal" -> ["open", "close"])
+  pub components: HashMap<String, Vec<String>>,
+  /// Also expose the methods a component never calls itself, which parents presumably call
+  pub auto: bool,
+  /// Members to expose whatever the component's name, e.g. the ones its parents were found to
+  /// use with [`project::ref_member_uses`]
+  pub members: Vec<String>,
+}
+
+/// Configuration for grouping and ordering the imports of the generated script.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ImportOrderConfig {
+  /// Import groups in output order. Each group is a regex matched against the import source, or
+  /// one of the special groups "type" (`import type` statements) and "side-effect" (imports
+  /// without bindings, e.g. stylesheets), which take precedence over the patterns. Imports
+  /// matching no group are placed last. Within a group the default order is kept.
+  pub groups: Vec<String>,
+  /// Separate the groups with a blank line
+  pub separate_groups: bool,
+}
+
+/// Output of a single SFC rewrite together with the metadata collected while transforming it.
+#[derive(Debug, Clone, Default)]
+pub struct RewriteReport {
+  /// The rewritten SFC
+  pub code: String,
+  /// Emitted events renamed by the conversion as (vue2_event, vue3_event) pairs
+  pub event_renames: Vec<(String, String)>,
+  /// Sidecar files generated by the conversion, relative to the output root
+  pub additional_files: Vec<(PathBuf, String)>,
+  /// Findings that need the author's attention (the conversion itself is not affected)
+  pub diagnostics: Vec<Diagnostic>,
+}
+
+/// Severity of a [`Diagnostic`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiagnosticSeverity {
+  Info,
+  Warning,
+}
+
+/// A finding reported during conversion, e.g. code that converts but may behave differently
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Diagnostic {
+  pub severity: DiagnosticSeverity,
+  /// Stable identifier of the check (e.g. "impure-computed")
+  pub code: String,
+  pub message: String,
+  /// Migration guide page explaining the change, if any
+  pub url: Option<String>,
+}
+
+impl Diagnostic {
+  pub fn warning(code: &str, message: impl Into<String>) -> Self {
+    Self {
+      severity: DiagnosticSeverity::Warning,
+      code: code.to_string(),
+      message: message.into(),
+      url: None,
+    }
+  }
+
+  pub fn info(code: &str, message: impl Into<String>) -> Self {
+    Self {
+      severity: DiagnosticSeverity::Info,
+      code: code.to_string(),
+      message: message.into(),
+      url: None,
+    }
+  }
+
+  /// Link the diagnostic to the migration guide
+  pub fn with_url(mut self, url: &str) -> Self {
+    self.url = Some(url.to_string());
+    self
+  }
+}
+
 pub fn rewrite_sfc(
   sfc: &str,
   options: Option<RewriteOptions>,
 ) -> Result<String, Box<dyn std::error::Error>> {
+  rewrite_sfc_with_report(sfc, options).map(|report| report.code)
+}
+
+/// Input of [`parse_component`]: the source of an SFC, or its sections when a custom step already
+/// split (and possibly changed) them
+#[derive(Debug, Clone)]
+pub enum ParsedInput<'a> {
+  Source(&'a str),
+  Sections(SfcSections),
+}
+
+impl<'a> From<&'a str> for ParsedInput<'a> {
+  fn from(sfc: &'a str) -> Self {
+    ParsedInput::Source(sfc)
+  }
+}
+
+impl<'a> From<&'a String> for ParsedInput<'a> {
+  fn from(sfc: &'a String) -> Self {
+    ParsedInput::Source(sfc)
+  }
+}
+
+impl From<SfcSections> for ParsedInput<'_> {
+  fn from(sections: SfcSections) -> Self {
+    ParsedInput::Sections(sections)
+  }
+}
+
+/// Parses an SFC into the component model the transformers work on, without rewriting it.
+///
+/// The model serializes to JSON, which is useful for debugging configs and for external tooling.
+/// Together with [`apply_transformers`] and [`assemble_sfc`] it makes up [`rewrite_sfc`], so custom
+/// steps can run in between.
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
+pub fn parse_component<'a>(
+  input: impl Into<ParsedInput<'a>>,
+) -> Result<TransformationContext, Box<dyn std::error::Error>> {
   // Parse the SFC sections
-  let sections = parse_sfc_sections(sfc)?;
+  let sections = match input.into() {
+    ParsedInput::Source(sfc) => parse_sfc_sections(sfc)?,
+    ParsedInput::Sections(sections) => sections,
+  };
 
   // Initialize parsing states
   let mut script_state = ScriptParsingState::new();
@@ -58,204 +542,1419 @@ pub fn rewrite_sfc(
     parse_template_section(template_content, &mut template_state)?;
   }
 
-  // Create transformation context
-  let transformation_context = TransformationContext {
+  Ok(TransformationContext {
     script_state,
     template_state,
-    sfc_sections: sections.clone(),
+    sfc_sections: sections,
+  })
+}
+
+/// Builds the `mixins` config entry of a Vue 2 mixin file from its data, computed properties and
+/// methods. The composable is named after the file, without a `mixin` suffix (`price-mixin` becomes
+/// `usePrice`).
+pub fn scaffold_mixin_config(
+  file_stem: &str,
+  source: &str,
+) -> Result<MixinConfig, Box<dyn std::error::Error>> {
+  let mut state = ScriptParsingState::new();
+  parse_script_section(source, &mut state)?;
+
+  let mut imports: Vec<String> = Vec::new();
+  let members = state
+    .data_properties
+    .into_iter()
+    .map(|property| property.name)
+    .chain(state.computed_properties)
+    .chain(state.methods);
+  for member in members {
+    if !imports.contains(&member) {
+      imports.push(member);
+    }
+  }
+
+  let pascal = project::component_tag_names(file_stem)
+    .into_iter()
+    .next()
+    .unwrap_or_default();
+  let base = pascal
+    .strip_suffix("Mixin")
+    .filter(|base| !base.is_empty())
+    .unwrap_or(&pascal);
+
+  Ok(MixinConfig {
+    name: format!("use{}", base),
+    imports,
+  })
+}
+
+/// A composable generated from a Vue 2 mixin file
+#[derive(Debug, Clone, Default)]
+pub struct MixinComposable {
+  /// Name of the composable, e.g. `usePrice`
+  pub name: String,
+  /// Parameters the composable expects from the component using it (`props`, `emit`)
+  pub parameters: Vec<String>,
+  /// Source of the composable module
+  pub code: String,
+  /// Findings that need the author's attention
+  pub diagnostics: Vec<Diagnostic>,
+}
+
+/// Converts a Vue 2 mixin file to a composable returning the mixin's data, computed properties and
+/// methods, named like [`scaffold_mixin_config`] names it.
+///
+/// The mixin is converted like a component. Its imports stay at module level, the rest of the
+/// setup code moves into the composable function, and `defineProps()` / `defineEmits()` become the
+/// `props` and `emit` parameters of the composable.
+pub fn convert_mixin(
+  file_stem: &str,
+  source: &str,
+  options: Option<RewriteOptions>,
+) -> Result<MixinComposable, Box<dyn std::error::Error>> {
+  let mixin_config = scaffold_mixin_config(file_stem, source)?;
+  let report = rewrite_sfc_with_report(&format!("<script>\n{}\n</script>\n", source), options)?;
+  let script = parse_sfc_sections(&report.code)?
+    .script_content
+    .unwrap_or_default();
+
+  // Imports are the leading lines up to the first statement
+  let mut import_end = 0;
+  let mut in_import = false;
+  for line in script.split_inclusive('\n') {
+    let trimmed = line.trim();
+    if in_import || trimmed.starts_with("import ") {
+      in_import = !trimmed.ends_with(';') && !trimmed.contains(" from ");
+    } else if !trimmed.is_empty() {
+      break;
+    }
+    import_end += line.len();
+  }
+  let imports = script[..import_end].trim();
+  let mut body = script[import_end..].to_string();
+
+  let mut parameters = Vec::new();
+  for (parameter, macro_name) in [("props", "defineProps"), ("emit", "defineEmits")] {
+    let declaration = format!("const {} = {}(", parameter, macro_name);
+    if let Some(start) = body.find(&declaration) {
+      let open = start + declaration.len() - 1;
+      if let Some(close) = transformers::body_transforms::argument_bounds(&body, open)
+        .and_then(|bounds| bounds.last().copied())
+      {
+        let mut end = close + 1;
+        if body[end..].starts_with(';') {
+          end += 1;
+        }
+        if body[end..].starts_with('\n') {
+          end += 1;
+        }
+        body.replace_range(start..end, "");
+        parameters.push(parameter.to_string());
+        continue;
+      }
+    }
+
+    let used = Regex::new(&format!(r"(^|[^\w$.]){}\b", parameter))
+      .is_ok_and(|pattern| pattern.is_match(&body));
+    if used {
+      parameters.push(parameter.to_string());
+    }
+  }
+
+  let returned: Vec<&str> = mixin_config
+    .imports
+    .iter()
+    .map(|member| member.as_str())
+    .filter(|member| body.contains(&format!("const {} ", member)))
+    .collect();
+  let return_statement = if returned.join(", ").len() <= 80 {
+    format!("  return {{ {} }};", returned.join(", "))
+  } else {
+    format!("  return {{\n    {},\n  }};", returned.join(",\n    "))
   };
 
-  // Configure transformers - enable all by default for now
-  let mut config = TransformerConfig {
-    enable_i18n: true,
-    enable_asset_transforms: true,
-    ..Default::default()
+  let indented: Vec<String> = body
+    .trim()
+    .lines()
+    .map(|line| {
+      if line.is_empty() {
+        String::new()
+      } else {
+        format!("  {}", line)
+      }
+    })
+    .collect();
+
+  let mut code = String::new();
+  if !imports.is_empty() {
+    code.push_str(imports);
+    code.push_str("\n\n");
+  }
+  code.push_str(&format!(
+    "export function {}({}) {{\n",
+    mixin_config.name,
+    parameters.join(", ")
+  ));
+  if !indented.is_empty() {
+    code.push_str(&indented.join("\n"));
+    code.push_str("\n\n");
+  }
+  code.push_str(&return_statement);
+  code.push_str("\n}\n");
+
+  Ok(MixinComposable {
+    name: mixin_config.name,
+    parameters,
+    code,
+    diagnostics: report.diagnostics,
+  })
+}
+
+/// Rewrites an SFC like [`rewrite_sfc`], but also returns the metadata needed for
+/// project-wide follow-up passes (e.g. renamed events).
+#[cfg_attr(
+  feature = "tracing",
+  tracing::instrument(level = "debug", skip_all, fields(bytes = sfc.len()))
+)]
+pub fn rewrite_sfc_with_report(
+  sfc: &str,
+  options: Option<RewriteOptions>,
+) -> Result<RewriteReport, Box<dyn std::error::Error>> {
+  // A `<!-- votc: ... -->` comment at the top of the file overrides the options for this file
+  let mut diagnostics = Vec::new();
+  let (sfc, overrides) = config::split_file_overrides(sfc);
+  let options = match overrides {
+    Some(overrides) => {
+      let (options, override_diagnostics) =
+        config::apply_file_overrides(options.unwrap_or_default(), overrides);
+      diagnostics.extend(override_diagnostics);
+      Some(options)
+    }
+    None => options,
   };
 
-  // Apply options if provided
-  if let Some(opts) = options {
+  // Parse the SFC into the component model
+  let transformation_context = parse_component(sfc)?;
+  let config = TransformerConfig::from(options.unwrap_or_default());
+  let transformation_result = apply_transformers(&transformation_context, &config);
+
+  let mut report = assemble_sfc(
+    transformation_result,
+    &transformation_context.sfc_sections,
+    &FormatOptions::from(&config),
+  )?;
+  diagnostics.append(&mut report.diagnostics);
+  report.diagnostics = diagnostics;
+
+  Ok(report)
+}
+
+impl From<RewriteOptions> for TransformerConfig {
+  /// The configuration [`rewrite_sfc`] runs the transformers with
+  fn from(opts: RewriteOptions) -> Self {
+    let mut config = TransformerConfig {
+      enable_i18n: true,
+      enable_asset_transforms: true,
+      enable_style_transforms: true,
+      aliases: default_aliases(),
+      ..Default::default()
+    };
+
     config.mixins = opts.mixins;
     config.imports_rewrite = opts.imports_rewrite;
     config.additional_imports = opts.additional_imports;
     config.import_keeplist = opts.import_keeplist;
+    config.event_bus = opts.event_bus;
+    config.global_properties = opts.global_properties;
+    config.instance_properties = opts.instance_properties;
+    config.date_plugin = opts.date_plugin;
+    config.enable_transition_transforms = opts.enable_transition_transforms;
+    if let Some(enabled) = opts.enable_style_transforms {
+      config.enable_style_transforms = enabled;
+    }
+    config.emit_mapping = opts.emit_mapping;
+    config.expose = opts.expose;
+    config.import_order = opts.import_order;
+    if let Some(aliases) = opts.aliases {
+      config.aliases = sorted_aliases(aliases);
+    }
+    config.asset_mode = opts.asset_mode.unwrap_or_default();
+    config.v_t_directive = opts.v_t_directive.unwrap_or_default();
+    config.set_locale = opts.set_locale.unwrap_or_default();
+    config.nuxt_i18n = opts.nuxt_i18n.unwrap_or_default();
+    config.http = opts.http.unwrap_or_default();
+    config.axios_data_helper = opts.axios_data_helper;
+    config.target = opts.target.unwrap_or_default();
+    config.ssr_target = opts.ssr_target;
+    config.nuxt_error = opts.nuxt_error.unwrap_or_default();
+    config.define_page_meta = opts.define_page_meta;
+    config.typescript = opts.typescript;
+    config.prefer_define_model = opts.prefer_define_model;
+    config.self_reference = opts.self_reference.unwrap_or_default();
+    config.file_name = opts.file_name;
+    config.disabled_transformers = opts.disable.unwrap_or_default();
+    config.script_style = opts.script_style.unwrap_or_default();
+    config.template_ref_style = opts.template_ref_style.unwrap_or_default();
+    config.require_components = opts.require_components.unwrap_or_default();
+    config.custom_options = opts.custom_options;
+
+    config
   }
+}
+
+/// Runs the transformers on a parsed component, returning the code, imports and template
+/// replacements [`assemble_sfc`] puts together
+pub fn apply_transformers(
+  context: &TransformationContext,
+  config: &TransformerConfig,
+) -> TransformationResult {
+  let mut context = context.clone();
+  // Transformers match import sources by their aliased path (e.g. "@/mixins/price")
+  for import_info in &mut context.script_state.imports {
+    import_info.source = rewrite_alias(&import_info.source, &config.aliases);
+  }
+  context.script_state.rename_shadowed_imports();
 
   // Apply transformations using the orchestrator
   let orchestrator = transformers::TransformerOrchestrator::new();
-  let transformation_result = orchestrator.transform(&transformation_context, &config);
+  let mut transformation_result = orchestrator.transform(&context, config);
+  let available_transformers = orchestrator.available_transformers();
+  let unknown_transformers = config
+    .disabled_transformers
+    .iter()
+    .filter(|name| !available_transformers.contains(&name.as_str()))
+    .map(|name| {
+      Diagnostic::warning(
+        "unknown-transformer",
+        format!(
+          "`{}` is not a transformer and was not disabled, available transformers: {}",
+          name,
+          available_transformers.join(", ")
+        ),
+      )
+    });
+  transformation_result
+    .diagnostics
+    .splice(0..0, unknown_transformers);
+
+  // What `assemble_sfc` needs from the original component, so it does not parse it again
+  transformation_result.registered_components = context
+    .script_state
+    .components
+    .iter()
+    .flat_map(|registration| [registration.name.clone(), registration.value.trim().to_string()])
+    .collect();
+  transformation_result.original_references =
+    original_references(&context, &transformation_result.imports_to_add);
+
+  transformation_result
+}
 
-  // Build the final SFC
-  let mut result_sfc = String::new();
+/// The bindings of the imports to add that the original component references outside its imports
+fn original_references(
+  context: &TransformationContext,
+  imports_to_add: &BTreeMap<String, Vec<String>>,
+) -> Vec<String> {
+  let sections = &context.sfc_sections;
+  let script = sections
+    .script_content
+    .as_deref()
+    .map(|script| SOURCE_IMPORT_PATTERN.replace_all(script, ""));
+  let sources: Vec<&str> = [
+    sections.template_content.as_deref(),
+    script.as_deref(),
+    sections.style_content.as_deref(),
+  ]
+  .into_iter()
+  .flatten()
+  .chain(sections.custom_blocks.iter().map(String::as_str))
+  .collect();
+
+  let mut references: Vec<String> = Vec::new();
+  for binding in format_imports(imports_to_add)
+    .iter()
+    .flat_map(|statement| import_bindings(statement))
+  {
+    if !references.contains(&binding)
+      && sources
+        .iter()
+        .any(|source| is_binding_used(&binding, source))
+    {
+      references.push(binding);
+    }
+  }
+  references
+}
 
-  // Add template section
-  if let Some(template_content) = &sections.template_content {
-    let mut final_template = template_content.clone();
+/// Output settings of [`assemble_sfc`]
+#[derive(Debug, Clone)]
+pub struct FormatOptions {
+  pub script_style: ScriptStyle,
+  pub target: Target,
+  /// Output `lang="ts"` scripts
+  pub typescript: bool,
+  /// Path aliases rewritten in the script, longest alias first
+  pub aliases: Vec<(String, String)>,
+  /// Import sources kept even when the output does not reference them
+  pub import_keeplist: Vec<String>,
+  pub import_order: Option<ImportOrderConfig>,
+}
 
-    // Apply template replacements
-    for replacement in &transformation_result.template_replacements {
-      final_template = final_template.replace(&replacement.find, &replacement.replace);
+impl From<&TransformerConfig> for FormatOptions {
+  fn from(config: &TransformerConfig) -> Self {
+    FormatOptions {
+      script_style: config.script_style,
+      target: config.target,
+      typescript: config.typescript,
+      aliases: config.aliases.clone(),
+      import_keeplist: config.import_keeplist.clone().unwrap_or_default(),
+      import_order: config.import_order.clone(),
     }
+  }
+}
+
+/// Puts the SFC together from the sections of the original component and the result of
+/// [`apply_transformers`]: the template with its replacements applied, the script with the imports
+/// the output still references, the rewritten style and the custom blocks.
+///
+/// Imports are only dropped when they are in the `original_references` of the result, the
+/// bindings [`apply_transformers`] found the original component using.
+pub fn assemble_sfc(
+  mut transformation_result: TransformationResult,
+  sections: &SfcSections,
+  format: &FormatOptions,
+) -> Result<RewriteReport, Box<dyn std::error::Error>> {
+  let mut diagnostics = Vec::new();
+
+  // Build the final SFC
+  let _generate = trace_span!("generate");
+  let mut result_sfc = String::new();
 
+  // Add template section
+  let final_template = sections.template_content.as_ref().map(|template_content| {
+    let _template = trace_span!("template");
+    let (final_template, conflicts) = apply_template_replacements(
+      template_content,
+      &transformation_result.template_replacements,
+    );
+    diagnostics.extend(conflicts);
+    final_template
+  });
+  if let Some(final_template) = &final_template {
     result_sfc.push_str("<template>\n");
-    result_sfc.push_str(&final_template);
+    result_sfc.push_str(final_template);
     result_sfc.push_str("\n</template>\n");
   }
 
   // Add script setup section
-  result_sfc.push_str("<script setup>\n");
+  let setup_attribute = match format.script_style {
+    ScriptStyle::SetupSugar => " setup",
+    ScriptStyle::SetupFunction => "",
+  };
+  match &sections.script_lang {
+    Some(lang) if !format.typescript || lang != "js" => {
+      result_sfc.push_str(&format!("<script{} lang=\"{}\">\n", setup_attribute, lang))
+    }
+    _ if format.typescript => {
+      result_sfc.push_str(&format!("<script{} lang=\"ts\">\n", setup_attribute))
+    }
+    _ => result_sfc.push_str(&format!("<script{}>\n", setup_attribute)),
+  }
 
-  // Add imports
-  let formatted_imports = format_imports(&transformation_result.imports_to_add);
-  for import in &formatted_imports {
-    result_sfc.push_str(import);
+  // Imports are inserted here once the rest of the output is known, so unused ones can be dropped
+  let imports_position = result_sfc.len();
+
+  // Vue 2.7 has no `defineOptions()`, the options are the default export of a normal `<script>`
+  if format.target == Target::Vue27
+    && format.script_style == ScriptStyle::SetupSugar
+    && !transformation_result.define_options.is_empty()
+  {
+    let mut contents: Vec<String> = transformation_result
+      .additional_scripts
+      .drain(..)
+      .map(|script| script_block_content(&script).to_string())
+      .collect();
+    contents.push(format!(
+      "export default {{ {} }};",
+      std::mem::take(&mut transformation_result.define_options).join(", ")
+    ));
+    transformation_result
+      .additional_scripts
+      .push(format!("<script>\n{}\n</script>", contents.join("\n\n")));
+  }
+
+  // Add structured code sections in the correct order: component options, setup code
+  // (composables, stores, props, ...), reactive state, computed properties, watchers, methods,
+  // lifecycle hooks and exposed members
+  let code_groups = match format.script_style {
+    ScriptStyle::SetupSugar => {
+      let define_options: Vec<String> = match transformation_result.define_options.is_empty() {
+        true => Vec::new(),
+        false => vec![format!(
+          "defineOptions({{ {} }});",
+          transformation_result.define_options.join(", ")
+        )],
+      };
+      let expose: Vec<String> = match transformation_result.exposed.is_empty() {
+        true => Vec::new(),
+        false => vec![format!(
+          "defineExpose({{ {} }});",
+          transformation_result.exposed.join(", ")
+        )],
+      };
+      format_code_groups(&[
+        &define_options,
+        &transformation_result.setup,
+        &transformation_result.reactive_state,
+        &transformation_result.computed_properties,
+        &transformation_result.watchers,
+        &transformation_result.methods,
+        &transformation_result.lifecycle_hooks,
+        &expose,
+      ])
+    }
+    ScriptStyle::SetupFunction => {
+      transformation_result.add_import("vue", "defineComponent");
+      // Registered components are resolved through the `components` option, not returned
+      setup_function_script(
+        &transformation_result,
+        final_template.as_deref().unwrap_or_default(),
+      )
+    }
+  };
+  result_sfc.push_str(&rewrite_path_aliases(&code_groups, &format.aliases));
+
+  result_sfc.push_str("</script>");
+
+  // Add additional script blocks (with path rewriting), which the setup function inlines
+  let additional_scripts = match format.script_style {
+    ScriptStyle::SetupSugar => transformation_result.additional_scripts.as_slice(),
+    ScriptStyle::SetupFunction => &[],
+  };
+  for script_block in additional_scripts {
     result_sfc.push('\n');
+    result_sfc.push_str(&rewrite_path_aliases(script_block, &format.aliases));
+  }
+
+  // Add style section if present
+  let style_content = transformation_result
+    .style_content
+    .as_ref()
+    .or(sections.style_content.as_ref());
+  if let Some(style_content) = style_content {
+    result_sfc.push_str("\n<style");
+    if let Some(attributes) = &sections.style_attributes {
+      result_sfc.push(' ');
+      result_sfc.push_str(attributes);
+    }
+    result_sfc.push_str(">\n");
+    result_sfc.push_str(style_content);
+    result_sfc.push_str("\n</style>");
+  }
+
+  // Add imports that are still referenced by the output
+  let mut formatted_imports = audit_imports(
+    format_imports(&transformation_result.imports_to_add),
+    &result_sfc,
+    &transformation_result,
+    &format.import_keeplist,
+  );
+  if let Some(import_order) = &format.import_order {
+    formatted_imports = order_imports(formatted_imports, import_order)?;
+  }
+  let mut imports_block = String::new();
+  for import in &formatted_imports {
+    imports_block.push_str(&rewrite_path_aliases(import, &format.aliases));
+    imports_block.push('\n');
   }
 
   if !formatted_imports.is_empty() {
+    imports_block.push('\n');
+  }
+  result_sfc.insert_str(imports_position, &imports_block);
+
+  // Custom blocks are kept as-is
+  for block in &sections.custom_blocks {
     result_sfc.push('\n');
+    result_sfc.push_str(block);
   }
 
-  // Add structured code sections in the correct order
-  let mut sections_added = false;
+  Ok(RewriteReport {
+    code: result_sfc,
+    event_renames: transformation_result.event_renames,
+    additional_files: transformation_result.additional_files,
+    diagnostics: [transformation_result.diagnostics, diagnostics].concat(),
+  })
+}
+
+/// Joins the code groups of the script with exactly one blank line between them.
+///
+/// Blank entries pushed by transformers separate related declarations within a group (e.g.
+/// `defineProps()` and `defineEmits()` in the setup code). They are kept as a single blank line,
+/// and dropped at the start and end of a group.
+fn format_code_groups(groups: &[&Vec<String>]) -> String {
+  let mut formatted_groups = Vec::new();
+
+  for group in groups {
+    let mut lines: Vec<&str> = Vec::new();
+    for entry in group.iter() {
+      let entry = entry.trim_end_matches('\n');
+      let is_separator = entry.trim().is_empty();
+      if is_separator && lines.last().is_none_or(|last| last.is_empty()) {
+        continue;
+      }
+      lines.push(if is_separator { "" } else { entry });
+    }
+    while lines.last().is_some_and(|last| last.is_empty()) {
+      lines.pop();
+    }
 
-  // 1. Setup code (composables, stores, router, etc.)
-  if !transformation_result.setup.is_empty() {
-    for line in &transformation_result.setup {
-      // Rewrite ~/ to @/ in dynamic imports
-      let rewritten_line = line.replace("'~/", "'@/").replace("\"~/", "\"@/");
-      result_sfc.push_str(&rewritten_line);
-      result_sfc.push('\n');
+    if !lines.is_empty() {
+      formatted_groups.push(format!("{}\n", lines.join("\n")));
     }
-    sections_added = true;
   }
 
-  // 2. Reactive state (ref and reactive declarations)
-  if !transformation_result.reactive_state.is_empty() {
-    if sections_added {
-      result_sfc.push('\n');
+  formatted_groups.join("\n")
+}
+
+/// Assembles the script as `export default defineComponent({ ... })`, with the code of
+/// `<script setup>` inside `setup()`.
+///
+/// `defineProps()` and `defineEmits()` become the `props` and `emits` options, the options of
+/// `defineOptions()` are kept as component options, and `setup()` returns the top-level bindings
+/// and imports the template uses, except the props and the components registered in the
+/// `components` option. The content of additional script blocks is kept before the component.
+fn setup_function_script(result: &TransformationResult, template: &str) -> String {
+  let mut setup = result.setup.join("\n");
+  let (props_binding, props) = take_macro_call(&mut setup, "defineProps");
+  let (emit_binding, emits) = take_macro_call(&mut setup, "defineEmits");
+  let setup: Vec<String> = setup.lines().map(str::to_string).collect();
+  let expose: Vec<String> = match result.exposed.is_empty() {
+    true => Vec::new(),
+    false => vec![format!("expose({{ {} }});", result.exposed.join(", "))],
+  };
+  let body = format_code_groups(&[
+    &setup,
+    &result.reactive_state,
+    &result.computed_properties,
+    &result.watchers,
+    &result.methods,
+    &result.lifecycle_hooks,
+    &expose,
+  ]);
+
+  // Bindings the template can use: top-level declarations, imports and the `emit` function
+  let mut bindings: Vec<String> = Vec::new();
+  for caps in TOP_LEVEL_DECLARATION_PATTERN.captures_iter(&body) {
+    match (caps.get(1), caps.get(2)) {
+      (Some(name), _) => bindings.push(name.as_str().to_string()),
+      (None, Some(pattern)) => bindings.extend(
+        pattern
+          .as_str()
+          .split(',')
+          .filter_map(|part| IDENTIFIER_PATTERN.find_iter(part).last())
+          .map(|name| name.as_str().to_string()),
+      ),
+      _ => {}
+    }
+  }
+  let declarations = bindings.clone();
+  for statement in format_imports(&result.imports_to_add) {
+    let Some(caps) = IMPORT_STATEMENT_PATTERN.captures(statement.trim()) else {
+      continue;
+    };
+    if caps[1].trim().starts_with("type ") {
+      continue;
     }
-    for line in &transformation_result.reactive_state {
-      // Rewrite ~/ to @/ in dynamic imports
-      let rewritten_line = line.replace("'~/", "'@/").replace("\"~/", "\"@/");
-      result_sfc.push_str(&rewritten_line);
-      result_sfc.push('\n');
+    bindings.extend(
+      caps[1]
+        .split([',', '{', '}'])
+        .map(str::trim)
+        .filter(|part| !part.starts_with("type "))
+        .filter_map(|part| part.split_whitespace().last())
+        .map(str::to_string),
+    );
+  }
+  if let Some(emit) = &emit_binding {
+    bindings.push(emit.clone());
+  }
+  let usage = TemplateUsage::parse(template);
+  let mut returned: Vec<String> = Vec::new();
+  for binding in bindings {
+    let is_returned = !returned.contains(&binding)
+      && Some(&binding) != props_binding.as_ref()
+      && !result.registered_components.contains(&binding)
+      && match &usage {
+        Some(usage) => usage.uses(&binding, declarations.contains(&binding)),
+        // A template that can not be parsed is searched for the name
+        None => is_binding_used(&binding, template),
+      };
+    if is_returned {
+      returned.push(binding);
+    }
+  }
+
+  // `setup(props, { emit, expose })`, with the parameters the body uses
+  let mut context: Vec<String> = Vec::new();
+  match emit_binding.as_deref() {
+    Some("emit") => context.push("emit".to_string()),
+    Some(emit) => context.push(format!("emit: {}", emit)),
+    None => {}
+  }
+  if !result.exposed.is_empty() {
+    context.push("expose".to_string());
+  }
+  let props_parameter = props_binding.as_deref().unwrap_or("props");
+  let parameters = match (props_binding.is_some(), context.is_empty()) {
+    (_, false) => format!("{}, {{ {} }}", props_parameter, context.join(", ")),
+    (true, true) => props_parameter.to_string(),
+    (false, true) => String::new(),
+  };
+
+  let mut options: Vec<String> = result.define_options.clone();
+  if let Some(props) = props {
+    options.push(format!("props: {}", props));
+  }
+  if let Some(emits) = emits {
+    options.push(format!("emits: {}", emits));
+  }
+
+  let mut body = indent(&body, "    ");
+  if !returned.is_empty() {
+    if !body.is_empty() {
+      body.push('\n');
     }
-    sections_added = true;
+    body.push_str(&format!("    return {{ {} }};\n", returned.join(", ")));
+  }
+
+  let mut code = String::new();
+  for script in &result.additional_scripts {
+    code.push_str(script_block_content(script));
+    code.push_str("\n\n");
+  }
+  code.push_str("export default defineComponent({\n");
+  for option in options {
+    code.push_str(&format!("{},\n", indent(&option, "  ").trim_end()));
+  }
+  code.push_str(&format!("  setup({}) {{\n{}  }},\n", parameters, body));
+  code.push_str("});\n");
+  code
+}
+
+/// What a template refers to, read from its expression layer instead of its text, so names that
+/// only appear in text, classes or other attributes do not count
+#[derive(Default)]
+struct TemplateUsage {
+  /// Identifiers read by mustaches and directive values, without loop variables and slot props
+  identifiers: Vec<String>,
+  /// Element names, lowercase
+  tags: Vec<String>,
+  /// Directive names without argument and modifiers (`v-b-toggle` for `v-b-toggle:collapse.a`)
+  directives: Vec<String>,
+  /// Values of static `ref` attributes
+  refs: Vec<String>,
+}
+
+impl TemplateUsage {
+  /// The usage of `template`, `None` when it can not be parsed
+  fn parse(template: &str) -> Option<Self> {
+    use lol_html::{element, rewrite_str, RewriteStrSettings};
+    use std::sync::{Arc, Mutex};
+
+    let mut state = TemplateParsingState::new();
+    parse_template_section(template, &mut state).ok()?;
+
+    let usage: Arc<Mutex<Self>> = Arc::default();
+    let usage_ref = Arc::clone(&usage);
+    let element_content_handlers = vec![element!("*", move |el| {
+      let mut usage = usage_ref.lock().unwrap();
+      usage.tags.push(el.tag_name().to_lowercase());
+      for attr in el.attributes() {
+        let name = attr.name();
+        if name == "ref" {
+          usage.refs.push(attr.value());
+        } else if name.starts_with("v-") {
+          let directive = name.split([':', '.']).next().unwrap_or_default();
+          usage.directives.push(directive.to_string());
+        }
+      }
+      Ok(())
+    })];
+    rewrite_str(
+      template,
+      RewriteStrSettings {
+        element_content_handlers,
+        ..RewriteStrSettings::new()
+      },
+    )
+    .ok()?;
+
+    let mut usage = std::mem::take(&mut *usage.lock().unwrap());
+    usage.identifiers = state.identifiers;
+    Some(usage)
   }
 
-  // 3. Computed properties
-  if !transformation_result.computed_properties.is_empty() {
-    if sections_added {
-      result_sfc.push('\n');
+  /// Whether the template uses `binding`: reads it, renders it as a component or directive, or,
+  /// for a template ref declared in setup (`is_declared`), names it in a `ref` attribute
+  fn uses(&self, binding: &str, is_declared: bool) -> bool {
+    if self
+      .identifiers
+      .iter()
+      .any(|identifier| identifier == binding)
+      || (is_declared && self.refs.iter().any(|name| name == binding))
+    {
+      return true;
+    }
+
+    // Components are rendered as `<MyButton>` or `<my-button>`
+    if binding.chars().next().is_some_and(char::is_uppercase) {
+      let tag = transformers::import_rewrite::to_kebab_case(binding);
+      return self
+        .tags
+        .iter()
+        .any(|name| *name == tag || name.eq_ignore_ascii_case(binding));
     }
-    for line in &transformation_result.computed_properties {
-      // Rewrite ~/ to @/ in dynamic imports
-      let rewritten_line = line.replace("'~/", "'@/").replace("\"~/", "\"@/");
-      result_sfc.push_str(&rewritten_line);
-      result_sfc.push('\n');
+
+    // Directive objects follow the vFocus naming convention
+    if let Some(directive) = binding.strip_prefix('v') {
+      if directive.chars().next().is_some_and(char::is_uppercase) {
+        let directive_name = format!(
+          "v-{}",
+          transformers::import_rewrite::to_kebab_case(directive)
+        );
+        return self.directives.contains(&directive_name);
+      }
     }
-    sections_added = true;
+
+    false
   }
+}
+
+/// Code of a `<script>` block generated by a transformer
+fn script_block_content(block: &str) -> &str {
+  block
+    .trim()
+    .trim_start_matches("<script>")
+    .trim_end_matches("</script>")
+    .trim()
+}
+
+/// Removes the `defineProps()` or `defineEmits()` statement from `code`, returning the variable it
+/// was assigned to and its argument
+fn take_macro_call(code: &mut String, name: &str) -> (Option<String>, Option<String>) {
+  let Some(caps) = MACRO_CALL_PATTERN
+    .captures_iter(code)
+    .find(|caps| &caps[2] == name)
+  else {
+    return (None, None);
+  };
+  let start = caps.get(0).map_or(0, |m| m.start());
+  let open = caps.get(0).map_or(0, |m| m.end()) - 1;
+  let binding = caps.get(1).map(|m| m.as_str().to_string());
+  let Some(close) = transformers::body_transforms::argument_bounds(code, open)
+    .and_then(|bounds| bounds.last().copied())
+  else {
+    return (None, None);
+  };
+
+  let argument = code[open + 1..close].trim().to_string();
+  let end = code[close..]
+    .find('\n')
+    .map_or(code.len(), |offset| close + offset + 1);
+  code.replace_range(start..end, "");
+  (binding, (!argument.is_empty()).then_some(argument))
+}
 
-  // 4. Watchers
-  if !transformation_result.watchers.is_empty() {
-    if sections_added {
-      result_sfc.push('\n');
+/// Indents every non-blank line of `code`
+fn indent(code: &str, prefix: &str) -> String {
+  code
+    .lines()
+    .map(|line| match line.trim().is_empty() {
+      true => "\n".to_string(),
+      false => format!("{}{}\n", prefix, line),
+    })
+    .collect()
+}
+
+/// The local bindings of an import statement: `a`, `ns` and `c` for
+/// `import a, * as ns, { b as c } from 'x'`
+fn import_bindings(statement: &str) -> Vec<String> {
+  let Some(caps) = IMPORT_STATEMENT_PATTERN.captures(statement.trim()) else {
+    return Vec::new();
+  };
+  let clause = caps[1].trim();
+  clause
+    .strip_prefix("type ")
+    .unwrap_or(clause)
+    .split([',', '{', '}'])
+    .map(str::trim)
+    .filter(|part| !part.is_empty())
+    .map(|part| part.rsplit(' ').next().unwrap_or(part).to_string())
+    .collect()
+}
+
+/// Removes import bindings that the conversion made unused: bindings the original SFC referenced
+/// (the `original_references` of the result) that are no longer referenced anywhere in `output`
+/// (script or template).
+///
+/// Bindings that were already unused, components registered in the `components` option, sources in
+/// `keeplist` and side-effect imports (`import 'x.css'`) are kept as-is. Statements where only some
+/// bindings are unused are rebuilt with the remaining bindings.
+fn audit_imports(
+  imports: Vec<String>,
+  output: &str,
+  transformation_result: &TransformationResult,
+  keeplist: &[String],
+) -> Vec<String> {
+  let mut result = Vec::new();
+
+  let is_needed = |local: &str| {
+    let local = local.to_string();
+    is_binding_used(&local, output)
+      || !transformation_result.original_references.contains(&local)
+      || transformation_result.registered_components.contains(&local)
+  };
+
+  for statement in imports {
+    let Some(caps) = IMPORT_STATEMENT_PATTERN.captures(statement.trim()) else {
+      result.push(statement);
+      continue;
+    };
+
+    let (type_prefix, clause) = match caps[1].trim().strip_prefix("type ") {
+      Some(clause) => ("type ", clause.trim()),
+      None => ("", caps[1].trim()),
+    };
+    let quote = &caps[2];
+    let source = &caps[3];
+
+    if keeplist.iter().any(|kept| kept == source) {
+      result.push(statement);
+      continue;
+    }
+
+    // Split `Default, * as ns, { a, b as c }` into its parts
+    let (head, named) = match (clause.find('{'), clause.rfind('}')) {
+      (Some(open), Some(close)) if open < close => (&clause[..open], Some(&clause[open + 1..close])),
+      _ => (clause, None),
+    };
+
+    let mut default_and_namespace = Vec::new();
+    for part in head.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+      let local = part.rsplit(' ').next().unwrap_or(part);
+      default_and_namespace.push((part.to_string(), is_needed(local)));
     }
-    for line in &transformation_result.watchers {
-      result_sfc.push_str(line);
-      result_sfc.push('\n');
+
+    let mut named_items = Vec::new();
+    for item in named
+      .unwrap_or_default()
+      .split(',')
+      .map(str::trim)
+      .filter(|i| !i.is_empty())
+    {
+      let local = item.rsplit(' ').next().unwrap_or(item);
+      named_items.push((item.to_string(), is_needed(local)));
+    }
+
+    let all_used = default_and_namespace
+      .iter()
+      .chain(named_items.iter())
+      .all(|(_, used)| *used);
+    if all_used {
+      result.push(statement);
+      continue;
+    }
+
+    let kept_head: Vec<String> = default_and_namespace
+      .into_iter()
+      .filter(|(_, used)| *used)
+      .map(|(part, _)| part)
+      .collect();
+    let kept_named: Vec<String> = named_items
+      .into_iter()
+      .filter(|(_, used)| *used)
+      .map(|(item, _)| item)
+      .collect();
+
+    let mut parts = kept_head;
+    if !kept_named.is_empty() {
+      parts.push(format!("{{ {} }}", kept_named.join(", ")));
+    }
+
+    if !parts.is_empty() {
+      result.push(format!(
+        "import {}{} from {}{}{};",
+        type_prefix,
+        parts.join(", "),
+        quote,
+        source,
+        quote
+      ));
     }
-    sections_added = true;
   }
 
-  // 5. Methods
-  if !transformation_result.methods.is_empty() {
-    if sections_added {
-      result_sfc.push('\n');
+  result
+}
+
+/// Checks whether an imported binding is referenced in the output, either as an identifier or,
+/// for components and directives, by its kebab-case tag (`<my-button>`) or directive (`v-b-toggle`)
+fn is_binding_used(local: &str, output: &str) -> bool {
+  let is_identifier_char = |c: char| c.is_alphanumeric() || c == '_' || c == '$';
+
+  let used_as_identifier = output.match_indices(local).any(|(index, _)| {
+    let before = &output[..index];
+    let after = &output[index + local.len()..];
+
+    let valid_start = match before.chars().next_back() {
+      Some('.') => before.ends_with("..."),
+      Some(c) => !is_identifier_char(c),
+      None => true,
+    };
+    let valid_end = after.chars().next().is_none_or(|c| !is_identifier_char(c));
+
+    valid_start && valid_end
+  });
+
+  if used_as_identifier {
+    return true;
+  }
+
+  let first_char = local.chars().next().unwrap_or_default();
+  if first_char.is_uppercase() {
+    let tag = transformers::import_rewrite::to_kebab_case(local);
+    return output.contains(&format!("<{}", tag));
+  }
+
+  // Directive objects follow the vFocus naming convention
+  if let Some(directive) = local.strip_prefix('v') {
+    if directive.chars().next().is_some_and(char::is_uppercase) {
+      let directive_name = format!("v-{}", transformers::import_rewrite::to_kebab_case(directive));
+      return output.contains(&directive_name);
     }
-    for line in &transformation_result.methods {
-      // Rewrite ~/ to @/ in dynamic imports
-      let rewritten_line = line.replace("'~/", "'@/").replace("\"~/", "\"@/");
-      result_sfc.push_str(&rewritten_line);
-      result_sfc.push('\n');
+  }
+
+  false
+}
+
+/// A replacement of a byte range of the original template
+struct TemplateEdit {
+  start: usize,
+  end: usize,
+  text: String,
+  // Index of the TemplateReplacement the edit comes from, earlier replacements win ties
+  replacement: usize,
+}
+
+/// Apply all template replacements in a single pass. Matches are only searched in the original
+/// template, so replaced text is never matched again. Overlapping matches are resolved
+/// leftmost-longest (`<b-navbar-next` wins over `<b-nav`); partial overlaps and different
+/// replacements of the same text are reported as conflicts.
+///
+/// `Expressions` and `Identifiers` replacements only apply inside mustache expressions and
+/// directive values, so plain text and HTML comments mentioning e.g. `$t(` are left untouched.
+/// Identifiers shadowed by a loop variable or slot prop are not replaced.
+fn apply_template_replacements(
+  template: &str,
+  replacements: &[TemplateReplacement],
+) -> (String, Vec<Diagnostic>) {
+  use aho_corasick::AhoCorasick;
+
+  let mut edits: Vec<TemplateEdit> = Vec::new();
+  let indices_in = |scope: ReplacementScope| -> Vec<usize> {
+    (0..replacements.len())
+      .filter(|&index| replacements[index].scope == scope && !replacements[index].find.is_empty())
+      .collect()
+  };
+  let automaton = |indices: &[usize]| {
+    AhoCorasick::new(indices.iter().map(|&index| &replacements[index].find))
+      .expect("Error building the template replacement automaton")
+  };
+
+  let template_indices = indices_in(ReplacementScope::Template);
+  if !template_indices.is_empty() {
+    for found in automaton(&template_indices).find_overlapping_iter(template) {
+      let replacement = template_indices[found.pattern().as_usize()];
+      edits.push(TemplateEdit {
+        start: found.start(),
+        end: found.end(),
+        text: replacements[replacement].replace.clone(),
+        replacement,
+      });
     }
-    sections_added = true;
   }
 
-  // 6. Lifecycle hooks
-  if !transformation_result.lifecycle_hooks.is_empty() {
-    if sections_added {
-      result_sfc.push('\n');
+  let expression_indices = indices_in(ReplacementScope::Expressions);
+  let identifier_indices = indices_in(ReplacementScope::Identifiers);
+  if !expression_indices.is_empty() || !identifier_indices.is_empty() {
+    let expression_automaton =
+      (!expression_indices.is_empty()).then(|| automaton(&expression_indices));
+    let scopes = match identifier_indices.is_empty() {
+      true => Vec::new(),
+      false => template_scopes(template),
+    };
+
+    for caps in TEMPLATE_EXPRESSION_PATTERN.captures_iter(template) {
+      // Comments have no capture group
+      let Some(expression) = caps.get(1).or_else(|| caps.get(2)).or_else(|| caps.get(3)) else {
+        continue;
+      };
+      let offset = expression.start();
+      let expression = expression.as_str();
+      let strings = string_literal_ranges(expression);
+      let locals = scope_locals(&scopes, offset);
+
+      if let Some(expression_automaton) = &expression_automaton {
+        for found in expression_automaton.find_overlapping_iter(expression) {
+          let replacement = expression_indices[found.pattern().as_usize()];
+          let find = &replacements[replacement].find;
+          if locals.contains(find)
+            || !is_expression_match(expression, find, found.start(), &strings)
+          {
+            continue;
+          }
+          edits.push(TemplateEdit {
+            start: offset + found.start(),
+            end: offset + found.end(),
+            text: replacements[replacement].replace.clone(),
+            replacement,
+          });
+        }
+      }
+
+      if identifier_indices.is_empty() {
+        continue;
+      }
+      for identifier in IDENTIFIER_PATTERN.find_iter(expression) {
+        if locals.iter().any(|local| local == identifier.as_str()) {
+          continue;
+        }
+        for &replacement in &identifier_indices {
+          let TemplateReplacement { find, replace, .. } = &replacements[replacement];
+          if find != identifier.as_str() {
+            continue;
+          }
+          if let Some(text) = identifier_replacement(expression, identifier, replace, &strings) {
+            edits.push(TemplateEdit {
+              start: offset + identifier.start(),
+              end: offset + identifier.end(),
+              text,
+              replacement,
+            });
+          }
+        }
+      }
+    }
+  }
+
+  edits.sort_by_key(|edit| (edit.start, std::cmp::Reverse(edit.end), edit.replacement));
+
+  let mut result = String::with_capacity(template.len());
+  // (kept, dropped) replacements of overlapping edits
+  let mut conflicts: Vec<(usize, usize)> = Vec::new();
+  let mut position = 0;
+  let mut index = 0;
+  while index < edits.len() {
+    let edit = &edits[index];
+    let mut nested = Vec::new();
+    index += 1;
+    while let Some(other) = edits.get(index).filter(|other| other.start < edit.end) {
+      let is_same = other.start == edit.start && other.end == edit.end;
+      if other.replacement == edit.replacement || (is_same && other.text == edit.text) {
+        // Repeated or identical match
+      } else if other.end > edit.end || is_same {
+        conflicts.push((edit.replacement, other.replacement));
+      } else if other.start == edit.start {
+        // Shorter match of the same text, `<b-nav` in `<b-navbar-next`
+      } else {
+        nested.push(other);
+      }
+      index += 1;
     }
-    for line in &transformation_result.lifecycle_hooks {
-      // Rewrite ~/ to @/ in dynamic imports
-      let rewritten_line = line.replace("'~/", "'@/").replace("\"~/", "\"@/");
-      result_sfc.push_str(&rewritten_line);
-      result_sfc.push('\n');
+
+    trace_event!(
+      TRACE,
+      find = %replacements[edit.replacement].find,
+      replace = %edit.text,
+      scope = ?replacements[edit.replacement].scope,
+      start = edit.start,
+      "template replacement"
+    );
+    result.push_str(&template[position..edit.start]);
+    if nested.is_empty() {
+      result.push_str(&edit.text);
+    } else {
+      let (text, dropped) = rebase_nested_edits(template, edit, &nested);
+      result.push_str(&text);
+      conflicts.extend(dropped.into_iter().map(|other| (edit.replacement, other)));
     }
+    position = edit.end;
   }
+  result.push_str(&template[position..]);
 
-  result_sfc.push_str("</script>");
+  let mut diagnostics = Vec::new();
+  for (position, &(kept, dropped)) in conflicts.iter().enumerate() {
+    if conflicts[..position].contains(&(kept, dropped)) {
+      continue;
+    }
+    let (kept, dropped) = (&replacements[kept], &replacements[dropped]);
+    let message = match kept.find == dropped.find {
+      true => format!(
+        "`{}` is replaced with both `{}` and `{}` in the template, only the first was applied",
+        kept.find, kept.replace, dropped.replace
+      ),
+      false => format!(
+        "`{}` overlaps `{}` in the template and was not replaced with `{}`",
+        dropped.find, kept.find, dropped.replace
+      ),
+    };
+    diagnostics.push(Diagnostic::warning(
+      "template-replacement-conflict",
+      message,
+    ));
+  }
 
-  // Add additional script blocks (with path rewriting)
-  for script_block in &transformation_result.additional_scripts {
-    result_sfc.push('\n');
-    // Rewrite ~/ to @/ in dynamic imports
-    let rewritten_block = script_block.replace("'~/", "'@/").replace("\"~/", "\"@/");
-    result_sfc.push_str(&rewritten_block);
+  (result, diagnostics)
+}
+
+/// Carry edits nested in a larger edit over to its replacement text, where the text they match is
+/// kept: in the unchanged start or end of the replacement, or exactly once in both. Nested matches
+/// the replacement removed are dropped, the replacements of ambiguous ones are returned
+fn rebase_nested_edits(
+  template: &str,
+  outer: &TemplateEdit,
+  nested: &[&TemplateEdit],
+) -> (String, Vec<usize>) {
+  let source = &template[outer.start..outer.end];
+  let text = outer.text.as_str();
+  let prefix: usize = source
+    .chars()
+    .zip(text.chars())
+    .take_while(|(a, b)| a == b)
+    .map(|(c, _)| c.len_utf8())
+    .sum();
+  let suffix: usize = source
+    .chars()
+    .rev()
+    .zip(text.chars().rev())
+    .take_while(|(a, b)| a == b)
+    .map(|(c, _)| c.len_utf8())
+    .sum::<usize>()
+    .min(source.len().min(text.len()) - prefix);
+
+  let mut rebased: Vec<(usize, usize, &str)> = Vec::new();
+  let mut ambiguous = Vec::new();
+  for edit in nested {
+    let (start, end) = (edit.start - outer.start, edit.end - outer.start);
+    let matched = &source[start..end];
+    let rebased_start = if end <= prefix {
+      Some(start)
+    } else if start >= source.len() - suffix {
+      Some(start + text.len() - source.len())
+    } else {
+      let in_text = occurrences(text, matched);
+      match (occurrences(source, matched).len(), in_text.as_slice()) {
+        (_, []) => continue,
+        (1, [found]) => Some(*found),
+        _ => None,
+      }
+    };
+    match rebased_start {
+      Some(start) => rebased.push((start, start + matched.len(), &edit.text)),
+      None => ambiguous.push(edit.replacement),
+    }
   }
 
-  // Add style section if present
-  if let Some(style_content) = &sections.style_content {
-    result_sfc.push_str("\n<style");
-    if let Some(attributes) = &sections.style_attributes {
-      result_sfc.push(' ');
-      result_sfc.push_str(attributes);
+  rebased.sort_by_key(|(start, end, _)| (*start, std::cmp::Reverse(*end)));
+  let mut result = String::with_capacity(text.len());
+  let mut position = 0;
+  for (start, end, replacement) in rebased {
+    if start < position {
+      continue;
+    }
+    result.push_str(&text[position..start]);
+    result.push_str(replacement);
+    position = end;
+  }
+  result.push_str(&text[position..]);
+
+  (result, ambiguous)
+}
+
+/// Byte offsets of `find` in `text`, where a name at the start or end of `find` is not part of a
+/// longer name
+fn occurrences(text: &str, find: &str) -> Vec<usize> {
+  let is_name_char = |c: char| c.is_alphanumeric() || c == '_' || c == '$';
+  text
+    .match_indices(find)
+    .map(|(start, _)| start)
+    .filter(|&start| {
+      let starts_name = find.starts_with(is_name_char)
+        && text[..start].chars().next_back().is_some_and(is_name_char);
+      let ends_name =
+        find.ends_with(is_name_char) && text[start + find.len()..].starts_with(is_name_char);
+      !starts_name && !ends_name
+    })
+    .collect()
+}
+
+/// Mustache expressions and directive values of a template, without plain text and HTML comments
+pub(crate) fn template_expressions(template: &str) -> Vec<&str> {
+  TEMPLATE_EXPRESSION_PATTERN
+    .captures_iter(template)
+    .filter_map(|caps| caps.get(1).or_else(|| caps.get(2)).or_else(|| caps.get(3)))
+    .map(|expression| expression.as_str())
+    .collect()
+}
+
+/// Whether a match of `find` at `start` is outside of strings, and not part of a longer name or
+/// property access
+fn is_expression_match(
+  expression: &str,
+  find: &str,
+  start: usize,
+  strings: &[std::ops::Range<usize>],
+) -> bool {
+  let is_name_char = |c: char| c.is_alphanumeric() || c == '_' || c == '$';
+  if strings.iter().any(|range| range.contains(&start)) {
+    return false;
+  }
+
+  let before = &expression[..start];
+  let starts_name = find.starts_with(is_name_char)
+    && before
+      .chars()
+      .next_back()
+      .is_some_and(|c| is_name_char(c) || (c == '.' && !before.ends_with("...")));
+  let ends_name =
+    find.ends_with(is_name_char) && expression[start + find.len()..].starts_with(is_name_char);
+  !starts_name && !ends_name
+}
+
+/// The replacement of an identifier of an expression, `None` for property accesses, object keys
+/// and string contents. Shorthand properties keep their key
+fn identifier_replacement(
+  expression: &str,
+  identifier: regex::Match,
+  replace: &str,
+  strings: &[std::ops::Range<usize>],
+) -> Option<String> {
+  let before = expression[..identifier.start()].trim_end();
+  let after = expression[identifier.end()..].trim_start();
+  let is_property = before.ends_with('.') && !before.ends_with("...");
+  let is_key = (before.ends_with('{') || before.ends_with(','))
+    && after.starts_with(':')
+    && !after.starts_with("::");
+  let is_string = strings
+    .iter()
+    .any(|range| range.contains(&identifier.start()));
+  if is_property || is_key || is_string {
+    return None;
+  }
+
+  let is_shorthand = (before.ends_with('{') || before.ends_with(','))
+    && (after.starts_with('}') || after.starts_with(','))
+    && enclosing_bracket(expression, identifier.start(), strings) == Some('{');
+
+  match is_shorthand {
+    true => Some(format!("{}: {}", identifier.as_str(), replace)),
+    false => Some(replace.to_string()),
+  }
+}
+
+/// Byte ranges of the string literals of an expression, quotes included, without the `${}` parts
+/// of template literals
+fn string_literal_ranges(expression: &str) -> Vec<std::ops::Range<usize>> {
+  let mut ranges = Vec::new();
+  let mut quote: Option<(char, usize)> = None;
+  // Brace depths at which the `${}` parts of template literals were opened
+  let mut interpolations: Vec<usize> = Vec::new();
+  let mut depth = 0usize;
+  let mut escaped = false;
+  let mut chars = expression.char_indices().peekable();
+
+  while let Some((index, c)) = chars.next() {
+    match quote {
+      Some((q, start)) => {
+        if escaped {
+          escaped = false;
+        } else if c == '\\' {
+          escaped = true;
+        } else if c == q {
+          ranges.push(start..index + 1);
+          quote = None;
+        } else if q == '`' && c == '$' && chars.peek().is_some_and(|(_, next)| *next == '{') {
+          chars.next();
+          ranges.push(start..index);
+          interpolations.push(depth);
+          depth += 1;
+          quote = None;
+        }
+      }
+      None => match c {
+        '\'' | '"' | '`' => quote = Some((c, index)),
+        '{' => depth += 1,
+        '}' => {
+          depth = depth.saturating_sub(1);
+          if interpolations.last() == Some(&depth) {
+            interpolations.pop();
+            quote = Some(('`', index));
+          }
+        }
+        _ => {}
+      },
     }
-    result_sfc.push_str(">\n");
-    result_sfc.push_str(style_content);
-    result_sfc.push_str("\n</style>");
+  }
+  if let Some((_, start)) = quote {
+    ranges.push(start..expression.len());
   }
 
-  Ok(result_sfc)
+  ranges
+}
+
+/// The innermost bracket (`(`, `[` or `{`) still open at a byte offset of an expression
+fn enclosing_bracket(
+  expression: &str,
+  position: usize,
+  strings: &[std::ops::Range<usize>],
+) -> Option<char> {
+  let mut open = Vec::new();
+  for (index, c) in expression[..position].char_indices() {
+    if strings.iter().any(|range| range.contains(&index)) {
+      continue;
+    }
+    match c {
+      '(' | '[' | '{' => open.push(c),
+      ')' | ']' | '}' => {
+        open.pop();
+      }
+      _ => {}
+    }
+  }
+  open.last().copied()
 }
 
-/// Format the imports HashMap into a sorted list of import statements
-fn format_imports(imports_map: &HashMap<String, Vec<String>>) -> Vec<String> {
+/// Format the imports map into a sorted list of import statements
+fn format_imports(imports_map: &BTreeMap<String, Vec<String>>) -> Vec<String> {
   let mut result = Vec::new();
 
   // Convert to vec for sorting
   let mut imports: Vec<(&String, &Vec<String>)> = imports_map.iter().collect();
 
-  // Sort imports: Vue imports first, then node_modules, then relative imports
-  imports.sort_by(|(path_a, _), (path_b, _)| {
-    let a_is_vue = *path_a == "vue";
-    let b_is_vue = *path_b == "vue";
-    let a_is_relative =
-      path_a.starts_with("@/") || path_a.starts_with("./") || path_a.starts_with("../");
-    let b_is_relative =
-      path_b.starts_with("@/") || path_b.starts_with("./") || path_b.starts_with("../");
-
-    match (a_is_vue, b_is_vue, a_is_relative, b_is_relative) {
-      (true, false, _, _) => std::cmp::Ordering::Less, // Vue imports first
-      (false, true, _, _) => std::cmp::Ordering::Greater, // Vue imports first
-      (_, _, false, true) => std::cmp::Ordering::Less, // node_modules before relative
-      (_, _, true, false) => std::cmp::Ordering::Greater, // relative after node_modules
-      (_, _, true, true) => {
-        // Within relative imports, prefer stores before composables
-        let a_is_store = path_a.starts_with("@/stores/");
-        let b_is_store = path_b.starts_with("@/stores/");
-        let a_is_composable = path_a.starts_with("@/composables/");
-        let b_is_composable = path_b.starts_with("@/composables/");
-
-        match (a_is_store, b_is_store, a_is_composable, b_is_composable) {
-          (true, false, _, true) => std::cmp::Ordering::Less, // stores before composables
-          (false, true, true, _) => std::cmp::Ordering::Greater, // stores before composables
-          _ => path_a.cmp(path_b),                            // alphabetical otherwise
-        }
-      }
-      _ => path_a.cmp(path_b), // alphabetical within the same group
+  // Sort imports: Vue imports first, then node_modules, then relative imports. Within relative
+  // imports stores come right before composables, anything else is alphabetical; the key keeps
+  // the order total, so it does not depend on the order of the map
+  imports.sort_by_key(|(path, _)| {
+    let is_relative = path.starts_with("@/") || path.starts_with("./") || path.starts_with("../");
+    let group = match (path.as_str() == "vue", is_relative) {
+      (true, _) => 0,
+      (false, false) => 1,
+      (false, true) => 2,
+    };
+    if is_relative && path.starts_with("@/stores/") {
+      (group, "@/composables/", 0, path.as_str())
+    } else if is_relative && path.starts_with("@/composables/") {
+      (group, "@/composables/", 1, path.as_str())
+    } else {
+      (group, path.as_str(), 0, path.as_str())
     }
   });
 
@@ -285,14 +1984,103 @@ fn format_imports(imports_map: &HashMap<String, Vec<String>>) -> Vec<String> {
   result
 }
 
-/// Rewrite import paths to use standard aliases
-fn rewrite_import_path(path: &str) -> String {
-  // Rewrite ~/ to @/ (common Nuxt.js alias conversion)
-  if let Some(stripped) = path.strip_prefix("~/") {
-    format!("@/{}", stripped)
-  } else {
-    path.to_string()
+/// Sorts import statements into the configured groups, keeping the default order within a group.
+/// Groups are separated by an empty entry when `separate_groups` is set.
+fn order_imports(
+  imports: Vec<String>,
+  import_order: &ImportOrderConfig,
+) -> Result<Vec<String>, regex::Error> {
+  let patterns = import_order
+    .groups
+    .iter()
+    .map(|group| match group.as_str() {
+      "type" | "side-effect" => Ok(None),
+      pattern => Regex::new(pattern).map(Some),
+    })
+    .collect::<Result<Vec<_>, _>>()?;
+
+  let position = |special: &str| import_order.groups.iter().position(|group| group == special);
+  let group_of = |statement: &str| {
+    let statement = statement.trim();
+    if statement.starts_with("import type ") {
+      if let Some(index) = position("type") {
+        return index;
+      }
+    }
+    if SIDE_EFFECT_IMPORT_PATTERN.is_match(statement) {
+      if let Some(index) = position("side-effect") {
+        return index;
+      }
+    }
+
+    let source = IMPORT_SOURCE_PATTERN
+      .captures(statement)
+      .map(|caps| caps[1].to_string())
+      .unwrap_or_default();
+    patterns
+      .iter()
+      .position(|pattern| pattern.as_ref().is_some_and(|pattern| pattern.is_match(&source)))
+      .unwrap_or(import_order.groups.len())
+  };
+
+  let mut grouped: Vec<(usize, String)> = imports
+    .into_iter()
+    .map(|statement| (group_of(&statement), statement))
+    .collect();
+  grouped.sort_by_key(|(group, _)| *group);
+
+  let mut result = Vec::new();
+  let mut current_group = None;
+  for (group, statement) in grouped {
+    if import_order.separate_groups && current_group.is_some_and(|current| current != group) {
+      result.push(String::new());
+    }
+    current_group = Some(group);
+    result.push(statement);
   }
+
+  Ok(result)
+}
+
+/// Path aliases rewritten when none are configured: Nuxt's `~/` becomes `@/`
+pub fn default_aliases() -> Vec<(String, String)> {
+  vec![("~/".to_string(), "@/".to_string())]
+}
+
+/// Configured aliases, longest first so that `~~/` is rewritten before `~/`
+pub fn sorted_aliases(aliases: HashMap<String, String>) -> Vec<(String, String)> {
+  let mut aliases: Vec<(String, String)> = aliases.into_iter().collect();
+  aliases.sort_by(|(a, _), (b, _)| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+  aliases
+}
+
+/// Rewrite the alias prefix of a path (e.g. `~/components/Foo.vue` -> `@/components/Foo.vue`)
+pub fn rewrite_alias(path: &str, aliases: &[(String, String)]) -> String {
+  aliases
+    .iter()
+    .find_map(|(alias, target)| {
+      path
+        .strip_prefix(alias.as_str())
+        .map(|rest| format!("{}{}", target, rest))
+    })
+    .unwrap_or_else(|| path.to_string())
+}
+
+/// Rewrite aliased paths in code: paths starting right after a quote or `url(`, as in static and
+/// dynamic imports, `require()` calls and asset URLs
+pub fn rewrite_path_aliases(code: &str, aliases: &[(String, String)]) -> String {
+  let mut result = code.to_string();
+
+  for (alias, target) in aliases {
+    for prefix in ["'", "\"", "`", "url("] {
+      result = result.replace(
+        &format!("{}{}", prefix, alias),
+        &format!("{}{}", prefix, target),
+      );
+    }
+  }
+
+  result
 }
 
 // Parser structures
@@ -372,7 +2160,7 @@ pub struct LifecycleHook {
 }
 
 /// Represents the parsed sections of a Vue Single File Component (SFC).
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct SfcSections {
   /// Content inside the `<template>` tag
   pub template_content: Option<String>,
@@ -382,13 +2170,18 @@ pub struct SfcSections {
   pub style_content: Option<String>,
   /// Attributes of the `<style>` tag (e.g., "scoped", "lang='scss'")
   pub style_attributes: Option<String>,
+  /// Language of the `<script>` tag (e.g., "ts")
+  pub script_lang: Option<String>,
+  /// Root-level custom blocks (e.g. `<i18n>`, `<docs>`), kept verbatim including their tags
+  pub custom_blocks: Vec<String>,
 }
 
 /// Parses a Vue Single File Component (SFC) string into its main sections.
 ///
 /// This function extracts the content from root-level `<template>`, `<script>`, and `<style>` tags
 /// while preserving any nested HTML tags within those sections. Only the first occurrence of each
-/// section type is captured.
+/// section type is captured. Other root-level blocks starting a line (e.g. `<i18n>`) are kept
+/// verbatim in `custom_blocks`.
 ///
 /// # Arguments
 ///
@@ -488,11 +2281,14 @@ pub struct SfcSections {
 /// // Template string in script is preserved as-is
 /// assert!(script.contains("'<template>Not extracted</template>'"));
 /// ```
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
 pub fn parse_sfc_sections(sfc_content: &str) -> Result<SfcSections, Box<dyn std::error::Error>> {
   let mut template_content: Option<String> = None;
   let mut script_content: Option<String> = None;
   let mut style_content: Option<String> = None;
   let mut style_attributes: Option<String> = None;
+  let mut script_lang: Option<String> = None;
+  let mut section_ranges: Vec<(usize, usize)> = Vec::new();
 
   // Extract content using string parsing since lol_html text handlers are complex for this use case
 
@@ -501,6 +2297,7 @@ pub fn parse_sfc_sections(sfc_content: &str) -> Result<SfcSections, Box<dyn std:
     if let Some(content_start) = sfc_content[start..].find('>') {
       let content_start = start + content_start + 1;
       if let Some(end) = find_closing_tag(sfc_content, content_start, "template") {
+        section_ranges.push((start, end));
         let content = sfc_content[content_start..end].trim();
         if !content.is_empty() {
           template_content = Some(content.to_string());
@@ -512,8 +2309,12 @@ pub fn parse_sfc_sections(sfc_content: &str) -> Result<SfcSections, Box<dyn std:
   // Extract script content
   if let Some(start) = sfc_content.find("<script") {
     if let Some(content_start) = sfc_content[start..].find('>') {
+      script_lang = SCRIPT_LANG_PATTERN
+        .captures(&sfc_content[start..start + content_start])
+        .map(|caps| caps[1].to_string());
       let content_start = start + content_start + 1;
       if let Some(end) = find_closing_tag(sfc_content, content_start, "script") {
+        section_ranges.push((start, end));
         let content = sfc_content[content_start..end].trim();
         if !content.is_empty() {
           script_content = Some(content.to_string());
@@ -537,6 +2338,7 @@ pub fn parse_sfc_sections(sfc_content: &str) -> Result<SfcSections, Box<dyn std:
 
       // Extract style content
       if let Some(end) = find_closing_tag(sfc_content, content_start, "style") {
+        section_ranges.push((start, end));
         let content = sfc_content[content_start..end].trim();
         if !content.is_empty() {
           style_content = Some(content.to_string());
@@ -545,16 +2347,40 @@ pub fn parse_sfc_sections(sfc_content: &str) -> Result<SfcSections, Box<dyn std:
     }
   }
 
+  // Extract custom blocks: other tags starting a line outside of the sections above
+  let mut custom_blocks = Vec::new();
+  for caps in ROOT_BLOCK_PATTERN.captures_iter(sfc_content) {
+    let start = caps.get(0).unwrap().start();
+    let tag_name = &caps[1];
+    let in_section = section_ranges
+      .iter()
+      .any(|(section_start, section_end)| start >= *section_start && start <= *section_end);
+    if in_section || ["template", "script", "style"].contains(&tag_name) {
+      continue;
+    }
+
+    let Some(content_start) = sfc_content[start..].find('>').map(|end| start + end + 1) else {
+      continue;
+    };
+    if let Some(end) = find_closing_tag(sfc_content, content_start, tag_name) {
+      let block_end = end + format!("</{}>", tag_name).len();
+      section_ranges.push((start, block_end));
+      custom_blocks.push(sfc_content[start..block_end].to_string());
+    }
+  }
+
   Ok(SfcSections {
     template_content,
     script_content,
     style_content,
     style_attributes,
+    script_lang,
+    custom_blocks,
   })
 }
 
 /// Helper function to find the closing tag while respecting nesting
-fn find_closing_tag(content: &str, start: usize, tag_name: &str) -> Option<usize> {
+pub(crate) fn find_closing_tag(content: &str, start: usize, tag_name: &str) -> Option<usize> {
   let search_content = &content[start..];
   let opening_tag = format!("<{}", tag_name);
   let closing_tag = format!("</{}>", tag_name);
@@ -601,7 +2427,7 @@ fn find_closing_tag(content: &str, start: usize, tag_name: &str) -> Option<usize
 }
 
 /// Represents the state that accumulates parsing results across different script elements.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct ScriptParsingState {
   pub identifiers: Vec<String>,
   pub function_calls: Vec<String>,
@@ -614,24 +2440,34 @@ pub struct ScriptParsingState {
   pub setup_content: Option<String>,
   pub props: Vec<PropInfo>,
   pub data_properties: Vec<DataPropertyInfo>,
+  pub data_object: bool, // `data` declared as a plain object instead of a function
+  pub data_spreads: Vec<String>, // Spreads of the data object that could not be expanded, e.g. `...createDefaults()`
   pub head_method: Option<MethodDetail>,
   pub fetch_method: Option<MethodDetail>,
+  pub fetch_options: Vec<(String, String)>, // Nuxt `fetchOnServer`, `fetchKey` and `fetchDelay`
+  pub page_options: Vec<(String, String)>, // Nuxt `watchQuery` and `key`, methods as arrow functions
   pub watchers: Vec<WatcherDetail>,
   pub nuxt_i18n: Option<String>, // Raw nuxtI18n object content
   pub async_data_method: Option<String>,
+  pub components: Vec<ComponentRegistration>,
+  pub component_name: Option<String>, // `name` option of the component
+  pub renamed_members: Vec<(String, String)>, // (key as written, identifier) of members like `'on-save'() {}`
+  pub computed_key_members: Vec<(String, String)>, // (option, key) of members like `[SAVE]() {}`
+  pub other_options: Vec<(String, String)>, // (option, raw value) of options no parser handles, e.g. `inheritAttrs`
 }
 
 /// Information about a method definition with its body.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct MethodDetail {
   pub name: String,
   pub parameters: Vec<String>, // Method parameter names
   pub body: String,
   pub is_async: bool,
+  pub comment: Option<String>, // Leading comments (e.g. JSDoc) of the method
 }
 
 /// Information about a watcher definition.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct WatcherDetail {
   pub watched_property: String,
   pub handler_body: String,
@@ -640,41 +2476,68 @@ pub struct WatcherDetail {
 }
 
 /// Information about a computed property definition with its getter/setter.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct ComputedDetail {
   pub name: String,
   pub getter: Option<String>,
   pub setter: Option<String>,
   pub setter_parameter: Option<String>, // Parameter name for setter (e.g., "value", "v")
   pub is_simple_function: bool, // true for computed: () => expr, false for { get, set }
+  pub comment: Option<String>, // Leading comments (e.g. JSDoc) of the computed property
 }
 
 /// Information about a prop definition.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct PropInfo {
   pub name: String,
   pub prop_type: Option<String>,
   pub required: Option<bool>,
   pub default_value: Option<String>,
   pub validator: Option<String>,
+  pub comment: Option<String>, // Leading comments (e.g. JSDoc) of the prop
 }
 
 /// Information about a data property.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct DataPropertyInfo {
   pub name: String,
   pub value: Option<String>,
 }
 
+/// The option a component member is declared in, which decides how `this.x` is rewritten.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SymbolKind {
+  Prop,     // `this.x` -> `props.x`
+  Data,     // `this.x` -> `x.value`, for reads and writes
+  Method,   // `this.x(...)` -> `x(...)`, `this.x` -> `x`
+  Computed, // `this.x` -> `x.value`
+}
+
+/// A component member (prop, data property, method or computed property).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ComponentSymbol {
+  pub name: String,
+  pub kind: SymbolKind,
+}
+
+/// A component registered in the `components` option.
+#[derive(Debug, Clone, Serialize)]
+pub struct ComponentRegistration {
+  pub name: String,  // Registered name, e.g. "TabHome" or "tab-home"
+  pub value: String, // Registered value, e.g. "TabHome" or "() => import('./TabHome.vue')"
+}
+
 /// Information about an import statement found in the script.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct ImportInfo {
   pub source: String,
-  pub imports: Vec<ImportItem>,
+  pub imports: Vec<ImportItem>, // Empty for side-effect imports (`import './styles.css';`)
+  pub is_type_only: bool,       // `import type { Item } from '...'`
 }
 
 /// Individual import item (default, named, namespace).
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct ImportItem {
   pub name: String,
   pub alias: Option<String>,
@@ -683,17 +2546,39 @@ pub struct ImportItem {
 }
 
 /// Represents the state that accumulates parsing results from template parsing.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct TemplateParsingState {
   pub identifiers: Vec<String>,
   pub function_calls: Vec<String>,
   pub function_call_details: Vec<FunctionCallDetail>,
   pub vue_directives: Vec<VueDirectiveInfo>,
   pub mustache_expressions: Vec<MustacheExpressionInfo>,
+  /// Loop variables and slot props, which are left out of `identifiers` and `function_calls`
+  /// where they are in scope
+  pub scopes: Vec<TemplateScope>,
+  /// Names of the Vue 2 filters applied with the pipe syntax (`{{ price | currency }}`)
+  pub filters: Vec<String>,
+}
+
+/// Names a `v-for` or slot directive declares for its element and the element's content.
+#[derive(Debug, Clone, Serialize)]
+pub struct TemplateScope {
+  /// Loop variables or slot props (e.g. `item` and `index` for `v-for="(item, index) in items"`)
+  pub names: Vec<String>,
+  /// Byte offset of the declaring element's start tag in the template
+  pub start: usize,
+  /// Byte offset after the element's end tag
+  pub end: usize,
+}
+
+impl TemplateScope {
+  pub fn contains(&self, position: usize) -> bool {
+    self.start <= position && position < self.end
+  }
 }
 
 /// Information about a Vue directive found in the template.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct VueDirectiveInfo {
   pub name: String,
   pub value: String,
@@ -701,7 +2586,7 @@ pub struct VueDirectiveInfo {
 }
 
 /// Information about a mustache expression found in the template.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct MustacheExpressionInfo {
   pub content: String,
 }
@@ -726,12 +2611,146 @@ impl ScriptParsingState {
       setup_content: None,
       props: Vec::new(),
       data_properties: Vec::new(),
+      data_object: false,
+      data_spreads: Vec::new(),
       head_method: None,
       fetch_method: None,
+      fetch_options: Vec::new(),
+      page_options: Vec::new(),
       watchers: Vec::new(),
       nuxt_i18n: None,
       async_data_method: None,
+      components: Vec::new(),
+      component_name: None,
+      renamed_members: Vec::new(),
+      computed_key_members: Vec::new(),
+      other_options: Vec::new(),
+    }
+  }
+
+  /// Component members in the order Vue 2 proxies them on the instance (props, data, methods,
+  /// computed). A name declared in several options is listed once per option.
+  pub fn symbols(&self) -> Vec<ComponentSymbol> {
+    let symbol = |name: &str, kind: SymbolKind| ComponentSymbol {
+      name: name.to_string(),
+      kind,
+    };
+
+    self
+      .props
+      .iter()
+      .map(|prop| symbol(&prop.name, SymbolKind::Prop))
+      .chain(
+        self
+          .data_properties
+          .iter()
+          .map(|data| symbol(&data.name, SymbolKind::Data)),
+      )
+      .chain(
+        self
+          .methods
+          .iter()
+          .map(|method| symbol(method, SymbolKind::Method)),
+      )
+      .chain(
+        self
+          .computed_properties
+          .iter()
+          .map(|computed| symbol(computed, SymbolKind::Computed)),
+      )
+      .collect()
+  }
+
+  /// The kind `this.name` resolves to. When a name is declared in several options the member
+  /// Vue 2 proxies first wins, the others are shadowed on the instance.
+  pub fn symbol_kind(&self, name: &str) -> Option<SymbolKind> {
+    self
+      .symbols()
+      .into_iter()
+      .find(|symbol| symbol.name == name)
+      .map(|symbol| symbol.kind)
+  }
+
+  /// Imported bindings a data property, computed property or method of the same name would
+  /// redeclare in `<script setup>`, with the alias they are imported as instead
+  /// (`limit` -> `importedLimit`). Props are read from `props` and do not collide.
+  pub fn shadowed_imports(&self) -> Vec<(String, String)> {
+    let members: Vec<ComponentSymbol> = self
+      .symbols()
+      .into_iter()
+      .filter(|symbol| symbol.kind != SymbolKind::Prop)
+      .collect();
+
+    self
+      .imports
+      .iter()
+      .filter(|import_info| !import_info.is_type_only)
+      .flat_map(|import_info| &import_info.imports)
+      .map(|item| item.alias.as_ref().unwrap_or(&item.name))
+      .filter(|binding| members.iter().any(|member| &member.name == *binding))
+      .map(|binding| {
+        let mut chars = binding.chars();
+        let capitalized: String = chars
+          .next()
+          .map(|first| first.to_uppercase().chain(chars).collect())
+          .unwrap_or_default();
+        (binding.clone(), format!("imported{}", capitalized))
+      })
+      .collect()
+  }
+
+  /// Rename the references of the code of the component to the imports returned by
+  /// [`ScriptParsingState::shadowed_imports`], which are imported under their alias
+  pub fn rename_shadowed_imports(&mut self) {
+    use transformers::this_scope::{rename_bindings, rename_function_bindings};
+
+    let renames = self.shadowed_imports();
+    if renames.is_empty() {
+      return;
+    }
+    let rename = |code: &mut String| *code = rename_bindings(code, &renames);
+    let rename_method = |method: &mut MethodDetail| {
+      method.body = rename_function_bindings(&method.parameters.join(", "), &method.body, &renames);
+    };
+
+    self.setup_content.iter_mut().for_each(rename);
+    self
+      .data_properties
+      .iter_mut()
+      .filter_map(|data| data.value.as_mut())
+      .for_each(rename);
+    for prop in &mut self.props {
+      prop.default_value.iter_mut().for_each(rename);
+      prop.validator.iter_mut().for_each(rename);
+    }
+    for computed in &mut self.computed_details {
+      computed.getter.iter_mut().for_each(rename);
+      if let Some(setter) = &mut computed.setter {
+        let parameter = computed.setter_parameter.as_deref().unwrap_or_default();
+        *setter = rename_function_bindings(parameter, setter, &renames);
+      }
     }
+    for watcher in &mut self.watchers {
+      let parameters = format!("{}, {}", watcher.param_names.0, watcher.param_names.1);
+      watcher.handler_body = rename_function_bindings(&parameters, &watcher.handler_body, &renames);
+    }
+    self.method_details.iter_mut().for_each(rename_method);
+    self.head_method.iter_mut().for_each(rename_method);
+    self.fetch_method.iter_mut().for_each(rename_method);
+  }
+
+  /// Names declared in more than one option, with the kinds they are declared as
+  pub fn symbol_collisions(&self) -> Vec<(String, Vec<SymbolKind>)> {
+    let mut collisions: Vec<(String, Vec<SymbolKind>)> = Vec::new();
+    for symbol in self.symbols() {
+      match collisions.iter_mut().find(|(name, _)| name == &symbol.name) {
+        Some((_, kinds)) if !kinds.contains(&symbol.kind) => kinds.push(symbol.kind),
+        Some(_) => {}
+        None => collisions.push((symbol.name, vec![symbol.kind])),
+      }
+    }
+    collisions.retain(|(_, kinds)| kinds.len() > 1);
+    collisions
   }
 }
 
@@ -749,6 +2768,58 @@ impl TemplateParsingState {
       function_call_details: Vec::new(),
       vue_directives: Vec::new(),
       mustache_expressions: Vec::new(),
+      scopes: Vec::new(),
+      filters: Vec::new(),
+    }
+  }
+
+  /// Loop variables and slot props in scope at a byte offset of the template
+  pub fn locals_at(&self, position: usize) -> Vec<String> {
+    scope_locals(&self.scopes, position)
+  }
+
+  /// Walk the JavaScript parts of a template expression and record its filters
+  fn add_expression(&mut self, expression: VueExpression, locals: &[String]) {
+    for filter in expression.filters {
+      if !self.filters.contains(&filter.name) {
+        self.filters.push(filter.name);
+      }
+    }
+
+    for javascript in &expression.expressions {
+      // An object literal alone parses as a block, with its keys as labels
+      let javascript = match javascript.trim_start().starts_with('{') {
+        true => format!("({})", javascript),
+        false => javascript.to_string(),
+      };
+      if let Some(tree) = crate::javascript::parse(&javascript) {
+        let mut walked = TemplateParsingState::new();
+        walk_tree_recursive_template(tree.root_node(), javascript.as_bytes(), &mut walked);
+        self.add_scoped(walked, locals);
+      }
+    }
+  }
+
+  /// Add the identifiers and calls found by walking an expression, skipping the given locals
+  fn add_scoped(&mut self, walked: TemplateParsingState, locals: &[String]) {
+    let is_local = |name: &str| {
+      let root = name.split(['.', '[', '(', '?']).next().unwrap_or(name).trim();
+      locals.iter().any(|local| local == root)
+    };
+
+    for identifier in walked.identifiers {
+      if !is_local(&identifier) && !self.identifiers.contains(&identifier) {
+        self.identifiers.push(identifier);
+      }
+    }
+    for call_detail in walked.function_call_details {
+      if is_local(&call_detail.name) {
+        continue;
+      }
+      if !self.function_calls.contains(&call_detail.name) {
+        self.function_calls.push(call_detail.name.clone());
+      }
+      self.function_call_details.push(call_detail);
     }
   }
 }
@@ -861,6 +2932,7 @@ impl TemplateParsingState {
 /// assert!(state.methods.contains(&"handleSubmit".to_string()));
 /// assert!(state.computed_properties.contains(&"canSubmit".to_string()));
 /// ```
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
 pub fn parse_script_section(
   script_content: &str,
   state: &mut ScriptParsingState,
@@ -869,13 +2941,7 @@ pub fn parse_script_section(
   extract_imports_and_setup(script_content, state)?;
 
   // Then use tree-sitter for Vue component structure
-  let language = tree_sitter_javascript::LANGUAGE.into();
-  let mut parser = Parser::new();
-  parser.set_language(&language)?;
-
-  let tree = parser
-    .parse(script_content, None)
-    .ok_or("Failed to parse script content")?;
+  let tree = javascript::parse(script_content).ok_or("Failed to parse script content")?;
   let root_node = tree.root_node();
 
   // Walk the AST to find Vue component structure
@@ -898,7 +2964,7 @@ fn extract_imports_and_setup(
     let trimmed = line.trim();
 
     if trimmed.starts_with("import ") {
-      if trimmed.contains(" from ") {
+      if trimmed.contains(" from ") || SIDE_EFFECT_IMPORT_PATTERN.is_match(trimmed) {
         // Single-line import
         if let Some(import_info) = parse_import_line(trimmed) {
           state.imports.push(import_info);
@@ -955,11 +3021,32 @@ fn parse_import_line(line: &str) -> Option<ImportInfo> {
     let mut imports = Vec::new();
 
     // Remove "import " from the beginning
-    let import_content = import_part
+    let mut import_content = import_part
       .strip_prefix("import")
       .unwrap_or(import_part)
       .trim();
 
+    let is_type_only = match import_content.strip_prefix("type ") {
+      Some(rest) => {
+        import_content = rest.trim();
+        true
+      }
+      None => false,
+    };
+
+    // Default import followed by named or namespace imports: import Vue, { PropType } from 'vue'
+    if !import_content.starts_with('{') && !import_content.starts_with('*') {
+      if let Some((default_import, rest)) = import_content.split_once(',') {
+        imports.push(ImportItem {
+          name: default_import.trim().to_string(),
+          alias: None,
+          is_default: true,
+          is_namespace: false,
+        });
+        import_content = rest.trim();
+      }
+    }
+
     if import_content.starts_with('{') && import_content.ends_with('}') {
       // Named imports: import { a, b, c } from 'module'
       let named_imports = &import_content[1..import_content.len() - 1];
@@ -1005,12 +3092,20 @@ fn parse_import_line(line: &str) -> Option<ImportInfo> {
     }
 
     return Some(ImportInfo {
-      source: rewrite_import_path(source),
+      source: source.to_string(),
       imports,
+      is_type_only,
     });
   }
 
-  None
+  // Side-effect import: import './styles.css'
+  SIDE_EFFECT_IMPORT_PATTERN
+    .captures(line)
+    .map(|caps| ImportInfo {
+      source: caps[1].to_string(),
+      imports: Vec::new(),
+      is_type_only: false,
+    })
 }
 
 /// Recursively walks the AST to find Vue component sections (methods, computed, etc.)
@@ -1041,8 +3136,12 @@ fn find_vue_component_sections(node: &Node, source: &str, state: &mut ScriptPars
     }
   }
 
-  // Look for direct object expressions (for cases like just { methods: {...} })
-  if node.kind() == "object" {
+  // Look for direct object expressions (for cases like just { methods: {...} }). Next to an
+  // exported object, objects assigned to variables are module state (`const defaults = {}`).
+  if node.kind() == "object"
+    && !(node.parent().map(|parent| parent.kind()) == Some("variable_declarator")
+      && exports_object(node))
+  {
     parse_vue_component_object(node, source, state);
     return;
   }
@@ -1055,6 +3154,62 @@ fn find_vue_component_sections(node: &Node, source: &str, state: &mut ScriptPars
   }
 }
 
+/// The `program` node at the root of the tree of a node
+fn program_node<'tree>(node: &Node<'tree>) -> Node<'tree> {
+  let mut root = *node;
+  while let Some(parent) = root.parent() {
+    root = parent;
+  }
+  root
+}
+
+/// Whether the script of a node exports an object literal with `export default { ... }`
+fn exports_object(node: &Node) -> bool {
+  let root = program_node(node);
+  let mut cursor = root.walk();
+  let exports_object = root.named_children(&mut cursor).any(|statement| {
+    statement.kind() == "export_statement"
+      && statement
+        .child_by_field_name("value")
+        .is_some_and(|value| value.kind() == "object")
+  });
+  exports_object
+}
+
+/// Parses the `components` option into its registrations
+fn parse_components_object(node: &Node, source: &str, state: &mut ScriptParsingState) {
+  if node.kind() != "object" {
+    return;
+  }
+
+  for i in 0..node.child_count() {
+    if let Some(child) = node.child(i) {
+      match child.kind() {
+        "shorthand_property_identifier" => {
+          let name = get_node_text(&child, source);
+          state.components.push(ComponentRegistration {
+            value: name.clone(),
+            name,
+          });
+        }
+        "pair" => {
+          if let (Some(key_node), Some(value_node)) = (
+            child.child_by_field_name("key"),
+            child.child_by_field_name("value"),
+          ) {
+            let key_text = get_node_text(&key_node, source);
+            state.components.push(ComponentRegistration {
+              name: key_text.trim_matches('"').trim_matches('\'').to_string(),
+              value: get_node_text(&value_node, source),
+            });
+          }
+        }
+        _ => {}
+      }
+    }
+  }
+}
+
 /// Parses variable declarations to detect async components
 fn parse_variable_declarations(node: &Node, source: &str, state: &mut ScriptParsingState) {
   // Look for variable_declarator nodes
@@ -1150,6 +3305,14 @@ fn parse_vue_component_object(node: &Node, source: &str, state: &mut ScriptParsi
             "head" => {
               parse_head_method(&value_node, source, state);
             }
+            "components" => {
+              parse_components_object(&value_node, source, state);
+              parse_general_node(&value_node, source, state);
+            }
+            "name" if value_node.kind() == "string" => {
+              let name = get_node_text(&value_node, source);
+              state.component_name = Some(name.trim_matches(|c| c == '"' || c == '\'').to_string());
+            }
             "watch" => {
               // Parse watchers object specially
               parse_watchers_object(&value_node, source, state);
@@ -1164,6 +3327,18 @@ fn parse_vue_component_object(node: &Node, source: &str, state: &mut ScriptParsi
               let content = get_node_text(&value_node, source);
               state.async_data_method = Some(content);
             }
+            "fetchOnServer" | "fetchKey" | "fetchDelay" => {
+              let content = get_node_text(&value_node, source);
+              state.fetch_options.push((key.to_string(), content));
+            }
+            "watchQuery" | "key" => {
+              let content = get_node_text(&value_node, source);
+              state.page_options.push((key.to_string(), content));
+            }
+            "fetch" if is_function_node(&value_node) => {
+              // `fetch: async function () {}` is the same hook as `async fetch() {}`
+              parse_fetch_method(&value_node, source, state);
+            }
             "beforeCreate" | "created" | "beforeMount" | "mounted" | "beforeUpdate" | "updated"
             | "beforeDestroy" | "destroyed" | "beforeUnmount" | "unmounted" | "activated"
             | "deactivated" | "fetch" => {
@@ -1171,19 +3346,16 @@ fn parse_vue_component_object(node: &Node, source: &str, state: &mut ScriptParsi
               parse_general_node(&value_node, source, state);
 
               // Also create method details for lifecycle methods (needed for transformers)
-              let is_async = check_if_async(&value_node, source);
-              let body = extract_method_body(&value_node, source);
-
-              state.method_details.push(MethodDetail {
-                name: key.to_string(),
-                parameters: Vec::new(), // TODO: Parse parameters
-                body,
-                is_async,
-              });
+              state
+                .method_details
+                .push(parse_lifecycle_hook(key, &value_node, source));
             }
             _ => {
               // Parse any other properties for identifiers and function calls
               parse_general_node(&value_node, source, state);
+              state
+                .other_options
+                .push((key.to_string(), get_node_text(&value_node, source)));
             }
           }
         }
@@ -1208,6 +3380,23 @@ fn parse_vue_component_object(node: &Node, source: &str, state: &mut ScriptParsi
               let content = get_node_text(&child, source);
               state.async_data_method = Some(content);
             }
+            "fetchOnServer" | "fetchKey" | "fetchDelay" => {
+              let content = get_node_text(&child, source);
+              state.fetch_options.push((method_name.to_string(), content));
+            }
+            "watchQuery" | "key" => {
+              let parameters = child
+                .child_by_field_name("parameters")
+                .map(|parameters| get_node_text(&parameters, source))
+                .unwrap_or_else(|| "()".to_string());
+              let body = child
+                .child_by_field_name("body")
+                .map(|body| get_node_text(&body, source))
+                .unwrap_or_else(|| "{}".to_string());
+              state
+                .page_options
+                .push((method_name.to_string(), format!("{} => {}", parameters, body)));
+            }
             _ => {
               // Handle lifecycle methods and other function definitions
               parse_general_node(&child, source, state);
@@ -1228,15 +3417,13 @@ fn parse_vue_component_object(node: &Node, source: &str, state: &mut ScriptParsi
                   | "activated"
                   | "deactivated"
               ) {
-                let is_async = check_if_async(&child, source);
-                let body = extract_method_body(&child, source);
-
-                state.method_details.push(MethodDetail {
-                  name: method_name.to_string(),
-                  parameters: Vec::new(), // TODO: Parse parameters
-                  body,
-                  is_async,
-                });
+                state
+                  .method_details
+                  .push(parse_lifecycle_hook(method_name, &child, source));
+              } else {
+                state
+                  .other_options
+                  .push((method_name.to_string(), get_node_text(&child, source)));
               }
             }
           }
@@ -1253,14 +3440,16 @@ fn parse_methods_object(node: &Node, source: &str, state: &mut ScriptParsingStat
       if child.kind() == "pair" {
         // Handle method: function() { ... } syntax
         if let (Some(key_node), Some(value_node)) = (child.child(0), child.child(2)) {
-          let method_text = get_node_text(&key_node, source);
-          let method_name = method_text.trim_matches('"').trim_matches('\'');
+          let Some(method_name) = member_name(&key_node, "methods", source, state) else {
+            continue;
+          };
+          let method_name = method_name.as_str();
 
           // Add to methods list for backward compatibility
           state.methods.push(method_name.to_string());
 
           // Extract method details
-          let is_async = check_if_async(&value_node, source);
+          let is_async = check_if_async(&value_node);
           let body = extract_method_body(&value_node, source);
           let parameters = extract_method_parameters(&value_node, source);
 
@@ -1269,37 +3458,36 @@ fn parse_methods_object(node: &Node, source: &str, state: &mut ScriptParsingStat
             parameters,
             body,
             is_async,
+            comment: leading_comments(&child, source),
           });
         }
       } else if child.kind() == "method_definition" {
         // Handle shorthand method syntax: methodName() { ... }
-        let mut body_index = 2;
         if let Some(name_node) = child.child_by_field_name("name") {
-          let method_text = get_node_text(&name_node, source);
-          let method_name = method_text.trim_matches('"').trim_matches('\'');
+          let Some(method_name) = member_name(&name_node, "methods", source, state) else {
+            continue;
+          };
+          let method_name = method_name.as_str();
 
           // Add to methods list for backward compatibility
           state.methods.push(method_name.to_string());
 
           // Extract method details
-          let is_async = check_if_async(&child, source);
+          let is_async = check_if_async(&child);
           let body = extract_method_body(&child, source);
           let parameters = extract_method_parameters(&child, source);
 
-          if is_async {
-            body_index = 3; // Async methods have an extra parameter
-          }
-
           state.method_details.push(MethodDetail {
             name: method_name.to_string(),
             parameters,
             body,
             is_async,
+            comment: leading_comments(&child, source),
           });
         }
 
         // Parse the method body for identifiers and function calls
-        if let Some(value_node) = child.child(body_index) {
+        if let Some(value_node) = child.child_by_field_name("body") {
           parse_general_node(&value_node, source, state);
         }
       } else if child.kind() == "spread_element" {
@@ -1316,8 +3504,10 @@ fn parse_computed_object(node: &Node, source: &str, state: &mut ScriptParsingSta
     if let Some(child) = node.child(i) {
       if child.kind() == "pair" {
         if let (Some(key_node), Some(value_node)) = (child.child(0), child.child(2)) {
-          let computed_text = get_node_text(&key_node, source);
-          let computed_name = computed_text.trim_matches('"').trim_matches('\'');
+          let Some(computed_name) = member_name(&key_node, "computed", source, state) else {
+            continue;
+          };
+          let computed_name = computed_name.as_str();
 
           // Add to computed properties list for backward compatibility
           state.computed_properties.push(computed_name.to_string());
@@ -1329,6 +3519,7 @@ fn parse_computed_object(node: &Node, source: &str, state: &mut ScriptParsingSta
             setter: None,
             setter_parameter: None,
             is_simple_function: false,
+            comment: leading_comments(&child, source),
           };
 
           // Check if it's a simple function: computed: () => expr
@@ -1346,8 +3537,10 @@ fn parse_computed_object(node: &Node, source: &str, state: &mut ScriptParsingSta
       } else if child.kind() == "method_definition" {
         // Handle shorthand computed syntax: computedName() { ... }
         if let Some(name_node) = child.child_by_field_name("name") {
-          let computed_text = get_node_text(&name_node, source);
-          let computed_name = computed_text.trim_matches('"').trim_matches('\'');
+          let Some(computed_name) = member_name(&name_node, "computed", source, state) else {
+            continue;
+          };
+          let computed_name = computed_name.as_str();
 
           state.computed_properties.push(computed_name.to_string());
 
@@ -1357,6 +3550,7 @@ fn parse_computed_object(node: &Node, source: &str, state: &mut ScriptParsingSta
             setter: None,
             setter_parameter: None,
             is_simple_function: true,
+            comment: leading_comments(&child, source),
           };
 
           state.computed_details.push(computed_detail);
@@ -1425,6 +3619,24 @@ fn parse_computed_getter_setter(
 
 /// Parses the props object to extract prop definitions
 fn parse_props_object(node: &Node, source: &str, state: &mut ScriptParsingState) {
+  // Array shorthand: props: ['title', 'value']
+  if node.kind() == "array" {
+    for i in 0..node.named_child_count() {
+      if let Some(child) = node.named_child(i).filter(|child| child.kind() == "string") {
+        let prop_text = get_node_text(&child, source);
+        state.props.push(PropInfo {
+          name: prop_text.trim_matches(|c| c == '"' || c == '\'').to_string(),
+          prop_type: None,
+          required: None,
+          default_value: None,
+          validator: None,
+          comment: None,
+        });
+      }
+    }
+    return;
+  }
+
   for i in 0..node.child_count() {
     if let Some(child) = node.child(i) {
       if child.kind() == "pair" {
@@ -1438,6 +3650,7 @@ fn parse_props_object(node: &Node, source: &str, state: &mut ScriptParsingState)
             required: None,
             default_value: None,
             validator: None,
+            comment: leading_comments(&child, source),
           };
 
           // Parse prop definition (could be object with type, required, default, etc.)
@@ -1448,7 +3661,11 @@ fn parse_props_object(node: &Node, source: &str, state: &mut ScriptParsingState)
             } else {
               // Simple prop definition: PropType or Array
               let type_text = get_node_text(&value_node, source);
-              prop_info.prop_type = Some(type_text);
+              let cast = prop_type_cast(&source[value_node.start_byte()..]);
+              prop_info.prop_type = Some(match cast {
+                Some(cast) => cast.to_string(),
+                None => type_text,
+              });
             }
           }
 
@@ -1459,6 +3676,28 @@ fn parse_props_object(node: &Node, source: &str, state: &mut ScriptParsingState)
   }
 }
 
+/// The `Type as PropType<...>` cast at the start of `source`, up to the `>` closing the type
+/// argument (arrows in function types do not close it)
+fn prop_type_cast(source: &str) -> Option<&str> {
+  let prefix = PROP_TYPE_CAST_PATTERN.find(source)?;
+  let mut depth = 1;
+  let mut previous = '<';
+  for (offset, c) in source[prefix.end()..].char_indices() {
+    match c {
+      '<' => depth += 1,
+      '>' if previous != '=' => {
+        depth -= 1;
+        if depth == 0 {
+          return Some(&source[..prefix.end() + offset + 1]);
+        }
+      }
+      _ => {}
+    }
+    previous = c;
+  }
+  None
+}
+
 /// Parses a complex prop definition object
 fn parse_prop_definition(node: &Node, source: &str, prop_info: &mut PropInfo) {
   for i in 0..node.child_count() {
@@ -1471,7 +3710,13 @@ fn parse_prop_definition(node: &Node, source: &str, prop_info: &mut PropInfo) {
 
           match key {
             "type" => {
-              prop_info.prop_type = Some(value_text);
+              // `Object as PropType<CartItem>` is TypeScript, which the JavaScript grammar only
+              // parses up to the cast, so the cast is read from the source
+              let cast = prop_type_cast(&source[value_node.start_byte()..]);
+              prop_info.prop_type = Some(match cast {
+                Some(cast) => cast.to_string(),
+                None => value_text,
+              });
             }
             "required" => {
               prop_info.required = Some(value_text == "true");
@@ -1485,6 +3730,25 @@ fn parse_prop_definition(node: &Node, source: &str, prop_info: &mut PropInfo) {
             _ => {}
           }
         }
+      } else if child.kind() == "method_definition" {
+        // Method shorthand: default() { return []; } or validator(value) { ... }
+        if let (Some(name_node), Some(parameters_node), Some(body_node)) = (
+          child.child_by_field_name("name"),
+          child.child_by_field_name("parameters"),
+          child.child_by_field_name("body"),
+        ) {
+          let function = format!(
+            "{} => {}",
+            get_node_text(&parameters_node, source),
+            get_node_text(&body_node, source)
+          );
+
+          match get_node_text(&name_node, source).as_str() {
+            "default" => prop_info.default_value = Some(function),
+            "validator" => prop_info.validator = Some(function),
+            _ => {}
+          }
+        }
       }
     }
   }
@@ -1502,6 +3766,10 @@ fn parse_data_function(node: &Node, source: &str, state: &mut ScriptParsingState
   } else if node.kind() == "arrow_function" {
     // Arrow function syntax: data: () => ({ ... })
     parse_data_arrow_function(node, source, state);
+  } else if node.kind() == "object" {
+    // Object syntax: data: { ... }, shared by all instances in Vue 2 (root instances, libraries)
+    state.data_object = true;
+    parse_data_object(node, source, state);
   } else {
     // General parsing for any other syntax
     parse_general_node(node, source, state);
@@ -1562,28 +3830,79 @@ fn find_return_object(node: &Node, source: &str, state: &mut ScriptParsingState)
 }
 
 /// Parses a data object to extract property names and values
+///
+/// Spreads of objects declared by a module-level `const` are expanded into their properties, which
+/// later properties override as in the object. Other spreads are recorded in `data_spreads`.
 fn parse_data_object(node: &Node, source: &str, state: &mut ScriptParsingState) {
   for i in 0..node.child_count() {
     if let Some(child) = node.child(i) {
-      if child.kind() == "pair" {
+      if child.kind() == "spread_element" {
+        let Some(argument) = child.named_child(0) else {
+          continue;
+        };
+        let object = match argument.kind() {
+          "identifier" => module_const_object(node, &get_node_text(&argument, source), source),
+          "object" => Some(argument),
+          _ => None,
+        };
+        match object {
+          Some(object) => parse_data_object(&object, source, state),
+          None => state.data_spreads.push(get_node_text(&child, source)),
+        }
+      } else if child.kind() == "pair" {
         if let Some(key_node) = child.child(0) {
-          let prop_text = get_node_text(&key_node, source);
-          let prop_name = prop_text.trim_matches('"').trim_matches('\'');
+          let Some(prop_name) = member_name(&key_node, "data", source, state) else {
+            continue;
+          };
+          let prop_name = prop_name.as_str();
 
           let value = child
             .child(2)
             .map(|value_node| get_node_text(&value_node, source));
 
-          state.data_properties.push(DataPropertyInfo {
-            name: prop_name.to_string(),
-            value,
-          });
+          match state
+            .data_properties
+            .iter_mut()
+            .find(|data| data.name == prop_name)
+          {
+            Some(data) => data.value = value,
+            None => state.data_properties.push(DataPropertyInfo {
+              name: prop_name.to_string(),
+              value,
+            }),
+          }
         }
       }
     }
   }
 }
 
+/// Object literal a module-level `const name = { ... }` of the script declares
+fn module_const_object<'tree>(node: &Node<'tree>, name: &str, source: &str) -> Option<Node<'tree>> {
+  let root = program_node(node);
+  let mut cursor = root.walk();
+  let statements: Vec<Node<'tree>> = root.named_children(&mut cursor).collect();
+  statements.into_iter().find_map(|statement| {
+    let declaration = match statement.kind() {
+      "export_statement" => statement.child_by_field_name("declaration")?,
+      _ => statement,
+    };
+    if declaration.kind() != "lexical_declaration"
+      || declaration.child(0).map(|keyword| keyword.kind()) != Some("const")
+    {
+      return None;
+    }
+
+    let mut cursor = declaration.walk();
+    let declarators: Vec<Node<'tree>> = declaration.named_children(&mut cursor).collect();
+    declarators.into_iter().find_map(|declarator| {
+      let declared = declarator.child_by_field_name("name")?;
+      let value = declarator.child_by_field_name("value")?;
+      (get_node_text(&declared, source) == name && value.kind() == "object").then_some(value)
+    })
+  })
+}
+
 /// General node parser that extracts identifiers and function calls
 fn parse_general_node(node: &Node, source: &str, state: &mut ScriptParsingState) {
   match node.kind() {
@@ -1641,7 +3960,7 @@ fn parse_general_node(node: &Node, source: &str, state: &mut ScriptParsingState)
 }
 
 /// Context containing all parsed information for transformation
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct TransformationContext {
   pub script_state: ScriptParsingState,
   pub template_state: TemplateParsingState,
@@ -1651,25 +3970,49 @@ pub struct TransformationContext {
 /// Result of a transformation containing all changes to be applied
 #[derive(Debug, Clone, Default)]
 pub struct TransformationResult {
-  pub imports_to_add: HashMap<String, Vec<String>>, // path => [import1, import2, ...]
+  pub imports_to_add: BTreeMap<String, Vec<String>>, // path => [import1, import2, ...]
   pub imports_to_remove: Vec<String>,
+  pub define_options: Vec<String>, // Entries passed to defineOptions() (e.g. "name: 'TreeItem'")
   pub setup: Vec<String>, // Composable setup (useStore, useRouter, etc.)
   pub reactive_state: Vec<String>, // ref() and reactive() declarations
   pub computed_properties: Vec<String>, // computed() declarations
   pub methods: Vec<String>, // Method definitions
   pub watchers: Vec<String>, // watch() and watchEffect() declarations
   pub lifecycle_hooks: Vec<String>, // onMounted, onBeforeUnmount, etc.
+  pub exposed: Vec<String>, // Members passed to defineExpose()
   pub template_replacements: Vec<TemplateReplacement>,
   pub additional_scripts: Vec<String>, // Additional script blocks to append
   pub skip_data_properties: Vec<String>, // Data properties to skip (handled by other transformers)
-  pub data_refs: HashMap<String, (String, u8)>, // property_name => (ref_declaration, priority)
+  pub data_refs: BTreeMap<String, (String, u8)>, // property_name => (ref_declaration, priority)
   pub resolved_identifiers: Vec<String>, // Identifiers that have been resolved by transformers
+  pub event_renames: Vec<(String, String)>, // (vue2_event, vue3_event) for emitted events that were renamed
+  pub additional_files: Vec<(PathBuf, String)>, // Sidecar files (path relative to the output root, content)
+  pub diagnostics: Vec<Diagnostic>, // Analysis findings reported to the user
+  pub style_content: Option<String>, // Rewritten <style> content, when a transformer changed it
+  pub registered_components: Vec<String>, // Names and values of the `components` option
+  pub original_references: Vec<String>, // Imported bindings the original component references
 }
 
 #[derive(Debug, Clone)]
 pub struct TemplateReplacement {
   pub find: String,
   pub replace: String,
+  pub scope: ReplacementScope,
+}
+
+/// Where in the template a [`TemplateReplacement`] applies
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplacementScope {
+  /// Anywhere in the template (tags, static attributes, ...)
+  Template,
+  /// Only inside mustache expressions and directive values, leaving text, comments and strings
+  /// untouched. Matches starting or ending with a name only replace whole names
+  /// (`$i18n.locale` leaves `$i18n.localeProperties` and `user.$i18n.locale` alone)
+  Expressions,
+  /// Whole identifiers inside mustache expressions and directive values, leaving property
+  /// accesses (`$event.target.value`), object keys and strings untouched. Shorthand properties
+  /// keep their key (`{ user }` becomes `{ user: authStore.user }`)
+  Identifiers,
 }
 
 /// Configuration for transformers
@@ -1683,6 +4026,43 @@ pub struct TransformerConfig {
   pub imports_rewrite: Option<HashMap<String, ImportRewrite>>,
   pub additional_imports: Option<HashMap<String, AdditionalImport>>,
   pub import_keeplist: Option<Vec<String>>,
+  pub event_bus: Option<EventBusConfig>,
+  pub global_properties: Option<Vec<String>>,
+  pub instance_properties: Option<InstancePropertyStyle>,
+  pub date_plugin: Option<DatePluginConfig>,
+  pub enable_transition_transforms: bool,
+  pub enable_style_transforms: bool,
+  pub emit_mapping: Option<EmitMappingConfig>,
+  pub expose: Option<ExposeConfig>,
+  pub import_order: Option<ImportOrderConfig>,
+  pub aliases: Vec<(String, String)>, // Longest alias first
+  pub asset_mode: AssetMode,
+  pub v_t_directive: VTDirectiveStyle,
+  pub set_locale: SetLocaleStyle,
+  pub nuxt_i18n: NuxtI18nStyle,
+  pub http: HttpConfig,
+  pub axios_data_helper: Option<String>,
+  pub target: Target,
+  pub ssr_target: bool,
+  pub nuxt_error: NuxtErrorConfig,
+  pub define_page_meta: bool,
+  pub typescript: bool,
+  pub prefer_define_model: bool,
+  pub self_reference: SelfReferenceStyle,
+  pub file_name: Option<String>,
+  pub disabled_transformers: Vec<String>,
+  pub script_style: ScriptStyle,
+  pub template_ref_style: TemplateRefStyle,
+  pub require_components: RequireComponentStyle,
+  pub custom_options: Option<HashMap<String, CustomOptionConfig>>,
+}
+
+impl TransformerConfig {
+  /// Whether props and emits are declared with type-based `defineProps<...>()` and
+  /// `defineEmits<...>()`, which only `<script setup>` compiles
+  pub fn type_based_macros(&self) -> bool {
+    self.typescript && self.script_style == ScriptStyle::SetupSugar
+  }
 }
 
 impl TransformationResult {
@@ -1696,18 +4076,31 @@ impl TransformationResult {
       self.imports_to_add.entry(path).or_default().extend(imports);
     }
     self.imports_to_remove.extend(other.imports_to_remove);
+    self.define_options.extend(other.define_options);
     self.setup.extend(other.setup);
     self.reactive_state.extend(other.reactive_state);
     self.computed_properties.extend(other.computed_properties);
     self.methods.extend(other.methods);
     self.watchers.extend(other.watchers);
     self.lifecycle_hooks.extend(other.lifecycle_hooks);
+    for member in other.exposed {
+      if !self.exposed.contains(&member) {
+        self.exposed.push(member);
+      }
+    }
     self
       .template_replacements
       .extend(other.template_replacements);
     self.additional_scripts.extend(other.additional_scripts);
     self.skip_data_properties.extend(other.skip_data_properties);
     self.resolved_identifiers.extend(other.resolved_identifiers);
+    self.event_renames.extend(other.event_renames);
+    self.additional_files.extend(other.additional_files);
+    self.diagnostics.extend(other.diagnostics);
+    self
+      .registered_components
+      .extend(other.registered_components);
+    self.original_references.extend(other.original_references);
 
     // Merge data refs by priority - higher priority overwrites lower priority
     for (prop_name, (ref_declaration, priority)) in other.data_refs {
@@ -1734,12 +4127,23 @@ impl TransformationResult {
       .push(import_item.to_string());
   }
 
+  /// Add a default import (`import name from 'path';`). `imports_to_add` holds the named imports
+  /// of each path, so default imports are kept as pre-formatted statements.
+  pub fn add_default_import(&mut self, path: &str, name: &str) {
+    self.add_import("__default__", &format!("import {} from '{}';", name, path));
+  }
+
   /// Add multiple import items to a specific path
   pub fn add_imports(&mut self, path: &str, import_items: &[&str]) {
     let imports = self.imports_to_add.entry(path.to_string()).or_default();
     imports.extend(import_items.iter().map(|s| s.to_string()));
   }
 
+  /// Add a sidecar file, with a path relative to the output root
+  pub fn add_file(&mut self, path: impl Into<PathBuf>, content: impl Into<String>) {
+    self.additional_files.push((path.into(), content.into()));
+  }
+
   /// Add content to the setup section (composable declarations, etc.)
   pub fn add_setup(&mut self, content: String) {
     self.setup.push(content);
@@ -1814,11 +4218,145 @@ fn get_node_text(node: &Node, source: &str) -> String {
   source[node.start_byte()..node.end_byte()].to_string()
 }
 
+/// Comments directly above a member (e.g. its JSDoc), one line each. A comment on the line of
+/// the previous member belongs to that member and is left out.
+fn leading_comments(node: &Node, source: &str) -> Option<String> {
+  let mut comments = Vec::new();
+  let mut previous = node.prev_sibling();
+  while let Some(comment) = previous.filter(|sibling| sibling.kind() == "comment") {
+    previous = comment.prev_sibling();
+    if previous.is_some_and(|sibling| sibling.end_position().row == comment.start_position().row)
+    {
+      break;
+    }
+    comments.push(comment);
+  }
+  if comments.is_empty() {
+    return None;
+  }
+
+  let lines: Vec<String> = comments
+    .iter()
+    .rev()
+    .flat_map(|comment| {
+      get_node_text(comment, source)
+        .lines()
+        .map(|line| {
+          // JSDoc continuation lines keep their ` * ` alignment
+          let line = line.trim();
+          if line.starts_with('*') {
+            format!(" {}", line)
+          } else {
+            line.to_string()
+          }
+        })
+        .collect::<Vec<_>>()
+    })
+    .collect();
+  Some(lines.join("\n"))
+}
+
 /// Check if a method is async by looking for the async keyword
-fn check_if_async(node: &Node, source: &str) -> bool {
-  // Check if the node itself contains 'async' keyword
-  let node_text = get_node_text(node, source);
-  node_text.contains("async")
+fn check_if_async(node: &Node) -> bool {
+  // The `async` keyword is a direct child of the function, method or arrow function node, nested
+  // async callbacks and `async` in strings or identifiers do not count
+  let mut cursor = node.walk();
+  let is_async = node
+    .children(&mut cursor)
+    .any(|child| child.kind() == "async");
+  is_async
+}
+
+/// Reserved words that are valid object keys but not valid `const` names
+const RESERVED_WORDS: [&str; 37] = [
+  "await", "break", "case", "catch", "class", "const", "continue", "debugger", "default", "delete",
+  "do", "else", "enum", "export", "extends", "false", "finally", "for", "function", "if", "import",
+  "in", "instanceof", "let", "new", "null", "return", "super", "switch", "this", "throw", "true",
+  "try", "typeof", "var", "void", "while",
+];
+
+/// Identifier a member of the `option` object is declared as in `<script setup>`.
+///
+/// Keys that are not valid identifiers (`'on-save'() {}`, `delete() {}`) are camelCased (or
+/// prefixed with `_`) and recorded in `renamed_members`. Computed keys (`[SAVE]() {}`) have no
+/// static name: they are recorded in `computed_key_members` and the member is skipped.
+fn member_name(
+  key_node: &Node,
+  option: &str,
+  source: &str,
+  state: &mut ScriptParsingState,
+) -> Option<String> {
+  let key_text = get_node_text(key_node, source);
+  if key_node.kind() == "computed_property_name" {
+    state
+      .computed_key_members
+      .push((option.to_string(), key_text));
+    return None;
+  }
+
+  let key = key_text.trim_matches(['"', '\'']);
+  let mut name = String::new();
+  let mut upper_next = false;
+  for c in key.chars() {
+    if c.is_alphanumeric() || c == '_' || c == '$' {
+      if upper_next && !name.is_empty() {
+        name.extend(c.to_uppercase());
+      } else {
+        name.push(c);
+      }
+      upper_next = false;
+    } else {
+      upper_next = true;
+    }
+  }
+  if name.is_empty()
+    || name.starts_with(|c: char| c.is_ascii_digit())
+    || RESERVED_WORDS.contains(&name.as_str())
+  {
+    name.insert(0, '_');
+  }
+
+  if name != key
+    && !state
+      .renamed_members
+      .iter()
+      .any(|(renamed, _)| renamed == key)
+  {
+    state
+      .renamed_members
+      .push((key.to_string(), name.clone()));
+  }
+  Some(name)
+}
+
+/// Whether a node is a function in any of its forms (`function () {}`, `async () => {}`, ...)
+fn is_function_node(node: &Node) -> bool {
+  matches!(
+    node.kind(),
+    "function" | "function_expression" | "arrow_function" | "method_definition"
+  )
+}
+
+/// Lifecycle hook details from any of its forms: `mounted() {}`, `mounted: function () {}`,
+/// `mounted: async () => {}` or `mounted: () => expression`
+fn parse_lifecycle_hook(name: &str, node: &Node, source: &str) -> MethodDetail {
+  let mut body = extract_method_body(node, source);
+  // An expression body becomes the single statement of the hook
+  if node
+    .child_by_field_name("body")
+    .is_some_and(|body_node| body_node.kind() != "statement_block")
+    && !body.ends_with(';')
+  {
+    body.push(';');
+  }
+
+  MethodDetail {
+    name: name.to_string(),
+    parameters: extract_method_parameters(node, source),
+    body,
+    is_async: check_if_async(node),
+    comment: None,
+  }
 }
 
 /// Extract the method body from a function/method node
@@ -1852,37 +4390,28 @@ fn extract_method_body(node: &Node, source: &str) -> String {
   }
 }
 
-/// Extract parameter names from a method/function definition
+/// Extract the parameters of a method/function definition as written
 fn extract_method_parameters(node: &Node, source: &str) -> Vec<String> {
-  let mut parameters = Vec::new();
+  // Single parameter of an arrow function without parentheses: `value => ...`
+  if let Some(parameter) = node.child_by_field_name("parameter") {
+    return vec![get_node_text(&parameter, source)];
+  }
 
-  // Look for formal_parameters node
-  if let Some(params_node) = node.child_by_field_name("parameters") {
-    // Iterate through the parameters
-    for i in 0..params_node.child_count() {
-      if let Some(child) = params_node.child(i) {
-        if child.kind() == "identifier" {
-          let param_name = get_node_text(&child, source);
-          parameters.push(param_name);
-        }
-      }
-    }
-  } else {
-    // Fallback: look for parameters in child nodes
-    for i in 0..node.child_count() {
-      if let Some(child) = node.child(i) {
-        if child.kind() == "formal_parameters" {
-          // Iterate through the parameters
-          for j in 0..child.child_count() {
-            if let Some(param_child) = child.child(j) {
-              if param_child.kind() == "identifier" {
-                let param_name = get_node_text(&param_child, source);
-                parameters.push(param_name);
-              }
-            }
-          }
-          break;
-        }
+  let params_node = node.child_by_field_name("parameters").or_else(|| {
+    let mut cursor = node.walk();
+    let formal_parameters = node
+      .children(&mut cursor)
+      .find(|child| child.kind() == "formal_parameters");
+    formal_parameters
+  });
+
+  // Each parameter verbatim, with its destructuring pattern, default value or rest syntax
+  let mut parameters = Vec::new();
+  if let Some(params_node) = params_node {
+    let mut cursor = params_node.walk();
+    for child in params_node.named_children(&mut cursor) {
+      if child.kind() != "comment" {
+        parameters.push(get_node_text(&child, source));
       }
     }
   }
@@ -2059,6 +4588,7 @@ pub fn walk_tree_recursive_template(
 /// assert!(state.identifiers.contains(&"items".to_string()));
 /// assert!(state.function_calls.contains(&"$t".to_string()));
 /// ```
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
 pub fn parse_template_section(
   template_content: &str,
   state: &mut TemplateParsingState,
@@ -2066,7 +4596,8 @@ pub fn parse_template_section(
   use lol_html::{doc_text, element, rewrite_str, RewriteStrSettings};
   use std::sync::{Arc, Mutex};
 
-  // Use Arc<Mutex<Vec<_>>> to collect results from closures
+  // Use Arc<Mutex<Vec<_>>> to collect results from closures, along with their position in the
+  // template to resolve loop variables and slot props
   let temp_directives = Arc::new(Mutex::new(Vec::new()));
   let temp_mustaches = Arc::new(Mutex::new(Vec::new()));
 
@@ -2074,6 +4605,7 @@ pub fn parse_template_section(
   let directives_ref = Arc::clone(&temp_directives);
   let element_content_handlers = vec![element!("*", move |el| {
     let tag_name = el.tag_name();
+    let position = el.source_location().bytes().start;
     let vue_attributes = el.attributes().iter().filter(|attr| {
       let name = attr.name();
       name.starts_with("v-") || name.starts_with(":") || name.starts_with("@")
@@ -2083,27 +4615,45 @@ pub fn parse_template_section(
       let attr_name = attr.name();
       let attr_value = attr.value();
 
-      directives_ref.lock().unwrap().push(VueDirectiveInfo {
-        name: attr_name.to_string(),
-        value: attr_value.to_string(),
-        element_tag: tag_name.to_string(),
-      });
+      directives_ref.lock().unwrap().push((
+        VueDirectiveInfo {
+          name: attr_name.to_string(),
+          value: attr_value.to_string(),
+          element_tag: tag_name.to_string(),
+        },
+        position,
+      ));
     }
 
     Ok(())
   })];
 
-  // Parse mustache expressions
+  // Parse mustache expressions. lol_html may split a text node into several chunks, so the text
+  // is buffered until the node ends to capture mustaches crossing a chunk boundary
   let mustaches_ref = Arc::clone(&temp_mustaches);
+  let mut text_node = String::new();
+  let mut text_node_start = 0;
   let document_content_handlers = vec![doc_text!(move |t| {
+    if text_node.is_empty() {
+      text_node_start = t.source_location().bytes().start;
+    }
+    text_node.push_str(t.as_str());
+    if !t.last_in_text_node() {
+      return Ok(());
+    }
+
     let mustache_regex = &*MUSTACHE_PATTERN;
-    for cap in mustache_regex.captures_iter(t.as_str()) {
+    for cap in mustache_regex.captures_iter(&text_node) {
       let mustache_content = cap.get(1).map_or("", |m| m.as_str()).trim();
 
-      mustaches_ref.lock().unwrap().push(MustacheExpressionInfo {
-        content: mustache_content.to_string(),
-      });
+      mustaches_ref.lock().unwrap().push((
+        MustacheExpressionInfo {
+          content: mustache_content.to_string(),
+        },
+        text_node_start + cap.get(0).map_or(0, |m| m.start()),
+      ));
     }
+    text_node.clear();
 
     Ok(())
   })];
@@ -2118,40 +4668,119 @@ pub fn parse_template_section(
     },
   )?;
 
-  // Now parse collected directives and mustaches with tree-sitter
-  let language = tree_sitter_javascript::LANGUAGE.into();
-  let mut parser = Parser::new();
-  parser.set_language(&language)?;
+  state.scopes.extend(template_scopes(template_content));
 
+  // Now parse collected directives and mustaches with tree-sitter, after splitting off the Vue
+  // syntax (v-for aliases, slot props, filters) it does not understand
   // Process directives
   let directives = temp_directives.lock().unwrap();
-  for directive in directives.iter() {
+  for (directive, position) in directives.iter() {
     state.vue_directives.push(directive.clone());
 
-    if let Some(tree) = parser.parse(directive.value.as_bytes(), None) {
-      let root_node = tree.root_node();
-      walk_tree_recursive_template(root_node, directive.value.as_bytes(), state);
-    }
+    // The loop variables of `v-for` are only in scope for the element itself, not for the
+    // iterated expression
+    let locals = if directive.name == "v-for" {
+      let enclosing: Vec<TemplateScope> = state
+        .scopes
+        .iter()
+        .filter(|scope| scope.start != *position)
+        .cloned()
+        .collect();
+      scope_locals(&enclosing, *position)
+    } else {
+      state.locals_at(*position)
+    };
+
+    let expression = VueExpression::from_directive(&directive.name, &directive.value);
+    state.add_expression(expression, &locals);
   }
 
   // Process mustache expressions
   let mustaches = temp_mustaches.lock().unwrap();
-  for mustache in mustaches.iter() {
+  for (mustache, position) in mustaches.iter() {
     state.mustache_expressions.push(mustache.clone());
 
-    if let Some(tree) = parser.parse(mustache.content.as_bytes(), None) {
-      let root_node = tree.root_node();
-      walk_tree_recursive_template(root_node, mustache.content.as_bytes(), state);
-    }
+    let locals = state.locals_at(*position);
+    let expression = VueExpression::from_mustache(&mustache.content);
+    state.add_expression(expression, &locals);
   }
 
   Ok(())
 }
 
+/// Collect the scopes `v-for` and slot directives (`v-slot`, `#name`, `slot-scope`) declare in a
+/// template. Self-closing elements (`<Item v-for="..." />`) only scope their own attributes.
+pub fn template_scopes(template_content: &str) -> Vec<TemplateScope> {
+  use lol_html::{element, end_tag, rewrite_str, RewriteStrSettings};
+  use std::sync::{Arc, Mutex};
+
+  let scopes: Arc<Mutex<Vec<TemplateScope>>> = Arc::new(Mutex::new(Vec::new()));
+  let scopes_ref = Arc::clone(&scopes);
+  let template_end = template_content.len();
+  let element_content_handlers = vec![element!("*", move |el| {
+    let names: Vec<String> = el
+      .attributes()
+      .iter()
+      .filter(|attr| attr.name() == "v-for" || is_slot_declaration(&attr.name()))
+      .flat_map(|attr| VueExpression::from_directive(&attr.name(), &attr.value()).declarations)
+      .collect();
+    if names.is_empty() {
+      return Ok(());
+    }
+
+    let location = el.source_location().bytes();
+    let closes_immediately = el.is_self_closing() || !el.can_have_content();
+    let index = {
+      let mut scopes = scopes_ref.lock().unwrap();
+      scopes.push(TemplateScope {
+        names,
+        start: location.start,
+        // Implicitly closed elements get no end tag callback and scope the rest of the template
+        end: if closes_immediately { location.end } else { template_end },
+      });
+      scopes.len() - 1
+    };
+
+    if !closes_immediately {
+      let scopes_ref = Arc::clone(&scopes_ref);
+      el.on_end_tag(end_tag!(move |end| {
+        scopes_ref.lock().unwrap()[index].end = end.source_location().bytes().end;
+        Ok(())
+      }))?;
+    }
+
+    Ok(())
+  })];
+
+  if rewrite_str(
+    template_content,
+    RewriteStrSettings {
+      element_content_handlers,
+      ..RewriteStrSettings::new()
+    },
+  )
+  .is_err()
+  {
+    return Vec::new();
+  }
+
+  let scopes = scopes.lock().unwrap().clone();
+  scopes
+}
+
+/// Names of the scopes containing a byte offset of the template
+fn scope_locals(scopes: &[TemplateScope], position: usize) -> Vec<String> {
+  scopes
+    .iter()
+    .filter(|scope| scope.contains(position))
+    .flat_map(|scope| scope.names.iter().cloned())
+    .collect()
+}
+
 /// Parses the head method to extract its body for transformation to useHead
 fn parse_head_method(node: &Node, source: &str, state: &mut ScriptParsingState) {
   // Extract head method details
-  let is_async = check_if_async(node, source);
+  let is_async = check_if_async(node);
   let body = extract_method_body(node, source);
 
   state.head_method = Some(MethodDetail {
@@ -2159,6 +4788,7 @@ fn parse_head_method(node: &Node, source: &str, state: &mut ScriptParsingState)
     parameters: Vec::new(), // head() method has no parameters
     body,
     is_async,
+    comment: None,
   });
 
   // Also parse for general identifiers and function calls
@@ -2168,7 +4798,7 @@ fn parse_head_method(node: &Node, source: &str, state: &mut ScriptParsingState)
 /// Parses the fetch method to extract its body for transformation to useFetch
 fn parse_fetch_method(node: &Node, source: &str, state: &mut ScriptParsingState) {
   // Extract fetch method details
-  let is_async = check_if_async(node, source);
+  let is_async = check_if_async(node);
   let body = extract_method_body(node, source);
 
   state.fetch_method = Some(MethodDetail {
@@ -2176,6 +4806,7 @@ fn parse_fetch_method(node: &Node, source: &str, state: &mut ScriptParsingState)
     parameters: Vec::new(), // fetch() method typically has no parameters
     body,
     is_async,
+    comment: None,
   });
 
   // Also parse for general identifiers and function calls
@@ -2192,7 +4823,7 @@ fn parse_watchers_object(node: &Node, source: &str, state: &mut ScriptParsingSta
           let watcher_text = get_node_text(&key_node, source);
           let watched_property = watcher_text.trim_matches('"').trim_matches('\'');
 
-          let is_async = check_if_async(&value_node, source);
+          let is_async = check_if_async(&value_node);
           let handler_body = extract_method_body(&value_node, source);
           let param_names = extract_watcher_param_names(&value_node, source);
 
@@ -2209,7 +4840,7 @@ fn parse_watchers_object(node: &Node, source: &str, state: &mut ScriptParsingSta
           let watcher_text = get_node_text(&name_node, source);
           let watched_property = watcher_text.trim_matches('"').trim_matches('\'');
 
-          let is_async = check_if_async(&child, source);
+          let is_async = check_if_async(&child);
           let handler_body = extract_method_body(&child, source);
           let param_names = extract_watcher_param_names(&child, source);
 
@@ -2229,7 +4860,7 @@ fn parse_watchers_object(node: &Node, source: &str, state: &mut ScriptParsingSta
 }
 
 /// Information about a function call with its parameters.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct FunctionCallDetail {
   pub name: String,
   pub arguments: Vec<String>,
@@ -0,0 +1,97 @@
+//! Console output of the command line tool.
+//!
+//! Messages go through the `log` facade, like the records of the library, so `--quiet` and
+//! `--verbose` filter both, for the conversion and for the subcommands. Errors and warnings are
+//! printed to stderr, everything else to stdout. Only the data a subcommand was asked for (the
+//! `--json` output, the model printed by `parse`, the scaffolded mixin configuration) is written to
+//! stdout directly. Directory runs draw an `indicatif` progress bar on stderr when it is a
+//! terminal, which log lines are printed above.
+
+use indicatif::{ProgressBar, ProgressStyle};
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use std::io::Write;
+use std::sync::{Mutex, MutexGuard};
+
+static PROGRESS: Mutex<Option<ProgressBar>> = Mutex::new(None);
+
+struct ConsoleLogger;
+
+impl Log for ConsoleLogger {
+  fn enabled(&self, metadata: &Metadata) -> bool {
+    metadata.level() <= log::max_level()
+  }
+
+  fn log(&self, record: &Record) {
+    if !self.enabled(record.metadata()) {
+      return;
+    }
+
+    let print = || match record.level() {
+      Level::Error | Level::Warn => eprintln!("{}", record.args()),
+      Level::Info | Level::Debug | Level::Trace => {
+        println!("{}", record.args());
+        let _ = std::io::stdout().flush();
+      }
+    };
+    match lock_progress().as_ref() {
+      Some(bar) => bar.suspend(print),
+      None => print(),
+    }
+  }
+
+  fn flush(&self) {
+    let _ = std::io::stdout().flush();
+  }
+}
+
+/// Install the console logger: `--quiet` only keeps errors, `--verbose` adds the per-file
+/// messages and the debug records of the library
+pub fn init(quiet: bool, verbose: bool) {
+  let level = if quiet {
+    LevelFilter::Error
+  } else if verbose {
+    LevelFilter::Debug
+  } else {
+    LevelFilter::Info
+  };
+
+  if log::set_logger(&ConsoleLogger).is_ok() {
+    log::set_max_level(level);
+  }
+}
+
+/// Show a progress bar over `total` files, unless stderr is not a terminal or `--quiet` is set
+pub fn start_progress(total: usize) {
+  if total == 0 || !log::log_enabled!(Level::Info) {
+    return;
+  }
+
+  // Drawn on stderr, and hidden by `indicatif` when it is not a terminal
+  let bar = ProgressBar::new(total as u64).with_style(
+    ProgressStyle::with_template("[{bar:30}] {pos}/{len} files ({eta} remaining)")
+      .expect("valid progress bar template")
+      .progress_chars("#>."),
+  );
+  *lock_progress() = Some(bar);
+}
+
+/// Count a processed file, whether it succeeded or not
+pub fn advance_progress() {
+  if let Some(bar) = lock_progress().as_ref() {
+    bar.inc(1);
+  }
+}
+
+/// Remove the progress bar, before the summary is printed
+pub fn finish_progress() {
+  if let Some(bar) = lock_progress().take() {
+    bar.finish_and_clear();
+  }
+}
+
+fn lock_progress() -> MutexGuard<'static, Option<ProgressBar>> {
+  // A panicking task must not take the console output down with it
+  PROGRESS
+    .lock()
+    .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
@@ -1,97 +1,30 @@
 use anyhow::{Context, Result};
 use clap::{Arg, ArgAction, Command};
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use tokio::fs as async_fs;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
 use vue_options_to_composition::{
-  rewrite_sfc, AdditionalImport, ImportRewrite, MixinConfig, RewriteOptions,
+  analyze::{self, ComponentInventory, Difficulty},
+  config, convert_mixin, parse_component, project, rewrite_sfc_with_report,
+  scaffold_mixin_config, server, store_modules, test_files, Diagnostic, DiagnosticSeverity,
+  RewriteOptions,
 };
 use walkdir::WalkDir;
 
-#[derive(Debug, Deserialize, Serialize)]
-struct CliConfig {
-  mixins: Option<HashMap<String, CliMixinConfig>>,
-  imports_rewrite: Option<HashMap<String, CliImportRewrite>>,
-  additional_imports: Option<HashMap<String, CliAdditionalImport>>,
-  import_keeplist: Option<Vec<String>>,
-}
-
-#[derive(Debug, Deserialize, Serialize)]
-struct CliMixinConfig {
-  name: String,
-  imports: Vec<String>,
-}
-
-#[derive(Debug, Deserialize, Serialize)]
-struct CliImportRewrite {
-  name: String,
-  component_rewrite: Option<HashMap<String, String>>,
-  directives: Option<HashMap<String, String>>,
-}
-
-#[derive(Debug, Deserialize, Serialize)]
-struct CliAdditionalImport {
-  import_path: Option<String>,
-  rewrite_to: Option<String>,
-}
-
-impl From<CliConfig> for RewriteOptions {
-  fn from(cli_config: CliConfig) -> Self {
-    RewriteOptions {
-      mixins: cli_config.mixins.map(|mixins| {
-        mixins
-          .into_iter()
-          .map(|(k, v)| {
-            (
-              k,
-              MixinConfig {
-                name: v.name,
-                imports: v.imports,
-              },
-            )
-          })
-          .collect()
-      }),
-      imports_rewrite: cli_config.imports_rewrite.map(|imports| {
-        imports
-          .into_iter()
-          .map(|(k, v)| {
-            (
-              k,
-              ImportRewrite {
-                name: v.name,
-                component_rewrite: v.component_rewrite,
-                directives: v.directives,
-              },
-            )
-          })
-          .collect()
-      }),
-      additional_imports: cli_config.additional_imports.map(|imports| {
-        imports
-          .into_iter()
-          .map(|(k, v)| {
-            (
-              k,
-              AdditionalImport {
-                import_path: v.import_path,
-                rewrite_to: v.rewrite_to,
-              },
-            )
-          })
-          .collect()
-      }),
-      import_keeplist: cli_config.import_keeplist,
-    }
-  }
-}
+mod cache;
+mod logging;
+mod review;
 
 #[tokio::main]
 async fn main() -> Result<()> {
   let matches = Command::new("vue-options-to-composition")
     .version("0.1.0")
     .about("Transform Vue 2 SFC to Vue 3 Composition API")
+    .subcommand_negates_reqs(true)
+    .args_conflicts_with_subcommands(true)
     .arg(
       Arg::new("input")
         .help("Path to Vue SFC file or directory containing .vue files")
@@ -120,8 +53,280 @@ async fn main() -> Result<()> {
         .num_args(0)
         .help("Process directories recursively"),
     )
+    .arg(
+      Arg::new("follow-symlinks")
+        .long("follow-symlinks")
+        .action(ArgAction::SetTrue)
+        .num_args(0)
+        .help("Descend into symlinked directories when processing recursively"),
+    )
+    .arg(
+      Arg::new("project")
+        .short('p')
+        .long("project")
+        .action(ArgAction::SetTrue)
+        .num_args(0)
+        .help("Update listeners in other components when a component's emitted events are renamed"),
+    )
+    .arg(
+      Arg::new("expose-refs")
+        .long("expose-refs")
+        .value_name("DIR")
+        .help("Expose the members components below DIR call on the converted components through $refs"),
+    )
+    .arg(
+      Arg::new("cache")
+        .long("cache")
+        .value_name("DIR")
+        .help("Skip files unchanged since the last run with the same cache directory and config"),
+    )
+    .arg(
+      Arg::new("check-config")
+        .long("check-config")
+        .action(ArgAction::SetTrue)
+        .num_args(0)
+        .requires("config")
+        .help("Print the effective configuration and check it against the input instead of converting"),
+    )
+    .arg(
+      Arg::new("quiet")
+        .short('q')
+        .long("quiet")
+        .action(ArgAction::SetTrue)
+        .global(true)
+        .num_args(0)
+        .conflicts_with("verbose")
+        .help("Only print errors, without progress bar or summary"),
+    )
+    .arg(
+      Arg::new("verbose")
+        .short('v')
+        .long("verbose")
+        .action(ArgAction::SetTrue)
+        .global(true)
+        .num_args(0)
+        .help("Also print every transformed file and the transformers applied to it"),
+    )
+    .subcommand(
+      Command::new("parse")
+        .about(
+          "Print the parsed component model (props, data, computed, watchers, directives, ...)",
+        )
+        .arg(
+          Arg::new("input")
+            .help("Path to Vue SFC file")
+            .required(true)
+            .index(1),
+        )
+        .arg(
+          Arg::new("json")
+            .long("json")
+            .action(ArgAction::SetTrue)
+            .num_args(0)
+            .help("Print the model as JSON"),
+        ),
+    )
+    .subcommand(
+      Command::new("review")
+        .about("Serve a local web UI to review conversions and write the accepted files")
+        .arg(
+          Arg::new("input")
+            .help("Path to Vue SFC file or directory containing .vue files")
+            .required(true)
+            .index(1),
+        )
+        .arg(
+          Arg::new("config")
+            .short('c')
+            .long("config")
+            .value_name("FILE")
+            .help("Configuration TOML file path"),
+        )
+        .arg(
+          Arg::new("recursive")
+            .short('r')
+            .long("recursive")
+            .action(ArgAction::SetTrue)
+            .num_args(0)
+            .help("Process directories recursively"),
+        )
+        .arg(
+          Arg::new("port")
+            .long("port")
+            .value_name("PORT")
+            .value_parser(clap::value_parser!(u16))
+            .default_value("8080")
+            .help("Port to serve the review UI on"),
+        ),
+    )
+    .subcommand(
+      Command::new("serve")
+        .about("Convert components sent as JSON-RPC requests over stdin/stdout, for editor extensions")
+        .arg(
+          Arg::new("config")
+            .short('c')
+            .long("config")
+            .value_name("FILE")
+            .help("Configuration TOML file path, used for requests without options"),
+        ),
+    )
+    .subcommand(
+      Command::new("analyze")
+        .about(
+          "Inventory the Options API features, missing config entries and conversion difficulty of components",
+        )
+        .arg(
+          Arg::new("input")
+            .help("Path to Vue SFC file or directory containing .vue files (searched recursively)")
+            .required(true)
+            .index(1),
+        )
+        .arg(
+          Arg::new("config")
+            .short('c')
+            .long("config")
+            .value_name("FILE")
+            .help("Configuration TOML file path"),
+        )
+        .arg(
+          Arg::new("json")
+            .long("json")
+            .action(ArgAction::SetTrue)
+            .num_args(0)
+            .help("Print the inventory as JSON"),
+        ),
+    )
+    .subcommand(
+      Command::new("scaffold-mixin-config")
+        .about("Print the [mixins] config entries for the Vue 2 mixin files in a directory")
+        .arg(
+          Arg::new("input")
+            .help("Directory containing mixin files (searched recursively) or a single mixin file")
+            .required(true)
+            .index(1),
+        ),
+    )
+    .subcommand(
+      Command::new("convert-mixin")
+        .about("Convert a Vue 2 mixin file to a composable")
+        .arg(
+          Arg::new("input")
+            .help("Path to the mixin file")
+            .required(true)
+            .index(1),
+        )
+        .arg(
+          Arg::new("config")
+            .short('c')
+            .long("config")
+            .value_name("FILE")
+            .help("Configuration TOML file path"),
+        )
+        .arg(
+          Arg::new("output")
+            .short('o')
+            .long("output")
+            .value_name("DIR")
+            .default_value("src/composables")
+            .help("Composables directory the composable is written to"),
+        ),
+    )
+    .subcommand(
+      Command::new("convert-store")
+        .about("Convert the Vuex modules of a store directory to Pinia stores")
+        .arg(
+          Arg::new("input")
+            .help("Vuex store directory (searched recursively)")
+            .required(true)
+            .index(1),
+        )
+        .arg(
+          Arg::new("output")
+            .short('o')
+            .long("output")
+            .value_name("DIR")
+            .default_value("src/stores")
+            .help("Stores directory the Pinia stores are written to"),
+        ),
+    )
+    .subcommand(
+      Command::new("tests")
+        .about("Rewrite co-located Jest/Vitest spec files for Vue Test Utils 2")
+        .arg(
+          Arg::new("input")
+            .help("Directory containing spec files (searched recursively)")
+            .required(true)
+            .index(1),
+        ),
+    )
     .get_matches();
 
+  logging::init(matches.get_flag("quiet"), matches.get_flag("verbose"));
+
+  match matches.subcommand() {
+    Some(("parse", parse_matches)) => {
+      let input_path = parse_matches.get_one::<String>("input").unwrap();
+      return parse_file(Path::new(input_path), parse_matches.get_flag("json")).await;
+    }
+    Some(("review", review_matches)) => {
+      let input_path = review_matches.get_one::<String>("input").unwrap();
+      let config = match review_matches.get_one::<String>("config") {
+        Some(config_path) => Some(load_config(config_path).await?),
+        None => None,
+      };
+      return review_path(
+        Path::new(input_path),
+        config,
+        review_matches.get_flag("recursive"),
+        *review_matches.get_one::<u16>("port").unwrap(),
+      )
+      .await;
+    }
+    Some(("serve", serve_matches)) => {
+      let config = match serve_matches.get_one::<String>("config") {
+        Some(config_path) => Some(read_config(config_path).await?),
+        None => None,
+      };
+      return serve_stdio(config).await;
+    }
+    Some(("analyze", analyze_matches)) => {
+      let input_path = analyze_matches.get_one::<String>("input").unwrap();
+      let config = match analyze_matches.get_one::<String>("config") {
+        Some(config_path) => Some(read_config(config_path).await?),
+        None => None,
+      };
+      return analyze_path(
+        Path::new(input_path),
+        config,
+        analyze_matches.get_flag("json"),
+      )
+      .await;
+    }
+    Some(("scaffold-mixin-config", scaffold_matches)) => {
+      let input_path = scaffold_matches.get_one::<String>("input").unwrap();
+      return scaffold_mixins(Path::new(input_path)).await;
+    }
+    Some(("convert-mixin", convert_matches)) => {
+      let input_path = convert_matches.get_one::<String>("input").unwrap();
+      let config = match convert_matches.get_one::<String>("config") {
+        Some(config_path) => Some(load_config(config_path).await?),
+        None => None,
+      };
+      let output_dir = convert_matches.get_one::<String>("output").unwrap();
+      return convert_mixin_file(Path::new(input_path), Path::new(output_dir), config).await;
+    }
+    Some(("convert-store", store_matches)) => {
+      let input_path = store_matches.get_one::<String>("input").unwrap();
+      let output_dir = store_matches.get_one::<String>("output").unwrap();
+      return convert_store_modules(Path::new(input_path), Path::new(output_dir)).await;
+    }
+    Some(("tests", tests_matches)) => {
+      let input_path = tests_matches.get_one::<String>("input").unwrap();
+      return rewrite_test_files(Path::new(input_path)).await;
+    }
+    _ => {}
+  }
+
   let input_path = matches.get_one::<String>("input").unwrap();
   let output_path = matches
     .get_one::<String>("output")
@@ -129,6 +334,13 @@ async fn main() -> Result<()> {
     .unwrap_or(input_path);
   let config_path = matches.get_one::<String>("config");
   let recursive = matches.get_flag("recursive");
+  let follow_symlinks = matches.get_flag("follow-symlinks");
+  let project_mode = matches.get_flag("project");
+
+  if matches.get_flag("check-config") {
+    let config = read_config(config_path.unwrap()).await?;
+    return check_config_path(Path::new(input_path), config, recursive, follow_symlinks).await;
+  }
 
   // Load configuration if provided
   let config = if let Some(config_path) = config_path {
@@ -137,7 +349,40 @@ async fn main() -> Result<()> {
     None
   };
 
-  let success_count = process_path(input_path, output_path, config, recursive).await?;
+  let ref_members = match matches.get_one::<String>("expose-refs") {
+    Some(source_dir) => collect_ref_members(Path::new(source_dir)).await?,
+    None => BTreeMap::new(),
+  };
+
+  // The cache is invalidated by any change to the effective configuration (with its environment
+  // variables substituted and its import paths resolved) or to the members used through refs
+  let cache = match matches.get_one::<String>("cache") {
+    Some(cache_dir) => {
+      let mut config_content = match &config {
+        Some(config) => serde_json::to_value(config)?.to_string(),
+        None => String::new(),
+      };
+      if !ref_members.is_empty() {
+        config_content.push_str(&serde_json::to_string(&ref_members)?);
+      }
+      Some(cache::Cache::load(Path::new(cache_dir), &config_content).await)
+    }
+    None => None,
+  };
+
+  let success_count = process_path(
+    input_path,
+    output_path,
+    FileOptions {
+      config,
+      ref_members,
+    },
+    recursive,
+    follow_symlinks,
+    project_mode,
+    cache,
+  )
+  .await?;
 
   if success_count == 0 {
     std::process::exit(1);
@@ -146,36 +391,710 @@ async fn main() -> Result<()> {
   Ok(())
 }
 
+async fn parse_file(input_path: &Path, json: bool) -> Result<()> {
+  let content = async_fs::read_to_string(input_path)
+    .await
+    .with_context(|| format!("Failed to read file: {}", input_path.display()))?;
+
+  let model = parse_component(&content)
+    .map_err(|e| anyhow::format_err!("{}", e))
+    .with_context(|| format!("Failed to parse: {}", input_path.display()))?;
+
+  if json {
+    println!("{}", serde_json::to_string_pretty(&model)?);
+  } else {
+    println!("{:#?}", model);
+  }
+
+  Ok(())
+}
+
+async fn review_path(
+  input_path: &Path,
+  config: Option<RewriteOptions>,
+  recursive: bool,
+  port: u16,
+) -> Result<()> {
+  let input_metadata = input_path
+    .metadata()
+    .with_context(|| format!("Path not found: {}", input_path.display()))?;
+
+  let (root, files) = if input_metadata.is_dir() {
+    let (files, skipped) = find_vue_files(input_path, recursive, false).await?;
+    print_skipped(&skipped);
+    (input_path.to_path_buf(), files)
+  } else {
+    let root = input_path.parent().unwrap_or_else(|| Path::new("."));
+    (root.to_path_buf(), vec![input_path.to_path_buf()])
+  };
+
+  if files.is_empty() {
+    log::info!("No .vue files found in the specified directory.");
+    return Ok(());
+  }
+
+  review::serve(&root, files, config, port).await
+}
+
+/// Inventory of a component file, as printed by `analyze --json`
+#[derive(Debug, Serialize)]
+struct FileInventory {
+  file: String,
+  #[serde(flatten)]
+  inventory: Option<ComponentInventory>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  error: Option<String>,
+}
+
+/// Answer JSON-RPC requests read line by line from stdin until `shutdown` or the end of input.
+/// Nothing else is printed to stdout, which carries the responses.
+async fn serve_stdio(config: Option<RewriteOptions>) -> Result<()> {
+  let mut server = server::Server::new(config);
+  let mut lines = BufReader::new(tokio::io::stdin()).lines();
+  let mut stdout = tokio::io::stdout();
+
+  while let Some(line) = lines.next_line().await? {
+    if line.trim().is_empty() {
+      continue;
+    }
+    if let Some(response) = server.handle(&line) {
+      stdout.write_all(response.as_bytes()).await?;
+      stdout.write_all(b"\n").await?;
+      stdout.flush().await?;
+    }
+    if server.is_shut_down() {
+      break;
+    }
+  }
+
+  Ok(())
+}
+
+/// Print the inventory of the components below `input_path` without rewriting them
+async fn analyze_path(input_path: &Path, config: Option<RewriteOptions>, json: bool) -> Result<()> {
+  let input_metadata = input_path
+    .metadata()
+    .with_context(|| format!("Path not found: {}", input_path.display()))?;
+
+  let mut files = if input_metadata.is_dir() {
+    let (files, skipped) = find_vue_files(input_path, true, false).await?;
+    if !json {
+      print_skipped(&skipped);
+    }
+    files
+  } else {
+    vec![input_path.to_path_buf()]
+  };
+  files.sort();
+
+  let mut inventories = Vec::new();
+  for path in &files {
+    let (inventory, error) = match read_source(path).await {
+      Ok(content) => match analyze::analyze_component(&content, config.clone()) {
+        Ok(inventory) => (Some(inventory), None),
+        Err(e) => (None, Some(e.to_string())),
+      },
+      Err(skipped) => (None, Some(skipped.reason)),
+    };
+    inventories.push(FileInventory {
+      file: path.display().to_string(),
+      inventory,
+      error,
+    });
+  }
+
+  if json {
+    println!("{}", serde_json::to_string_pretty(&inventories)?);
+    return Ok(());
+  }
+
+  if inventories.is_empty() {
+    log::info!("No .vue files found in the specified directory.");
+    return Ok(());
+  }
+
+  let mut difficulties: HashMap<Difficulty, usize> = HashMap::new();
+  let mut features: HashMap<&str, usize> = HashMap::new();
+  let mut mixins: HashMap<&str, usize> = HashMap::new();
+  let mut injections: HashMap<&str, usize> = HashMap::new();
+  let mut failed = 0;
+
+  for file in &inventories {
+    let Some(inventory) = &file.inventory else {
+      log::error!(
+        "🔎 {} [failed]\n   ❌ {}",
+        file.file,
+        file.error.as_deref().unwrap_or_default()
+      );
+      failed += 1;
+      continue;
+    };
+
+    let difficulty = match inventory.difficulty {
+      Difficulty::Easy => "easy",
+      Difficulty::Medium => "medium",
+      Difficulty::Hard => "hard",
+    };
+    log::info!("🔎 {} [{}]", file.file, difficulty);
+    if !inventory.features.is_empty() {
+      log::info!("   features: {}", inventory.features.join(", "));
+    }
+    if !inventory.unconfigured_mixins.is_empty() {
+      log::info!(
+        "   unconfigured mixins: {}",
+        inventory.unconfigured_mixins.join(", ")
+      );
+    }
+    if !inventory.unknown_injections.is_empty() {
+      log::info!(
+        "   unknown injections: {}",
+        inventory.unknown_injections.join(", ")
+      );
+    }
+    if inventory.unresolved > 0 || inventory.warnings > 0 {
+      log::info!(
+        "   {} unresolved identifier(s), {} warning(s)",
+        inventory.unresolved,
+        inventory.warnings
+      );
+    }
+
+    *difficulties.entry(inventory.difficulty).or_default() += 1;
+    for feature in &inventory.features {
+      *features.entry(feature).or_default() += 1;
+    }
+    for mixin in &inventory.unconfigured_mixins {
+      *mixins.entry(mixin).or_default() += 1;
+    }
+    for injection in &inventory.unknown_injections {
+      *injections.entry(injection).or_default() += 1;
+    }
+  }
+
+  log::info!(
+    "\n📊 {} component(s): {} easy, {} medium, {} hard{}",
+    inventories.len(),
+    difficulties.get(&Difficulty::Easy).unwrap_or(&0),
+    difficulties.get(&Difficulty::Medium).unwrap_or(&0),
+    difficulties.get(&Difficulty::Hard).unwrap_or(&0),
+    if failed > 0 {
+      format!(", {} failed", failed)
+    } else {
+      String::new()
+    }
+  );
+  for (label, counts) in [
+    ("features", features),
+    ("mixins to configure", mixins),
+    ("unknown injections", injections),
+  ] {
+    if counts.is_empty() {
+      continue;
+    }
+    // Most used first
+    let mut counts: Vec<(&str, usize)> = counts.into_iter().collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+    let listed: Vec<String> = counts
+      .iter()
+      .map(|(name, count)| format!("{} ({})", name, count))
+      .collect();
+    log::info!("   {}: {}", label, listed.join(", "));
+  }
+
+  Ok(())
+}
+
+/// Print the effective configuration and the entries no component below `input_path` uses,
+/// exiting with an error when there are some
+async fn check_config_path(
+  input_path: &Path,
+  config: RewriteOptions,
+  recursive: bool,
+  follow_symlinks: bool,
+) -> Result<()> {
+  let input_metadata = input_path
+    .metadata()
+    .with_context(|| format!("Path not found: {}", input_path.display()))?;
+
+  let files = if input_metadata.is_dir() {
+    let (files, skipped) = find_vue_files(input_path, recursive, follow_symlinks).await?;
+    print_skipped(&skipped);
+    files
+  } else {
+    vec![input_path.to_path_buf()]
+  };
+
+  let mut import_sources: Vec<String> = Vec::new();
+  for path in &files {
+    let Ok(content) = read_source(path).await else {
+      continue;
+    };
+    let Ok(context) = parse_component(&content) else {
+      continue;
+    };
+    for import_info in context.script_state.imports {
+      if !import_sources.contains(&import_info.source) {
+        import_sources.push(import_info.source);
+      }
+    }
+  }
+
+  log::info!("📝 Effective configuration:\n{:#?}\n", config);
+
+  let diagnostics = config::check_config(&config, &import_sources);
+  if diagnostics.is_empty() {
+    log::info!(
+      "✅ Configuration checked against {} component(s)",
+      files.len()
+    );
+    return Ok(());
+  }
+
+  log::error!(
+    "🔎 Configuration checked against {} component(s), {} problem(s):",
+    files.len(),
+    diagnostics.len()
+  );
+  diagnostics.iter().for_each(print_diagnostic);
+  std::process::exit(1);
+}
+
+/// Print the `[mixins.xxx]` config entries of the mixin files below `input_path`. Files without
+/// members are listed as TOML comments, so the output can be appended to a config as is.
+async fn scaffold_mixins(input_path: &Path) -> Result<()> {
+  let input_metadata = input_path
+    .metadata()
+    .with_context(|| format!("Path not found: {}", input_path.display()))?;
+
+  let mut mixin_files: Vec<PathBuf> = if input_metadata.is_dir() {
+    WalkDir::new(input_path)
+      .into_iter()
+      .filter_entry(|e| {
+        let name = e.file_name();
+        name != "node_modules" && name != ".git" && name != "dist" && name != "build"
+      })
+      .filter_map(|e| e.ok())
+      .map(|entry| entry.into_path())
+      .filter(|path| {
+        path.is_file()
+          && !is_spec_file(path)
+          && ["js", "ts", "mjs"]
+            .iter()
+            .any(|ext| path.extension().is_some_and(|e| e == *ext))
+      })
+      .collect()
+  } else {
+    vec![input_path.to_path_buf()]
+  };
+  mixin_files.sort();
+
+  let mut keys: Vec<String> = Vec::new();
+  let mut entries = Vec::new();
+  let mut skipped = Vec::new();
+
+  for path in &mixin_files {
+    let content = async_fs::read_to_string(path)
+      .await
+      .with_context(|| format!("Failed to read file: {}", path.display()))?;
+
+    // The mixin transformer matches mixins by the last segment of their import path
+    let key = path
+      .file_stem()
+      .map(|stem| stem.to_string_lossy().to_string())
+      .unwrap_or_default();
+
+    let reason = match scaffold_mixin_config(&key, &content) {
+      Ok(_) if keys.contains(&key) => format!("duplicate mixin name `{}`", key),
+      Ok(config) if config.imports.is_empty() => "no data, computed or methods found".to_string(),
+      Ok(config) => {
+        let imports: Vec<String> = config
+          .imports
+          .iter()
+          .map(|import| format!("\"{}\"", import))
+          .collect();
+        // Stems like `price.mixin` need a quoted key
+        let table_key = if key
+          .chars()
+          .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+          key.clone()
+        } else {
+          format!("\"{}\"", key)
+        };
+        entries.push(format!(
+          "[mixins.{}]\nname = \"{}\"\nimports = [{}]",
+          table_key,
+          config.name,
+          imports.join(", ")
+        ));
+        keys.push(key);
+        continue;
+      }
+      Err(e) => format!("failed to parse: {}", e),
+    };
+    skipped.push(format!("# Skipped {}: {}", path.display(), reason));
+  }
+
+  if entries.is_empty() && skipped.is_empty() {
+    println!("# No mixin files found in the specified directory.");
+    return Ok(());
+  }
+
+  println!("{}", entries.join("\n\n"));
+  if !skipped.is_empty() {
+    if !entries.is_empty() {
+      println!();
+    }
+    println!("{}", skipped.join("\n"));
+  }
+
+  Ok(())
+}
+
+/// Convert a mixin file to a composable written to `output_dir` as `useXxx.js` (`.ts` for
+/// TypeScript mixins), the path the mixin transformer imports composables from
+async fn convert_mixin_file(
+  input_path: &Path,
+  output_dir: &Path,
+  config: Option<RewriteOptions>,
+) -> Result<()> {
+  log::info!("📄 Converting mixin: {}", input_path.display());
+
+  let content = async_fs::read_to_string(input_path)
+    .await
+    .with_context(|| format!("Failed to read file: {}", input_path.display()))?;
+
+  let file_stem = input_path
+    .file_stem()
+    .map(|stem| stem.to_string_lossy().to_string())
+    .unwrap_or_default();
+  let composable = convert_mixin(&file_stem, &content, config)
+    .map_err(|e| anyhow::format_err!("{}", e))
+    .with_context(|| format!("Failed to convert mixin: {}", input_path.display()))?;
+
+  let extension = if input_path.extension().is_some_and(|ext| ext == "ts") {
+    "ts"
+  } else {
+    "js"
+  };
+  let output_path = output_dir.join(format!("{}.{}", composable.name, extension));
+  if output_path.exists() {
+    anyhow::bail!("Composable already exists: {}", output_path.display());
+  }
+
+  async_fs::create_dir_all(output_dir)
+    .await
+    .with_context(|| {
+      format!(
+        "Failed to create output directory: {}",
+        output_dir.display()
+      )
+    })?;
+  async_fs::write(&output_path, &composable.code)
+    .await
+    .with_context(|| format!("Failed to write output file: {}", output_path.display()))?;
+
+  log::info!("   ✅ Written to: {}", output_path.display());
+
+  if !composable.parameters.is_empty() {
+    log::info!(
+      "   ℹ️  [composable-parameters] `{}` expects ({}) from the component using it, pass them where it is called",
+      composable.name,
+      composable.parameters.join(", ")
+    );
+  }
+  for diagnostic in &composable.diagnostics {
+    print_diagnostic(diagnostic);
+  }
+
+  Ok(())
+}
+
+/// Convert the Vuex modules below `input_path` to Pinia stores written to `output_dir` as
+/// `<namespace>.js`, the path the Vuex transformer imports stores from
+async fn convert_store_modules(input_path: &Path, output_dir: &Path) -> Result<()> {
+  if !input_path.is_dir() {
+    anyhow::bail!("Directory not found: {}", input_path.display());
+  }
+
+  let mut module_files: Vec<PathBuf> = WalkDir::new(input_path)
+    .into_iter()
+    .filter_entry(|e| e.file_name() != "node_modules")
+    .filter_map(|e| e.ok())
+    .map(|entry| entry.into_path())
+    .filter(|path| {
+      path.is_file()
+        && !is_spec_file(path)
+        && ["js", "ts", "mjs"]
+          .iter()
+          .any(|ext| path.extension().is_some_and(|e| e == *ext))
+    })
+    .collect();
+  module_files.sort();
+
+  let mut converted = 0;
+  for path in &module_files {
+    let stem = path
+      .file_stem()
+      .map(|stem| stem.to_string_lossy().to_string())
+      .unwrap_or_default();
+    let parent = path.parent().unwrap_or(input_path);
+
+    // Modules are namespaced by their file name, or by their directory for `cart/index.js`
+    let namespace = match stem.as_str() {
+      "index" if parent == input_path => {
+        log::warn!("⏭️  Skipped root store: {}", path.display());
+        continue;
+      }
+      "index" => parent
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default(),
+      "state" | "getters" | "mutations" | "actions" => {
+        log::warn!(
+          "⏭️  Skipped {}: modules split into several files are not supported, merge them first",
+          path.display()
+        );
+        continue;
+      }
+      _ => stem,
+    };
+
+    let content = async_fs::read_to_string(path)
+      .await
+      .with_context(|| format!("Failed to read file: {}", path.display()))?;
+    let Some(conversion) = store_modules::convert_store_module(&namespace, &content) else {
+      log::warn!(
+        "⏭️  Skipped {}: no state, getters, mutations or actions found",
+        path.display()
+      );
+      continue;
+    };
+
+    log::info!("📄 Converting store module: {}", path.display());
+
+    let extension = if path.extension().is_some_and(|ext| ext == "ts") {
+      "ts"
+    } else {
+      "js"
+    };
+    let output_path = output_dir.join(format!("{}.{}", namespace, extension));
+    if output_path.exists() {
+      log::warn!("   ⏭️  Store already exists: {}", output_path.display());
+      continue;
+    }
+
+    async_fs::create_dir_all(output_dir)
+      .await
+      .with_context(|| {
+        format!(
+          "Failed to create output directory: {}",
+          output_dir.display()
+        )
+      })?;
+    async_fs::write(&output_path, &conversion.code)
+      .await
+      .with_context(|| format!("Failed to write output file: {}", output_path.display()))?;
+    log::info!(
+      "   ✅ Written {} to: {}",
+      conversion.name,
+      output_path.display()
+    );
+    converted += 1;
+
+    for diagnostic in &conversion.diagnostics {
+      print_diagnostic(diagnostic);
+    }
+  }
+
+  log::info!(
+    "\n✨ Converted {} store module(s) to Pinia stores",
+    converted
+  );
+
+  Ok(())
+}
+
+/// Rewrite `*.spec.*` / `*.test.*` files below `input_path` in place
+async fn rewrite_test_files(input_path: &Path) -> Result<()> {
+  if !input_path.is_dir() {
+    anyhow::bail!("Directory not found: {}", input_path.display());
+  }
+
+  let spec_files: Vec<PathBuf> = WalkDir::new(input_path)
+    .into_iter()
+    .filter_entry(|e| {
+      let name = e.file_name();
+      name != "node_modules" && name != ".git" && name != "dist" && name != "build"
+    })
+    .filter_map(|e| e.ok())
+    .map(|entry| entry.into_path())
+    .filter(|path| path.is_file() && is_spec_file(path))
+    .collect();
+
+  if spec_files.is_empty() {
+    log::info!("No spec files found in the specified directory.");
+    return Ok(());
+  }
+
+  let mut rewritten = 0;
+  for path in &spec_files {
+    let content = async_fs::read_to_string(path)
+      .await
+      .with_context(|| format!("Failed to read file: {}", path.display()))?;
+
+    let rewrite = test_files::rewrite_test_file(&content);
+    if rewrite.code == content && rewrite.diagnostics.is_empty() {
+      continue;
+    }
+
+    log::info!("🧪 {}", path.display());
+    if rewrite.code != content {
+      async_fs::write(path, &rewrite.code)
+        .await
+        .with_context(|| format!("Failed to write file: {}", path.display()))?;
+      log::info!("   ✅ Rewritten");
+      rewritten += 1;
+    }
+
+    for diagnostic in &rewrite.diagnostics {
+      print_diagnostic(diagnostic);
+    }
+  }
+
+  log::info!(
+    "\n✨ Rewrote {} of {} spec file(s)",
+    rewritten,
+    spec_files.len()
+  );
+
+  Ok(())
+}
+
+/// Whether the file is a JavaScript/TypeScript spec (`Button.spec.js`, `Button.test.ts`, ...)
+fn is_spec_file(path: &Path) -> bool {
+  let name = path
+    .file_name()
+    .map(|name| name.to_string_lossy())
+    .unwrap_or_default();
+  let is_script = ["js", "ts", "jsx", "tsx", "mjs"]
+    .iter()
+    .any(|ext| path.extension().is_some_and(|e| e == *ext));
+
+  is_script && (name.contains(".spec.") || name.contains(".test."))
+}
+
 async fn load_config(config_path: &str) -> Result<RewriteOptions> {
+  let config = read_config(config_path).await?;
+
+  if let Ok(resolved_path) = Path::new(config_path).canonicalize() {
+    log::info!("📝 Loaded configuration from: {}", resolved_path.display());
+  }
+
+  Ok(config)
+}
+
+/// Read the configuration file without reporting it, for commands printing machine-readable output
+async fn read_config(config_path: &str) -> Result<RewriteOptions> {
   let resolved_path = Path::new(config_path)
     .canonicalize()
     .with_context(|| format!("Configuration file not found: {}", config_path))?;
 
-  println!("📝 Loaded configuration from: {}", resolved_path.display());
-
   let config_content = async_fs::read_to_string(&resolved_path)
     .await
     .with_context(|| format!("Failed to read configuration file: {}", config_path))?;
 
-  let cli_config: CliConfig = toml::from_str(&config_content)
-    .with_context(|| format!("Invalid TOML in configuration file: {}", config_path))?;
+  let config_dir = resolved_path.parent().unwrap_or(Path::new("."));
+  config::parse_config_file(&config_content, config_dir)
+    .with_context(|| format!("Invalid TOML in configuration file: {}", config_path))
+}
+
+/// A file or directory left out of a run, listed in the summary
+#[derive(Debug)]
+struct SkippedFile {
+  path: PathBuf,
+  reason: String,
+}
 
-  Ok(cli_config.into())
+impl std::fmt::Display for SkippedFile {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}: {}", self.path.display(), self.reason)
+  }
 }
 
-async fn find_vue_files(dir_path: &Path, recursive: bool) -> Result<Vec<PathBuf>> {
+impl std::error::Error for SkippedFile {}
+
+fn print_skipped(skipped: &[SkippedFile]) {
+  if skipped.is_empty() {
+    return;
+  }
+  let mut message = format!("⏭️  Skipped {} path(s):", skipped.len());
+  for file in skipped {
+    message.push_str(&format!("\n   {}", file));
+  }
+  log::warn!("{}", message);
+}
+
+/// Read a source file, skipping files that can not be read, look binary or are not UTF-8
+async fn read_source(path: &Path) -> std::result::Result<String, SkippedFile> {
+  let skipped = |reason: String| SkippedFile {
+    path: path.to_path_buf(),
+    reason,
+  };
+  let bytes = async_fs::read(path)
+    .await
+    .map_err(|e| skipped(format!("unreadable ({})", e)))?;
+
+  // Same heuristic as git: a NUL byte in the first 8000 bytes means binary content
+  if bytes.iter().take(8000).any(|byte| *byte == 0) {
+    return Err(skipped("binary content".to_string()));
+  }
+
+  String::from_utf8(bytes).map_err(|e| {
+    skipped(format!(
+      "not valid UTF-8 (invalid byte at offset {})",
+      e.utf8_error().valid_up_to()
+    ))
+  })
+}
+
+/// The `.vue` files of a directory, and the entries that could not be read (permission denied,
+/// symlink loops, ...). Symlinked files are always included, symlinked directories are only
+/// descended into with `follow_symlinks`.
+async fn find_vue_files(
+  dir_path: &Path,
+  recursive: bool,
+  follow_symlinks: bool,
+) -> Result<(Vec<PathBuf>, Vec<SkippedFile>)> {
   let mut vue_files = Vec::new();
+  let mut skipped = Vec::new();
 
   if recursive {
     for entry in WalkDir::new(dir_path)
+      .follow_links(follow_symlinks)
       .into_iter()
       .filter_entry(|e| {
         // Skip common directories that shouldn't contain Vue source files
         let name = e.file_name();
         name != "node_modules" && name != ".git" && name != "dist" && name != "build"
       })
-      .filter_map(|e| e.ok())
     {
+      let entry = match entry {
+        Ok(entry) => entry,
+        Err(e) => {
+          let reason = match e.loop_ancestor() {
+            Some(ancestor) => format!("symlink loop to {}", ancestor.display()),
+            None => e
+              .io_error()
+              .map_or_else(|| e.to_string(), |io_error| io_error.to_string()),
+          };
+          skipped.push(SkippedFile {
+            path: e.path().unwrap_or(dir_path).to_path_buf(),
+            reason,
+          });
+          continue;
+        }
+      };
       let path = entry.path();
       if path.is_file() && path.extension().is_some_and(|ext| ext == "vue") {
         vue_files.push(path.to_path_buf());
@@ -186,35 +1105,81 @@ async fn find_vue_files(dir_path: &Path, recursive: bool) -> Result<Vec<PathBuf>
       .await
       .with_context(|| format!("Error reading directory: {}", dir_path.display()))?;
 
-    while let Some(entry) = entries.next_entry().await? {
-      let path = entry.path();
-      if path.is_file() && path.extension().is_some_and(|ext| ext == "vue") {
-        vue_files.push(path);
+    loop {
+      match entries.next_entry().await {
+        Ok(Some(entry)) => {
+          let path = entry.path();
+          if path.is_file() && path.extension().is_some_and(|ext| ext == "vue") {
+            vue_files.push(path);
+          }
+        }
+        Ok(None) => break,
+        Err(e) => {
+          skipped.push(SkippedFile {
+            path: dir_path.to_path_buf(),
+            reason: e.to_string(),
+          });
+          break;
+        }
       }
     }
   }
 
-  Ok(vue_files)
+  Ok((vue_files, skipped))
+}
+
+/// Result of transforming a single file
+struct FileOutcome {
+  /// Canonical input path, the key of the file in the cache
+  input_path: PathBuf,
+  /// Hash of the input that was converted
+  input_hash: String,
+  /// Whether the file was skipped because the cache has it
+  cached: bool,
+  output_path: PathBuf,
+  additional_files: Vec<(PathBuf, String)>,
+  component_tags: Vec<String>,
+  event_renames: Vec<(String, String)>,
 }
 
 async fn transform_file(
   input_path: &Path,
   output_path: &Path,
   config: Option<RewriteOptions>,
-) -> Result<bool> {
+  cache: Option<Arc<cache::Cache>>,
+) -> Result<FileOutcome> {
   let resolved_input = input_path
     .canonicalize()
     .with_context(|| format!("Input file not found: {}", input_path.display()))?;
 
-  println!("📄 Transforming: {}", resolved_input.display());
+  // Read the input file, unreadable, binary and non-UTF-8 files are skipped
+  let content = read_source(input_path).await?;
 
-  // Read the input file
-  let content = async_fs::read_to_string(&resolved_input)
-    .await
-    .with_context(|| format!("Failed to read file: {}", resolved_input.display()))?;
+  let component_tags = input_path
+    .file_stem()
+    .map(|stem| project::component_tag_names(&stem.to_string_lossy()))
+    .unwrap_or_default();
+
+  if let Some(cache) = &cache {
+    if let Some(entry) = cache.fresh(&resolved_input, &content, output_path).await {
+      log::debug!(
+        "📄 Transforming: {}\n   💾 Unchanged since the last run",
+        resolved_input.display()
+      );
+      return Ok(FileOutcome {
+        input_path: resolved_input,
+        input_hash: entry.input.clone(),
+        cached: true,
+        output_path: output_path.to_path_buf(),
+        additional_files: Vec::new(),
+        component_tags,
+        event_renames: entry.event_renames.clone(),
+      });
+    }
+  }
 
   // Transform the content using rewrite_sfc
-  let transformed = rewrite_sfc(&content, config)
+  let report = rewrite_sfc_with_report(&content, config)
     .map_err(|e| anyhow::format_err!("{}", e))
     .with_context(|| format!("Failed to transform file: {}", resolved_input.display()))?;
 
@@ -231,29 +1196,324 @@ async fn transform_file(
   }
 
   // Write the transformed content
-  async_fs::write(output_path, transformed)
+  async_fs::write(output_path, &report.code)
     .await
     .with_context(|| format!("Failed to write output file: {}", output_path.display()))?;
 
+  // One message per file so the output of parallel transformations does not interleave. Files
+  // with diagnostics are always reported, the others only with `--verbose`.
+  let mut message = format!("📄 Transforming: {}\n", resolved_input.display());
   if resolved_input
     == output_path
       .canonicalize()
       .unwrap_or_else(|_| output_path.to_path_buf())
   {
-    println!("   ✅ Overwritten {} successfully", output_path.display());
+    message.push_str(&format!("   ✅ Overwritten {} successfully", output_path.display()));
   } else {
-    println!("   ✅ Written to: {}", output_path.display());
+    message.push_str(&format!("   ✅ Written to: {}", output_path.display()));
   }
+  for diagnostic in &report.diagnostics {
+    message.push('\n');
+    message.push_str(&format_diagnostic(diagnostic));
+  }
+  let level = if report
+    .diagnostics
+    .iter()
+    .any(|diagnostic| diagnostic.severity == DiagnosticSeverity::Warning)
+  {
+    log::Level::Warn
+  } else if report.diagnostics.is_empty() {
+    log::Level::Debug
+  } else {
+    log::Level::Info
+  };
+  log::log!(level, "{}", message);
 
-  Ok(true)
+  Ok(FileOutcome {
+    input_hash: cache::hash(content.as_bytes()),
+    input_path: resolved_input,
+    cached: false,
+    output_path: output_path.to_path_buf(),
+    additional_files: report.additional_files,
+    component_tags,
+    event_renames: report.event_renames,
+  })
+}
+
+fn print_diagnostic(diagnostic: &Diagnostic) {
+  let level = match diagnostic.severity {
+    DiagnosticSeverity::Warning => log::Level::Warn,
+    DiagnosticSeverity::Info => log::Level::Info,
+  };
+  log::log!(level, "{}", format_diagnostic(diagnostic));
+}
+
+fn format_diagnostic(diagnostic: &Diagnostic) -> String {
+  let icon = match diagnostic.severity {
+    DiagnosticSeverity::Warning => "⚠️ ",
+    DiagnosticSeverity::Info => "ℹ️ ",
+  };
+  let mut line = format!("   {} [{}] {}", icon, diagnostic.code, diagnostic.message);
+  if let Some(url) = &diagnostic.url {
+    line.push_str(&format!("\n      See {}", url));
+  }
+  line
+}
+
+/// Writes the sidecar files generated by transformers relative to `output_root`.
+///
+/// Existing files are never overwritten, and when several components generate the same
+/// path only the first one is written (identical content is silently deduplicated).
+/// Returns the number of files written.
+async fn write_additional_files(output_root: &Path, outcomes: &[FileOutcome]) -> Result<usize> {
+  let mut planned: Vec<(PathBuf, &str, &Path)> = Vec::new();
+
+  for outcome in outcomes {
+    for (relative_path, content) in &outcome.additional_files {
+      if relative_path.is_absolute()
+        || relative_path
+          .components()
+          .any(|c| matches!(c, std::path::Component::ParentDir))
+      {
+        log::warn!(
+          "   ⚠️  Skipping generated file outside of the output directory: {} (from {})",
+          relative_path.display(),
+          outcome.output_path.display()
+        );
+        continue;
+      }
+
+      let target = output_root.join(relative_path);
+      match planned.iter().find(|(path, _, _)| *path == target) {
+        Some((_, existing, _)) if *existing == content.as_str() => {}
+        Some((_, _, first_source)) => {
+          log::warn!(
+            "   ⚠️  Conflicting generated file {} from {} (keeping the one from {})",
+            target.display(),
+            outcome.output_path.display(),
+            first_source.display()
+          );
+        }
+        None => planned.push((target, content, &outcome.output_path)),
+      }
+    }
+  }
+
+  if planned.is_empty() {
+    return Ok(0);
+  }
+
+  log::info!("\n📎 Generated files:");
+  let mut written = 0;
+  for (target, content, source) in planned {
+    if target.exists() {
+      log::warn!(
+        "   ⚠️  Not overwriting existing file: {} (from {})",
+        target.display(),
+        source.display()
+      );
+      continue;
+    }
+
+    if let Some(parent) = target.parent() {
+      async_fs::create_dir_all(parent)
+        .await
+        .with_context(|| format!("Failed to create output directory: {}", parent.display()))?;
+    }
+
+    async_fs::write(&target, content)
+      .await
+      .with_context(|| format!("Failed to write generated file: {}", target.display()))?;
+
+    written += 1;
+    log::info!("   ✅ {} (from {})", target.display(), source.display());
+  }
+
+  Ok(written)
+}
+
+/// Second pass for project mode: rewrites listeners in every transformed file for
+/// components whose emitted events were renamed. Returns the number of files changed.
+async fn propagate_event_renames(outcomes: &[FileOutcome]) -> Result<usize> {
+  let renamed: Vec<&FileOutcome> = outcomes
+    .iter()
+    .filter(|outcome| !outcome.event_renames.is_empty() && !outcome.component_tags.is_empty())
+    .collect();
+
+  if renamed.is_empty() {
+    return Ok(0);
+  }
+
+  log::info!("\n🔗 Updating listeners for renamed events:");
+  for outcome in &renamed {
+    for (from, to) in &outcome.event_renames {
+      log::info!("   {}: {} → {}", outcome.component_tags[0], from, to);
+    }
+  }
+
+  let mut changed_files = 0;
+  for outcome in outcomes {
+    let content = async_fs::read_to_string(&outcome.output_path)
+      .await
+      .with_context(|| format!("Failed to read file: {}", outcome.output_path.display()))?;
+
+    let mut updated = content.clone();
+    let mut applied = Vec::new();
+    for component in &renamed {
+      if component.output_path == outcome.output_path {
+        continue;
+      }
+      let (rewritten, renames) = project::rewrite_component_listeners(
+        &updated,
+        &component.component_tags,
+        &component.event_renames,
+      );
+      updated = rewritten;
+      applied.extend(renames);
+    }
+
+    if applied.is_empty() || updated == content {
+      continue;
+    }
+
+    async_fs::write(&outcome.output_path, updated)
+      .await
+      .with_context(|| {
+        format!(
+          "Failed to write output file: {}",
+          outcome.output_path.display()
+        )
+      })?;
+
+    changed_files += 1;
+    log::info!("   📄 {}", outcome.output_path.display());
+    for rename in applied {
+      log::info!(
+        "      <{}> @{} → @{}",
+        rename.component, rename.from, rename.to
+      );
+    }
+  }
+
+  Ok(changed_files)
+}
+
+/// Record the files of a run in the cache, with the hash of their output once every pass (listener
+/// updates in project mode) is done
+async fn save_cache(cache: &cache::Cache, outcomes: &[FileOutcome]) -> Result<()> {
+  let mut files = BTreeMap::new();
+  for outcome in outcomes {
+    let output = async_fs::read(&outcome.output_path)
+      .await
+      .with_context(|| format!("Failed to read file: {}", outcome.output_path.display()))?;
+    files.insert(
+      outcome.input_path.clone(),
+      cache::CacheEntry {
+        input: outcome.input_hash.clone(),
+        output: cache::hash(&output),
+        event_renames: outcome.event_renames.clone(),
+      },
+    );
+  }
+
+  cache.save(files).await
+}
+
+/// Options of the files of a run: the configured options, plus the file name and the members each
+/// component exposes for its parents
+struct FileOptions {
+  config: Option<RewriteOptions>,
+  /// Members used through template refs, by canonical path of the component
+  ref_members: BTreeMap<PathBuf, Vec<String>>,
+}
+
+impl FileOptions {
+  fn for_file(&self, path: &Path) -> Option<RewriteOptions> {
+    let mut options = self.config.clone().unwrap_or_default();
+    options.file_name = path
+      .file_name()
+      .map(|file_name| file_name.to_string_lossy().to_string());
+    let canonical_path = path.canonicalize().ok();
+
+    // Import paths resolved against the configuration file are made relative to the component
+    if let Some(file_dir) = canonical_path.as_deref().and_then(Path::parent) {
+      config::relativize_import_paths(&mut options, file_dir);
+    }
+
+    let members = canonical_path
+      .as_ref()
+      .and_then(|path| self.ref_members.get(path));
+    if let Some(members) = members {
+      options
+        .expose
+        .get_or_insert_with(Default::default)
+        .members
+        .extend(members.iter().cloned());
+    }
+    Some(options)
+  }
+}
+
+/// Find the members the components below `source_dir` use on imported components through
+/// template refs (`this.$refs.modal.open()`), by canonical path of the imported component
+async fn collect_ref_members(source_dir: &Path) -> Result<BTreeMap<PathBuf, Vec<String>>> {
+  let (files, _) = find_vue_files(source_dir, true, false).await?;
+
+  let mut ref_members: BTreeMap<PathBuf, Vec<String>> = BTreeMap::new();
+  for file in files {
+    let Ok(content) = async_fs::read_to_string(&file).await else {
+      continue;
+    };
+    for ref_use in project::ref_member_uses(&content) {
+      let Some(component) = resolve_component_source(&file, &ref_use.source, source_dir) else {
+        continue;
+      };
+      let members = ref_members.entry(component).or_default();
+      for member in ref_use.members {
+        if !members.contains(&member) {
+          members.push(member);
+        }
+      }
+    }
+  }
+
+  log::info!(
+    "🔗 Found members used through template refs in {} component(s)",
+    ref_members.len()
+  );
+  Ok(ref_members)
+}
+
+/// Canonical path of an imported component: relative to the importing file, or to the source
+/// directory for the `@/` and `~/` aliases. `.vue` and `/index.vue` are tried when the file
+/// does not exist as written.
+fn resolve_component_source(importer: &Path, source: &str, source_dir: &Path) -> Option<PathBuf> {
+  let base = match source.strip_prefix("@/").or_else(|| source.strip_prefix("~/")) {
+    Some(path) => source_dir.join(path),
+    None if source.starts_with('.') => importer.parent()?.join(source),
+    None => return None,
+  };
+
+  [
+    base.clone(),
+    PathBuf::from(format!("{}.vue", base.display())),
+    base.join("index.vue"),
+  ]
+  .into_iter()
+  .find(|candidate| candidate.is_file())
+  .and_then(|candidate| candidate.canonicalize().ok())
 }
 
 async fn process_path(
   input_path: &str,
   output_path: &str,
-  config: Option<RewriteOptions>,
+  options: FileOptions,
   recursive: bool,
+  follow_symlinks: bool,
+  project_mode: bool,
+  cache: Option<cache::Cache>,
 ) -> Result<usize> {
+  let cache = cache.map(Arc::new);
   let input_path = Path::new(input_path);
   let output_path = Path::new(output_path);
 
@@ -264,31 +1524,44 @@ async fn process_path(
   if input_metadata.is_file() {
     // Single file processing
     if input_path.extension().is_none_or(|ext| ext != "vue") {
-      println!("Warning: Input file does not have a .vue extension");
+      log::warn!("Input file does not have a .vue extension");
     }
 
-    let success = transform_file(input_path, output_path, config)
-      .await
-      .map_err(|e| {
-        eprintln!("   ❌ Error: {}", e);
-        e
-      })
-      .unwrap_or(false);
+    let config = options.for_file(input_path);
+    let outcome = match transform_file(input_path, output_path, config, cache.clone()).await {
+      Ok(outcome) => outcome,
+      Err(e) => {
+        match e.downcast_ref::<SkippedFile>() {
+          Some(skipped) => print_skipped(std::slice::from_ref(skipped)),
+          None => log::error!("❌ Error: {}", e),
+        }
+        return Ok(0);
+      }
+    };
 
-    Ok(if success { 1 } else { 0 })
+    let output_root = output_path.parent().unwrap_or_else(|| Path::new("."));
+    let outcomes = [outcome];
+    write_additional_files(output_root, &outcomes).await?;
+    if let Some(cache) = &cache {
+      save_cache(cache, &outcomes).await?;
+    }
+
+    Ok(1)
   } else if input_metadata.is_dir() {
     // Directory processing
-    println!("🔍 Searching for .vue files in: {}", input_path.display());
+    log::info!("🔍 Searching for .vue files in: {}", input_path.display());
 
-    let vue_files = find_vue_files(input_path, recursive).await?;
+    let (vue_files, mut skipped) = find_vue_files(input_path, recursive, follow_symlinks).await?;
 
     if vue_files.is_empty() {
-      println!("No .vue files found in the specified directory.");
+      print_skipped(&skipped);
+      log::info!("No .vue files found in the specified directory.");
       return Ok(0);
     }
 
-    println!("Found {} .vue file(s)", vue_files.len());
+    log::info!("Found {} .vue file(s)", vue_files.len());
     let total_files = vue_files.len();
+    logging::start_progress(total_files);
 
     // Create tasks for parallel processing
     let mut tasks = Vec::new();
@@ -299,45 +1572,77 @@ async fn process_path(
         // Overwrite in place
         vue_file.clone()
       } else {
-        // Map to output directory structure
-        let relative_path = vue_file.strip_prefix(input_path).with_context(|| {
-          format!(
-            "Failed to calculate relative path for: {}",
-            vue_file.display()
-          )
-        })?;
+        // Map to output directory structure (symlinked directories may resolve elsewhere)
+        let Ok(relative_path) = vue_file.strip_prefix(input_path) else {
+          skipped.push(SkippedFile {
+            path: vue_file,
+            reason: "outside of the input directory".to_string(),
+          });
+          continue;
+        };
         output_path.join(relative_path)
       };
 
-      // Spawn a task for each file transformation
-      let config_cloned = config.clone();
+      // Spawn a task for each file transformation, a failing file never stops the others
+      let config_cloned = options.for_file(&vue_file);
+      let cache_cloned = cache.clone();
+      let path = vue_file.display().to_string();
       let task = tokio::spawn(async move {
-        transform_file(&vue_file, &output_file, config_cloned)
-          .await
-          .map_err(|e| {
-            eprintln!("   ❌ Error: {}", e);
-            e
-          })
-          .unwrap_or(false)
+        let outcome = transform_file(&vue_file, &output_file, config_cloned, cache_cloned).await;
+        logging::advance_progress();
+        outcome
       });
 
-      tasks.push(task);
+      tasks.push((path, task));
     }
 
     // Wait for all tasks to complete and count successes
-    let mut success_count = 0;
-    for task in tasks {
-      if let Ok(success) = task.await {
-        if success {
-          success_count += 1;
-        }
+    let mut outcomes = Vec::new();
+    for (path, task) in tasks {
+      match task.await {
+        Ok(Ok(outcome)) => outcomes.push(outcome),
+        Ok(Err(e)) => match e.downcast::<SkippedFile>() {
+          Ok(file) => skipped.push(file),
+          Err(e) => log::error!("❌ Error: {}", e),
+        },
+        Err(e) => log::error!("❌ Error: {} ({})", path, e),
       }
     }
+    let success_count = outcomes.len();
+    logging::finish_progress();
 
-    println!(
-      "\n📊 Summary: {}/{} files transformed successfully",
-      success_count, total_files
-    );
+    if project_mode {
+      let changed_files = propagate_event_renames(&outcomes).await?;
+      if changed_files > 0 {
+        log::info!("   Updated listeners in {} file(s)", changed_files);
+      }
+    }
+
+    let generated_files = write_additional_files(output_path, &outcomes).await?;
+    if generated_files > 0 {
+      log::info!("   Generated {} additional file(s)", generated_files);
+    }
+
+    if let Some(cache) = &cache {
+      save_cache(cache, &outcomes).await?;
+    }
+
+    let cached_files = outcomes.iter().filter(|outcome| outcome.cached).count();
+    if cached_files > 0 {
+      log::info!(
+        "\n📊 Summary: {}/{} files transformed successfully ({} unchanged since the last run)",
+        success_count,
+        total_files,
+        cached_files
+      );
+    } else {
+      log::info!(
+        "\n📊 Summary: {}/{} files transformed successfully",
+        success_count,
+        total_files
+      );
+    }
+    print_skipped(&skipped);
     Ok(success_count)
   } else {
     anyhow::bail!("Input path is neither a file nor a directory");
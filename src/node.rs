@@ -0,0 +1,117 @@
+//! Node.js bindings built with napi-rs, enabled by the `napi` feature.
+//!
+//! Build the addon with `cargo rustc --release --lib --crate-type cdylib --features napi` and load
+//! the library renamed to `vue-options-to-composition.node`. Options are the configuration file
+//! structure as an object (`{ mixins: { ... }, target: "nuxt3" }`).
+
+use crate::config::ConfigFile;
+use crate::{rewrite_sfc_with_report, DiagnosticSeverity, RewriteOptions};
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use std::path::Path;
+use walkdir::WalkDir;
+
+/// A finding reported during conversion
+#[napi(object)]
+pub struct JsDiagnostic {
+  /// `warning` or `info`
+  pub severity: String,
+  pub code: String,
+  pub message: String,
+  pub url: Option<String>,
+}
+
+/// Outcome of a file converted by `rewriteDir`
+#[napi(object)]
+pub struct RewrittenFile {
+  pub path: String,
+  pub diagnostics: Vec<JsDiagnostic>,
+  /// Why the file was left unchanged, when it could not be converted
+  pub error: Option<String>,
+}
+
+fn rewrite_options(options: Option<serde_json::Value>) -> Result<Option<RewriteOptions>> {
+  options
+    .map(|options| {
+      serde_json::from_value::<ConfigFile>(options)
+        .map(Into::into)
+        .map_err(|e| Error::new(Status::InvalidArg, format!("Invalid options: {}", e)))
+    })
+    .transpose()
+}
+
+/// Convert the content of an SFC, like the command line does for a file
+#[napi(js_name = "rewriteSfc")]
+pub fn rewrite_sfc(content: String, options: Option<serde_json::Value>) -> Result<String> {
+  let options = rewrite_options(options)?;
+  crate::rewrite_sfc(&content, options).map_err(|e| Error::from_reason(e.to_string()))
+}
+
+/// Convert the `.vue` files of a directory (recursively, without `node_modules`, `.git`, `dist`
+/// and `build`) in place. A failing file never stops the others, its error is returned instead.
+#[napi(js_name = "rewriteDir")]
+pub fn rewrite_dir(path: String, options: Option<serde_json::Value>) -> Result<Vec<RewrittenFile>> {
+  let options = rewrite_options(options)?;
+  if !Path::new(&path).is_dir() {
+    return Err(Error::new(
+      Status::InvalidArg,
+      format!("Not a directory: {}", path),
+    ));
+  }
+
+  let mut files = Vec::new();
+  for entry in WalkDir::new(&path)
+    .sort_by_file_name()
+    .into_iter()
+    .filter_entry(|e| {
+      let name = e.file_name();
+      name != "node_modules" && name != ".git" && name != "dist" && name != "build"
+    })
+    .filter_map(|entry| entry.ok())
+  {
+    let file = entry.path();
+    if !file.is_file() || file.extension().is_none_or(|ext| ext != "vue") {
+      continue;
+    }
+
+    let mut rewritten = RewrittenFile {
+      path: file.display().to_string(),
+      diagnostics: Vec::new(),
+      error: None,
+    };
+    let result = std::fs::read_to_string(file)
+      .map_err(|e| e.to_string())
+      .and_then(|content| {
+        let options = RewriteOptions {
+          file_name: entry.file_name().to_str().map(str::to_string),
+          ..options.clone().unwrap_or_default()
+        };
+        rewrite_sfc_with_report(&content, Some(options)).map_err(|e| e.to_string())
+      })
+      .and_then(|report| {
+        std::fs::write(file, &report.code).map_err(|e| e.to_string())?;
+        Ok(report.diagnostics)
+      });
+    match result {
+      Ok(diagnostics) => {
+        rewritten.diagnostics = diagnostics
+          .into_iter()
+          .map(|diagnostic| JsDiagnostic {
+            severity: match diagnostic.severity {
+              DiagnosticSeverity::Warning => "warning",
+              DiagnosticSeverity::Info => "info",
+            }
+            .to_string(),
+            code: diagnostic.code,
+            message: diagnostic.message,
+            url: diagnostic.url,
+          })
+          .collect();
+      }
+      Err(error) => rewritten.error = Some(error),
+    }
+    files.push(rewritten);
+  }
+
+  Ok(files)
+}
@@ -0,0 +1,309 @@
+//! Project-wide passes that run before or after every file has been rewritten individually.
+//!
+//! Converting a single component can change its public contract (e.g. `$emit('input')`
+//! becoming `emit('update:modelValue')`). The helpers in this module propagate such
+//! changes to the parent components that listen to those events, and find the members
+//! parents use through template refs, which `<script setup>` children have to expose.
+
+use crate::transformers::import_rewrite::to_kebab_case;
+use crate::{find_closing_tag, parse_script_section, parse_sfc_sections, ScriptParsingState};
+use lazy_static::lazy_static;
+use regex::Regex;
+
+lazy_static! {
+  // `$refs.modal.open`, `$refs['modal']?.open`, instance members (`$el`) are left out
+  static ref REF_MEMBER_PATTERN: Regex = Regex::new(
+    r#"\$refs\s*(?:\.\s*([\w$]+)|\[\s*['"]([^'"]+)['"]\s*\])\s*\??\.\s*([A-Za-z_][\w$]*)"#
+  )
+  .unwrap();
+  static ref REF_ATTRIBUTE_PATTERN: Regex = Regex::new(r#"\sref\s*=\s*["']([^"']+)["']"#).unwrap();
+}
+
+/// Members a parent component uses on a child component through a template ref
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RefMemberUse {
+  /// Import source of the child component, as written in the parent (e.g. `./DsModal.vue`)
+  pub source: String,
+  /// Members accessed through the ref, in order of appearance (e.g. `open` for
+  /// `this.$refs.modal.open()`)
+  pub members: Vec<String>,
+}
+
+/// A single listener rename performed in a consumer file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ListenerRename {
+  /// Tag name of the component the listener is attached to
+  pub component: String,
+  /// Original event name
+  pub from: String,
+  /// New event name
+  pub to: String,
+}
+
+/// Returns the tag names a component can be used with in templates, derived from its file stem.
+///
+/// `UserCard` yields `["UserCard", "user-card"]`.
+pub fn component_tag_names(file_stem: &str) -> Vec<String> {
+  let pascal = file_stem
+    .split(['-', '_'])
+    .filter(|part| !part.is_empty())
+    .map(|part| {
+      let mut chars = part.chars();
+      match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect::<String>(),
+        None => String::new(),
+      }
+    })
+    .collect::<String>();
+
+  let kebab = to_kebab_case(&pascal);
+  if kebab == pascal {
+    vec![pascal]
+  } else {
+    vec![pascal, kebab]
+  }
+}
+
+/// Rewrites `@event` / `v-on:event` listeners on the given component tags inside the
+/// `<template>` block of an SFC.
+///
+/// Only the template block is touched; script and style blocks are returned unchanged.
+/// Returns the new SFC source together with the renames that were applied.
+pub fn rewrite_component_listeners(
+  sfc: &str,
+  component_tags: &[String],
+  renames: &[(String, String)],
+) -> (String, Vec<ListenerRename>) {
+  let mut applied = Vec::new();
+
+  if component_tags.is_empty() || renames.is_empty() {
+    return (sfc.to_string(), applied);
+  }
+
+  let Some((start, end)) = template_bounds(sfc) else {
+    return (sfc.to_string(), applied);
+  };
+
+  let template = &sfc[start..end];
+  let mut output = String::with_capacity(template.len());
+  let mut pos = 0;
+
+  while let Some(offset) = template[pos..].find('<') {
+    let tag_start = pos + offset;
+    output.push_str(&template[pos..tag_start]);
+
+    let Some(tag_end) = find_tag_end(template, tag_start) else {
+      pos = tag_start;
+      break;
+    };
+
+    let tag = &template[tag_start..tag_end];
+    let tag_name: String = tag[1..]
+      .chars()
+      .take_while(|c| c.is_alphanumeric() || *c == '-' || *c == '_' || *c == '.')
+      .collect();
+
+    if component_tags.iter().any(|t| t == &tag_name) {
+      output.push_str(&rewrite_tag_listeners(
+        tag,
+        &tag_name,
+        renames,
+        &mut applied,
+      ));
+    } else {
+      output.push_str(tag);
+    }
+
+    pos = tag_end;
+  }
+  output.push_str(&template[pos..]);
+
+  let mut result = String::with_capacity(sfc.len());
+  result.push_str(&sfc[..start]);
+  result.push_str(&output);
+  result.push_str(&sfc[end..]);
+
+  (result, applied)
+}
+
+/// Returns the byte range of the template block contents
+fn template_bounds(sfc: &str) -> Option<(usize, usize)> {
+  let start = sfc.find("<template")?;
+  let content_start = start + sfc[start..].find('>')? + 1;
+  let end = find_closing_tag(sfc, content_start, "template")?;
+  Some((content_start, end))
+}
+
+/// Finds the end (exclusive) of the tag starting at `start`, skipping over quoted attribute values
+fn find_tag_end(template: &str, start: usize) -> Option<usize> {
+  let mut quote: Option<char> = None;
+
+  for (i, c) in template[start + 1..].char_indices() {
+    match quote {
+      Some(q) if c == q => quote = None,
+      Some(_) => {}
+      None if c == '"' || c == '\'' => quote = Some(c),
+      None if c == '>' => return Some(start + 1 + i + 1),
+      None => {}
+    }
+  }
+
+  None
+}
+
+/// Renames listener attributes within a single opening tag
+fn rewrite_tag_listeners(
+  tag: &str,
+  tag_name: &str,
+  renames: &[(String, String)],
+  applied: &mut Vec<ListenerRename>,
+) -> String {
+  let mut output = String::with_capacity(tag.len());
+  let mut quote: Option<char> = None;
+  let mut prev: Option<char> = None;
+  let mut i = 0;
+
+  while i < tag.len() {
+    let c = tag[i..].chars().next().unwrap();
+
+    if let Some(q) = quote {
+      if c == q {
+        quote = None;
+      }
+    } else if c == '"' || c == '\'' {
+      quote = Some(c);
+    } else if prev.is_some_and(char::is_whitespace) {
+      let prefix = if tag[i..].starts_with('@') {
+        Some("@")
+      } else if tag[i..].starts_with("v-on:") {
+        Some("v-on:")
+      } else {
+        None
+      };
+
+      if let Some(prefix) = prefix {
+        let name_start = i + prefix.len();
+        let name_len = tag[name_start..]
+          .find(|ch: char| ch == '.' || ch == '=' || ch == '>' || ch == '/' || ch.is_whitespace())
+          .unwrap_or(tag.len() - name_start);
+        let event = &tag[name_start..name_start + name_len];
+
+        if let Some((from, to)) = renames.iter().find(|(from, _)| from == event) {
+          output.push_str(prefix);
+          output.push_str(to);
+          applied.push(ListenerRename {
+            component: tag_name.to_string(),
+            from: from.clone(),
+            to: to.clone(),
+          });
+          prev = to.chars().last();
+          i = name_start + name_len;
+          continue;
+        }
+      }
+    }
+
+    output.push(c);
+    prev = Some(c);
+    i += c.len_utf8();
+  }
+
+  output
+}
+
+/// Finds the members a component accesses on imported child components through template refs.
+///
+/// `this.$refs.modal.open()` with `<DsModal ref="modal" />` in the template and `DsModal`
+/// imported from `./DsModal.vue` yields `open` for `./DsModal.vue`. Refs on elements, dynamic
+/// refs and components that are not imported are ignored.
+pub fn ref_member_uses(sfc: &str) -> Vec<RefMemberUse> {
+  let Ok(sections) = parse_sfc_sections(sfc) else {
+    return Vec::new();
+  };
+  let script = sections.script_content.as_deref().unwrap_or("");
+  let template = sections.template_content.as_deref().unwrap_or("");
+
+  // Members by ref name, from the script and the template
+  let mut members_by_ref: Vec<(String, Vec<String>)> = Vec::new();
+  for caps in REF_MEMBER_PATTERN
+    .captures_iter(script)
+    .chain(REF_MEMBER_PATTERN.captures_iter(template))
+  {
+    let Some(ref_name) = caps.get(1).or(caps.get(2)) else {
+      continue;
+    };
+    let member = caps[3].to_string();
+    match members_by_ref
+      .iter_mut()
+      .find(|(name, _)| name == ref_name.as_str())
+    {
+      Some((_, members)) if members.contains(&member) => {}
+      Some((_, members)) => members.push(member),
+      None => members_by_ref.push((ref_name.as_str().to_string(), vec![member])),
+    }
+  }
+  if members_by_ref.is_empty() {
+    return Vec::new();
+  }
+
+  let mut script_state = ScriptParsingState::new();
+  if parse_script_section(script, &mut script_state).is_err() {
+    return Vec::new();
+  }
+
+  let mut uses: Vec<RefMemberUse> = Vec::new();
+  let mut pos = 0;
+  while let Some(offset) = template[pos..].find('<') {
+    let tag_start = pos + offset;
+    let Some(tag_end) = find_tag_end(template, tag_start) else {
+      break;
+    };
+    pos = tag_end;
+
+    let tag = &template[tag_start..tag_end];
+    let Some(ref_name) = REF_ATTRIBUTE_PATTERN
+      .captures(tag)
+      .map(|caps| caps[1].to_string())
+    else {
+      continue;
+    };
+    let Some((_, members)) = members_by_ref.iter().find(|(name, _)| *name == ref_name) else {
+      continue;
+    };
+    let tag_name: String = tag[1..]
+      .chars()
+      .take_while(|c| c.is_alphanumeric() || *c == '-' || *c == '_')
+      .collect();
+
+    // `<DsModal>` and `<ds-modal>` both resolve to the `DsModal` import
+    let source = script_state.imports.iter().find_map(|import_info| {
+      import_info
+        .imports
+        .iter()
+        .any(|item| {
+          let local_name = item.alias.as_deref().unwrap_or(&item.name);
+          local_name == tag_name || to_kebab_case(local_name) == tag_name
+        })
+        .then(|| import_info.source.clone())
+    });
+    let Some(source) = source else {
+      continue;
+    };
+
+    match uses.iter_mut().find(|existing| existing.source == source) {
+      Some(existing) => {
+        for member in members {
+          if !existing.members.contains(member) {
+            existing.members.push(member.clone());
+          }
+        }
+      }
+      None => uses.push(RefMemberUse {
+        source,
+        members: members.clone(),
+      }),
+    }
+  }
+
+  uses
+}
@@ -0,0 +1,362 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::hash_map::RandomState;
+use std::hash::BuildHasher;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::Mutex;
+use vue_options_to_composition::{rewrite_sfc_with_report, RewriteOptions};
+
+/// Review page, served with the session token in place of [`TOKEN_PLACEHOLDER`]
+const REVIEW_PAGE: &str = include_str!("review.html");
+
+/// Placeholder for the session token in the review page
+const TOKEN_PLACEHOLDER: &str = "__REVIEW_TOKEN__";
+
+/// Header the review page sends the session token in
+const TOKEN_HEADER: &str = "x-review-token";
+
+/// Largest request body accepted (the page only posts lists of file ids)
+const MAX_BODY_SIZE: usize = 1024 * 1024;
+
+/// A converted file awaiting review
+#[derive(Debug, Clone, Serialize)]
+struct ReviewFile {
+  id: usize,
+  path: String,
+  original: String,
+  converted: String,
+  fixmes: Vec<String>,
+  diagnostics: Vec<String>,
+  error: Option<String>,
+  written: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct AcceptRequest {
+  ids: Vec<usize>,
+}
+
+#[derive(Debug, Serialize)]
+struct AcceptResponse {
+  written: Vec<String>,
+}
+
+struct ReviewState {
+  files: Vec<ReviewFile>,
+  paths: Vec<PathBuf>,
+  /// Random token embedded in the served page, required by the API
+  token: String,
+  /// Host headers of the bound address (`127.0.0.1:<port>`, `localhost:<port>`)
+  hosts: Vec<String>,
+}
+
+/// A parsed HTTP request
+struct Request {
+  method: String,
+  path: String,
+  /// Header names are lowercase
+  headers: Vec<(String, String)>,
+  body: Vec<u8>,
+}
+
+impl Request {
+  fn header(&self, name: &str) -> Option<&str> {
+    self
+      .headers
+      .iter()
+      .find(|(header, _)| header == name)
+      .map(|(_, value)| value.as_str())
+  }
+}
+
+/// Convert `files` in memory and serve a local review UI on `127.0.0.1:<port>`.
+///
+/// Nothing is written until files are accepted in the UI, which overwrites them with their
+/// converted version. Requests must come from the bound address, and the API only answers the
+/// page it served, which sends a random per-session token: other pages open in the browser (and
+/// DNS rebinding) can neither read the conversions nor write files.
+pub async fn serve(
+  root: &Path,
+  files: Vec<PathBuf>,
+  config: Option<RewriteOptions>,
+  port: u16,
+) -> Result<()> {
+  let mut review_files = Vec::new();
+
+  for (id, path) in files.iter().enumerate() {
+    let original = tokio::fs::read_to_string(path)
+      .await
+      .with_context(|| format!("Failed to read file: {}", path.display()))?;
+
+    let display_path = path
+      .strip_prefix(root)
+      .unwrap_or(path)
+      .display()
+      .to_string();
+    let mut file = ReviewFile {
+      id,
+      path: display_path,
+      converted: String::new(),
+      fixmes: Vec::new(),
+      diagnostics: Vec::new(),
+      error: None,
+      written: false,
+      original,
+    };
+
+    match rewrite_sfc_with_report(&file.original, config.clone()) {
+      Ok(report) => {
+        file.fixmes = report
+          .code
+          .lines()
+          .filter(|line| line.contains("FIXME"))
+          .map(|line| line.trim().to_string())
+          .collect();
+        file.diagnostics = report
+          .diagnostics
+          .iter()
+          .map(|diagnostic| match &diagnostic.url {
+            Some(url) => format!("[{}] {} ({})", diagnostic.code, diagnostic.message, url),
+            None => format!("[{}] {}", diagnostic.code, diagnostic.message),
+          })
+          .collect();
+        file.converted = report.code;
+      }
+      Err(e) => file.error = Some(e.to_string()),
+    }
+
+    review_files.push(file);
+  }
+
+  let state = Arc::new(Mutex::new(ReviewState {
+    files: review_files,
+    paths: files,
+    token: session_token(),
+    hosts: vec![format!("127.0.0.1:{}", port), format!("localhost:{}", port)],
+  }));
+
+  let listener = TcpListener::bind(("127.0.0.1", port))
+    .await
+    .with_context(|| format!("Failed to listen on port {}", port))?;
+
+  log::info!(
+    "🔎 Reviewing {} file(s) at http://127.0.0.1:{}/ (Ctrl+C to stop)",
+    state.lock().await.files.len(),
+    port
+  );
+
+  loop {
+    let (stream, _) = listener.accept().await?;
+    let state = Arc::clone(&state);
+
+    tokio::spawn(async move {
+      if let Err(e) = handle_connection(stream, state).await {
+        log::error!("   ❌ Review server error: {}", e);
+      }
+    });
+  }
+}
+
+async fn handle_connection(mut stream: TcpStream, state: Arc<Mutex<ReviewState>>) -> Result<()> {
+  let Some(request) = read_request(&mut stream).await? else {
+    return write_response(&mut stream, "400 Bad Request", "text/plain", "Bad request").await;
+  };
+
+  let (token, hosts) = {
+    let state = state.lock().await;
+    (state.token.clone(), state.hosts.clone())
+  };
+  if !is_same_origin(&request, &hosts) {
+    return write_response(&mut stream, "403 Forbidden", "text/plain", "Forbidden").await;
+  }
+  let is_api = request.path.starts_with("/api/");
+  if is_api && request.header(TOKEN_HEADER) != Some(token.as_str()) {
+    return write_response(&mut stream, "403 Forbidden", "text/plain", "Invalid token").await;
+  }
+
+  match (request.method.as_str(), request.path.as_str()) {
+    ("GET", "/") => {
+      write_response(
+        &mut stream,
+        "200 OK",
+        "text/html; charset=utf-8",
+        &REVIEW_PAGE.replace(TOKEN_PLACEHOLDER, &token),
+      )
+      .await
+    }
+    ("GET", "/api/files") => {
+      let json = serde_json::to_string(&state.lock().await.files)?;
+      write_response(&mut stream, "200 OK", "application/json", &json).await
+    }
+    ("POST", "/api/accept") => {
+      let is_json = request.header("content-type").is_some_and(|content_type| {
+        content_type
+          .split(';')
+          .next()
+          .is_some_and(|mime| mime.trim().eq_ignore_ascii_case("application/json"))
+      });
+      if !is_json {
+        return write_response(
+          &mut stream,
+          "415 Unsupported Media Type",
+          "text/plain",
+          "Expected application/json",
+        )
+        .await;
+      }
+
+      let Ok(accept) = serde_json::from_slice::<AcceptRequest>(&request.body) else {
+        return write_response(&mut stream, "400 Bad Request", "text/plain", "Invalid body").await;
+      };
+
+      let written = write_accepted(&mut *state.lock().await, &accept.ids).await?;
+      let json = serde_json::to_string(&AcceptResponse { written })?;
+      write_response(&mut stream, "200 OK", "application/json", &json).await
+    }
+    _ => write_response(&mut stream, "404 Not Found", "text/plain", "Not found").await,
+  }
+}
+
+/// Whether the request was sent to the bound address (not a rebound DNS name) and, when the
+/// browser sends an `Origin`, by a page served from it
+fn is_same_origin(request: &Request, hosts: &[String]) -> bool {
+  let Some(host) = request.header("host") else {
+    return false;
+  };
+  if !hosts
+    .iter()
+    .any(|allowed| allowed.eq_ignore_ascii_case(host))
+  {
+    return false;
+  }
+
+  match request.header("origin") {
+    Some(origin) => hosts
+      .iter()
+      .any(|allowed| origin.eq_ignore_ascii_case(&format!("http://{}", allowed))),
+    None => true,
+  }
+}
+
+/// Random token for a review session: the OS-seeded keys of [`RandomState`], hashed with the time
+fn session_token() -> String {
+  let mut hasher = Sha256::new();
+  for _ in 0..4 {
+    hasher.update(
+      RandomState::new()
+        .hash_one(std::process::id())
+        .to_le_bytes(),
+    );
+  }
+  hasher.update(format!("{:?}", std::time::SystemTime::now()).as_bytes());
+  hasher
+    .finalize()
+    .iter()
+    .map(|byte| format!("{:02x}", byte))
+    .collect()
+}
+
+/// Overwrite accepted files with their converted content, returning the written paths
+async fn write_accepted(state: &mut ReviewState, ids: &[usize]) -> Result<Vec<String>> {
+  let mut written = Vec::new();
+
+  for &id in ids {
+    let (Some(file), Some(path)) = (state.files.get_mut(id), state.paths.get(id)) else {
+      continue;
+    };
+    if file.error.is_some() || file.written {
+      continue;
+    }
+
+    tokio::fs::write(path, &file.converted)
+      .await
+      .with_context(|| format!("Failed to write file: {}", path.display()))?;
+    log::info!("   ✅ Accepted {}", file.path);
+
+    file.written = true;
+    written.push(file.path.clone());
+  }
+
+  Ok(written)
+}
+
+/// Read a request
+async fn read_request(stream: &mut TcpStream) -> Result<Option<Request>> {
+  let mut buffer = Vec::new();
+  let mut chunk = [0u8; 4096];
+
+  let header_end = loop {
+    let read = stream.read(&mut chunk).await?;
+    if read == 0 {
+      return Ok(None);
+    }
+    buffer.extend_from_slice(&chunk[..read]);
+
+    if let Some(position) = buffer.windows(4).position(|window| window == b"\r\n\r\n") {
+      break position + 4;
+    }
+    if buffer.len() > MAX_BODY_SIZE {
+      return Ok(None);
+    }
+  };
+
+  let head = String::from_utf8_lossy(&buffer[..header_end]).to_string();
+  let mut lines = head.lines();
+  let mut request_line = lines.next().unwrap_or("").split_whitespace();
+  let (Some(method), Some(path)) = (request_line.next(), request_line.next()) else {
+    return Ok(None);
+  };
+
+  let headers: Vec<(String, String)> = lines
+    .filter_map(|line| line.split_once(':'))
+    .map(|(name, value)| (name.trim().to_ascii_lowercase(), value.trim().to_string()))
+    .collect();
+
+  let content_length = headers
+    .iter()
+    .find(|(name, _)| name == "content-length")
+    .and_then(|(_, value)| value.parse::<usize>().ok())
+    .unwrap_or(0);
+  if content_length > MAX_BODY_SIZE {
+    return Ok(None);
+  }
+
+  let mut body = buffer[header_end..].to_vec();
+  while body.len() < content_length {
+    let read = stream.read(&mut chunk).await?;
+    if read == 0 {
+      break;
+    }
+    body.extend_from_slice(&chunk[..read]);
+  }
+  body.truncate(content_length);
+
+  Ok(Some(Request {
+    method: method.to_string(),
+    path: path.to_string(),
+    headers,
+    body,
+  }))
+}
+
+async fn write_response(
+  stream: &mut TcpStream,
+  status: &str,
+  content_type: &str,
+  body: &str,
+) -> Result<()> {
+  let response = format!(
+    "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+    status,
+    content_type,
+    body.len(),
+    body
+  );
+  stream.write_all(response.as_bytes()).await?;
+  stream.flush().await?;
+  Ok(())
+}
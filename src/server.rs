@@ -0,0 +1,105 @@
+//! JSON-RPC 2.0 protocol of the `serve` command, for editor extensions.
+//!
+//! Each message is a single line of JSON. A long-running process converts components without
+//! paying for process startup on every code action:
+//!
+//! ```text
+//! --> {"jsonrpc":"2.0","id":1,"method":"rewrite","params":{"sfc":"<template>...","options":{"target":"nuxt3"}}}
+//! <-- {"jsonrpc":"2.0","id":1,"result":{"code":"<template>...","diagnostics":[...]}}
+//! --> {"jsonrpc":"2.0","id":2,"method":"shutdown"}
+//! <-- {"jsonrpc":"2.0","id":2,"result":null}
+//! ```
+//!
+//! `options` is the configuration file structure as an object and replaces the options the server
+//! was started with for that request.
+
+use crate::config::ConfigFile;
+use crate::{rewrite_sfc_with_report, RewriteOptions};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+/// Invalid JSON
+pub const PARSE_ERROR: i64 = -32700;
+/// Valid JSON, but not a request object
+pub const INVALID_REQUEST: i64 = -32600;
+pub const METHOD_NOT_FOUND: i64 = -32601;
+pub const INVALID_PARAMS: i64 = -32602;
+/// The component could not be converted
+pub const CONVERSION_FAILED: i64 = -32000;
+
+#[derive(Deserialize)]
+struct Request {
+  id: Option<Value>,
+  method: String,
+  #[serde(default)]
+  params: Value,
+}
+
+#[derive(Deserialize)]
+struct RewriteParams {
+  sfc: String,
+  options: Option<ConfigFile>,
+}
+
+/// State of a `serve` session
+pub struct Server {
+  options: Option<RewriteOptions>,
+  shut_down: bool,
+}
+
+impl Server {
+  /// A server converting with `options` unless a request brings its own
+  pub fn new(options: Option<RewriteOptions>) -> Self {
+    Self {
+      options,
+      shut_down: false,
+    }
+  }
+
+  /// Whether a `shutdown` request was handled, the host should stop reading then
+  pub fn is_shut_down(&self) -> bool {
+    self.shut_down
+  }
+
+  /// Handle one message and return the response line, or `None` for notifications (requests
+  /// without an `id`)
+  pub fn handle(&mut self, message: &str) -> Option<String> {
+    let value: Value = match serde_json::from_str(message) {
+      Ok(value) => value,
+      Err(e) => return Some(error_response(Value::Null, PARSE_ERROR, e.to_string())),
+    };
+    let request: Request = match serde_json::from_value(value) {
+      Ok(request) => request,
+      Err(e) => return Some(error_response(Value::Null, INVALID_REQUEST, e.to_string())),
+    };
+
+    let result = match request.method.as_str() {
+      "rewrite" => self.rewrite(request.params),
+      "shutdown" => {
+        self.shut_down = true;
+        Ok(Value::Null)
+      }
+      method => Err((METHOD_NOT_FOUND, format!("Unknown method: {}", method))),
+    };
+
+    let id = request.id?;
+    Some(match result {
+      Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }).to_string(),
+      Err((code, message)) => error_response(id, code, message),
+    })
+  }
+
+  fn rewrite(&self, params: Value) -> Result<Value, (i64, String)> {
+    let params: RewriteParams =
+      serde_json::from_value(params).map_err(|e| (INVALID_PARAMS, e.to_string()))?;
+    let options = params.options.map(Into::into).or(self.options.clone());
+
+    let report = rewrite_sfc_with_report(&params.sfc, options)
+      .map_err(|e| (CONVERSION_FAILED, e.to_string()))?;
+    Ok(json!({ "code": report.code, "diagnostics": report.diagnostics }))
+  }
+}
+
+fn error_response(id: Value, code: i64, message: String) -> String {
+  json!({ "jsonrpc": "2.0", "id": id, "error": { "code": code, "message": message } }).to_string()
+}
@@ -0,0 +1,668 @@
+//! Conversion of Vuex store modules to Pinia stores.
+//!
+//! Components are rewritten to call `useCartStore()` from `@/stores/cart`; this module generates
+//! those stores from the Vuex modules, with the same naming scheme. The conversion handles:
+//! - `state` is kept, a state object is wrapped in a function
+//! - Getters stay getters, reads of the `getters` argument become `this.x`
+//! - Mutations and actions become actions: the state or context argument is dropped, `state.x`
+//!   becomes `this.x`, and `commit('x', payload)` / `dispatch('x', payload)` become
+//!   `this.x(payload)` (`useOtherStore().x(payload)` for namespaced calls to other modules)
+//!
+//! Root state and getters, nested modules and mutations sharing a name with an action are
+//! reported.
+
+use crate::transformers::body_transforms::argument_bounds;
+use crate::transformers::vuex::capitalize_first_letter;
+use crate::Diagnostic;
+use lazy_static::lazy_static;
+use regex::Regex;
+use tree_sitter::Node;
+
+lazy_static! {
+  static ref ROOT_OPTION_PATTERN: Regex = Regex::new(r"\{\s*root\s*:\s*true\s*\}").unwrap();
+}
+
+/// Sections of a Vuex module
+const SECTIONS: [&str; 4] = ["state", "getters", "mutations", "actions"];
+
+/// Result of converting a store module
+#[derive(Debug, Clone, Default)]
+pub struct StoreModuleConversion {
+  /// Name of the generated store composable, e.g. `useCartStore`
+  pub name: String,
+  /// Source of the Pinia store module
+  pub code: String,
+  /// Findings that need the author's attention
+  pub diagnostics: Vec<Diagnostic>,
+}
+
+/// Convert a Vuex module, exported as an object (`export default { state, getters, ... }`) or as
+/// Nuxt-style named exports (`export const state = () => ({ ... })`), to a Pinia store named
+/// after `namespace`. Returns `None` when the source defines none of the module sections.
+pub fn convert_store_module(namespace: &str, source: &str) -> Option<StoreModuleConversion> {
+  let tree = crate::javascript::parse(source)?;
+  let root = tree.root_node();
+
+  let mut diagnostics = Vec::new();
+  let mut sections: Vec<(String, Node)> = Vec::new();
+  let mut consumed: Vec<(usize, usize)> = Vec::new();
+
+  let mut cursor = root.walk();
+  let statements: Vec<Node> = root.named_children(&mut cursor).collect();
+  for statement in &statements {
+    if statement.kind() != "export_statement" {
+      continue;
+    }
+
+    if let Some(value) = statement.child_by_field_name("value") {
+      // export default { ... } or export default cart
+      let object = match value.kind() {
+        "object" => Some(value),
+        "identifier" => top_level_value(&statements, text(&value, source), source, &mut consumed)
+          .filter(|value| value.kind() == "object"),
+        _ => None,
+      };
+      let Some(object) = object else {
+        continue;
+      };
+      consumed.push((statement.start_byte(), statement.end_byte()));
+
+      for (key, member) in members(&object, source) {
+        if key == "modules" {
+          diagnostics.push(Diagnostic::warning(
+            "store-nested-modules",
+            "The module registers nested `modules`, convert them to stores of their own",
+          ));
+        }
+        if !SECTIONS.contains(&key.as_str()) {
+          continue;
+        }
+        let section = match member.kind() {
+          "shorthand_property_identifier" => {
+            top_level_value(&statements, &key, source, &mut consumed)
+          }
+          "pair" => member.child_by_field_name("value"),
+          _ => Some(member),
+        };
+        if let Some(section) = section {
+          sections.push((key, section));
+        }
+      }
+    } else if let Some(declaration) = statement.child_by_field_name("declaration") {
+      // export const state = () => ({ ... })
+      let mut cursor = declaration.walk();
+      for declarator in declaration.named_children(&mut cursor) {
+        let (Some(name), Some(value)) = (
+          declarator.child_by_field_name("name"),
+          declarator.child_by_field_name("value"),
+        ) else {
+          continue;
+        };
+        let name = text(&name, source);
+        if SECTIONS.contains(&name) {
+          sections.push((name.to_string(), value));
+        }
+        if SECTIONS.contains(&name) || name == "namespaced" {
+          consumed.push((statement.start_byte(), statement.end_byte()));
+        }
+      }
+    }
+  }
+
+  if sections.is_empty() {
+    return None;
+  }
+
+  let section = |name: &str| {
+    sections
+      .iter()
+      .find(|(key, _)| key == name)
+      .map(|(_, node)| *node)
+  };
+
+  let mut stores: Vec<String> = Vec::new();
+  let mut entries: Vec<String> = Vec::new();
+
+  if let Some(state) = section("state") {
+    let column = line_indentation(&state, source);
+    let value = text(&state, source);
+    let entry = match state.kind() {
+      "object" => format!("state: () => ({})", value),
+      "method_definition" => value.to_string(),
+      _ => format!("state: {}", value),
+    };
+    entries.push(indent(&dedent(&entry, column), 2));
+  }
+
+  if let Some(getters) = section("getters").filter(|getters| getters.kind() == "object") {
+    let mut converted = Vec::new();
+    for (name, member) in members(&getters, source) {
+      let Some(function) = function_parts(&member, source) else {
+        converted.push(text(&member, source).to_string());
+        continue;
+      };
+      let column = line_indentation(&member, source);
+      let param = |index: usize| function.params.get(index).map(|param| text(param, source));
+      check_root_access(&name, &function, source, &mut diagnostics);
+
+      let mut body = text(&function.body, source).to_string();
+      let mut uses_this = false;
+      if let Some(getters_param) = param(1) {
+        let replaced = replace_identifier(&body, getters_param, "this");
+        uses_this = replaced != body;
+        body = replaced;
+      }
+
+      let state_param = param(0).unwrap_or_default();
+      let entry = if function.body.kind() != "statement_block" && !uses_this {
+        format!("{}: ({}) => {}", name, state_param, body)
+      } else if function.body.kind() != "statement_block" {
+        format!(
+          "{}({}) {}",
+          name,
+          state_param,
+          expression_block(&format!("return {}", body), column)
+        )
+      } else {
+        format!("{}({}) {}", name, state_param, body)
+      };
+      converted.push(dedent(&entry, column));
+    }
+    entries.push(object_entry("getters", &converted));
+  }
+
+  // Mutations sharing a name with an action are renamed, the action usually commits them
+  let action_names: Vec<String> = section("actions")
+    .filter(|actions| actions.kind() == "object")
+    .map(|actions| {
+      members(&actions, source)
+        .into_iter()
+        .map(|(name, _)| name)
+        .collect()
+    })
+    .unwrap_or_default();
+  let mut mutation_renames: Vec<(String, String)> = Vec::new();
+
+  let mut actions = Vec::new();
+  if let Some(mutations) = section("mutations").filter(|mutations| mutations.kind() == "object") {
+    for (name, member) in members(&mutations, source) {
+      let Some(function) = function_parts(&member, source) else {
+        actions.push(text(&member, source).to_string());
+        continue;
+      };
+      let column = line_indentation(&member, source);
+
+      let mut action_name = name.clone();
+      if action_names.contains(&name) {
+        action_name = format!("{}Mutation", name);
+        diagnostics.push(Diagnostic::warning(
+          "store-name-collision",
+          format!(
+            "Mutation `{}` has the name of an action, it was renamed to `{}`",
+            name, action_name
+          ),
+        ));
+        mutation_renames.push((name.clone(), action_name.clone()));
+      }
+
+      let mut body = text(&unparenthesized(function.body), source).to_string();
+      if let Some(state_param) = function.params.first() {
+        body = replace_identifier(&body, text(state_param, source), "this");
+      }
+      if function.body.kind() != "statement_block" {
+        body = expression_block(&body, column);
+      }
+
+      let params: Vec<&str> = function.params[1.min(function.params.len())..]
+        .iter()
+        .map(|param| text(param, source))
+        .collect();
+      actions.push(dedent(
+        &format!("{}({}) {}", action_name, params.join(", "), body),
+        column,
+      ));
+    }
+  }
+
+  if let Some(action_section) = section("actions").filter(|actions| actions.kind() == "object") {
+    for (name, member) in members(&action_section, source) {
+      let Some(function) = function_parts(&member, source) else {
+        actions.push(text(&member, source).to_string());
+        continue;
+      };
+      let column = line_indentation(&member, source);
+      check_root_access(&name, &function, source, &mut diagnostics);
+
+      let mut body = text(&unparenthesized(function.body), source).to_string();
+      if let Some(context) = function.params.first() {
+        if context.kind() == "object_pattern" {
+          let destructured = pattern_names(context, source);
+          for helper in ["commit", "dispatch"] {
+            if destructured.iter().any(|name| name == helper) {
+              body = rewrite_store_calls(
+                &body,
+                helper,
+                namespace,
+                &mutation_renames,
+                &mut stores,
+                &mut diagnostics,
+              );
+            }
+          }
+          for accessor in ["state", "getters"] {
+            if destructured.iter().any(|name| name == accessor) {
+              body = replace_identifier(&body, accessor, "this");
+            }
+          }
+        } else {
+          let context = text(context, source);
+          for helper in ["commit", "dispatch"] {
+            body = rewrite_store_calls(
+              &body,
+              &format!("{}.{}", context, helper),
+              namespace,
+              &mutation_renames,
+              &mut stores,
+              &mut diagnostics,
+            );
+          }
+          for accessor in ["state", "getters"] {
+            body = replace_identifier(&body, &format!("{}.{}", context, accessor), "this");
+          }
+          if Regex::new(&format!(r"(^|[^\w$.]){}\b", regex::escape(context)))
+            .is_ok_and(|pattern| pattern.is_match(&body))
+          {
+            diagnostics.push(Diagnostic::warning(
+              "store-context",
+              format!(
+                "Action `{}` still uses the `{}` context, the store is `this` in Pinia actions",
+                name, context
+              ),
+            ));
+          }
+        }
+      }
+      if function.body.kind() != "statement_block" {
+        body = expression_block(&format!("return {}", body), column);
+      }
+
+      let params: Vec<&str> = function.params[1.min(function.params.len())..]
+        .iter()
+        .map(|param| text(param, source))
+        .collect();
+      let prefix = if function.is_async { "async " } else { "" };
+      actions.push(dedent(
+        &format!("{}{}({}) {}", prefix, name, params.join(", "), body),
+        column,
+      ));
+    }
+  }
+
+  if !actions.is_empty() {
+    entries.push(object_entry("actions", &actions));
+  }
+
+  // Imports first, then the other statements the module declares (helpers, constants)
+  let mut imports = vec!["import { defineStore } from 'pinia';".to_string()];
+  let mut preamble = Vec::new();
+  for statement in &statements {
+    let range = (statement.start_byte(), statement.end_byte());
+    if consumed.contains(&range) {
+      continue;
+    }
+    let statement_text = text(statement, source);
+    if statement.kind() == "import_statement" {
+      let from_vuex = statement
+        .child_by_field_name("source")
+        .is_some_and(|import_source| {
+          text(&import_source, source).trim_matches(['\'', '"']) == "vuex"
+        });
+      if !from_vuex {
+        imports.push(statement_text.to_string());
+      }
+    } else {
+      preamble.push(dedent(statement_text, line_indentation(statement, source)));
+    }
+  }
+  stores.sort();
+  for store in &stores {
+    imports.push(format!(
+      "import {{ use{}Store }} from '@/stores/{}';",
+      capitalize_first_letter(store),
+      store
+    ));
+  }
+
+  let name = format!("use{}Store", capitalize_first_letter(namespace));
+  let mut code = imports.join("\n");
+  code.push_str("\n\n");
+  if !preamble.is_empty() {
+    code.push_str(&preamble.join("\n\n"));
+    code.push_str("\n\n");
+  }
+  code.push_str(&format!(
+    "export const {} = defineStore('{}', {{\n{},\n}});\n",
+    name,
+    namespace,
+    entries.join(",\n\n")
+  ));
+
+  Some(StoreModuleConversion {
+    name,
+    code,
+    diagnostics,
+  })
+}
+
+/// Parameters and body of a function member (`name() {}`, `name: () => ...`, `name: function () {}`)
+struct FunctionParts<'a> {
+  is_async: bool,
+  params: Vec<Node<'a>>,
+  body: Node<'a>,
+}
+
+fn function_parts<'a>(member: &Node<'a>, source: &str) -> Option<FunctionParts<'a>> {
+  let function = match member.kind() {
+    "method_definition" => *member,
+    "pair" => member.child_by_field_name("value").filter(|value| {
+      matches!(
+        value.kind(),
+        "arrow_function" | "function_expression" | "function"
+      )
+    })?,
+    _ => return None,
+  };
+
+  let params = match function.child_by_field_name("parameters") {
+    Some(parameters) => {
+      let mut cursor = parameters.walk();
+      parameters
+        .named_children(&mut cursor)
+        .filter(|param| param.kind() != "comment")
+        .collect()
+    }
+    None => function
+      .child_by_field_name("parameter")
+      .into_iter()
+      .collect(),
+  };
+
+  let mut cursor = function.walk();
+  let is_async = function
+    .children(&mut cursor)
+    .any(|child| child.kind() == "async")
+    || text(&function, source).starts_with("async");
+
+  Some(FunctionParts {
+    is_async,
+    params,
+    body: function.child_by_field_name("body")?,
+  })
+}
+
+/// Report getters and actions reading `rootState` / `rootGetters`
+fn check_root_access(
+  name: &str,
+  function: &FunctionParts,
+  source: &str,
+  diagnostics: &mut Vec<Diagnostic>,
+) {
+  let body = text(&function.body, source);
+
+  // Getters receive them as the third and fourth argument, actions in their context
+  let mut roots: Vec<String> = function
+    .params
+    .iter()
+    .skip(2)
+    .map(|param| text(param, source).to_string())
+    .collect();
+  match function.params.first() {
+    Some(context) if context.kind() == "object_pattern" => roots.extend(
+      pattern_names(context, source)
+        .into_iter()
+        .filter(|name| name == "rootState" || name == "rootGetters"),
+    ),
+    Some(context) if function.params.len() <= 2 => {
+      let context = text(context, source);
+      roots.push(format!("{}.rootState", context));
+      roots.push(format!("{}.rootGetters", context));
+    }
+    _ => {}
+  }
+
+  let uses_root = roots.iter().any(|root| {
+    Regex::new(&format!(r"(^|[^\w$.]){}\b", regex::escape(root)))
+      .is_ok_and(|pattern| pattern.is_match(body))
+  });
+  if uses_root {
+    diagnostics.push(Diagnostic::warning(
+      "store-root-access",
+      format!(
+        "`{}` reads the root state or getters, use the other stores (`useXStore()`) instead",
+        name
+      ),
+    ));
+  }
+}
+
+/// Rewrite `commit('x', payload)` / `dispatch('x', payload)` calls to store calls
+fn rewrite_store_calls(
+  code: &str,
+  callee: &str,
+  namespace: &str,
+  mutation_renames: &[(String, String)],
+  stores: &mut Vec<String>,
+  diagnostics: &mut Vec<Diagnostic>,
+) -> String {
+  let Ok(pattern) = Regex::new(&format!(r"(^|[^\w$.])({})\s*\(", regex::escape(callee))) else {
+    return code.to_string();
+  };
+  let is_commit = callee.ends_with("commit");
+
+  let calls: Vec<(usize, usize)> = pattern
+    .captures_iter(code)
+    .map(|caps| (caps.get(2).unwrap().start(), caps.get(0).unwrap().end() - 1))
+    .collect();
+
+  let mut result = code.to_string();
+  for (start, open) in calls.into_iter().rev() {
+    let Some(bounds) = argument_bounds(&result, open) else {
+      continue;
+    };
+    let close = *bounds.last().unwrap();
+
+    let mut arguments = Vec::new();
+    let mut argument_start = open + 1;
+    for bound in &bounds {
+      arguments.push(result[argument_start..*bound].trim().to_string());
+      argument_start = bound + 1;
+    }
+
+    let target = arguments.first().and_then(|first| {
+      let quote = first.chars().next()?;
+      (matches!(quote, '\'' | '"' | '`') && first.ends_with(quote) && !first.contains("${"))
+        .then(|| first[1..first.len() - 1].to_string())
+    });
+    let Some(target) = target else {
+      diagnostics.push(Diagnostic::warning(
+        "store-dynamic-call",
+        format!(
+          "`{}` is called with a dynamic name, call the store action directly",
+          callee
+        ),
+      ));
+      continue;
+    };
+
+    let store_call = match target.split_once('/') {
+      Some((module, action)) if module != namespace => {
+        if !stores.iter().any(|store| store == module) {
+          stores.push(module.to_string());
+        }
+        format!("use{}Store().{}", capitalize_first_letter(module), action)
+      }
+      Some((_, action)) => format!("this.{}", action),
+      None => {
+        let renamed = mutation_renames
+          .iter()
+          .find(|(from, _)| is_commit && from == &target)
+          .map(|(_, to)| to.clone());
+        format!("this.{}", renamed.unwrap_or(target))
+      }
+    };
+
+    let payload: Vec<&str> = arguments[1..]
+      .iter()
+      .map(|argument| argument.as_str())
+      .filter(|argument| !argument.is_empty() && !ROOT_OPTION_PATTERN.is_match(argument))
+      .collect();
+    result.replace_range(
+      start..close + 1,
+      &format!("{}({})", store_call, payload.join(", ")),
+    );
+  }
+
+  result
+}
+
+/// Expression of an arrow function body without its wrapping parentheses
+fn unparenthesized(body: Node) -> Node {
+  match body.kind() {
+    "parenthesized_expression" => body.named_child(0).unwrap_or(body),
+    _ => body,
+  }
+}
+
+/// Block running `statement`, for a member starting at `indentation`
+fn expression_block(statement: &str, indentation: usize) -> String {
+  let padding = " ".repeat(indentation);
+  format!("{{\n{}  {};\n{}}}", padding, statement, padding)
+}
+
+/// Replace an identifier (or a member path like `context.state`) with `replacement`
+fn replace_identifier(code: &str, name: &str, replacement: &str) -> String {
+  let Ok(pattern) = Regex::new(&format!(r"(^|[^\w$.]){}\b", regex::escape(name))) else {
+    return code.to_string();
+  };
+  pattern
+    .replace_all(code, |caps: &regex::Captures| {
+      format!("{}{}", &caps[1], replacement)
+    })
+    .to_string()
+}
+
+/// Names bound by a destructuring pattern (`{ commit, state: moduleState }` yields `commit` and
+/// `state`)
+fn pattern_names(pattern: &Node, source: &str) -> Vec<String> {
+  let mut cursor = pattern.walk();
+  pattern
+    .named_children(&mut cursor)
+    .filter_map(|property| match property.kind() {
+      "shorthand_property_identifier_pattern" => Some(text(&property, source).to_string()),
+      "pair_pattern" => property
+        .child_by_field_name("key")
+        .map(|key| text(&key, source).to_string()),
+      _ => None,
+    })
+    .collect()
+}
+
+/// Members of an object literal by key (`pair`, methods and shorthand properties)
+fn members<'a>(object: &Node<'a>, source: &str) -> Vec<(String, Node<'a>)> {
+  let mut cursor = object.walk();
+  object
+    .named_children(&mut cursor)
+    .filter_map(|member| match member.kind() {
+      "pair" => member.child_by_field_name("key").map(|key| {
+        let key = text(&key, source).trim_matches(['\'', '"']).to_string();
+        (key, member)
+      }),
+      "method_definition" => member
+        .child_by_field_name("name")
+        .map(|name| (text(&name, source).to_string(), member)),
+      "shorthand_property_identifier" => Some((text(&member, source).to_string(), member)),
+      _ => None,
+    })
+    .collect()
+}
+
+/// Value of a top-level `const name = value;` declaration, marking the declaration as consumed
+fn top_level_value<'a>(
+  statements: &[Node<'a>],
+  name: &str,
+  source: &str,
+  consumed: &mut Vec<(usize, usize)>,
+) -> Option<Node<'a>> {
+  for statement in statements {
+    if !matches!(
+      statement.kind(),
+      "lexical_declaration" | "variable_declaration"
+    ) {
+      continue;
+    }
+    let mut cursor = statement.walk();
+    let declarators: Vec<Node> = statement.named_children(&mut cursor).collect();
+    for declarator in declarators {
+      let is_match = declarator
+        .child_by_field_name("name")
+        .is_some_and(|declared| text(&declared, source) == name);
+      if let Some(value) = declarator.child_by_field_name("value").filter(|_| is_match) {
+        consumed.push((statement.start_byte(), statement.end_byte()));
+        return Some(value);
+      }
+    }
+  }
+
+  None
+}
+
+/// `name: { ... }` entry of the store options with its members
+fn object_entry(name: &str, members: &[String]) -> String {
+  let members: Vec<String> = members.iter().map(|member| indent(member, 4)).collect();
+  format!("  {}: {{\n{},\n  }}", name, members.join(",\n"))
+}
+
+/// Remove up to `column` spaces of indentation from the lines after the first one
+fn dedent(code: &str, column: usize) -> String {
+  code
+    .lines()
+    .enumerate()
+    .map(|(index, line)| {
+      if index == 0 {
+        return line;
+      }
+      let indentation = line.len() - line.trim_start_matches(' ').len();
+      &line[indentation.min(column)..]
+    })
+    .collect::<Vec<_>>()
+    .join("\n")
+}
+
+/// Indent every non-empty line by `width` spaces
+fn indent(code: &str, width: usize) -> String {
+  code
+    .lines()
+    .map(|line| {
+      if line.is_empty() {
+        String::new()
+      } else {
+        format!("{}{}", " ".repeat(width), line)
+      }
+    })
+    .collect::<Vec<_>>()
+    .join("\n")
+}
+
+/// Indentation of the line a node starts on
+fn line_indentation(node: &Node, source: &str) -> usize {
+  let line_start = source[..node.start_byte()]
+    .rfind('\n')
+    .map(|position| position + 1)
+    .unwrap_or(0);
+  let line = &source[line_start..];
+  line.len() - line.trim_start_matches(' ').len()
+}
+
+fn text<'a>(node: &Node, source: &'a str) -> &'a str {
+  &source[node.start_byte()..node.end_byte()]
+}
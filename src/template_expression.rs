@@ -0,0 +1,222 @@
+//! Parsing of Vue template expressions that are not plain JavaScript.
+//!
+//! Directive values are analyzed with tree-sitter's JavaScript grammar, which has no notion of
+//! Vue's template syntax. This layer splits those values into the JavaScript expressions they
+//! evaluate and the names they declare before anything is handed to tree-sitter:
+//! - `v-for="(item, index) in items"` declares `item` and `index`, and evaluates `items`
+//! - Slot props (`v-slot="{ item }"`, `#row="{ row }"`, `slot-scope="props"`) only declare names
+//! - Vue 2 filters (`{{ price | currency('EUR') }}`, `:title="name | capitalize"`) evaluate the
+//!   base expression and the filter arguments, and report the filter names
+
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::Serialize;
+use tree_sitter::Node;
+
+lazy_static! {
+  // Vue's alias pattern: `(item, index) in items`, `item of items`
+  static ref V_FOR_PATTERN: Regex = Regex::new(r"(?s)^\s*(.*?)\s+(?:in|of)\s+(.*)$").unwrap();
+  static ref FILTER_PATTERN: Regex =
+    Regex::new(r"(?s)^\s*([A-Za-z_$][\w$]*)\s*(?:\((.*)\))?\s*$").unwrap();
+}
+
+/// A Vue 2 filter applied with the pipe syntax
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FilterCall {
+  pub name: String,
+  /// Argument list without the parentheses (e.g. `'EUR', 2`), empty without arguments
+  pub arguments: String,
+}
+
+/// A directive value or mustache content split into its parts
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VueExpression {
+  /// JavaScript expressions evaluated in the scope of the element
+  pub expressions: Vec<String>,
+  /// Loop variables or slot props the directive declares
+  pub declarations: Vec<String>,
+  /// Filters applied to the value, in order
+  pub filters: Vec<FilterCall>,
+}
+
+impl VueExpression {
+  /// Parse the value of a directive by its attribute name (`v-for`, `:title`, `@click`, ...)
+  pub fn from_directive(name: &str, value: &str) -> Self {
+    if name == "v-for" {
+      return match V_FOR_PATTERN.captures(value) {
+        Some(caps) => Self {
+          expressions: vec![caps[2].trim().to_string()],
+          declarations: declared_names(&caps[1]),
+          filters: Vec::new(),
+        },
+        None => Self::javascript(value),
+      };
+    }
+
+    if is_slot_declaration(name) {
+      return Self {
+        declarations: declared_names(value),
+        ..Self::default()
+      };
+    }
+
+    // Filters are only allowed in mustaches and bindings, `|` is a bitwise or elsewhere
+    if name.starts_with(':') || name.starts_with("v-bind") {
+      Self::with_filters(value)
+    } else {
+      Self::javascript(value)
+    }
+  }
+
+  /// Parse the content of a `{{ }}` interpolation
+  pub fn from_mustache(content: &str) -> Self {
+    Self::with_filters(content)
+  }
+
+  fn javascript(value: &str) -> Self {
+    Self {
+      expressions: vec![value.to_string()],
+      ..Self::default()
+    }
+  }
+
+  fn with_filters(value: &str) -> Self {
+    let mut segments = split_filters(value).into_iter();
+    let mut expression = Self::javascript(segments.next().unwrap_or_default().trim());
+
+    for segment in segments {
+      let Some(caps) = FILTER_PATTERN.captures(segment) else {
+        // Not a filter after all, analyze the value as a whole
+        return Self::javascript(value);
+      };
+      let arguments = caps
+        .get(2)
+        .map_or("", |arguments| arguments.as_str().trim());
+      if !arguments.is_empty() {
+        expression.expressions.push(format!("[{}]", arguments));
+      }
+      expression.filters.push(FilterCall {
+        name: caps[1].to_string(),
+        arguments: arguments.to_string(),
+      });
+    }
+
+    expression
+  }
+}
+
+/// Whether an attribute declares slot props (`v-slot`, `v-slot:item`, `#item`, `slot-scope` and
+/// the pre-2.5 `scope`)
+pub fn is_slot_declaration(attribute: &str) -> bool {
+  attribute == "v-slot"
+    || attribute.starts_with("v-slot:")
+    || attribute.starts_with('#')
+    || attribute == "slot-scope"
+    || attribute == "scope"
+}
+
+/// Split an expression on the filter pipes, following Vue 2's `parseFilters`: a single `|`
+/// outside strings, template literals, regular expressions and brackets
+pub fn split_filters(value: &str) -> Vec<&str> {
+  let bytes = value.as_bytes();
+  let mut segments = Vec::new();
+  let mut segment_start = 0;
+  let mut depth = 0i32;
+  let mut quote: Option<u8> = None;
+  let mut in_regex = false;
+  let mut index = 0;
+
+  while index < bytes.len() {
+    let byte = bytes[index];
+    if let Some(q) = quote {
+      if byte == b'\\' {
+        index += 1;
+      } else if byte == q {
+        quote = None;
+      }
+    } else if in_regex {
+      if byte == b'\\' {
+        index += 1;
+      } else if byte == b'/' {
+        in_regex = false;
+      }
+    } else {
+      match byte {
+        b'\'' | b'"' | b'`' => quote = Some(byte),
+        b'(' | b'[' | b'{' => depth += 1,
+        b')' | b']' | b'}' => depth -= 1,
+        b'/' => {
+          // A slash starts a regular expression unless it follows a value
+          let previous = value[..index].trim_end().bytes().last();
+          if !previous.is_some_and(|p| p.is_ascii_alphanumeric() || b"_$)]".contains(&p)) {
+            in_regex = true;
+          }
+        }
+        b'|'
+          if depth == 0
+            && bytes.get(index + 1) != Some(&b'|')
+            && (index == 0 || bytes[index - 1] != b'|') =>
+        {
+          segments.push(&value[segment_start..index]);
+          segment_start = index + 1;
+        }
+        _ => {}
+      }
+    }
+    index += 1;
+  }
+
+  segments.push(&value[segment_start..]);
+  segments
+}
+
+/// Names bound by a `v-for` alias or slot props pattern (`(item, index)`, `{ id, name: title }`,
+/// `props`), without the names used in default values
+pub fn declared_names(pattern: &str) -> Vec<String> {
+  let pattern = pattern.trim();
+  let pattern = pattern
+    .strip_prefix('(')
+    .and_then(|inner| inner.strip_suffix(')'))
+    .unwrap_or(pattern);
+  let source = format!("let [{}] = [];", pattern);
+
+  let Some(tree) = crate::javascript::parse(&source) else {
+    return Vec::new();
+  };
+  let Some(declared) = tree
+    .root_node()
+    .named_child(0)
+    .and_then(|declaration| declaration.named_child(0))
+    .and_then(|declarator| declarator.child_by_field_name("name"))
+  else {
+    return Vec::new();
+  };
+
+  let mut names = Vec::new();
+  collect_declared_names(declared, &source, &mut names);
+  names
+}
+
+fn collect_declared_names(node: Node, source: &str, names: &mut Vec<String>) {
+  match node.kind() {
+    "identifier" | "shorthand_property_identifier_pattern" => {
+      names.push(source[node.start_byte()..node.end_byte()].to_string());
+    }
+    "assignment_pattern" | "object_assignment_pattern" => {
+      if let Some(left) = node.child_by_field_name("left") {
+        collect_declared_names(left, source, names);
+      }
+    }
+    "pair_pattern" => {
+      if let Some(value) = node.child_by_field_name("value") {
+        collect_declared_names(value, source, names);
+      }
+    }
+    _ => {
+      let mut cursor = node.walk();
+      for child in node.named_children(&mut cursor) {
+        collect_declared_names(child, source, names);
+      }
+    }
+  }
+}
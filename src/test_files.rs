@@ -0,0 +1,408 @@
+//! Rewrites of Jest/Vitest spec files written for Vue Test Utils 1.
+//!
+//! Converted components are mounted with Vue Test Utils 2, which dropped `createLocalVue()` and
+//! moved the instance-level mounting options under `global`. The rewrite handles:
+//! - `const localVue = createLocalVue();` and `localVue.use(Plugin)` are removed, the plugins move
+//!   to `global.plugins`
+//! - `mocks`, `stubs` and `provide` mounting options move to `global`
+//! - `propsData` is renamed to `props`
+//!
+//! Accesses to `wrapper.vm.x` are reported, since `<script setup>` components only expose what
+//! they pass to `defineExpose()`.
+
+use crate::Diagnostic;
+use tree_sitter::Node;
+
+/// Mounting options moved under `global` in Vue Test Utils 2
+const GLOBAL_OPTIONS: [&str; 3] = ["mocks", "stubs", "provide"];
+
+/// Functions mounting a component
+const MOUNT_FUNCTIONS: [&str; 2] = ["mount", "shallowMount"];
+
+/// Result of rewriting a spec file
+#[derive(Debug, Clone, Default)]
+pub struct TestFileRewrite {
+  /// The rewritten source
+  pub code: String,
+  /// Findings that need the author's attention
+  pub diagnostics: Vec<Diagnostic>,
+}
+
+/// Source edit: (start, end, replacement)
+type Edit = (usize, usize, String);
+
+/// Rewrite a spec file for Vue Test Utils 2
+pub fn rewrite_test_file(source: &str) -> TestFileRewrite {
+  let mut rewrite = TestFileRewrite {
+    code: source.to_string(),
+    diagnostics: Vec::new(),
+  };
+
+  let Some(tree) = crate::javascript::parse(source) else {
+    return rewrite;
+  };
+  let root = tree.root_node();
+
+  let mut edits: Vec<Edit> = Vec::new();
+
+  // Local Vue instances and the plugins installed on them
+  let mut local_vues: Vec<String> = Vec::new();
+  let mut plugins: Vec<String> = Vec::new();
+  collect_local_vue(&root, source, &mut local_vues, &mut edits);
+  collect_plugins(&root, source, &local_vues, &mut plugins, &mut edits);
+
+  let mut vm_accesses: Vec<String> = Vec::new();
+  let mut other_local_vue_usages = 0;
+  walk(&root, &mut |node| match node.kind() {
+    "object" if is_mounting_options(node, source, &local_vues) => {
+      rewrite_mounting_options(node, source, &local_vues, &plugins, &mut edits);
+    }
+    "import_specifier" if text(node, source) == "createLocalVue" => {
+      let named_imports = node.parent();
+      let import = named_imports
+        .and_then(|named| named.parent())
+        .and_then(|clause| clause.parent());
+      let is_only_import = named_imports.is_some_and(|named| named.named_child_count() == 1)
+        && named_imports
+          .and_then(|named| named.parent())
+          .is_some_and(|clause| clause.named_child_count() == 1);
+
+      match import {
+        Some(import) if is_only_import => remove_statement(&import, source, &mut edits),
+        _ => remove_list_item(node, source, &mut edits),
+      }
+    }
+    "member_expression" => {
+      let object = node.child_by_field_name("object");
+      if let (Some(object), Some(property)) = (object, node.child_by_field_name("property")) {
+        if object.kind() == "member_expression"
+          && object
+            .child_by_field_name("property")
+            .is_some_and(|vm| text(&vm, source) == "vm")
+        {
+          let name = text(&property, source).to_string();
+          if !vm_accesses.contains(&name) {
+            vm_accesses.push(name);
+          }
+        } else if local_vues.contains(&text(&object, source).to_string())
+          && !edits
+            .iter()
+            .any(|(start, end, _)| *start <= node.start_byte() && node.end_byte() <= *end)
+        {
+          other_local_vue_usages += 1;
+        }
+      }
+    }
+    _ => {}
+  });
+
+  if !plugins.is_empty() {
+    rewrite.diagnostics.push(Diagnostic::info(
+      "test-plugins",
+      format!(
+        "Plugins installed with `localVue.use()` ({}) were moved to `global.plugins`, Vue 3 plugins are usually instances (e.g. `createPinia()`, `createI18n()`)",
+        plugins.join(", ")
+      ),
+    ));
+  }
+  if other_local_vue_usages > 0 {
+    rewrite.diagnostics.push(Diagnostic::warning(
+      "test-local-vue",
+      "`localVue` is still used beyond `.use()`, register components and directives through `global.components` / `global.directives`",
+    ));
+  }
+  for name in vm_accesses {
+    rewrite.diagnostics.push(Diagnostic::warning(
+      "test-vm-access",
+      format!(
+        "`wrapper.vm.{}` accesses component internals, `<script setup>` components only expose what is passed to `defineExpose()`",
+        name
+      ),
+    ));
+  }
+
+  rewrite.code = apply_edits(source, edits);
+  rewrite
+}
+
+/// Find `const localVue = createLocalVue();` declarations and remove them
+fn collect_local_vue(
+  root: &Node,
+  source: &str,
+  local_vues: &mut Vec<String>,
+  edits: &mut Vec<Edit>,
+) {
+  walk(root, &mut |node| {
+    if !matches!(node.kind(), "lexical_declaration" | "variable_declaration") {
+      return;
+    }
+
+    let mut cursor = node.walk();
+    let declarators: Vec<Node> = node.named_children(&mut cursor).collect();
+    if declarators.len() != 1 {
+      return;
+    }
+
+    let (Some(name), Some(value)) = (
+      declarators[0].child_by_field_name("name"),
+      declarators[0].child_by_field_name("value"),
+    ) else {
+      return;
+    };
+
+    let is_create_local_vue = value.kind() == "call_expression"
+      && value
+        .child_by_field_name("function")
+        .is_some_and(|function| text(&function, source) == "createLocalVue");
+    if is_create_local_vue {
+      local_vues.push(text(&name, source).to_string());
+      remove_statement(node, source, edits);
+    }
+  });
+}
+
+/// Find `localVue.use(Plugin, options)` statements, collect the plugins and remove them
+fn collect_plugins(
+  root: &Node,
+  source: &str,
+  local_vues: &[String],
+  plugins: &mut Vec<String>,
+  edits: &mut Vec<Edit>,
+) {
+  walk(root, &mut |node| {
+    if node.kind() != "expression_statement" {
+      return;
+    }
+    let Some(call) = node
+      .named_child(0)
+      .filter(|call| call.kind() == "call_expression")
+    else {
+      return;
+    };
+    let (Some(function), Some(arguments)) = (
+      call.child_by_field_name("function"),
+      call.child_by_field_name("arguments"),
+    ) else {
+      return;
+    };
+
+    let is_use = function.kind() == "member_expression"
+      && function
+        .child_by_field_name("object")
+        .is_some_and(|object| local_vues.contains(&text(&object, source).to_string()))
+      && function
+        .child_by_field_name("property")
+        .is_some_and(|property| text(&property, source) == "use");
+    if !is_use {
+      return;
+    }
+
+    let mut cursor = arguments.walk();
+    let args: Vec<&str> = arguments
+      .named_children(&mut cursor)
+      .map(|arg| text(&arg, source))
+      .collect();
+    let plugin = match args.as_slice() {
+      [plugin] => plugin.to_string(),
+      [plugin, options @ ..] => format!("[{}, {}]", plugin, options.join(", ")),
+      [] => return,
+    };
+
+    plugins.push(plugin);
+    remove_statement(node, source, edits);
+  });
+}
+
+/// Whether the object literal holds mounting options: passed to `mount()` / `shallowMount()`,
+/// or built elsewhere with a `localVue`
+fn is_mounting_options(object: &Node, source: &str, local_vues: &[String]) -> bool {
+  let has_local_vue = properties(object, source)
+    .iter()
+    .any(|(key, _)| local_vues.contains(key) || key == "localVue");
+  if has_local_vue {
+    return true;
+  }
+
+  let Some(arguments) = object
+    .parent()
+    .filter(|parent| parent.kind() == "arguments")
+  else {
+    return false;
+  };
+  let Some(call) = arguments.parent() else {
+    return false;
+  };
+
+  let is_mount = call
+    .child_by_field_name("function")
+    .is_some_and(|function| MOUNT_FUNCTIONS.contains(&text(&function, source)));
+  let mut cursor = arguments.walk();
+  let is_second_argument = arguments.named_children(&mut cursor).nth(1) == Some(*object);
+
+  is_mount && is_second_argument
+}
+
+/// Move `localVue`, `mocks`, `stubs` and `provide` under `global` and rename `propsData`
+fn rewrite_mounting_options(
+  object: &Node,
+  source: &str,
+  local_vues: &[String],
+  plugins: &[String],
+  edits: &mut Vec<Edit>,
+) {
+  let properties = properties(object, source);
+  // Already written for Vue Test Utils 2
+  if properties.iter().any(|(key, _)| key == "global") {
+    return;
+  }
+
+  let mut global_entries = Vec::new();
+  let mut moved = Vec::new();
+
+  for (key, property) in &properties {
+    if local_vues.contains(key) || key == "localVue" {
+      if !plugins.is_empty() {
+        global_entries.push(format!("plugins: [{}]", plugins.join(", ")));
+      }
+      moved.push(*property);
+    } else if GLOBAL_OPTIONS.contains(&key.as_str()) {
+      global_entries.push(text(property, source).to_string());
+      moved.push(*property);
+    } else if key == "propsData" {
+      if property.kind() == "shorthand_property_identifier" {
+        edits.push((
+          property.start_byte(),
+          property.end_byte(),
+          "props: propsData".to_string(),
+        ));
+      } else if let Some(key_node) = property.child_by_field_name("key") {
+        edits.push((
+          key_node.start_byte(),
+          key_node.end_byte(),
+          "props".to_string(),
+        ));
+      }
+    }
+  }
+
+  let Some((first, rest)) = moved.split_first() else {
+    return;
+  };
+
+  if global_entries.is_empty() {
+    remove_list_item(first, source, edits);
+  } else {
+    edits.push((
+      first.start_byte(),
+      first.end_byte(),
+      format!("global: {{ {} }}", global_entries.join(", ")),
+    ));
+  }
+  for property in rest {
+    remove_list_item(property, source, edits);
+  }
+}
+
+/// Properties of an object literal by key (`pair` and shorthand properties)
+fn properties<'a>(object: &Node<'a>, source: &str) -> Vec<(String, Node<'a>)> {
+  let mut cursor = object.walk();
+  object
+    .named_children(&mut cursor)
+    .filter_map(|property| match property.kind() {
+      "pair" => property.child_by_field_name("key").map(|key| {
+        let key = text(&key, source).trim_matches(['\'', '"']).to_string();
+        (key, property)
+      }),
+      "shorthand_property_identifier" => Some((text(&property, source).to_string(), property)),
+      _ => None,
+    })
+    .collect()
+}
+
+/// Remove an item of a comma separated list (object property, import specifier) with its comma
+fn remove_list_item(node: &Node, source: &str, edits: &mut Vec<Edit>) {
+  let trailing_comma = node.next_sibling().filter(|next| next.kind() == ",");
+  let preceding_comma = node
+    .prev_sibling()
+    .filter(|previous| previous.kind() == ",");
+
+  let (start, end) = match (preceding_comma, trailing_comma) {
+    // First item: remove up to the next one
+    (None, _) => (
+      node.start_byte(),
+      node
+        .next_named_sibling()
+        .map_or(node.end_byte(), |next| next.start_byte()),
+    ),
+    // Remove the line (or space) before the item together with its comma
+    (Some(_), Some(comma)) => (
+      source[..node.start_byte()].trim_end().len(),
+      comma.end_byte(),
+    ),
+    // Last item without a trailing comma: remove the preceding comma instead
+    (Some(comma), None) => (comma.start_byte(), node.end_byte()),
+  };
+
+  edits.push((start, end, String::new()));
+}
+
+/// Remove a statement together with its line when nothing else is on it
+fn remove_statement(node: &Node, source: &str, edits: &mut Vec<Edit>) {
+  let line_start = source[..node.start_byte()]
+    .rfind('\n')
+    .map(|position| position + 1)
+    .unwrap_or(0);
+  let line_end = source[node.end_byte()..]
+    .find('\n')
+    .map(|position| node.end_byte() + position + 1)
+    .unwrap_or(source.len());
+
+  let alone_on_line = source[line_start..node.start_byte()].trim().is_empty()
+    && source[node.end_byte()..line_end].trim().is_empty();
+  if alone_on_line {
+    edits.push((line_start, line_end, String::new()));
+  } else {
+    edits.push((node.start_byte(), node.end_byte(), String::new()));
+  }
+}
+
+fn apply_edits(source: &str, mut edits: Vec<Edit>) -> String {
+  edits.sort_by_key(|(start, end, _)| (*start, *end));
+
+  let mut result = String::with_capacity(source.len());
+  let mut position = 0;
+  for (start, end, replacement) in edits {
+    if start < position {
+      // Overlapping removals are merged
+      if replacement.is_empty() {
+        position = position.max(end);
+      }
+      continue;
+    }
+    result.push_str(&source[position..start]);
+    result.push_str(&replacement);
+    position = end;
+  }
+  result.push_str(&source[position..]);
+
+  // Removed statements leave their surrounding blank lines behind
+  if !source.contains("\n\n\n") {
+    while result.contains("\n\n\n") {
+      result = result.replace("\n\n\n", "\n\n");
+    }
+  }
+
+  result
+}
+
+fn walk<'a>(node: &Node<'a>, visit: &mut impl FnMut(&Node<'a>)) {
+  visit(node);
+  let mut cursor = node.walk();
+  for child in node.children(&mut cursor) {
+    walk(&child, visit);
+  }
+}
+
+fn text<'a>(node: &Node, source: &'a str) -> &'a str {
+  &source[node.start_byte()..node.end_byte()]
+}
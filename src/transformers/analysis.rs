@@ -0,0 +1,158 @@
+use super::Transformer;
+use crate::{Diagnostic, TransformationContext, TransformationResult, TransformerConfig};
+use lazy_static::lazy_static;
+use regex::Regex;
+
+lazy_static! {
+  static ref IMPURE_CALL_PATTERN: Regex = Regex::new(
+    r"\b(Date\.now|Math\.random|performance\.now|crypto\.randomUUID|crypto\.getRandomValues)\s*\(|\bnew\s+Date\s*\(\s*\)"
+  )
+  .unwrap();
+  static ref ADD_LISTENER_PATTERN: Regex = Regex::new(
+    r#"([\w$.]+)\.addEventListener\(\s*['"`]([^'"`]+)['"`]\s*,\s*this\.([\w$]+)"#
+  )
+  .unwrap();
+  static ref REMOVE_LISTENER_PATTERN: Regex = Regex::new(
+    r#"([\w$.]+)\.removeEventListener\(\s*['"`]([^'"`]+)['"`]\s*,\s*this\.([\w$]+)"#
+  )
+  .unwrap();
+}
+
+/// Analysis-only transformer that reports migration hazards as diagnostics
+///
+/// This transformer never changes the output. It currently reports:
+/// - Computed getters calling impure functions (`Date.now()`, `Math.random()`, `new Date()`, ...),
+///   which are cached by `computed()` and can cause SSR hydration mismatches
+/// - `addEventListener` calls using a component method without a matching `removeEventListener`
+///   in `beforeDestroy`/`destroyed` (converted to `onBeforeUnmount`/`onUnmounted`)
+pub struct AnalysisTransformer;
+
+impl Default for AnalysisTransformer {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl AnalysisTransformer {
+  pub fn new() -> Self {
+    Self
+  }
+
+  /// Report computed getters that depend on non-deterministic functions
+  fn check_impure_computed(&self, context: &TransformationContext) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    for computed in &context.script_state.computed_details {
+      let Some(getter) = &computed.getter else {
+        continue;
+      };
+
+      let mut calls: Vec<String> = Vec::new();
+      for cap in IMPURE_CALL_PATTERN.captures_iter(getter) {
+        let call = match cap.get(1) {
+          Some(function) => format!("{}()", function.as_str()),
+          None => "new Date()".to_string(),
+        };
+        if !calls.contains(&call) {
+          calls.push(call);
+        }
+      }
+
+      if !calls.is_empty() {
+        diagnostics.push(Diagnostic::warning(
+          "impure-computed",
+          format!(
+            "computed `{}` calls {}; its value is cached and may differ between server and client, consider a method or a ref",
+            computed.name,
+            calls.join(", ")
+          ),
+        ));
+      }
+    }
+
+    diagnostics
+  }
+
+  /// Report listeners registered with a component method that are never removed on teardown
+  fn check_listener_cleanup(&self, context: &TransformationContext) -> Vec<Diagnostic> {
+    let methods = &context.script_state.method_details;
+
+    let removed: Vec<(String, String, String)> = methods
+      .iter()
+      .filter(|method| {
+        matches!(
+          method.name.as_str(),
+          "beforeDestroy" | "destroyed" | "beforeUnmount" | "unmounted"
+        )
+      })
+      .flat_map(|method| REMOVE_LISTENER_PATTERN.captures_iter(&method.body))
+      .map(|caps| {
+        (
+          caps[1].to_string(),
+          caps[2].to_string(),
+          caps[3].to_string(),
+        )
+      })
+      .collect();
+
+    let mut diagnostics = Vec::new();
+    let mut reported = Vec::new();
+
+    for method in methods {
+      for caps in ADD_LISTENER_PATTERN.captures_iter(&method.body) {
+        let (target, event, handler) = (&caps[1], &caps[2], &caps[3]);
+
+        // Only handlers that are component methods become new function references
+        if !context.script_state.methods.iter().any(|m| m == handler) {
+          continue;
+        }
+
+        let is_removed = removed
+          .iter()
+          .any(|(t, e, h)| t == target && e == event && h == handler);
+        let key = (target.to_string(), event.to_string(), handler.to_string());
+
+        if !is_removed && !reported.contains(&key) {
+          diagnostics.push(Diagnostic::warning(
+            "listener-cleanup",
+            format!(
+              "`{}.addEventListener('{}', {})` in `{}` has no matching removeEventListener in onBeforeUnmount, the listener will leak",
+              target, event, handler, method.name
+            ),
+          ));
+          reported.push(key);
+        }
+      }
+    }
+
+    diagnostics
+  }
+}
+
+impl Transformer for AnalysisTransformer {
+  fn name(&self) -> &'static str {
+    "analysis"
+  }
+
+  fn should_transform(&self, context: &TransformationContext, _config: &TransformerConfig) -> bool {
+    !context.script_state.computed_details.is_empty()
+      || !context.script_state.method_details.is_empty()
+  }
+
+  fn transform(
+    &self,
+    context: &TransformationContext,
+    _config: &TransformerConfig,
+  ) -> TransformationResult {
+    let mut result = TransformationResult::new();
+
+    result
+      .diagnostics
+      .extend(self.check_impure_computed(context));
+    result
+      .diagnostics
+      .extend(self.check_listener_cleanup(context));
+
+    result
+  }
+}
@@ -1,9 +1,57 @@
-use super::Transformer;
-use crate::{TemplateReplacement, TransformationContext, TransformationResult, TransformerConfig};
+use super::{BodyTransformFn, StyleTransformFn, Transformer};
+use crate::{
+  rewrite_alias, rewrite_path_aliases, AssetMode, Diagnostic, ReplacementScope,
+  TemplateReplacement, TransformationContext, TransformationResult, TransformerConfig,
+};
+use lazy_static::lazy_static;
+use regex::Regex;
+
+lazy_static! {
+  static ref REQUIRE_PATTERN: Regex =
+    Regex::new(r#"require\(\s*(?:'([^'`]*)'|"([^"`]*)")\s*\)"#).unwrap();
+  static ref DYNAMIC_REQUIRE_PATTERN: Regex =
+    Regex::new(r#"require\(\s*(`[^`]*`|[^'"\s)\[][^)]*)\)"#).unwrap();
+  static ref REQUIRE_SRC_PATTERN: Regex =
+    Regex::new(r#":src="require\(\s*'([^']+)'\s*\)""#).unwrap();
+  static ref SVG_SRC_PATTERN: Regex = Regex::new(r#"src="([^"]*\.svg)""#).unwrap();
+  static ref STATIC_ASSET_ATTRIBUTE_PATTERN: Regex =
+    Regex::new(r#"(\s)(src|poster)="([^"{}:]+)""#).unwrap();
+}
+
+/// Attribute and `url()` prefixes after which aliased asset paths are rewritten
+const ASSET_PATH_PREFIXES: [&str; 3] = ["\"", "'", "("];
+
+/// Extensions of required modules that are code rather than assets
+const MODULE_EXTENSIONS: [&str; 7] = ["js", "mjs", "cjs", "ts", "vue", "json", "node"];
+
+/// Extensions of assets named with the `img` prefix, other assets use `asset`
+const IMAGE_EXTENSIONS: [&str; 9] = [
+  "png", "jpg", "jpeg", "gif", "svg", "webp", "avif", "ico", "bmp",
+];
 
 /// Transformer for converting asset paths and other template transformations
+///
+/// Aliased asset paths (`~/assets/logo.png`) are rewritten with the configured `aliases` in the
+/// template and in `<style>` (`url(~/assets/bg.png)`, `url(~assets/bg.png)`).
+///
+/// Webpack's `require('~/assets/logo.png')` is converted depending on `asset_mode`:
+/// - `path` (default): `:src="require(...)"` bindings become static `src` attributes, which Vite
+///   resolves when compiling the template. Other requires are reported.
+/// - `import`: every required asset (template bindings, data values, methods) is hoisted to an
+///   `import imgLogo from '@/assets/logo.png';` declaration and referenced by name
+/// - `url`: like `import`, with `const imgLogo = new URL('./logo.png', import.meta.url).href;`
+///
+/// Hoisted assets are named after their file (`hero-banner.jpg` becomes `imgHeroBanner`), with a
+/// numeric suffix when the name is taken. Static `src` / `poster` attributes referring to an
+/// asset used more than once share its binding, so every path is imported once.
 pub struct AssetsTransformer;
 
+/// A required asset hoisted to a setup binding
+struct Asset {
+  path: String, // Source path with the aliases applied
+  name: String,
+}
+
 impl Default for AssetsTransformer {
     fn default() -> Self {
         Self::new()
@@ -15,50 +63,347 @@ impl AssetsTransformer {
     Self
   }
 
-  /// Check if there are asset paths that need transformation
-  fn has_asset_transformations(&self, context: &TransformationContext) -> bool {
-    if let Some(template_content) = &context.sfc_sections.template_content {
-      // Check for Nuxt-style asset paths or require() calls
-      template_content.contains("~/assets/") 
-        || template_content.contains("~assets/")
-        || template_content.contains("require(")
+  /// Required path of a `require()` match
+  fn required_path<'a>(caps: &'a regex::Captures) -> &'a str {
+    caps
+      .get(1)
+      .or_else(|| caps.get(2))
+      .map(|path| path.as_str())
+      .unwrap_or_default()
+  }
+
+  /// Whether a required path refers to an asset (`logo.png`) rather than a module
+  fn is_asset_path(path: &str) -> bool {
+    let file_name = path.rsplit('/').next().unwrap_or(path);
+    match file_name.rsplit_once('.') {
+      Some((stem, extension)) => {
+        !stem.is_empty() && !MODULE_EXTENSIONS.contains(&extension.to_lowercase().as_str())
+      }
+      None => false,
+    }
+  }
+
+  /// Whether a static attribute value is a local asset path (not a URL or public file)
+  fn is_local_asset_path(path: &str) -> bool {
+    !path.starts_with('/') && !path.starts_with("data:") && Self::is_asset_path(path)
+  }
+
+  /// Binding name derived from the file name: `img` or `asset` followed by the PascalCase stem
+  fn asset_name(path: &str) -> String {
+    let file_name = path.rsplit('/').next().unwrap_or(path);
+    let (stem, extension) = file_name.rsplit_once('.').unwrap_or((file_name, ""));
+    let prefix = if IMAGE_EXTENSIONS.contains(&extension.to_lowercase().as_str()) {
+      "img"
     } else {
-      false
+      "asset"
+    };
+
+    let words = stem
+      .split(|c: char| !c.is_ascii_alphanumeric())
+      .filter(|word| !word.is_empty());
+    let mut name = prefix.to_string();
+    for word in words {
+      let mut chars = word.chars();
+      if let Some(first) = chars.next() {
+        name.push(first.to_ascii_uppercase());
+        name.push_str(chars.as_str());
+      }
     }
+    name
+  }
+
+  /// Names already bound in the component, which hoisted assets must not shadow
+  fn taken_names(context: &TransformationContext) -> Vec<String> {
+    let state = &context.script_state;
+    let mut names: Vec<String> = state
+      .data_properties
+      .iter()
+      .map(|data| data.name.clone())
+      .chain(state.props.iter().map(|prop| prop.name.clone()))
+      .chain(state.methods.iter().cloned())
+      .chain(state.computed_properties.iter().cloned())
+      .collect();
+    for import in &state.imports {
+      names.extend(
+        import
+          .imports
+          .iter()
+          .map(|item| item.alias.clone().unwrap_or_else(|| item.name.clone())),
+      );
+    }
+    names
+  }
+
+  /// Asset paths required in `source`, with the aliases applied
+  fn required_asset_paths(source: &str, config: &TransformerConfig) -> Vec<(usize, String)> {
+    REQUIRE_PATTERN
+      .captures_iter(source)
+      .filter(|caps| Self::is_asset_path(Self::required_path(caps)))
+      .map(|caps| {
+        let path = rewrite_alias(Self::required_path(&caps), &config.aliases);
+        (caps.get(0).unwrap().start(), path)
+      })
+      .collect()
+  }
+
+  /// Asset paths referenced by static attributes of the template, with the aliases applied
+  fn static_asset_paths(template: &str, config: &TransformerConfig) -> Vec<(usize, String)> {
+    STATIC_ASSET_ATTRIBUTE_PATTERN
+      .captures_iter(template)
+      .filter(|caps| Self::is_local_asset_path(&caps[3]))
+      .map(|caps| {
+        let path = rewrite_alias(&caps[3], &config.aliases);
+        (caps.get(0).unwrap().start(), path)
+      })
+      .collect()
+  }
+
+  /// Assets to hoist, in order of appearance: every asset required in the template or the script,
+  /// and statically referenced assets used more than once
+  fn collect_assets(context: &TransformationContext, config: &TransformerConfig) -> Vec<Asset> {
+    let template = context.sfc_sections.template_content.as_deref().unwrap_or_default();
+    let script = context.sfc_sections.script_content.as_deref().unwrap_or_default();
+
+    let static_paths: Vec<String> = Self::static_asset_paths(template, config)
+      .into_iter()
+      .map(|(_, path)| path)
+      .collect();
+    let mut template_references = Self::required_asset_paths(template, config);
+    template_references.extend(Self::static_asset_paths(template, config));
+    template_references.sort_by_key(|(position, _)| *position);
+
+    let references: Vec<String> = template_references
+      .into_iter()
+      .chain(Self::required_asset_paths(script, config))
+      .map(|(_, path)| path)
+      .collect();
+
+    let mut taken = Self::taken_names(context);
+    let mut assets: Vec<Asset> = Vec::new();
+    for path in &references {
+      let is_shared = !static_paths.contains(path)
+        || references.iter().filter(|other| *other == path).count() > 1;
+      if !is_shared || assets.iter().any(|asset| asset.path == *path) {
+        continue;
+      }
+
+      let base = Self::asset_name(path);
+      let mut name = base.clone();
+      let mut suffix = 2;
+      while taken.contains(&name) {
+        name = format!("{}{}", base, suffix);
+        suffix += 1;
+      }
+      taken.push(name.clone());
+
+      assets.push(Asset {
+        path: path.clone(),
+        name,
+      });
+    }
+
+    assets
+  }
+
+  /// Replace the asset requires of `code` with the hoisted bindings
+  fn replace_requires(code: &str, assets: &[Asset], config: &TransformerConfig) -> String {
+    REQUIRE_PATTERN
+      .replace_all(code, |caps: &regex::Captures| {
+        let path = rewrite_alias(Self::required_path(caps), &config.aliases);
+        assets
+          .iter()
+          .find(|asset| asset.path == path)
+          .map(|asset| asset.name.clone())
+          .unwrap_or_else(|| caps[0].to_string())
+      })
+      .to_string()
+  }
+
+  /// Declaration of an asset hoisted as a URL ([`AssetMode::Url`])
+  fn asset_declaration(asset: &Asset) -> String {
+    format!(
+      "const {} = new URL('{}', import.meta.url).href;",
+      asset.name, asset.path
+    )
+  }
+
+  /// Source an asset is imported from
+  fn asset_import_path(asset: &Asset) -> String {
+    // SVGs are imported as components by vite-svg-loader unless `?url` is given
+    let suffix = if asset.path.ends_with(".svg") { "?url" } else { "" };
+    format!("{}{}", asset.path, suffix)
+  }
+
+  /// Findings about requires that cannot be converted in the configured mode
+  fn require_diagnostics(
+    context: &TransformationContext,
+    config: &TransformerConfig,
+    assets: &[Asset],
+  ) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    let sources = [
+      ("template", &context.sfc_sections.template_content),
+      ("script", &context.sfc_sections.script_content),
+    ];
+
+    for (section, source) in sources {
+      let Some(source) = source else {
+        continue;
+      };
+
+      for caps in DYNAMIC_REQUIRE_PATTERN.captures_iter(source) {
+        diagnostics.push(Diagnostic::warning(
+          "dynamic-asset-require",
+          format!(
+            "`{}` in the {} has a dynamic path and was not converted, use `new URL(..., import.meta.url)` or `import.meta.glob()`",
+            &caps[0], section
+          ),
+        ));
+      }
+
+      if config.asset_mode == AssetMode::Path && section == "script" {
+        for caps in REQUIRE_PATTERN.captures_iter(source) {
+          if Self::is_asset_path(Self::required_path(&caps)) {
+            diagnostics.push(Diagnostic::warning(
+              "asset-require",
+              format!(
+                "`{}` in the script is not supported by Vite, set `asset_mode` to `import` or `url` to convert it",
+                &caps[0]
+              ),
+            ));
+          }
+        }
+      }
+    }
+
+    if config.asset_mode == AssetMode::Url {
+      for asset in assets.iter().filter(|asset| !asset.path.starts_with('.')) {
+        diagnostics.push(Diagnostic::warning(
+          "asset-url-alias",
+          format!(
+            "`new URL('{}', import.meta.url)` is resolved relative to the component, Vite does not apply aliases there: use a relative path",
+            asset.path
+          ),
+        ));
+      }
+    }
+
+    diagnostics
+  }
+
+  /// Target of Nuxt's `~/` alias, also used for its `~assets/` shorthand
+  fn nuxt_alias_target(config: &TransformerConfig) -> Option<&str> {
+    config
+      .aliases
+      .iter()
+      .find(|(alias, _)| alias == "~/")
+      .map(|(_, target)| target.as_str())
+  }
+
+  /// Check whether `content` contains an aliased path
+  fn has_aliased_paths(content: &str, config: &TransformerConfig) -> bool {
+    config.aliases.iter().any(|(alias, _)| {
+      ASSET_PATH_PREFIXES
+        .iter()
+        .any(|prefix| content.contains(&format!("{}{}", prefix, alias)))
+    })
+  }
+
+  /// Rewrite Nuxt's `~assets/` shorthand in `url()` and `@import` paths
+  fn rewrite_assets_shorthand(content: &str, config: &TransformerConfig) -> String {
+    let Some(target) = Self::nuxt_alias_target(config) else {
+      return content.to_string();
+    };
+
+    ASSET_PATH_PREFIXES
+      .iter()
+      .fold(content.to_string(), |content, prefix| {
+        content.replace(
+          &format!("{}~assets/", prefix),
+          &format!("{}{}assets/", prefix, target),
+        )
+      })
+  }
+
+  /// Check if there are asset paths that need transformation
+  fn has_asset_transformations(
+    &self,
+    context: &TransformationContext,
+    config: &TransformerConfig,
+  ) -> bool {
+    let in_template = context
+      .sfc_sections
+      .template_content
+      .as_ref()
+      .is_some_and(|template_content| {
+        // Check for aliased asset paths or require() calls
+        Self::has_aliased_paths(template_content, config)
+          || template_content.contains("~assets/")
+          || template_content.contains("require(")
+      });
+    let in_script = context
+      .sfc_sections
+      .script_content
+      .as_ref()
+      .is_some_and(|script| script.contains("require("));
+    let in_style = context
+      .sfc_sections
+      .style_content
+      .as_ref()
+      .is_some_and(|style| Self::has_aliased_paths(style, config) || style.contains("~assets/"));
+
+    in_template || in_script || in_style
   }
 
   /// Generate template replacements for asset paths
   fn generate_template_replacements(
     &self,
     context: &TransformationContext,
+    config: &TransformerConfig,
   ) -> Vec<TemplateReplacement> {
-    let mut replacements = vec![
-      // Transform Nuxt-style asset paths to standard Vue paths
-      TemplateReplacement {
-        find: "~/assets/".to_string(),
-        replace: "@/assets/".to_string(),
-      },
-      TemplateReplacement {
-        find: "~assets/".to_string(),
-        replace: "@/assets/".to_string(),
-      },
-    ];
+    let mut replacements = Vec::new();
+
+    // Replace the required assets with their hoisted bindings, before the paths are aliased
+    if config.asset_mode != AssetMode::Path {
+      if let Some(template_content) = &context.sfc_sections.template_content {
+        let assets = Self::collect_assets(context, config);
+        for caps in STATIC_ASSET_ATTRIBUTE_PATTERN.captures_iter(template_content) {
+          let path = rewrite_alias(&caps[3], &config.aliases);
+          let Some(asset) = assets.iter().find(|asset| asset.path == path) else {
+            continue;
+          };
+          replacements.push(TemplateReplacement {
+            find: caps[0].to_string(),
+            replace: format!("{}:{}=\"{}\"", &caps[1], &caps[2], asset.name),
+            scope: ReplacementScope::Template,
+          });
+        }
+        for caps in REQUIRE_PATTERN.captures_iter(template_content) {
+          let replace = Self::replace_requires(&caps[0], &assets, config);
+          let is_new = !replacements
+            .iter()
+            .any(|r: &TemplateReplacement| r.find == caps[0]);
+          if replace != caps[0] && is_new {
+            replacements.push(TemplateReplacement {
+              find: caps[0].to_string(),
+              replace,
+              scope: ReplacementScope::Expressions,
+            });
+          }
+        }
+      }
+    }
 
     // Add require() removal and SVG ?url replacements if needed
     if let Some(template_content) = &context.sfc_sections.template_content {
-      use regex::Regex;
-      
-      if template_content.contains("require(") {
+      if config.asset_mode == AssetMode::Path && template_content.contains("require(") {
         // Find all require patterns and create specific replacements
-        let require_regex = Regex::new(r#":src="require\(([^)]+)\)""#).unwrap();
-        for captures in require_regex.captures_iter(template_content) {
+        for captures in REQUIRE_SRC_PATTERN.captures_iter(template_content) {
           if let Some(path_match) = captures.get(1) {
-            let path = path_match.as_str();
             let full_match = captures.get(0).unwrap().as_str();
             
-            // Replace :src="require('path')" with src="path" 
-            // Extract the path without quotes and add consistent double quotes
-            let clean_path = path.trim_matches('\'').trim_matches('"');
+            // Replace :src="require('path')" with src="path"
+            let clean_path = rewrite_alias(path_match.as_str(), &config.aliases);
             
             // Add ?url suffix for SVG files
             let final_path = if clean_path.ends_with(".svg") {
@@ -70,6 +415,7 @@ impl AssetsTransformer {
             replacements.push(TemplateReplacement {
               find: full_match.to_string(),
               replace: format!("src=\"{}\"", final_path),
+              scope: ReplacementScope::Template,
             });
           }
         }
@@ -77,8 +423,7 @@ impl AssetsTransformer {
       
       // Also handle direct SVG src attributes (add ?url if missing)
       if template_content.contains(".svg") {
-        let svg_regex = Regex::new(r#"src="([^"]*\.svg)""#).unwrap();
-        for captures in svg_regex.captures_iter(template_content) {
+        for captures in SVG_SRC_PATTERN.captures_iter(template_content) {
           if let Some(path_match) = captures.get(1) {
             let path = path_match.as_str();
             let full_match = captures.get(0).unwrap().as_str();
@@ -87,7 +432,8 @@ impl AssetsTransformer {
             if !path.ends_with("?url") {
               replacements.push(TemplateReplacement {
                 find: full_match.to_string(),
-                replace: format!("src=\"{}?url\"", path),
+                replace: format!("src=\"{}?url\"", rewrite_alias(path, &config.aliases)),
+                scope: ReplacementScope::Template,
               });
             }
           }
@@ -95,6 +441,24 @@ impl AssetsTransformer {
       }
     }
 
+    // Transform the remaining aliased asset paths (e.g. Nuxt's `~/assets/`)
+    for (alias, target) in &config.aliases {
+      for prefix in ASSET_PATH_PREFIXES {
+        replacements.push(TemplateReplacement {
+          find: format!("{}{}", prefix, alias),
+          replace: format!("{}{}", prefix, target),
+          scope: ReplacementScope::Template,
+        });
+      }
+    }
+    if let Some(target) = Self::nuxt_alias_target(config) {
+      replacements.push(TemplateReplacement {
+        find: "~assets/".to_string(),
+        replace: format!("{}assets/", target),
+        scope: ReplacementScope::Template,
+      });
+    }
+
     replacements
   }
 }
@@ -105,21 +469,58 @@ impl Transformer for AssetsTransformer {
   }
 
   fn should_transform(&self, context: &TransformationContext, config: &TransformerConfig) -> bool {
-    config.enable_asset_transforms && self.has_asset_transformations(context)
+    config.enable_asset_transforms && self.has_asset_transformations(context, config)
   }
 
   fn transform(
     &self,
     context: &TransformationContext,
-    _config: &TransformerConfig,
+    config: &TransformerConfig,
   ) -> TransformationResult {
     let mut result = TransformationResult::new();
 
     // Generate template replacements
     result
       .template_replacements
-      .extend(self.generate_template_replacements(context));
+      .extend(self.generate_template_replacements(context, config));
+
+    // Hoist the required assets
+    let assets = Self::collect_assets(context, config);
+    if config.asset_mode != AssetMode::Path {
+      for asset in &assets {
+        match config.asset_mode {
+          AssetMode::Url => result.setup.push(Self::asset_declaration(asset)),
+          _ => result.add_default_import(&Self::asset_import_path(asset), &asset.name),
+        }
+      }
+    }
 
     result
+      .diagnostics
+      .extend(Self::require_diagnostics(context, config, &assets));
+
+    result
+  }
+
+  fn get_body_transform(&self) -> Option<Box<BodyTransformFn>> {
+    Some(Box::new(
+      |body: &str, context: &TransformationContext, config: &TransformerConfig| {
+        if config.asset_mode == AssetMode::Path || !body.contains("require(") {
+          return body.to_string();
+        }
+
+        let assets = AssetsTransformer::collect_assets(context, config);
+        AssetsTransformer::replace_requires(body, &assets, config)
+      },
+    ))
+  }
+
+  fn get_style_transform(&self) -> Option<Box<StyleTransformFn>> {
+    Some(Box::new(
+      |style: &str, _context: &TransformationContext, config: &TransformerConfig| {
+        let style = rewrite_path_aliases(style, &config.aliases);
+        AssetsTransformer::rewrite_assets_shorthand(&style, config)
+      },
+    ))
   }
 }
@@ -1,10 +1,30 @@
+use super::body_transforms::argument_bounds;
 use super::{BodyTransformFn, Transformer};
-use crate::{TransformationContext, TransformationResult, TransformerConfig};
+use crate::{Diagnostic, TransformationContext, TransformationResult, TransformerConfig};
+use lazy_static::lazy_static;
+use regex::Regex;
+
+lazy_static! {
+  static ref SHORTCUT_PATTERN: Regex = Regex::new(
+    r"(?:this\.)?\$axios\s*\.\s*\$(get|post|put|patch|delete|head|options|request)\s*\("
+  )
+  .unwrap();
+  static ref INSTANCE_CONFIG_PATTERN: Regex = Regex::new(concat!(
+    r"(?:this\.)?\$axios\s*\.\s*(defaults(?:\.[\w$]+)*|interceptors(?:\.[\w$]+)*",
+    r"|set(?:Token|Header|BaseURL)\b|on(?:Request|Response|Error|RequestError|ResponseError)\b)"
+  ))
+  .unwrap();
+}
 
 /// Transformer for converting axios usage from Options API to Composition API
 ///
-/// This transformer handles the conversion of axios calls from `this.$axios` to a
-/// composable-based approach using `useHttp()`.
+/// This transformer handles the conversion of axios calls from `this.$axios` to the configured
+/// HTTP client, by default a composable-based approach using `useHttp()`:
+/// - The Nuxt axios shortcuts (`$get`, `$post`, `$put`, `$patch`, `$delete`, ...) resolve to the
+///   response data, so they become `http.get(...).then((response) => response.data)`, or are
+///   wrapped in the `axios_data_helper` when configured
+/// - Changes to the shared instance (`defaults`, `interceptors`, `setToken`, `onError`, ...) are
+///   reported, since they belong where the client is created
 pub struct AxiosTransformer;
 
 impl Default for AxiosTransformer {
@@ -32,6 +52,76 @@ impl AxiosTransformer {
         .iter()
         .any(|id| id.contains("$axios"))
   }
+
+  /// Whether the script already imports `name`
+  fn imports_name(context: &TransformationContext, name: &str) -> bool {
+    context.script_state.imports.iter().any(|import| {
+      import
+        .imports
+        .iter()
+        .any(|item| item.alias.as_deref().unwrap_or(&item.name) == name)
+    })
+  }
+
+  /// Rewrite the Nuxt axios shortcuts to the plain axios method, unwrapping the response data
+  fn rewrite_shortcuts(code: &str, config: &TransformerConfig) -> String {
+    let mut result = code.to_string();
+    let starts: Vec<usize> = SHORTCUT_PATTERN
+      .find_iter(code)
+      .map(|shortcut| shortcut.start())
+      .collect();
+
+    // Back to front, so calls nested in the arguments are rewritten first
+    for start in starts.into_iter().rev() {
+      let Some(caps) = SHORTCUT_PATTERN.captures_at(&result, start) else {
+        continue;
+      };
+      let shortcut = caps.get(0).unwrap();
+      let Some(close) = argument_bounds(&result, shortcut.end() - 1)
+        .and_then(|bounds| bounds.last().copied())
+      else {
+        continue;
+      };
+
+      let call = format!(
+        "{}.{}({})",
+        config.http.name,
+        &caps[1],
+        &result[shortcut.end()..close]
+      );
+      let replacement = match &config.axios_data_helper {
+        Some(helper) => format!("{}({})", helper, call),
+        None => format!("{}.then((response) => response.data)", call),
+      };
+      result.replace_range(shortcut.start()..close + 1, &replacement);
+    }
+
+    result
+  }
+
+  /// Warnings for changes to the shared axios instance
+  fn instance_config_diagnostics(script: &str) -> Vec<Diagnostic> {
+    let mut seen: Vec<&str> = Vec::new();
+
+    INSTANCE_CONFIG_PATTERN
+      .captures_iter(script)
+      .filter_map(|caps| {
+        let property = caps.get(1).unwrap().as_str();
+        if seen.contains(&property) {
+          return None;
+        }
+        seen.push(property);
+
+        Some(Diagnostic::warning(
+          "axios-instance-config",
+          format!(
+            "`$axios.{}` changes the shared axios instance, move it to where the HTTP client is created (e.g. a plugin)",
+            property
+          ),
+        ))
+      })
+      .collect()
+  }
 }
 
 impl Transformer for AxiosTransformer {
@@ -46,7 +136,7 @@ impl Transformer for AxiosTransformer {
   fn transform(
     &self,
     context: &TransformationContext,
-    _config: &TransformerConfig,
+    config: &TransformerConfig,
   ) -> TransformationResult {
     let mut result = TransformationResult::default();
 
@@ -54,31 +144,51 @@ impl Transformer for AxiosTransformer {
       return result;
     }
 
-    // Add useHttp import
-    result.add_import("@/composables/useHttp", "useHttp");
+    let http = &config.http;
+    match &http.composable {
+      Some(composable) => {
+        result.add_import(&http.import_path, composable);
+        result
+          .setup
+          .push(format!("const {} = {}();", http.name, composable));
+      }
+      // The component may already import the shared client itself
+      None if Self::imports_name(context, &http.name) => {}
+      None => {
+        result.add_default_import(&http.import_path, &http.name);
+      }
+    }
 
-    // Add http composable setup
-    result.setup.push("const http = useHttp();".to_string());
+    if let Some(script) = &context.sfc_sections.script_content {
+      if let Some(helper) = &config.axios_data_helper {
+        if SHORTCUT_PATTERN.is_match(script) {
+          result.add_import(&http.import_path, helper);
+        }
+      }
+      result
+        .diagnostics
+        .extend(Self::instance_config_diagnostics(script));
+    }
 
     result
   }
 
   fn get_body_transform(&self) -> Option<Box<BodyTransformFn>> {
     Some(Box::new(
-      |body: &str, context: &TransformationContext, _config: &TransformerConfig| {
+      |body: &str, context: &TransformationContext, config: &TransformerConfig| {
         let axios_transformer = AxiosTransformer::new();
         if !axios_transformer.has_axios_usage(context) {
           return body.to_string();
         }
 
-        // Transform this.$axios calls to http calls
+        // Transform this.$axios calls to calls on the HTTP client
         // Handle cases where this.$axios is followed by newlines and method calls
-        let mut result = body.to_string();
+        let mut result = AxiosTransformer::rewrite_shortcuts(body, config);
 
         // Then handle any remaining this.$axios occurrences
-        result = result.replace("this.$axios", "http");
+        result = result.replace("this.$axios", &config.http.name);
 
-        result.replace("$axios", "http")
+        result.replace("$axios", &config.http.name)
       },
     ))
   }
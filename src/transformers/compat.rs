@@ -0,0 +1,259 @@
+use super::Transformer;
+use crate::{Diagnostic, TransformationContext, TransformationResult, TransformerConfig};
+use lazy_static::lazy_static;
+use regex::Regex;
+
+lazy_static! {
+  static ref CHILDREN_PATTERN: Regex = Regex::new(r"\$children\b").unwrap();
+  static ref ONCE_PATTERN: Regex = Regex::new(r"\$once\s*\(").unwrap();
+  static ref SCRIPT_HOOK_EVENT_PATTERN: Regex =
+    Regex::new(r#"\$(?:on|once)\s*\(\s*['"`]hook:(\w+)"#).unwrap();
+  static ref TEMPLATE_HOOK_EVENT_PATTERN: Regex = Regex::new(r"(?:@|v-on:)hook:([\w-]+)").unwrap();
+  static ref PROTOTYPE_PATTERN: Regex = Regex::new(r"\bVue\.prototype\.([\w$]+)").unwrap();
+  static ref FILTER_REGISTRATION_PATTERN: Regex =
+    Regex::new(r#"\bVue\.filter\s*\(\s*['"`]([\w$]+)"#).unwrap();
+  static ref FILTERS_OPTION_PATTERN: Regex = Regex::new(r"(?m)^\s*filters\s*:\s*\{").unwrap();
+  static ref INLINE_TEMPLATE_PATTERN: Regex =
+    Regex::new(r"<([\w-]+)[^>]*\sinline-template\b").unwrap();
+}
+
+const GUIDE: &str = "https://v3-migration.vuejs.org/breaking-changes";
+
+/// Analysis-only transformer that reports Options API features without a Vue 3 equivalent
+///
+/// This transformer never changes the output. It reports, with a link to the migration guide:
+/// - `$children`, removed in favor of template refs
+/// - `$once` and `hook:` lifecycle events (`@hook:mounted`, `$on('hook:beforeDestroy')`)
+/// - `Vue.prototype` access, replaced by `app.config.globalProperties`
+/// - Filters: `Vue.filter()` registrations, the `filters` option and `{{ value | filter }}`
+/// - Components rendered with `inline-template`
+pub struct CompatTransformer;
+
+impl Default for CompatTransformer {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl CompatTransformer {
+  pub fn new() -> Self {
+    Self
+  }
+
+  /// Distinct first capture groups of a pattern, in order of appearance
+  fn captured_names(pattern: &Regex, content: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for caps in pattern.captures_iter(content) {
+      if !names.iter().any(|name| name == &caps[1]) {
+        names.push(caps[1].to_string());
+      }
+    }
+    names
+  }
+
+  fn quoted(names: &[String]) -> String {
+    names
+      .iter()
+      .map(|name| format!("`{}`", name))
+      .collect::<Vec<_>>()
+      .join(", ")
+  }
+
+  fn check_instance_api(script: &str, template: &str) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    if CHILDREN_PATTERN.is_match(script) || CHILDREN_PATTERN.is_match(template) {
+      diagnostics.push(
+        Diagnostic::warning(
+          "compat-children",
+          "`$children` was removed in Vue 3, reach child components through template refs instead",
+        )
+        .with_url(&format!("{}/children.html", GUIDE)),
+      );
+    }
+
+    // `$once('hook:...')` is reported with the hook events
+    let once_calls = ONCE_PATTERN.find_iter(script).count();
+    let once_hooks = SCRIPT_HOOK_EVENT_PATTERN
+      .find_iter(script)
+      .filter(|found| found.as_str().starts_with("$once"))
+      .count();
+    if once_calls > once_hooks {
+      diagnostics.push(
+        Diagnostic::warning(
+          "compat-once",
+          "`$once` was removed from the instance API in Vue 3, use an event emitter library (e.g. mitt) or remove the listener after the first call",
+        )
+        .with_url(&format!("{}/events-api.html", GUIDE)),
+      );
+    }
+
+    let script_hooks = Self::captured_names(&SCRIPT_HOOK_EVENT_PATTERN, script);
+    if !script_hooks.is_empty() {
+      diagnostics.push(
+        Diagnostic::warning(
+          "compat-hook-events",
+          format!(
+            "Lifecycle hook events ({}) can no longer be listened to with `$on`/`$once`, call the matching `on*` lifecycle hook in setup instead",
+            Self::quoted(&script_hooks)
+          ),
+        )
+        .with_url(&format!("{}/vnode-lifecycle-events.html", GUIDE)),
+      );
+    }
+
+    let template_hooks = Self::captured_names(&TEMPLATE_HOOK_EVENT_PATTERN, template);
+    if !template_hooks.is_empty() {
+      diagnostics.push(
+        Diagnostic::warning(
+          "compat-hook-events",
+          format!(
+            "`@hook:` listeners ({}) are `@vue:` listeners in Vue 3 (e.g. `@vue:mounted`), and `destroyed`/`beforeDestroy` became `unmounted`/`beforeUnmount`",
+            Self::quoted(&template_hooks)
+          ),
+        )
+        .with_url(&format!("{}/vnode-lifecycle-events.html", GUIDE)),
+      );
+    }
+
+    diagnostics
+  }
+
+  fn check_global_api(script: &str) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    let prototype_properties = Self::captured_names(&PROTOTYPE_PATTERN, script);
+    if !prototype_properties.is_empty() {
+      diagnostics.push(
+        Diagnostic::warning(
+          "compat-prototype",
+          format!(
+            "`Vue.prototype` ({}) no longer exists, register global properties with `app.config.globalProperties` or provide them",
+            Self::quoted(&prototype_properties)
+          ),
+        )
+        .with_url(&format!("{}/global-api.html", GUIDE)),
+      );
+    }
+
+    diagnostics
+  }
+
+  fn check_filters(
+    context: &TransformationContext,
+    config: &TransformerConfig,
+    script: &str,
+  ) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    let url = format!("{}/filters.html", GUIDE);
+
+    let registered = Self::captured_names(&FILTER_REGISTRATION_PATTERN, script);
+    if !registered.is_empty() {
+      diagnostics.push(
+        Diagnostic::warning(
+          "compat-filters",
+          format!(
+            "`Vue.filter()` registrations ({}) were removed in Vue 3, expose the functions as global properties or import them",
+            Self::quoted(&registered)
+          ),
+        )
+        .with_url(&url),
+      );
+    }
+
+    if FILTERS_OPTION_PATTERN.is_match(script) {
+      diagnostics.push(
+        Diagnostic::warning(
+          "compat-filters",
+          "The `filters` option was removed in Vue 3 and is dropped, turn the filters into functions",
+        )
+        .with_url(&url),
+      );
+    }
+
+    // The date plugin converts its own filter and reports what it leaves
+    let template_filters: Vec<String> = context
+      .template_state
+      .filters
+      .iter()
+      .filter(|filter| {
+        config
+          .date_plugin
+          .as_ref()
+          .is_none_or(|date_plugin| &date_plugin.filter != *filter)
+      })
+      .cloned()
+      .collect();
+    if !template_filters.is_empty() {
+      diagnostics.push(
+        Diagnostic::warning(
+          "compat-filters",
+          format!(
+            "The template uses filters ({}), which Vue 3 no longer supports, call them as functions (`{{{{ currency(price) }}}}`)",
+            Self::quoted(&template_filters)
+          ),
+        )
+        .with_url(&url),
+      );
+    }
+
+    diagnostics
+  }
+
+  fn check_inline_templates(template: &str) -> Vec<Diagnostic> {
+    let components = Self::captured_names(&INLINE_TEMPLATE_PATTERN, template);
+    if components.is_empty() {
+      return Vec::new();
+    }
+
+    vec![Diagnostic::warning(
+      "compat-inline-template",
+      format!(
+        "`inline-template` ({}) was removed in Vue 3, move the content into the component's template or a default slot",
+        Self::quoted(&components)
+      ),
+    )
+    .with_url(&format!("{}/inline-template-attribute.html", GUIDE))]
+  }
+}
+
+impl Transformer for CompatTransformer {
+  fn name(&self) -> &'static str {
+    "compat"
+  }
+
+  fn should_transform(
+    &self,
+    _context: &TransformationContext,
+    _config: &TransformerConfig,
+  ) -> bool {
+    true
+  }
+
+  fn transform(
+    &self,
+    context: &TransformationContext,
+    config: &TransformerConfig,
+  ) -> TransformationResult {
+    let mut result = TransformationResult::new();
+    let script = context.sfc_sections.script_content.as_deref().unwrap_or("");
+    let template = context
+      .sfc_sections
+      .template_content
+      .as_deref()
+      .unwrap_or("");
+
+    result
+      .diagnostics
+      .extend(Self::check_instance_api(script, template));
+    result.diagnostics.extend(Self::check_global_api(script));
+    result
+      .diagnostics
+      .extend(Self::check_filters(context, config, script));
+    result
+      .diagnostics
+      .extend(Self::check_inline_templates(template));
+
+    result
+  }
+}
@@ -0,0 +1,433 @@
+use super::import_rewrite::to_kebab_case;
+use super::Transformer;
+use crate::project::component_tag_names;
+use crate::{
+  rewrite_alias, ComponentRegistration, Diagnostic, ReplacementScope, RequireComponentStyle,
+  ScriptStyle, SelfReferenceStyle, TemplateReplacement, TransformationContext,
+  TransformationResult, TransformerConfig,
+};
+use std::path::Path;
+use lazy_static::lazy_static;
+use regex::Regex;
+
+lazy_static! {
+  static ref IDENTIFIER_PATTERN: Regex = Regex::new(r"^[a-zA-Z_$][a-zA-Z0-9_$]*$").unwrap();
+  static ref ASYNC_IMPORT_PATTERN: Regex =
+    Regex::new(r"(?s)^\(\s*\)\s*=>\s*(import\s*\(.+\))$").unwrap();
+  static ref REQUIRE_PATTERN: Regex =
+    Regex::new(r#"^require\(\s*['"`]([^'"`]+)['"`]\s*\)(?:\.default)?$"#).unwrap();
+  static ref ASYNC_REQUIRE_PATTERN: Regex = Regex::new(
+    r#"(?s)^\(?\s*([\w$]+)\s*\)?\s*=>\s*require\(\s*\[\s*['"`]([^'"`]+)['"`]\s*\]\s*,\s*([\w$]+)\s*\)$"#
+  )
+  .unwrap();
+  static ref STRING_LITERAL_PATTERN: Regex = Regex::new(r#"'([\w-]+)'|"([\w-]+)""#).unwrap();
+  static ref STATIC_IS_PATTERN: Regex =
+    Regex::new(r#"(<component\b[^>]*?\s)is="([\w-]+)""#).unwrap();
+  static ref DYNAMIC_IS_PATTERN: Regex =
+    Regex::new(r#"(?:\s:|\sv-bind:)is="([a-zA-Z_$][a-zA-Z0-9_$]*)""#).unwrap();
+}
+
+/// Name of the generated lookup for components selected by name through `:is`
+const DYNAMIC_COMPONENTS_NAME: &str = "dynamicComponents";
+
+/// Transformer for the `components` option
+///
+/// `<script setup>` resolves components from bindings in scope, so registrations that are not a
+/// plain import need a binding of their own:
+/// - `TabArchive: () => import('./TabArchive.vue')` becomes
+///   `const TabArchive = defineAsyncComponent(() => import('./TabArchive.vue'));`
+/// - `Tab: TabHome` becomes `const Tab = TabHome;`
+/// - `TabHome: require('./TabHome.vue').default`, which Vite can not load, becomes
+///   `import TabHome from './TabHome.vue';`, or an async component with
+///   `require_components = "async"`. The webpack form of async components,
+///   `resolve => require(['./TabHome.vue'], resolve)`, is always converted to one.
+///
+/// Dynamic components referring to registered components by name are resolved as well:
+/// - `<component is="TabHome">` becomes `<component :is="TabHome">`
+/// - `<component :is="currentView">`, where `currentView` holds registered names such as
+///   `'TabPosts'`, becomes `<component :is="dynamicComponents[currentView]">` with a
+///   `const dynamicComponents = { TabHome, TabPosts, TabArchive };` lookup of the registered
+///   components
+///
+/// Recursive components rendering themselves by their `name` option (`<tree-item>` in
+/// `TreeItem`'s template) lose that name in `<script setup>`. Depending on `self_reference`,
+/// the name is kept with `defineOptions({ name: 'TreeItem' })`, or the self-references are
+/// renamed to the file name, which `<script setup>` components can refer to themselves by.
+///
+/// With the `setup-function` script style, components are still resolved by their registration:
+/// the `name` and `components` options are kept, async registrations wrapped in
+/// `defineAsyncComponent()`.
+pub struct ComponentsTransformer;
+
+impl Default for ComponentsTransformer {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl ComponentsTransformer {
+  pub fn new() -> Self {
+    Self
+  }
+
+  /// Binding the registration is available as in `<script setup>`
+  fn binding_name(registration: &ComponentRegistration) -> String {
+    if IDENTIFIER_PATTERN.is_match(&registration.value) && !Self::needs_alias(registration) {
+      return registration.value.clone();
+    }
+
+    registration
+      .name
+      .split('-')
+      .map(|part| {
+        let mut chars = part.chars();
+        match chars.next() {
+          Some(first) => first.to_uppercase().chain(chars).collect::<String>(),
+          None => String::new(),
+        }
+      })
+      .collect()
+  }
+
+  /// Dynamic import of an async registration: `() => import('./Tab.vue')`, the webpack
+  /// `resolve => require(['./Tab.vue'], resolve)`, or a `require()` loaded asynchronously
+  fn async_import(
+    registration: &ComponentRegistration,
+    config: &TransformerConfig,
+  ) -> Option<String> {
+    let value = registration.value.trim();
+    if let Some(caps) = ASYNC_IMPORT_PATTERN.captures(value) {
+      return Some(caps[1].to_string());
+    }
+    if let Some(caps) = ASYNC_REQUIRE_PATTERN.captures(value) {
+      if caps[1] == caps[3] {
+        return Some(format!(
+          "import('{}')",
+          rewrite_alias(&caps[2], &config.aliases)
+        ));
+      }
+    }
+    match config.require_components {
+      RequireComponentStyle::Async => {
+        Self::required_path(registration, config).map(|path| format!("import('{}')", path))
+      }
+      RequireComponentStyle::Import => None,
+    }
+  }
+
+  /// Path of a registration loading its component with `require()`
+  fn required_path(
+    registration: &ComponentRegistration,
+    config: &TransformerConfig,
+  ) -> Option<String> {
+    REQUIRE_PATTERN
+      .captures(registration.value.trim())
+      .map(|caps| rewrite_alias(&caps[1], &config.aliases))
+  }
+
+  /// Whether the registered name differs from the imported binding (`Tab: TabHome`)
+  fn needs_alias(registration: &ComponentRegistration) -> bool {
+    registration.name != registration.value
+      && registration.name != to_kebab_case(&registration.value)
+  }
+
+  /// Find the registration a component name (`TabHome` or `tab-home`) refers to
+  fn find_registration<'a>(
+    context: &'a TransformationContext,
+    name: &str,
+  ) -> Option<&'a ComponentRegistration> {
+    context.script_state.components.iter().find(|registration| {
+      let binding = Self::binding_name(registration);
+      registration.name == name || binding == name || to_kebab_case(&binding) == name
+    })
+  }
+
+  /// String literals used as component names in data, props defaults and script bodies, with the
+  /// binding they refer to
+  fn component_name_literals(context: &TransformationContext) -> Vec<(String, String)> {
+    let script_state = &context.script_state;
+    let sources = script_state
+      .data_properties
+      .iter()
+      .filter_map(|prop| prop.value.as_ref())
+      .chain(
+        script_state
+          .props
+          .iter()
+          .filter_map(|prop| prop.default_value.as_ref()),
+      )
+      .chain(
+        script_state
+          .method_details
+          .iter()
+          .map(|method| &method.body),
+      )
+      .chain(
+        script_state
+          .computed_details
+          .iter()
+          .flat_map(|computed| computed.getter.iter().chain(computed.setter.iter())),
+      )
+      .chain(
+        script_state
+          .watchers
+          .iter()
+          .map(|watcher| &watcher.handler_body),
+      );
+
+    let mut literals: Vec<(String, String)> = Vec::new();
+    for source in sources {
+      for caps in STRING_LITERAL_PATTERN.captures_iter(source) {
+        let literal = caps.get(1).or_else(|| caps.get(2)).unwrap().as_str();
+        if let Some(registration) = Self::find_registration(context, literal) {
+          if !literals.iter().any(|(existing, _)| existing == literal) {
+            literals.push((literal.to_string(), Self::binding_name(registration)));
+          }
+        }
+      }
+    }
+
+    literals
+  }
+
+  /// Tags the template renders the component itself with, by its `name` option
+  fn self_reference_tags(context: &TransformationContext) -> Vec<String> {
+    let (Some(name), Some(template)) = (
+      &context.script_state.component_name,
+      &context.sfc_sections.template_content,
+    ) else {
+      return Vec::new();
+    };
+    // A registered component of the same name shadows the component itself
+    if Self::find_registration(context, name).is_some() {
+      return Vec::new();
+    }
+
+    component_tag_names(name)
+      .into_iter()
+      .filter(|tag| {
+        Regex::new(&format!(r"<{}[\s/>]", regex::escape(tag)))
+          .is_ok_and(|pattern| pattern.is_match(template))
+      })
+      .collect()
+  }
+
+  /// Keep a recursive component able to render itself
+  fn transform_self_references(
+    context: &TransformationContext,
+    config: &TransformerConfig,
+    result: &mut TransformationResult,
+  ) {
+    let tags = Self::self_reference_tags(context);
+    let Some(name) = &context.script_state.component_name else {
+      return;
+    };
+    if tags.is_empty() {
+      return;
+    }
+
+    let file_tags = config
+      .file_name
+      .as_deref()
+      .and_then(|file_name| Path::new(file_name).file_stem())
+      .map(|stem| component_tag_names(&stem.to_string_lossy()));
+    match (config.self_reference, file_tags) {
+      (SelfReferenceStyle::FileName, Some(file_tags)) => {
+        // `<TreeItem>` and `<tree-item>` both refer to `TreeItem.vue` already
+        for tag in tags.iter().filter(|tag| !file_tags.contains(tag)) {
+          for end in [" ", ">", "/", "\n", "\t", "\r"] {
+            result.template_replacements.push(TemplateReplacement {
+              find: format!("<{}{}", tag, end),
+              replace: format!("<{}{}", file_tags[0], end),
+              scope: ReplacementScope::Template,
+            });
+          }
+          result.template_replacements.push(TemplateReplacement {
+            find: format!("</{}>", tag),
+            replace: format!("</{}>", file_tags[0]),
+            scope: ReplacementScope::Template,
+          });
+        }
+      }
+      (SelfReferenceStyle::FileName, None) => {
+        result.define_options.push(format!("name: '{}'", name));
+        result.diagnostics.push(Diagnostic::info(
+          "self-reference-define-options",
+          format!(
+            "The component renders itself as `{}` but its file name is unknown, the name was kept with `defineOptions()`",
+            tags[0]
+          ),
+        ));
+      }
+      (SelfReferenceStyle::DefineOptions, _) => {
+        result.define_options.push(format!("name: '{}'", name));
+      }
+    }
+  }
+
+  /// The `name` and `components` options of a component keeping its options around `setup()`
+  fn keep_component_options(
+    context: &TransformationContext,
+    config: &TransformerConfig,
+    result: &mut TransformationResult,
+  ) {
+    if let Some(name) = &context.script_state.component_name {
+      result.define_options.push(format!("name: '{}'", name));
+    }
+    if context.script_state.components.is_empty() {
+      return;
+    }
+
+    let mut entries = Vec::new();
+    for registration in &context.script_state.components {
+      let key = match IDENTIFIER_PATTERN.is_match(&registration.name) {
+        true => registration.name.clone(),
+        false => format!("'{}'", registration.name),
+      };
+      if let Some(import) = Self::async_import(registration, config) {
+        result.add_import("vue", "defineAsyncComponent");
+        entries.push(format!("{}: defineAsyncComponent(() => {})", key, import));
+      } else if let Some(path) = Self::required_path(registration, config) {
+        let binding = Self::binding_name(registration);
+        result.add_default_import(&path, &binding);
+        entries.push(match key == binding {
+          true => key,
+          false => format!("{}: {}", key, binding),
+        });
+      } else if key == registration.value {
+        entries.push(key);
+      } else {
+        entries.push(format!("{}: {}", key, registration.value));
+      }
+    }
+    result
+      .define_options
+      .push(format!("components: {{ {} }}", entries.join(", ")));
+  }
+
+  /// Identifiers bound to `:is` that select a component by name
+  fn dynamic_is_bindings(context: &TransformationContext) -> Vec<String> {
+    let Some(template) = &context.sfc_sections.template_content else {
+      return Vec::new();
+    };
+
+    let mut bindings: Vec<String> = Vec::new();
+    for caps in DYNAMIC_IS_PATTERN.captures_iter(template) {
+      let name = caps[1].to_string();
+      // `:is="TabHome"` already refers to the component itself
+      let is_component = context
+        .script_state
+        .components
+        .iter()
+        .any(|registration| Self::binding_name(registration) == name);
+
+      if !is_component && !bindings.contains(&name) {
+        bindings.push(name);
+      }
+    }
+
+    bindings
+  }
+}
+
+impl Transformer for ComponentsTransformer {
+  fn name(&self) -> &'static str {
+    "components"
+  }
+
+  fn should_transform(&self, context: &TransformationContext, config: &TransformerConfig) -> bool {
+    !context.script_state.components.is_empty()
+      || !Self::self_reference_tags(context).is_empty()
+      || (config.script_style == ScriptStyle::SetupFunction
+        && context.script_state.component_name.is_some())
+  }
+
+  fn transform(
+    &self,
+    context: &TransformationContext,
+    config: &TransformerConfig,
+  ) -> TransformationResult {
+    let mut result = TransformationResult::new();
+    if config.script_style == ScriptStyle::SetupFunction {
+      Self::keep_component_options(context, config, &mut result);
+      return result;
+    }
+    Self::transform_self_references(context, config, &mut result);
+
+    for registration in &context.script_state.components {
+      let binding = Self::binding_name(registration);
+
+      if let Some(import) = Self::async_import(registration, config) {
+        result.add_import("vue", "defineAsyncComponent");
+        result.setup.push(format!(
+          "const {} = defineAsyncComponent(() => {});",
+          binding, import
+        ));
+      } else if let Some(path) = Self::required_path(registration, config) {
+        result.add_default_import(&path, &binding);
+      } else if binding != registration.value {
+        result
+          .setup
+          .push(format!("const {} = {};", binding, registration.value));
+      }
+    }
+
+    let Some(template) = &context.sfc_sections.template_content else {
+      return result;
+    };
+
+    for caps in STATIC_IS_PATTERN.captures_iter(template) {
+      let Some(registration) = Self::find_registration(context, &caps[2]) else {
+        continue;
+      };
+
+      let find = caps[0].to_string();
+      if !result.template_replacements.iter().any(|r| r.find == find) {
+        result.template_replacements.push(TemplateReplacement {
+          replace: format!("{}:is=\"{}\"", &caps[1], Self::binding_name(registration)),
+          find,
+          scope: ReplacementScope::Template,
+        });
+      }
+    }
+
+    let literals = Self::component_name_literals(context);
+    let bindings = Self::dynamic_is_bindings(context);
+    if literals.is_empty() || bindings.is_empty() {
+      return result;
+    }
+
+    // Every registered component, plus the other spellings the script selects them by
+    let mut entries: Vec<String> = context
+      .script_state
+      .components
+      .iter()
+      .map(Self::binding_name)
+      .collect();
+    for (literal, binding) in &literals {
+      if literal != binding {
+        entries.push(format!("'{}': {}", literal, binding));
+      }
+    }
+
+    result.setup.push(format!(
+      "const {} = {{ {} }};",
+      DYNAMIC_COMPONENTS_NAME,
+      entries.join(", ")
+    ));
+
+    // Also covers `v-bind:is`
+    for name in bindings {
+      result.template_replacements.push(TemplateReplacement {
+        find: format!(":is=\"{}\"", name),
+        replace: format!(":is=\"{}[{}]\"", DYNAMIC_COMPONENTS_NAME, name),
+        scope: ReplacementScope::Template,
+      });
+    }
+    result
+      .resolved_identifiers
+      .push(DYNAMIC_COMPONENTS_NAME.to_string());
+
+    result
+  }
+}
@@ -1,12 +1,26 @@
 use super::Transformer;
+use super::body_transforms::is_written;
+use super::emit::{EmitTransformer, ModelBinding};
+use super::i18n::I18N_FUNCTIONS;
+use super::typescript;
+use super::vue2::Vue2Transformer;
+use super::vuex::VuexTransformer;
 use super::TransformerOrchestrator;
-use crate::{TemplateReplacement, TransformationContext, TransformationResult, TransformerConfig};
-use std::collections::HashMap;
+use crate::{
+  Diagnostic, ImportItem, PropInfo, ReplacementScope, SymbolKind, TemplateReplacement,
+  TransformationContext, TransformationResult, TransformerConfig,
+};
+use std::collections::BTreeMap;
 use lazy_static::lazy_static;
 use regex::Regex;
 
 lazy_static! {
     static ref ASYNC_COMPONENT_DETECTION_PATTERN: Regex = Regex::new(r"const\s+\w+\s*=\s*\(\s*\)\s*=>\s*import\s*\(").unwrap();
+    static ref PROP_TYPE_IMPORT_PATTERN: Regex = Regex::new(r"\bimport\s[^;]*\bPropType\b").unwrap();
+    static ref THIS_PATTERN: Regex = Regex::new(r"\bthis\b").unwrap();
+    static ref THIS_MEMBER_PATTERN: Regex = Regex::new(r"(^|[^\w$.])this\.(\$?[\w$]+)").unwrap();
+    static ref FUNCTION_PARAMETERS_PATTERN: Regex =
+      Regex::new(r"^(\s*(?:async\s+)?(?:function\s*[\w$]*\s*)?)\(([^)]*)\)").unwrap();
     static ref ASYNC_COMPONENT_TRANSFORM_PATTERN: Regex = Regex::new(r"(?s)const\s+(\w+)\s*=\s*\(\s*\)\s*=>\s*import\s*\(([^)]+)\)").unwrap();
 }
 
@@ -93,16 +107,66 @@ impl CompositionTransformer {
   }
 
   /// Generate Vue imports for data properties, computed properties, and watchers
-  fn generate_vue_imports(&self, context: &TransformationContext) -> Vec<String> {
+  fn generate_vue_imports(
+    &self,
+    context: &TransformationContext,
+    config: &TransformerConfig,
+  ) -> Vec<String> {
     let mut vue_imports = Vec::new();
 
-    // Collect needed Vue imports
-    if !context.script_state.data_properties.is_empty() {
-      vue_imports.push("ref".to_string());
+    // Collect needed Vue imports, PropType only when the component does not import it yet
+    let imports_prop_type = context
+      .sfc_sections
+      .script_content
+      .as_ref()
+      .is_some_and(|script| PROP_TYPE_IMPORT_PATTERN.is_match(script));
+    // Type-based props consume the `PropType` casts, unless validators keep the runtime declaration
+    let typed_props = config.type_based_macros()
+      && context
+        .script_state
+        .props
+        .iter()
+        .all(|prop| prop.validator.is_none());
+    if !imports_prop_type
+      && !typed_props
+      && context
+        .script_state
+        .props
+        .iter()
+        .any(|prop| prop.prop_type.as_ref().is_some_and(|t| t.contains("PropType<")))
+    {
+      vue_imports.push("PropType".to_string());
+    }
+
+    // Data made observable with `Vue.observable()` is declared with `reactive()`
+    for data_prop in &context.script_state.data_properties {
+      let import = match Vue2Transformer::observable_data(context, &data_prop.name) {
+        Some(_) => "reactive".to_string(),
+        None => "ref".to_string(),
+      };
+      if !vue_imports.contains(&import) {
+        vue_imports.push(import);
+      }
+      if let Some(value) = &data_prop.value {
+        vue_imports.extend(
+          Vue2Transformer::global_api_imports(value, config.target)
+            .into_iter()
+            .map(str::to_string),
+        );
+      }
     }
 
-    if !context.script_state.computed_details.is_empty()
-      || !context.script_state.computed_properties.is_empty()
+    let model_computed = EmitTransformer::model_binding(context, config).map(|model| model.computed);
+    if context
+      .script_state
+      .computed_details
+      .iter()
+      .any(|computed| Some(&computed.name) != model_computed.as_ref())
+      || context
+        .script_state
+        .computed_properties
+        .iter()
+        .any(|computed| Some(computed) != model_computed.as_ref())
     {
       vue_imports.push("computed".to_string());
     }
@@ -116,19 +180,68 @@ impl CompositionTransformer {
       vue_imports.push("defineAsyncComponent".to_string());
     }
 
+    // Vue.observable() / Vue.nextTick() at module level are converted in the setup content
+    if let Some(setup_content) = &context.script_state.setup_content {
+      vue_imports.extend(
+        Vue2Transformer::global_api_imports(setup_content, config.target)
+          .into_iter()
+          .map(str::to_string),
+      );
+    }
+
     // Return Vue imports as vector for adding to result
     vue_imports
   }
 
   /// Generate setup code for props using defineProps
-  fn generate_props_definition(&self, context: &TransformationContext) -> Vec<String> {
+  fn generate_props_definition(
+    &self,
+    context: &TransformationContext,
+    config: &TransformerConfig,
+  ) -> Vec<String> {
     let mut setup_code = Vec::new();
-
-    if !context.script_state.props.is_empty() {
+    let model_prop = EmitTransformer::renamed_model_prop(context, config);
+    let model = EmitTransformer::model_binding(context, config);
+    let props: Vec<&PropInfo> = context
+      .script_state
+      .props
+      .iter()
+      .filter(|prop| model.as_ref().is_none_or(|model| model.prop != prop.name))
+      .collect();
+
+    let is_array_syntax = props.iter().all(|prop| {
+      prop.prop_type.is_none()
+        && prop.required.is_none()
+        && prop.default_value.is_none()
+        && prop.validator.is_none()
+    });
+
+    let has_validator = props.iter().any(|prop| prop.validator.is_some());
+    if config.type_based_macros() && !props.is_empty() && !has_validator {
+      setup_code = Self::generate_typed_props_definition(context, &props, model_prop.as_deref());
+    } else if !props.is_empty() && is_array_syntax {
+      // Props without options keep the array shorthand: defineProps(['title', 'value'])
+      let names: Vec<String> = props
+        .iter()
+        .map(|prop| match &model_prop {
+          Some(model_prop) if prop.name == "value" => format!("'{}'", model_prop),
+          _ => format!("'{}'", prop.name),
+        })
+        .collect();
+      setup_code.push(format!("const props = defineProps([{}]);", names.join(", ")));
+      setup_code.push("".to_string()); // Empty line for readability
+    } else if !props.is_empty() {
       setup_code.push("const props = defineProps({".to_string());
 
-      for prop in &context.script_state.props {
-        setup_code.push(format!("  {}: {{", prop.name));
+      for prop in &props {
+        let name = match &model_prop {
+          Some(model_prop) if prop.name == "value" => model_prop,
+          _ => &prop.name,
+        };
+        if let Some(comment) = &prop.comment {
+          setup_code.extend(comment.lines().map(|line| format!("  {}", line)));
+        }
+        setup_code.push(format!("  {}: {{", name));
 
         if let Some(prop_type) = &prop.prop_type {
           setup_code.push(format!("    type: {},", prop_type));
@@ -139,7 +252,14 @@ impl CompositionTransformer {
         }
 
         if let Some(default_value) = &prop.default_value {
-          setup_code.push(format!("    default: {},", default_value));
+          let default_value = Self::rewrite_prop_function(default_value, context, 0);
+          let default_value = Self::wrap_prop_default(&default_value, prop.prop_type.as_deref());
+          setup_code.push(Self::format_prop_option("default", &default_value));
+        }
+
+        if let Some(validator) = &prop.validator {
+          let validator = Self::rewrite_prop_function(validator, context, 1);
+          setup_code.push(Self::format_prop_option("validator", &validator));
         }
 
         setup_code.push("  },".to_string());
@@ -149,25 +269,474 @@ impl CompositionTransformer {
       setup_code.push("".to_string()); // Empty line for readability
     }
 
+    if let Some(model) = &model {
+      setup_code.extend(Self::generate_define_model(model, context, config));
+    }
+
+    setup_code
+  }
+
+  /// Generate `defineProps<{ ... }>()` with the prop types inferred from the runtime types, the
+  /// defaults passed to `withDefaults()`
+  fn generate_typed_props_definition(
+    context: &TransformationContext,
+    props: &[&PropInfo],
+    model_prop: Option<&str>,
+  ) -> Vec<String> {
+    let mut setup_code = Vec::new();
+    let mut defaults = Vec::new();
+    let no_members = |_: &str| None;
+
+    setup_code.push("defineProps<{".to_string());
+    for prop in props {
+      let name = match model_prop {
+        Some(model_prop) if prop.name == "value" => model_prop,
+        _ => &prop.name,
+      };
+      if let Some(comment) = &prop.comment {
+        setup_code.extend(comment.lines().map(|line| format!("  {}", line)));
+      }
+      let optional = if prop.required == Some(true) { "" } else { "?" };
+      setup_code.push(format!(
+        "  {}{}: {};",
+        name,
+        optional,
+        typescript::prop_type(prop, &no_members)
+      ));
+
+      if let Some(default_value) = &prop.default_value {
+        let default_value = Self::rewrite_prop_function(default_value, context, 0);
+        let default_value = Self::wrap_prop_default(&default_value, prop.prop_type.as_deref());
+        // Prop options are indented for the runtime declaration, defaults sit one level higher
+        let option = Self::format_prop_option(name, &default_value);
+        defaults.push(
+          option
+            .lines()
+            .map(|line| line.strip_prefix("  ").unwrap_or(line))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        );
+      }
+    }
+
+    if defaults.is_empty() {
+      setup_code[0] = "const props = defineProps<{".to_string();
+      setup_code.push("}>();".to_string());
+    } else {
+      setup_code[0] = "const props = withDefaults(defineProps<{".to_string();
+      setup_code.push("}>(), {".to_string());
+      setup_code.extend(defaults);
+      setup_code.push("});".to_string());
+    }
+    setup_code.push("".to_string()); // Empty line for readability
+
+    setup_code
+  }
+
+  /// Generate `defineModel()` for a `v-model` passthrough computed, named after the computed and
+  /// declared with the options of the prop it replaces
+  fn generate_define_model(
+    model: &ModelBinding,
+    context: &TransformationContext,
+    config: &TransformerConfig,
+  ) -> Vec<String> {
+    let mut setup_code = Vec::new();
+    let Some(prop) = context
+      .script_state
+      .props
+      .iter()
+      .find(|prop| prop.name == model.prop)
+    else {
+      return setup_code;
+    };
+
+    let mut options = Vec::new();
+    let mut type_argument = String::new();
+    if config.typescript {
+      type_argument = format!("<{}>", typescript::prop_type(prop, &|_| None));
+    } else if let Some(prop_type) = &prop.prop_type {
+      options.push(format!("type: {}", prop_type));
+    }
+    if let Some(required) = prop.required {
+      options.push(format!("required: {}", required));
+    }
+    if let Some(default_value) = &prop.default_value {
+      options.push(format!(
+        "default: {}",
+        Self::wrap_prop_default(default_value, prop.prop_type.as_deref())
+      ));
+    }
+
+    let mut arguments = Vec::new();
+    if model.name != "modelValue" {
+      arguments.push(format!("'{}'", model.name));
+    }
+    if !options.is_empty() {
+      arguments.push(format!("{{ {} }}", options.join(", ")));
+    }
+
+    if let Some(computed) = context
+      .script_state
+      .computed_details
+      .iter()
+      .find(|computed| computed.name == model.computed)
+    {
+      if let Some(comment) = &computed.comment {
+        setup_code.extend(comment.lines().map(str::to_string));
+      }
+    }
+    setup_code.push(format!(
+      "const {} = defineModel{}({});",
+      model.computed,
+      type_argument,
+      arguments.join(", ")
+    ));
+    setup_code.push("".to_string()); // Empty line for readability
+
     setup_code
   }
 
+  /// Format a prop option, re-indenting the continuation lines of multi-line functions
+  fn format_prop_option(option: &str, value: &str) -> String {
+    let mut lines = value.lines();
+    let first_line = lines.next().unwrap_or_default();
+    let rest: Vec<&str> = lines.collect();
+    let indent = rest
+      .iter()
+      .filter(|line| !line.trim().is_empty())
+      .map(|line| line.len() - line.trim_start().len())
+      .min()
+      .unwrap_or(0);
+
+    let mut formatted = format!("    {}: {}", option, first_line);
+    for line in rest {
+      formatted.push('\n');
+      if !line.trim().is_empty() {
+        formatted.push_str(&format!("    {}", &line[indent.min(line.len())..]));
+      }
+    }
+    formatted.push(',');
+    formatted
+  }
+
+  /// Wrap object, array and instance defaults in a factory function, as Vue requires for
+  /// non-primitive defaults. Functions and defaults of `Function` props are kept as-is.
+  fn wrap_prop_default(default_value: &str, prop_type: Option<&str>) -> String {
+    let value = default_value.trim();
+    if prop_type == Some("Function") {
+      return default_value.to_string();
+    }
+
+    if value.starts_with('{') {
+      format!("() => ({})", value)
+    } else if value.starts_with('[') || value.starts_with("new ") {
+      format!("() => {}", value)
+    } else {
+      default_value.to_string()
+    }
+  }
+
+  /// Rewrite `this` in a prop default or validator, which Vue 3 calls without a component
+  /// instance. Other props are read from the props argument (the first one of defaults, the
+  /// second one of validators) and i18n functions from `useI18n()`, which works in defaults since
+  /// they run during setup. Other members are left for [`Self::prop_this_diagnostics`].
+  fn rewrite_prop_function(
+    function: &str,
+    context: &TransformationContext,
+    props_index: usize,
+  ) -> String {
+    // Name of the props argument, added when the function does not declare it
+    let head = FUNCTION_PARAMETERS_PATTERN.captures(function);
+    let mut parameters: Vec<String> = head
+      .as_ref()
+      .map(|caps| {
+        caps[2]
+          .split(',')
+          .map(|parameter| parameter.trim().to_string())
+          .filter(|parameter| !parameter.is_empty())
+          .collect()
+      })
+      .unwrap_or_default();
+    let declared = parameters.get(props_index).cloned();
+    let props_name = declared.clone().unwrap_or_else(|| "props".to_string());
+
+    let mut uses_props = false;
+    let rewritten = THIS_MEMBER_PATTERN.replace_all(function, |caps: &regex::Captures| {
+      let member = &caps[2];
+      if let Some((_, function)) = I18N_FUNCTIONS.iter().find(|(vue2, _)| *vue2 == member) {
+        format!("{}useI18n().{}", &caps[1], function)
+      } else if head.is_some() && context.script_state.props.iter().any(|prop| prop.name == member)
+      {
+        uses_props = true;
+        format!("{}{}.{}", &caps[1], props_name, member)
+      } else {
+        caps[0].to_string()
+      }
+    });
+
+    match head {
+      Some(caps) if uses_props && declared.is_none() => {
+        while parameters.len() < props_index {
+          parameters.push("_".repeat(parameters.len() + 1));
+        }
+        parameters.push(props_name);
+        format!(
+          "{}({}){}",
+          &caps[1],
+          parameters.join(", "),
+          &rewritten[caps[0].len()..]
+        )
+      }
+      _ => rewritten.to_string(),
+    }
+  }
+
+  /// Whether a prop default or validator calls i18n functions through `this`
+  fn prop_functions_use_i18n(context: &TransformationContext) -> bool {
+    context.script_state.props.iter().any(|prop| {
+      [&prop.default_value, &prop.validator]
+        .into_iter()
+        .flatten()
+        .any(|function| {
+          THIS_MEMBER_PATTERN
+            .captures_iter(function)
+            .any(|caps| I18N_FUNCTIONS.iter().any(|(vue2, _)| *vue2 == &caps[2]))
+        })
+    })
+  }
+
+  /// Diagnostics for prop defaults and validators referencing `this` in a way that can not be
+  /// rewritten, Vue 3 does not bind it
+  fn prop_this_diagnostics(&self, context: &TransformationContext) -> Vec<Diagnostic> {
+    context
+      .script_state
+      .props
+      .iter()
+      .flat_map(|prop| {
+        [("default", &prop.default_value, 0), ("validator", &prop.validator, 1)]
+          .into_iter()
+          .filter_map(|(option, function, props_index)| {
+            let function = function.as_ref()?;
+            let rewritten = Self::rewrite_prop_function(function, context, props_index);
+            THIS_PATTERN.is_match(&rewritten).then(|| {
+              let members = THIS_MEMBER_PATTERN
+                .captures_iter(&rewritten)
+                .map(|caps| format!("`this.{}`", &caps[2]))
+                .collect::<Vec<_>>();
+              (option, members)
+            })
+          })
+          .map(|(option, members)| {
+            let references = match members.is_empty() {
+              true => "`this`".to_string(),
+              false => members.join(", "),
+            };
+            Diagnostic::warning(
+              "prop-default-this",
+              format!(
+                "The {} of prop `{}` references {}, which is not available in Vue 3. Only other props (read from the props argument) and i18n functions are rewritten, move the rest to a constant or the parent",
+                option, prop.name, references
+              ),
+            )
+          })
+          .collect::<Vec<_>>()
+      })
+      .collect()
+  }
+
+  /// Diagnostic for `data` declared as a plain object, whose state Vue 2 shared between instances
+  fn data_object_diagnostics(&self, context: &TransformationContext) -> Vec<Diagnostic> {
+    if !context.script_state.data_object {
+      return Vec::new();
+    }
+
+    vec![Diagnostic::warning(
+      "data-object",
+      "`data` is declared as an object, its properties were converted to refs created per instance. Move them to a module-level ref or a store if the state was meant to be shared",
+    )]
+  }
+
+  /// Diagnostic for spreads of the data object the parser could not expand into properties
+  fn data_spread_diagnostics(&self, context: &TransformationContext) -> Vec<Diagnostic> {
+    let spreads = &context.script_state.data_spreads;
+    if spreads.is_empty() {
+      return Vec::new();
+    }
+
+    vec![Diagnostic::warning(
+      "data-spread",
+      format!(
+        "Spreads in `data` ({}) are not objects declared with a module-level `const` and were dropped, declare their properties in `data` or move them to a `reactive()` object",
+        spreads
+          .iter()
+          .map(|spread| format!("`{}`", spread))
+          .collect::<Vec<_>>()
+          .join(", ")
+      ),
+    )]
+  }
+
+  /// Diagnostics for names declared in several options, `this.x` resolves to the member Vue 2
+  /// proxies first
+  fn symbol_collision_diagnostics(&self, context: &TransformationContext) -> Vec<Diagnostic> {
+    context
+      .script_state
+      .symbol_collisions()
+      .into_iter()
+      .map(|(name, kinds)| {
+        let options = kinds
+          .iter()
+          .map(|kind| match kind {
+            SymbolKind::Prop => "props",
+            SymbolKind::Data => "data",
+            SymbolKind::Method => "methods",
+            SymbolKind::Computed => "computed",
+          })
+          .collect::<Vec<_>>();
+        Diagnostic::warning(
+          "symbol-collision",
+          format!(
+            "`{}` is declared in {}, `this.{}` was converted as the {} member. Rename the others, they would redeclare the same constant in <script setup>",
+            name,
+            options.join(" and "),
+            name,
+            options[0]
+          ),
+        )
+      })
+      .collect()
+  }
+
+  /// Diagnostics for members renamed to valid identifiers and members with computed keys, which
+  /// are not converted
+  fn member_key_diagnostics(&self, context: &TransformationContext) -> Vec<Diagnostic> {
+    let renamed = context
+      .script_state
+      .renamed_members
+      .iter()
+      .map(|(key, name)| {
+        Diagnostic::info(
+          "member-renamed",
+          format!(
+            "`{}` is not a valid variable name and was renamed to `{}`, update references from other components (e.g. through template refs)",
+            key, name
+          ),
+        )
+      });
+    let computed_keys = context
+      .script_state
+      .computed_key_members
+      .iter()
+      .map(|(option, key)| {
+        Diagnostic::warning(
+          "member-computed-key",
+          format!(
+            "The member `{}` of `{}` has a computed key and was not converted, declare it with a static name",
+            key, option
+          ),
+        )
+      });
+    renamed.chain(computed_keys).collect()
+  }
+
+  /// Diagnostics for writes to computed properties without setter (`this.fullName = x`), which
+  /// Vue 3 rejects at runtime. The writes are marked with a FIXME by the body transforms.
+  fn computed_write_diagnostics(&self, context: &TransformationContext) -> Vec<Diagnostic> {
+    let state = &context.script_state;
+    let members = state
+      .method_details
+      .iter()
+      .map(|method| (format!("`{}`", method.name), method.body.as_str()))
+      .chain(state.watchers.iter().map(|watcher| {
+        (
+          format!("The `{}` watcher", watcher.watched_property),
+          watcher.handler_body.as_str(),
+        )
+      }))
+      .chain(state.computed_details.iter().flat_map(|computed| {
+        [&computed.getter, &computed.setter]
+          .into_iter()
+          .flatten()
+          .map(|body| (format!("`{}`", computed.name), body.as_str()))
+      }))
+      .collect::<Vec<_>>();
+
+    let mut diagnostics = Vec::new();
+    for computed in state
+      .computed_details
+      .iter()
+      .filter(|computed| computed.setter.is_none())
+    {
+      let Ok(access) = Regex::new(&format!(
+        r"(?:^|[^\w$.]|\.\.\.)(this\.{})\b",
+        regex::escape(&computed.name)
+      )) else {
+        continue;
+      };
+
+      for (member, body) in &members {
+        let written = access.captures_iter(body).any(|caps| {
+          caps
+            .get(1)
+            .is_some_and(|found| is_written(body, found.start(), found.end()))
+        });
+        if written {
+          diagnostics.push(Diagnostic::warning(
+            "computed-write",
+            format!(
+              "{} assigns `this.{}`, but the computed property has no setter and Vue 3 rejects the write at runtime. Add a setter or turn it into a ref",
+              member, computed.name
+            ),
+          ));
+        }
+      }
+    }
+
+    diagnostics
+  }
+
   /// Generate setup code for data properties as refs
   fn generate_data_refs(
     &self,
     context: &TransformationContext,
     config: &TransformerConfig,
-  ) -> HashMap<String, (String, u8)> {
-    use std::collections::HashMap;
-    let mut data_refs = HashMap::new();
+  ) -> BTreeMap<String, (String, u8)> {
+    let mut data_refs = BTreeMap::new();
+    let body_transformer = TransformerOrchestrator::get_body_transformer();
 
     for data_prop in &context.script_state.data_properties {
       let initial_value = data_prop.value.as_deref().unwrap_or("undefined");
 
+      // `Vue.observable()` data is already reactive and is not wrapped in a ref
+      if let Some(object) = Vue2Transformer::observable_data(context, &data_prop.name) {
+        let transformed_object = body_transformer(
+          &Vue2Transformer::transform_global_api(object, config.target),
+          context,
+          config,
+        );
+        let declaration = format!("const {} = reactive({});", data_prop.name, transformed_object);
+        data_refs.insert(data_prop.name.clone(), (declaration, 0));
+        continue;
+      }
+
       // Apply transformations to the initial value
-      let transformed_value = self.transform_data_value(initial_value, context, config);
+      let transformed_value = body_transformer(
+        &Vue2Transformer::transform_global_api(initial_value, config.target),
+        context,
+        config,
+      );
 
-      let ref_declaration = format!("const {} = ref({});", data_prop.name, transformed_value);
+      // `ref(null)` or `ref([])` would be typed too narrowly to assign anything to
+      let type_argument = match config.typescript {
+        true => typescript::ref_type(initial_value)
+          .map(|ts_type| format!("<{}>", ts_type))
+          .unwrap_or_default(),
+        false => String::new(),
+      };
+      let ref_declaration = format!(
+        "const {} = ref{}({});",
+        data_prop.name, type_argument, transformed_value
+      );
 
       // Use priority 0 for default data() refs (can be overridden by other transformers)
       data_refs.insert(data_prop.name.clone(), (ref_declaration, 0));
@@ -176,18 +745,6 @@ impl CompositionTransformer {
     data_refs
   }
 
-  /// Transform data property initial values
-  fn transform_data_value(
-    &self,
-    value: &str,
-    context: &TransformationContext,
-    config: &TransformerConfig,
-  ) -> String {
-    // Use the orchestrator's body transformer which applies all available transforms
-    let body_transformer = TransformerOrchestrator::get_body_transformer();
-    body_transformer(value, context, config)
-  }
-
   /// Generate setup code for computed properties
   fn generate_computed_properties(
     &self,
@@ -197,7 +754,16 @@ impl CompositionTransformer {
     let mut setup_code = Vec::new();
 
     if !context.script_state.computed_details.is_empty() {
+      let model = EmitTransformer::model_binding(context, config);
+      let body_transformer = TransformerOrchestrator::get_body_transformer();
       for computed_detail in &context.script_state.computed_details {
+        // The passthrough computed of a model becomes the `defineModel()` ref
+        if model.as_ref().is_some_and(|model| model.computed == computed_detail.name) {
+          continue;
+        }
+        if let Some(comment) = &computed_detail.comment {
+          setup_code.extend(comment.lines().map(str::to_string));
+        }
         // Generate computed property based on whether it has getter/setter
         if let (Some(getter), Some(setter)) = (&computed_detail.getter, &computed_detail.setter) {
           // Computed with getter and setter
@@ -205,7 +771,7 @@ impl CompositionTransformer {
           setup_code.push("  get() {".to_string());
 
           // Transform the getter body
-          let transformed_getter = self.transform_computed_body(getter, context, config);
+          let transformed_getter = body_transformer(getter, context, config);
           for line in transformed_getter.lines() {
             if !line.trim().is_empty() {
               setup_code.push(format!("    {}", line));
@@ -219,7 +785,7 @@ impl CompositionTransformer {
           setup_code.push(format!("  set({}) {{", setter_param));
 
           // Transform the setter body
-          let transformed_setter = self.transform_computed_body(setter, context, config);
+          let transformed_setter = body_transformer(setter, context, config);
           for line in transformed_setter.lines() {
             if !line.trim().is_empty() {
               setup_code.push(format!("    {}", line));
@@ -236,7 +802,7 @@ impl CompositionTransformer {
           ));
 
           // Transform the getter body
-          let transformed_getter = self.transform_computed_body(getter, context, config);
+          let transformed_getter = body_transformer(getter, context, config);
           for line in transformed_getter.lines() {
             if !line.trim().is_empty() {
               setup_code.push(format!("  {}", line));
@@ -264,18 +830,6 @@ impl CompositionTransformer {
     setup_code
   }
 
-  /// Transform computed property body by applying other transformers
-  fn transform_computed_body(
-    &self,
-    body: &str,
-    context: &TransformationContext,
-    config: &TransformerConfig,
-  ) -> String {
-    // Use the orchestrator's body transformer which applies all available transforms
-    let body_transformer = TransformerOrchestrator::get_body_transformer();
-    body_transformer(body, context, config)
-  }
-
   /// Generate setup code for methods
   fn generate_methods(
     &self,
@@ -286,6 +840,7 @@ impl CompositionTransformer {
 
     // Use method_details if available, otherwise fall back to method names
     if !context.script_state.method_details.is_empty() {
+      let body_transformer = TransformerOrchestrator::get_body_transformer();
       for method_detail in &context.script_state.method_details {
         // Skip lifecycle methods that are handled by other transformers (like Vue2 transformer)
         if matches!(
@@ -307,11 +862,18 @@ impl CompositionTransformer {
         }
 
         let async_part = if method_detail.is_async { "async " } else { "" };
-        let params_str = method_detail.parameters.join(", ");
+        let mut params_str = method_detail.parameters.join(", ");
+        // Default values may read other members (`page = this.page`)
+        if params_str.contains("this.") {
+          params_str = body_transformer(&params_str, context, config);
+        }
 
         // Transform the method body using other transformers
-        let transformed_body = self.transform_method_body(&method_detail.body, context, config);
+        let transformed_body = body_transformer(&method_detail.body, context, config);
 
+        if let Some(comment) = &method_detail.comment {
+          setup_code.extend(comment.lines().map(str::to_string));
+        }
         setup_code.push(format!(
           "const {} = {}({}) => {{",
           method_detail.name, async_part, params_str
@@ -346,23 +908,26 @@ impl CompositionTransformer {
     setup_code
   }
 
-  /// Transform method body by applying other transformers
-  fn transform_method_body(
-    &self,
-    body: &str,
-    context: &TransformationContext,
-    config: &TransformerConfig,
-  ) -> String {
-    // Use the orchestrator's body transformer which applies all available transforms
-    let body_transformer = TransformerOrchestrator::get_body_transformer();
-    body_transformer(body, context, config)
-  }
-
   /// Generate existing imports that are not handled by other transformers
   fn generate_existing_imports(&self, context: &TransformationContext) -> Vec<String> {
     // Generate imports from parsed import information, but only for simple imports
     // that don't have special handling (no mixins, no bootstrap-vue, etc.)
     let mut imports = Vec::new();
+    // Bindings redeclared by a component member are imported under another name
+    let shadowed = context.script_state.shadowed_imports();
+    let local_name = |binding: &String| {
+      shadowed
+        .iter()
+        .find(|(name, _)| name == binding)
+        .map_or(binding.clone(), |(_, alias)| alias.clone())
+    };
+    let named_import = |item: &ImportItem| {
+      let binding = local_name(item.alias.as_ref().unwrap_or(&item.name));
+      match binding == item.name {
+        true => binding,
+        false => format!("{} as {}", item.name, binding),
+      }
+    };
 
     for import_info in &context.script_state.imports {
       // Skip imports that are likely handled by other transformers
@@ -374,18 +939,22 @@ impl CompositionTransformer {
         continue;
       }
 
+      // Side-effect imports (e.g. stylesheets) are kept as-is
+      if import_info.imports.is_empty() {
+        imports.push(format!("import '{}';", import_info.source));
+        continue;
+      }
+
       // Generate simple imports from relative paths or library imports
       let mut import_parts = Vec::new();
 
       for import_item in &import_info.imports {
         if import_item.is_default {
-          import_parts.push(import_item.name.clone());
+          import_parts.push(local_name(&import_item.name));
         } else if import_item.is_namespace {
-          import_parts.push(format!("* as {}", import_item.name));
-        } else if let Some(alias) = &import_item.alias {
-          import_parts.push(format!("{} as {}", import_item.name, alias));
+          import_parts.push(format!("* as {}", local_name(&import_item.name)));
         } else {
-          import_parts.push(import_item.name.clone());
+          import_parts.push(named_import(import_item));
         }
       }
 
@@ -417,19 +986,13 @@ impl CompositionTransformer {
             .imports
             .iter()
             .filter(|item| item.is_default)
-            .map(|item| item.name.clone())
+            .map(|item| local_name(&item.name))
             .collect();
           let named_parts: Vec<String> = import_info
             .imports
             .iter()
             .filter(|item| !item.is_default && !item.is_namespace)
-            .map(|item| {
-              if let Some(alias) = &item.alias {
-                format!("{} as {}", item.name, alias)
-              } else {
-                item.name.clone()
-              }
-            })
+            .map(named_import)
             .collect();
 
           if !default_parts.is_empty() && !named_parts.is_empty() {
@@ -448,7 +1011,10 @@ impl CompositionTransformer {
             )
           }
         };
-        imports.push(import_statement);
+        imports.push(match import_info.is_type_only {
+          true => import_statement.replacen("import ", "import type ", 1),
+          false => import_statement,
+        });
       }
     }
 
@@ -466,10 +1032,15 @@ impl CompositionTransformer {
   }
 
   /// Generate setup content (constants and other code between imports and export)
-  fn generate_setup_content(&self, context: &TransformationContext) -> Vec<String> {
+  fn generate_setup_content(
+    &self,
+    context: &TransformationContext,
+    config: &TransformerConfig,
+  ) -> Vec<String> {
     if let Some(setup_content) = &context.script_state.setup_content {
       // First, transform async components in the entire content to handle multi-line declarations
-      let transformed_content = ASYNC_COMPONENT_TRANSFORM_PATTERN.replace_all(&setup_content, "const $1 = defineAsyncComponent(() => import($2))");
+      let transformed_content = ASYNC_COMPONENT_TRANSFORM_PATTERN.replace_all(setup_content, "const $1 = defineAsyncComponent(() => import($2))");
+      let transformed_content = Vue2Transformer::transform_global_api(&transformed_content, config.target);
       
       // Extract everything except import statements from setup content
       let mut result = Vec::new();
@@ -489,6 +1060,39 @@ impl CompositionTransformer {
     }
   }
 
+  /// Source of the `watch()` call for a watched property or path (`total`, `filters.page`)
+  fn watch_source(&self, watched: &str, context: &TransformationContext) -> String {
+    let (name, path) = match watched.split_once('.') {
+      Some((name, path)) => (name, Some(path)),
+      None => (watched, None),
+    };
+
+    // Store state and getters mapped with mapState and mapGetters become computed refs
+    let kind = context.script_state.symbol_kind(name).or_else(|| {
+      VuexTransformer::mapped_computed_aliases(context)
+        .iter()
+        .any(|alias| alias == name)
+        .then_some(SymbolKind::Computed)
+    });
+
+    match (kind, path) {
+      // Reactive objects are watched deeply, their properties through a getter
+      (Some(SymbolKind::Data), Some(path))
+        if Vue2Transformer::observable_data(context, name).is_some() =>
+      {
+        format!("() => {}.{}", name, path)
+      }
+      // Refs and computed refs are watched directly
+      (Some(SymbolKind::Data | SymbolKind::Computed), None) => name.to_string(),
+      (Some(SymbolKind::Data | SymbolKind::Computed), Some(path)) => {
+        format!("() => {}.value.{}", name, path)
+      }
+      // Props are not refs, they are watched through a getter
+      (Some(SymbolKind::Prop), _) => format!("() => props.{}", watched),
+      _ => watched.to_string(),
+    }
+  }
+
   /// Generate setup code for watchers
   fn generate_watchers(
     &self,
@@ -496,16 +1100,18 @@ impl CompositionTransformer {
     config: &TransformerConfig,
   ) -> Vec<String> {
     let mut setup_code = Vec::new();
+    let body_transformer = TransformerOrchestrator::get_body_transformer();
 
     for watcher in &context.script_state.watchers {
       // Transform the watcher body using other transformers
-      let transformed_body = self.transform_watcher_body(&watcher.handler_body, context, config);
+      let transformed_body = body_transformer(&watcher.handler_body, context, config);
 
       // Generate watch call
       let async_part = if watcher.is_async { "async " } else { "" };
       setup_code.push(format!(
         "watch({}, {}({}, {}) => {{",
-        watcher.watched_property, async_part, watcher.param_names.0, watcher.param_names.1
+        self.watch_source(&watcher.watched_property, context),
+        async_part, watcher.param_names.0, watcher.param_names.1
       ));
 
       // Add the transformed body with proper indentation
@@ -525,18 +1131,6 @@ impl CompositionTransformer {
     setup_code
   }
 
-  /// Transform watcher body by applying other transformers
-  fn transform_watcher_body(
-    &self,
-    body: &str,
-    context: &TransformationContext,
-    config: &TransformerConfig,
-  ) -> String {
-    // Use the orchestrator's body transformer which applies all available transforms
-    let body_transformer = TransformerOrchestrator::get_body_transformer();
-    body_transformer(body, context, config)
-  }
-
   /// Generate return statement for setup function
   fn generate_return_statement(&self, _context: &TransformationContext) -> Vec<String> {
     // In <script setup>, we don't need a return statement
@@ -547,14 +1141,25 @@ impl CompositionTransformer {
   /// Generate template replacements for reactive references
   fn generate_template_replacements(
     &self,
-    _context: &TransformationContext,
+    context: &TransformationContext,
   ) -> Vec<TemplateReplacement> {
-    
-
     // In Composition API, we don't need .value in templates, so no replacements needed
-    // The template syntax remains the same
-
-    Vec::new()
+    // The template syntax remains the same, except for members renamed to valid identifiers
+    // (`this['on-save']()` -> `onSave()`)
+    context
+      .script_state
+      .renamed_members
+      .iter()
+      .flat_map(|(key, name)| {
+        [format!("this['{}']", key), format!("this[\"{}\"]", key)]
+          .into_iter()
+          .map(|find| TemplateReplacement {
+            find,
+            replace: name.clone(),
+            scope: ReplacementScope::Expressions,
+          })
+      })
+      .collect()
   }
 }
 
@@ -593,7 +1198,7 @@ impl Transformer for CompositionTransformer {
     }
 
     // Add Vue imports
-    let vue_imports = self.generate_vue_imports(context);
+    let vue_imports = self.generate_vue_imports(context, config);
     if !vue_imports.is_empty() {
       result.add_imports(
         "vue",
@@ -602,8 +1207,29 @@ impl Transformer for CompositionTransformer {
     }
 
     // Generate setup code - existing content and defineProps
-    result.setup.extend(self.generate_setup_content(context));
-    result.setup.extend(self.generate_props_definition(context));
+    result.setup.extend(self.generate_setup_content(context, config));
+    result.setup.extend(self.generate_props_definition(context, config));
+    result.diagnostics.extend(self.prop_this_diagnostics(context));
+    if config.type_based_macros()
+      && context.script_state.props.iter().any(|prop| prop.validator.is_some())
+    {
+      result.diagnostics.push(Diagnostic::info(
+        "typescript-runtime-props",
+        "Props keep their runtime declaration since a type-based `defineProps` can not declare validators",
+      ));
+    }
+    if Self::prop_functions_use_i18n(context) {
+      result.add_import("vue-i18n", "useI18n");
+    }
+    result.diagnostics.extend(self.data_object_diagnostics(context));
+    result.diagnostics.extend(self.data_spread_diagnostics(context));
+    result.diagnostics.extend(self.member_key_diagnostics(context));
+    result
+      .diagnostics
+      .extend(self.computed_write_diagnostics(context));
+    result
+      .diagnostics
+      .extend(self.symbol_collision_diagnostics(context));
 
     // Add data refs to the reactive_state
     let data_refs = self.generate_data_refs(context, config);
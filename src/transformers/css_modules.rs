@@ -0,0 +1,125 @@
+use super::{BodyTransformFn, Transformer};
+use crate::{TransformationContext, TransformationResult, TransformerConfig};
+use lazy_static::lazy_static;
+use regex::Regex;
+
+lazy_static! {
+  static ref MODULE_ATTRIBUTE_PATTERN: Regex =
+    Regex::new(r#"(?:^|\s)module(?:\s*=\s*["']([^"']+)["'])?(?:\s|$)"#).unwrap();
+}
+
+/// Transformer for CSS Modules (`<style module>`)
+///
+/// This transformer handles:
+/// - Converting script access `this.$style.button` to `style.button` with
+///   `const style = useCssModule();`
+/// - Named modules (`<style module="classes">`): `this.classes.button` to `classes.button` with
+///   `const classes = useCssModule('classes');`
+///
+/// The `module` attribute is kept and template usage (`:class="$style.button"`) is left as-is,
+/// since it still works in `<script setup>`.
+pub struct CssModulesTransformer;
+
+impl Default for CssModulesTransformer {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl CssModulesTransformer {
+  pub fn new() -> Self {
+    Self
+  }
+
+  /// Instance property of the style module (`$style` or the module name) and the setup binding it
+  /// is converted to
+  fn module_binding(context: &TransformationContext) -> Option<(String, String)> {
+    let attributes = context.sfc_sections.style_attributes.as_ref()?;
+    let caps = MODULE_ATTRIBUTE_PATTERN.captures(attributes)?;
+
+    Some(match caps.get(1) {
+      Some(name) => (name.as_str().to_string(), name.as_str().to_string()),
+      None => ("$style".to_string(), "style".to_string()),
+    })
+  }
+
+  fn access_pattern(property: &str) -> Option<Regex> {
+    Regex::new(&format!(r"this\.{}\b", regex::escape(property))).ok()
+  }
+
+  /// Check if script bodies access the style module
+  fn has_script_usage(context: &TransformationContext, property: &str) -> bool {
+    let Some(pattern) = Self::access_pattern(property) else {
+      return false;
+    };
+    let script_state = &context.script_state;
+
+    script_state
+      .method_details
+      .iter()
+      .any(|method| pattern.is_match(&method.body))
+      || script_state.computed_details.iter().any(|computed| {
+        computed
+          .getter
+          .iter()
+          .chain(computed.setter.iter())
+          .any(|body| pattern.is_match(body))
+      })
+      || script_state
+        .watchers
+        .iter()
+        .any(|watcher| pattern.is_match(&watcher.handler_body))
+  }
+}
+
+impl Transformer for CssModulesTransformer {
+  fn name(&self) -> &'static str {
+    "css_modules"
+  }
+
+  fn should_transform(&self, context: &TransformationContext, _config: &TransformerConfig) -> bool {
+    Self::module_binding(context)
+      .is_some_and(|(property, _)| Self::has_script_usage(context, &property))
+  }
+
+  fn transform(
+    &self,
+    context: &TransformationContext,
+    _config: &TransformerConfig,
+  ) -> TransformationResult {
+    let mut result = TransformationResult::new();
+    let Some((property, binding)) = Self::module_binding(context) else {
+      return result;
+    };
+
+    result.add_import("vue", "useCssModule");
+    let argument = if property == "$style" {
+      String::new()
+    } else {
+      format!("'{}'", property)
+    };
+    result
+      .setup
+      .push(format!("const {} = useCssModule({});", binding, argument));
+    result.resolved_identifiers.push(binding);
+
+    result
+  }
+
+  fn get_body_transform(&self) -> Option<Box<BodyTransformFn>> {
+    Some(Box::new(
+      |body: &str, context: &TransformationContext, _config: &TransformerConfig| {
+        let Some((property, binding)) = CssModulesTransformer::module_binding(context) else {
+          return body.to_string();
+        };
+        let Some(pattern) = CssModulesTransformer::access_pattern(&property) else {
+          return body.to_string();
+        };
+
+        pattern
+          .replace_all(body, regex::NoExpand(&binding))
+          .to_string()
+      },
+    ))
+  }
+}
@@ -0,0 +1,248 @@
+use super::{BodyTransformFn, Transformer};
+use crate::{
+  DatePluginConfig, Diagnostic, ReplacementScope, TemplateReplacement, TransformationContext,
+  TransformationResult, TransformerConfig,
+};
+use lazy_static::lazy_static;
+use regex::Regex;
+
+lazy_static! {
+  static ref TEMPLATE_EXPRESSION_PATTERN: Regex =
+    Regex::new(r#"(?s)\{\{(.*?)\}\}|(?:\s:|\sv-bind:)[\w.-]+="([^"]*)""#).unwrap();
+  static ref FILTER_CALL_PATTERN: Regex = Regex::new(r"(?s)^([\w$]+)\s*(?:\((.*)\))?$").unwrap();
+}
+
+/// vue-moment filter arguments that do not describe a format
+const NON_FORMAT_FILTER_ARGUMENTS: [&str; 7] = [
+  "add", "subtract", "from", "calendar", "utc", "timezone", "duration",
+];
+
+/// Transformer for date plugins installed on the prototype (e.g. vue-moment)
+///
+/// This transformer handles:
+/// - Converting `this.$moment(date)` to `dayjs(date)` (or the configured function)
+/// - Converting template usage `$moment(date)` and pipes `{{ date | moment('LL') }}` to
+///   `dayjs(date).format('LL')`
+/// - Adding either a direct import (`import dayjs from 'dayjs';`) or a composable
+///   (`const dayjs = useDate();`)
+pub struct DatePluginTransformer;
+
+impl Default for DatePluginTransformer {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl DatePluginTransformer {
+  pub fn new() -> Self {
+    Self
+  }
+
+  /// Check if script bodies or the template use the configured date plugin
+  fn has_date_plugin_usage(context: &TransformationContext, plugin: &DatePluginConfig) -> bool {
+    let script_state = &context.script_state;
+    let this_access = format!("this.{}", plugin.property);
+
+    let uses_in_script = script_state
+      .method_details
+      .iter()
+      .any(|method| method.body.contains(&this_access))
+      || script_state.computed_details.iter().any(|computed| {
+        computed
+          .getter
+          .iter()
+          .chain(computed.setter.iter())
+          .any(|body| body.contains(&this_access))
+      })
+      || script_state
+        .watchers
+        .iter()
+        .any(|watcher| watcher.handler_body.contains(&this_access));
+
+    uses_in_script
+      || context
+        .sfc_sections
+        .template_content
+        .as_ref()
+        .is_some_and(|template| {
+          template.contains(&format!("{}(", plugin.property)) || {
+            let (replacements, unconverted) = Self::filter_rewrites(template, plugin);
+            !replacements.is_empty() || !unconverted.is_empty()
+          }
+        })
+  }
+
+  /// Split an expression on filter pipes, ignoring `||`
+  fn split_filters(expression: &str) -> Vec<&str> {
+    let bytes = expression.as_bytes();
+    let mut parts = Vec::new();
+    let mut start = 0;
+
+    for (i, byte) in bytes.iter().enumerate() {
+      let is_pipe =
+        *byte == b'|' && bytes.get(i + 1) != Some(&b'|') && (i == 0 || bytes[i - 1] != b'|');
+      if is_pipe {
+        parts.push(&expression[start..i]);
+        start = i + 1;
+      }
+    }
+    parts.push(&expression[start..]);
+
+    parts
+  }
+
+  /// Convert `value | moment('LL')` to `dayjs(value).format('LL')`.
+  ///
+  /// Returns `Ok(None)` when the expression does not use the date filter and `Err` when it does
+  /// but cannot be converted automatically.
+  fn rewrite_filter_expression(
+    expression: &str,
+    plugin: &DatePluginConfig,
+  ) -> Result<Option<String>, String> {
+    let parts = Self::split_filters(expression);
+    if parts.len() < 2 {
+      return Ok(None);
+    }
+
+    let uses_date_filter = parts[1..].iter().any(|part| {
+      FILTER_CALL_PATTERN
+        .captures(part.trim())
+        .is_some_and(|caps| caps[1] == plugin.filter)
+    });
+    if !uses_date_filter {
+      return Ok(None);
+    }
+
+    if parts.len() > 2 {
+      return Err("it is chained with other filters".to_string());
+    }
+
+    let value = parts[0].trim();
+    let caps = FILTER_CALL_PATTERN.captures(parts[1].trim()).unwrap();
+    let args = caps.get(2).map(|args| args.as_str().trim()).unwrap_or("");
+
+    if args.is_empty() {
+      return Ok(Some(format!("{}({})", plugin.name, value)));
+    }
+
+    let first_arg = args.split(',').next().unwrap_or("").trim();
+    let is_string_literal = first_arg.len() >= 2
+      && (first_arg.starts_with('\'') || first_arg.starts_with('"'))
+      && first_arg.ends_with(&first_arg[..1]);
+    let keyword = first_arg.trim_matches(|c| c == '\'' || c == '"');
+
+    if !is_string_literal || args.contains(',') || NON_FORMAT_FILTER_ARGUMENTS.contains(&keyword) {
+      return Err(format!("it uses the arguments `{}`", args));
+    }
+
+    Ok(Some(format!("{}({}).format({})", plugin.name, value, args)))
+  }
+
+  /// Literal template replacements for date filter pipes, plus the pipes that could not be
+  /// converted together with the reason
+  fn filter_rewrites(
+    template: &str,
+    plugin: &DatePluginConfig,
+  ) -> (Vec<TemplateReplacement>, Vec<String>) {
+    let mut replacements: Vec<TemplateReplacement> = Vec::new();
+    let mut unconverted = Vec::new();
+
+    for caps in TEMPLATE_EXPRESSION_PATTERN.captures_iter(template) {
+      let full_match = caps.get(0).unwrap().as_str();
+      let expression = caps.get(1).or_else(|| caps.get(2)).unwrap();
+
+      match Self::rewrite_filter_expression(expression.as_str(), plugin) {
+        Ok(Some(rewritten)) => {
+          let replace = if caps.get(1).is_some() {
+            format!("{{{{ {} }}}}", rewritten)
+          } else {
+            full_match.replace(expression.as_str(), &rewritten)
+          };
+
+          if !replacements.iter().any(|r| r.find == full_match) {
+            replacements.push(TemplateReplacement {
+              find: full_match.to_string(),
+              replace,
+              scope: ReplacementScope::Template,
+            });
+          }
+        }
+        Ok(None) => {}
+        Err(reason) => unconverted.push(format!("`{}` ({})", expression.as_str().trim(), reason)),
+      }
+    }
+
+    (replacements, unconverted)
+  }
+}
+
+impl Transformer for DatePluginTransformer {
+  fn name(&self) -> &'static str {
+    "date_plugin"
+  }
+
+  fn should_transform(&self, context: &TransformationContext, config: &TransformerConfig) -> bool {
+    config
+      .date_plugin
+      .as_ref()
+      .is_some_and(|plugin| Self::has_date_plugin_usage(context, plugin))
+  }
+
+  fn transform(
+    &self,
+    context: &TransformationContext,
+    config: &TransformerConfig,
+  ) -> TransformationResult {
+    let mut result = TransformationResult::new();
+    let Some(plugin) = &config.date_plugin else {
+      return result;
+    };
+
+    match &plugin.composable {
+      Some(composable) => {
+        result.add_import(&plugin.import_path, composable);
+        result
+          .setup
+          .push(format!("const {} = {}();", plugin.name, composable));
+      }
+      None => {
+        result.add_default_import(&plugin.import_path, &plugin.name);
+      }
+    }
+
+    if let Some(template) = &context.sfc_sections.template_content {
+      let (replacements, unconverted) = Self::filter_rewrites(template, plugin);
+      result.template_replacements.extend(replacements);
+
+      for expression in unconverted {
+        result.diagnostics.push(Diagnostic::warning(
+          "date-filter",
+          format!(
+            "Date filter {} was not converted, rewrite it with `{}` manually",
+            expression, plugin.name
+          ),
+        ));
+      }
+    }
+
+    result.template_replacements.push(TemplateReplacement {
+      find: format!("{}(", plugin.property),
+      replace: format!("{}(", plugin.name),
+      scope: ReplacementScope::Expressions,
+    });
+
+    result
+  }
+
+  fn get_body_transform(&self) -> Option<Box<BodyTransformFn>> {
+    Some(Box::new(
+      |body: &str, _context: &TransformationContext, config: &TransformerConfig| {
+        let Some(plugin) = &config.date_plugin else {
+          return body.to_string();
+        };
+
+        body.replace(&format!("this.{}", plugin.property), &plugin.name)
+      },
+    ))
+  }
+}
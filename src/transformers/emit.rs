@@ -1,11 +1,39 @@
 use super::{BodyTransformFn, Transformer};
-use crate::{TransformationContext, TransformationResult, TransformerConfig};
+use super::body_transforms::argument_bounds;
+use super::typescript;
+use crate::{
+  Diagnostic, EmitMappingConfig, ReplacementScope, ScriptStyle, Target, TemplateReplacement,
+  TransformationContext, TransformationResult, TransformerConfig,
+};
+use std::collections::HashMap;
 use lazy_static::lazy_static;
 use regex::Regex;
 
 lazy_static! {
     static ref EMIT_TEMPLATE_PATTERN: Regex = Regex::new(r#"\$emit\s*\(\s*['"`]([^'"`]+)['"`]"#).unwrap();
+    static ref EMIT_ACCESS_PATTERN: Regex = Regex::new(r"\$emit\b").unwrap();
     static ref EMIT_THIS_PATTERN: Regex = Regex::new(r#"this\.\$emit\s*\(\s*['"`]([^'"`]+)['"`]"#).unwrap();
+    static ref EMIT_CALL_PATTERN: Regex = Regex::new(r"\$emit\s*\(\s*([^,)]+)").unwrap();
+    static ref THIS_EMIT_CALL_PATTERN: Regex = Regex::new(r"this\.\$emit\s*\(").unwrap();
+    static ref THIS_VALUE_PATTERN: Regex = Regex::new(r"this\.value\b").unwrap();
+    static ref PASSTHROUGH_GETTER_PATTERN: Regex =
+      Regex::new(r"^\s*return\s+this\.(value|modelValue)\s*;?\s*$").unwrap();
+    static ref PASSTHROUGH_SETTER_PATTERN: Regex =
+      Regex::new(r#"^\s*this\.\$emit\s*\(\s*['"]([^'"]+)['"]\s*,\s*([\w$]+)\s*\)\s*;?\s*$"#).unwrap();
+}
+
+/// A `v-model` implemented by a prop, its update event and a computed passing both through, which
+/// `prefer_define_model` turns into a `defineModel()` ref named after the computed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModelBinding {
+  /// The declared prop (`value` or `modelValue`)
+  pub prop: String,
+  /// The emitted event, before mapping (`input`, `update:modelValue`)
+  pub event: String,
+  /// The passthrough computed
+  pub computed: String,
+  /// The name of the model, the prop unless the emit mapping renames it
+  pub name: String,
 }
 
 /// Transformer for converting Vue2 $emit usage to Vue3 defineEmits pattern
@@ -13,7 +41,10 @@ lazy_static! {
 /// This transformer handles the conversion of:
 /// - `this.$emit('event', data)` -> `emit('event', data)`
 /// - Generates `const emit = defineEmits(['event1', 'event2']);`
-/// - Maps Vue2 event names to Vue3 equivalents (e.g., 'input' -> 'update:value')
+/// - Maps Vue2 event names to Vue3 equivalents (e.g., 'input' -> 'update:value'), configurable
+///   globally or per component with `[emit_mapping]`, which can also rename the `value` prop
+/// - Falls back to `const emit = defineEmits();` when an event name is not a string literal
+///   (`this.$emit(eventName, payload)`), reporting the dynamic sites
 pub struct EmitTransformer;
 
 impl Default for EmitTransformer {
@@ -27,6 +58,20 @@ impl EmitTransformer {
     Self
   }
 
+  /// Check whether `text` contains a component `$emit` (`this.$emit` or a bare template
+  /// `$emit`), as opposed to an emit on another instance such as `$nuxt.$emit` or an event bus
+  fn contains_component_emit(text: &str) -> bool {
+    EMIT_ACCESS_PATTERN
+      .find_iter(text)
+      .any(|m| Self::is_component_emit_at(text, m.start()))
+  }
+
+  /// Check whether the `$emit` starting at `start` is called on the component itself
+  fn is_component_emit_at(text: &str, start: usize) -> bool {
+    let text_before = text[..start].trim_end();
+    !text_before.ends_with('.') || text_before.ends_with("this.")
+  }
+
   /// Check if context contains $emit usage
   fn has_emit_usage(&self, context: &TransformationContext) -> bool {
     self.has_emit_in_identifiers(context) || self.has_emit_in_methods(context) || self.has_emit_in_computed(context) || self.has_emit_in_template(context)
@@ -39,17 +84,17 @@ impl EmitTransformer {
       .template_state
       .function_calls
       .iter()
-      .any(|call| call.contains("$emit") && !call.contains("$nuxt.$emit"))
+      .any(|call| Self::contains_component_emit(call))
       || context
         .template_state
         .identifiers
         .iter()
-        .any(|id| id.contains("$emit") && !id.contains("$nuxt.$emit"))
+        .any(|id| Self::contains_component_emit(id))
       || context
         .template_state
         .vue_directives
         .iter()
-        .any(|directive| directive.value.contains("$emit") && !directive.value.contains("$nuxt.$emit"))
+        .any(|directive| Self::contains_component_emit(&directive.value))
   }
 
   /// Check for $emit in identifiers and function calls
@@ -58,12 +103,12 @@ impl EmitTransformer {
       .script_state
       .identifiers
       .iter()
-      .any(|id| id.contains("$emit") && !id.contains("$nuxt.$emit"))
+      .any(|id| Self::contains_component_emit(id))
       || context
         .script_state
         .function_calls
         .iter()
-        .any(|call| call.contains("$emit") && !call.contains("$nuxt.$emit"))
+        .any(|call| Self::contains_component_emit(call))
   }
 
   /// Check for $emit usage in method bodies
@@ -72,7 +117,7 @@ impl EmitTransformer {
       .script_state
       .method_details
       .iter()
-      .any(|method| method.body.contains("$emit") && !method.body.contains("$nuxt.$emit"))
+      .any(|method| Self::contains_component_emit(&method.body))
   }
 
   /// Check for $emit usage in computed property setters
@@ -83,22 +128,21 @@ impl EmitTransformer {
       .iter()
       .any(|computed| {
         if let Some(setter) = &computed.setter {
-          setter.contains("$emit") && !setter.contains("$nuxt.$emit")
+          Self::contains_component_emit(setter)
         } else {
           false
         }
       })
   }
 
-  /// Extract emit event names from method bodies and function calls
+  /// Extract the original (unmapped) emit event names from method bodies and function calls
   fn extract_emit_events(&self, context: &TransformationContext) -> Vec<String> {
     let mut events = Vec::new();
 
     // Helper function to add unique events while preserving order
     let mut add_event = |event: String| {
-      let mapped_event = self.map_event_name(&event);
-      if !events.contains(&mapped_event) {
-        events.push(mapped_event);
+      if !events.contains(&event) {
+        events.push(event);
       }
     };
 
@@ -113,7 +157,7 @@ impl EmitTransformer {
              (cleaned_arg.starts_with('"') && cleaned_arg.ends_with('"')) ||
              (cleaned_arg.starts_with('`') && cleaned_arg.ends_with('`')) {
             let event_name = cleaned_arg.trim_matches('\'').trim_matches('"').trim_matches('`');
-            if !event_name.is_empty() {
+            if !event_name.is_empty() && !event_name.contains("${") {
               add_event(event_name.to_string());
               break; // Only take the first string argument
             }
@@ -151,7 +195,6 @@ impl EmitTransformer {
       }
     }
 
-    // Events are already mapped in add_event function
     events
   }
 
@@ -160,16 +203,14 @@ impl EmitTransformer {
     let mut events = Vec::new();
     
     // Look for Vue component $emit calls: this.$emit('eventName', ...)
-    // Simple regex to find $emit calls, but exclude $nuxt.$emit patterns
+    // Simple regex to find $emit calls, but exclude emits on other instances ($nuxt.$emit, buses)
     {
       let re = &*EMIT_TEMPLATE_PATTERN;
       for cap in re.captures_iter(body) {
-        // Check if this $emit is part of $nuxt.$emit by looking at the text before the match
         let match_start = cap.get(0).unwrap().start();
-        let text_before = if match_start >= 5 { &body[match_start-5..match_start] } else { &body[0..match_start] };
-        
-        if !text_before.ends_with("$nuxt.") {
-          if let Some(event_name) = cap.get(1) {
+
+        if Self::is_component_emit_at(body, match_start) {
+          if let Some(event_name) = cap.get(1).filter(|name| !name.as_str().contains("${")) {
             events.push(event_name.as_str().to_string());
           }
         }
@@ -183,13 +224,258 @@ impl EmitTransformer {
     }
   }
 
+  /// Check whether an emitted event name is computed at runtime (a variable, expression or
+  /// interpolated template literal)
+  fn is_dynamic_event(argument: &str) -> bool {
+    let argument = argument.trim();
+    let is_literal = ['\'', '"', '`']
+      .iter()
+      .any(|quote| argument.starts_with(*quote) && argument.ends_with(*quote) && argument.len() > 1);
+    !is_literal || argument.contains("${")
+  }
+
+  /// Dynamic event names emitted in `body`
+  fn dynamic_events_in_body(body: &str) -> Vec<String> {
+    EMIT_CALL_PATTERN
+      .captures_iter(body)
+      .filter(|caps| Self::is_component_emit_at(body, caps.get(0).unwrap().start()))
+      .map(|caps| caps[1].trim().to_string())
+      .filter(|argument| Self::is_dynamic_event(argument))
+      .collect()
+  }
+
+  /// Sites emitting a dynamic event name, described for diagnostics
+  fn collect_dynamic_emits(&self, context: &TransformationContext) -> Vec<String> {
+    let mut sites = Vec::new();
+    let quote = |event: String| {
+      if event.starts_with('`') {
+        event
+      } else {
+        format!("`{}`", event)
+      }
+    };
+
+    for directive in &context.template_state.vue_directives {
+      for event in Self::dynamic_events_in_body(&directive.value) {
+        sites.push(format!("{} in the template", quote(event)));
+      }
+    }
+
+    for method in &context.script_state.method_details {
+      for event in Self::dynamic_events_in_body(&method.body) {
+        sites.push(format!("{} in method `{}`", quote(event), method.name));
+      }
+    }
+
+    for computed in &context.script_state.computed_details {
+      if let Some(setter) = &computed.setter {
+        for event in Self::dynamic_events_in_body(setter) {
+          sites.push(format!("{} in the setter of `{}`", quote(event), computed.name));
+        }
+      }
+    }
+
+    sites.dedup();
+    sites
+  }
+
+  /// The event mapping for the component: the defaults, overridden by the configured mapping
+  /// and then by the mapping configured for the component's `name`
+  fn resolve_mapping(
+    context: &TransformationContext,
+    config: &TransformerConfig,
+  ) -> EmitMappingConfig {
+    // `v-model` still binds `value` and `input` on the Vue 2.7 runtime
+    let events = match config.target {
+      Target::Vue27 => HashMap::new(),
+      _ => HashMap::from([("input".to_string(), "update:value".to_string())]),
+    };
+    let mut mapping = EmitMappingConfig {
+      events,
+      ..Default::default()
+    };
+
+    let Some(configured) = &config.emit_mapping else {
+      return mapping;
+    };
+
+    let component = context
+      .script_state
+      .component_name
+      .as_ref()
+      .and_then(|name| configured.components.get(name));
+
+    for overrides in std::iter::once(configured).chain(component) {
+      mapping.events.extend(overrides.events.clone());
+      if overrides.model_prop.is_some() {
+        mapping.model_prop = overrides.model_prop.clone();
+      }
+    }
+
+    mapping
+  }
+
+  /// The name the `value` prop is renamed to, if the mapping renames it and the component
+  /// declares it
+  pub fn renamed_model_prop(
+    context: &TransformationContext,
+    config: &TransformerConfig,
+  ) -> Option<String> {
+    Self::resolve_mapping(context, config)
+      .model_prop
+      .filter(|model_prop| model_prop != "value")
+      .filter(|_| context.script_state.props.iter().any(|prop| prop.name == "value"))
+  }
+
+  /// The `v-model` binding to replace by `defineModel()`, with `prefer_define_model` and only when
+  /// the prop and the event are used by nothing but the passthrough computed
+  pub fn model_binding(
+    context: &TransformationContext,
+    config: &TransformerConfig,
+  ) -> Option<ModelBinding> {
+    // `defineModel()` only compiles in `<script setup>`, from Vue 3.4
+    if !config.prefer_define_model
+      || config.script_style != ScriptStyle::SetupSugar
+      || config.target == Target::Vue27
+    {
+      return None;
+    }
+    let script = context.sfc_sections.script_content.as_deref()?;
+
+    context.script_state.computed_details.iter().find_map(|computed| {
+      let prop = PASSTHROUGH_GETTER_PATTERN.captures(computed.getter.as_deref()?)?[1].to_string();
+      let setter = PASSTHROUGH_SETTER_PATTERN.captures(computed.setter.as_deref()?)?;
+      let event = setter[1].to_string();
+      let is_update_event = match prop.as_str() {
+        "value" => event == "input" || event == "update:value",
+        _ => event == "update:modelValue",
+      };
+      if !is_update_event
+        || computed.setter_parameter.as_deref() != Some(&setter[2])
+        || !context.script_state.props.iter().any(|declared| declared.name == prop)
+      {
+        return None;
+      }
+
+      // Any other use of the prop or the event would be left without a declaration
+      let prop_reads = Regex::new(&format!(r"\bthis\.{}\b", regex::escape(&prop))).ok()?;
+      let emits_event = |body: &str| {
+        EMIT_TEMPLATE_PATTERN
+          .captures_iter(body)
+          .filter(|caps| caps[1] == event)
+          .count()
+      };
+      let template_uses = context
+        .template_state
+        .identifiers
+        .iter()
+        .any(|identifier| identifier == &prop)
+        || context
+          .template_state
+          .vue_directives
+          .iter()
+          .any(|directive| emits_event(&directive.value) > 0);
+      if prop_reads.find_iter(script).count() != 1 || emits_event(script) != 1 || template_uses {
+        return None;
+      }
+
+      let name = match prop.as_str() {
+        "value" => Self::renamed_model_prop(context, config).unwrap_or_else(|| prop.clone()),
+        _ => prop.clone(),
+      };
+      Some(ModelBinding {
+        prop,
+        event,
+        computed: computed.name.clone(),
+        name,
+      })
+    })
+  }
+
   /// Map Vue2 event names to Vue3 equivalents
-  fn map_event_name(&self, event: &str) -> String {
-    match event {
-      "input" => "update:value".to_string(),
-      // Add more mappings as needed
-      _ => event.to_string(),
+  fn map_event_name(&self, event: &str, mapping: &EmitMappingConfig) -> String {
+    mapping
+      .events
+      .get(event)
+      .cloned()
+      .unwrap_or_else(|| event.to_string())
+  }
+
+  /// Argument lists of the component `$emit` calls in `body`, the event name first
+  fn emit_calls_in_body(body: &str) -> Vec<Vec<String>> {
+    let mut calls = Vec::new();
+    for found in EMIT_ACCESS_PATTERN.find_iter(body) {
+      if !Self::is_component_emit_at(body, found.start()) {
+        continue;
+      }
+      let rest = &body[found.end()..];
+      if !rest.trim_start().starts_with('(') {
+        continue;
+      }
+      let open = found.end() + rest.len() - rest.trim_start().len();
+      let Some(bounds) = argument_bounds(body, open) else {
+        continue;
+      };
+      let mut start = open + 1;
+      let mut arguments = Vec::new();
+      for bound in bounds {
+        let argument = body[start..bound].trim();
+        if !argument.is_empty() {
+          arguments.push(argument.to_string());
+        }
+        start = bound + 1;
+      }
+      calls.push(arguments);
+    }
+    calls
+  }
+
+  /// Generate `defineEmits<{ ... }>()` with the payload types inferred from the emitted values
+  fn generate_typed_emit_setup(
+    &self,
+    context: &TransformationContext,
+    events: &[String],
+    mapping: &EmitMappingConfig,
+  ) -> String {
+    let mut bodies: Vec<&str> = context
+      .template_state
+      .vue_directives
+      .iter()
+      .map(|directive| directive.value.as_str())
+      .collect();
+    bodies.extend(context.script_state.method_details.iter().map(|method| method.body.as_str()));
+    bodies.extend(
+      context
+        .script_state
+        .computed_details
+        .iter()
+        .filter_map(|computed| computed.setter.as_deref()),
+    );
+
+    let mut calls: Vec<(String, Vec<Vec<String>>)> = events
+      .iter()
+      .map(|event| (event.clone(), Vec::new()))
+      .collect();
+    for arguments in bodies.into_iter().flat_map(Self::emit_calls_in_body) {
+      let Some((event, payload)) = arguments.split_first() else {
+        continue;
+      };
+      let event = self.map_event_name(event.trim_matches(['\'', '"', '`']), mapping);
+      if let Some((_, event_calls)) = calls.iter_mut().find(|(name, _)| *name == event) {
+        event_calls.push(payload.to_vec());
+      }
     }
+
+    let resolve = |name: &str| typescript::member_type(context, name);
+    let signatures = typescript::emit_signatures(&calls, &resolve);
+    format!(
+      "const emit = defineEmits<{{\n{}\n}}>();",
+      signatures
+        .iter()
+        .map(|signature| format!("  {}", signature))
+        .collect::<Vec<_>>()
+        .join("\n")
+    )
   }
 
   /// Generate the defineEmits setup code
@@ -213,23 +499,88 @@ impl Transformer for EmitTransformer {
     "emit"
   }
 
-  fn should_transform(&self, context: &TransformationContext, _config: &TransformerConfig) -> bool {
-    self.has_emit_usage(context)
+  fn should_transform(&self, context: &TransformationContext, config: &TransformerConfig) -> bool {
+    self.has_emit_usage(context) || Self::renamed_model_prop(context, config).is_some()
   }
 
   fn transform(
     &self,
     context: &TransformationContext,
-    _config: &TransformerConfig,
+    config: &TransformerConfig,
   ) -> TransformationResult {
     let mut result = TransformationResult::default();
+    let mapping = Self::resolve_mapping(context, config);
+
+    if let Some(model_prop) = Self::renamed_model_prop(context, config) {
+      result.template_replacements.push(TemplateReplacement {
+        find: "value".to_string(),
+        replace: model_prop.clone(),
+        scope: ReplacementScope::Identifiers,
+      });
+      result.diagnostics.push(Diagnostic::info(
+        "model-prop-renamed",
+        format!(
+          "The `value` prop was renamed to `{}`, parents binding `:value` instead of `v-model` need to be updated",
+          model_prop
+        ),
+      ));
+    }
 
     if self.has_emit_usage(context) {
-      let events = self.extract_emit_events(context);
-      
-      if !events.is_empty() {
+      let model_event = Self::model_binding(context, config).map(|binding| binding.event);
+      let original_events = self.extract_emit_events(context);
+      let mut events: Vec<String> = Vec::new();
+
+      for event in &original_events {
+        let mapped_event = self.map_event_name(event, &mapping);
+
+        // Record renamed events so listeners in other files can be updated
+        if &mapped_event != event {
+          result
+            .event_renames
+            .push((event.clone(), mapped_event.clone()));
+
+          // Template emits of the renamed event
+          for quote in ['\'', '"'] {
+            result.template_replacements.push(TemplateReplacement {
+              find: format!("$emit({}{}{}", quote, event, quote),
+              replace: format!("emit({}{}{}", quote, mapped_event, quote),
+              scope: ReplacementScope::Expressions,
+            });
+          }
+        }
+
+        // `defineModel()` declares the update event of the model itself
+        if !events.contains(&mapped_event) && Some(event) != model_event.as_ref() {
+          events.push(mapped_event);
+        }
+      }
+
+      let dynamic_emits = self.collect_dynamic_emits(context);
+      if !dynamic_emits.is_empty() {
+        // The emitted events cannot be listed, declare emits without validation
+        result.add_setup("const emit = defineEmits();".to_string());
+        result.add_setup("".to_string()); // Add blank line
+
+        result.diagnostics.push(Diagnostic::warning(
+          "dynamic-emit",
+          format!(
+            "Events are emitted with a dynamic name ({}), `defineEmits()` was generated without a list of events{}",
+            dynamic_emits.join(", "),
+            if events.is_empty() {
+              String::new()
+            } else {
+              format!(". Known events: {}", events.join(", "))
+            }
+          ),
+        ));
+      } else if !events.is_empty() {
         // Generate defineEmits setup code
-        let emit_setup = self.generate_emit_setup(&events);
+        let emit_setup = if config.type_based_macros() {
+          self.generate_typed_emit_setup(context, &events, &mapping)
+        } else {
+          self.generate_emit_setup(&events)
+        };
         result.add_setup(emit_setup);
         result.add_setup("".to_string()); // Add blank line
       }
@@ -238,6 +589,7 @@ impl Transformer for EmitTransformer {
       result.template_replacements.push(crate::TemplateReplacement {
         find: "$emit(".to_string(),
         replace: "emit(".to_string(),
+        scope: crate::ReplacementScope::Expressions,
       });
     }
 
@@ -246,10 +598,18 @@ impl Transformer for EmitTransformer {
 
   fn get_body_transform(&self) -> Option<Box<BodyTransformFn>> {
     Some(Box::new(
-      |body: &str, context: &TransformationContext, _config: &TransformerConfig| {
+      |body: &str, context: &TransformationContext, config: &TransformerConfig| {
         let emit_transformer = EmitTransformer::new();
+        let mapping = EmitTransformer::resolve_mapping(context, config);
         let mut transformed_body = body.to_string();
 
+        // Accesses of the renamed `value` prop
+        if let Some(model_prop) = EmitTransformer::renamed_model_prop(context, config) {
+          transformed_body = THIS_VALUE_PATTERN
+            .replace_all(&transformed_body, format!("props.{}", model_prop))
+            .to_string();
+        }
+
         // Transform $emit usage
         if emit_transformer.has_emit_usage(context) {
           // Transform this.$emit calls to emit calls
@@ -257,25 +617,32 @@ impl Transformer for EmitTransformer {
             let re = &*EMIT_THIS_PATTERN;
             transformed_body = re.replace_all(&transformed_body, |caps: &regex::Captures| {
               let event_name = &caps[1];
-              let mapped_event = emit_transformer.map_event_name(event_name);
+              if event_name.contains("${") {
+                return caps[0].replacen("this.$emit", "emit", 1);
+              }
+              let mapped_event = emit_transformer.map_event_name(event_name, &mapping);
               format!("emit('{}'", mapped_event)
             }).to_string();
           }
 
+          // Dynamic event names are passed through as is
+          transformed_body = THIS_EMIT_CALL_PATTERN.replace_all(&transformed_body, "emit(").to_string();
+
           // Also handle cases without 'this.' (but exclude $nuxt.$emit)
           {
             let re = &*EMIT_TEMPLATE_PATTERN;
             transformed_body = re.replace_all(&transformed_body, |caps: &regex::Captures| {
               let full_match = caps.get(0).unwrap();
               let match_start = full_match.start();
-              let text_before = if match_start >= 6 { &transformed_body[match_start-6..match_start] } else { &transformed_body[0..match_start] };
-              
-              if text_before.ends_with("$nuxt.") || text_before.ends_with("this.$nuxt.") {
-                // This is a nuxt event bus emit, don't transform
+
+              if !EmitTransformer::is_component_emit_at(&transformed_body, match_start) {
+                // This is an emit on another instance (e.g. $nuxt event bus), don't transform
                 full_match.as_str().to_string()
+              } else if caps[1].contains("${") {
+                full_match.as_str().replacen("$emit", "emit", 1)
               } else {
                 let event_name = &caps[1];
-                let mapped_event = emit_transformer.map_event_name(event_name);
+                let mapped_event = emit_transformer.map_event_name(event_name, &mapping);
                 format!("emit('{}'", mapped_event)
               }
             }).to_string();
@@ -0,0 +1,179 @@
+use super::{BodyTransformFn, Transformer};
+use crate::{
+  EventBusConfig, ReplacementScope, TemplateReplacement, TransformationContext,
+  TransformationResult, TransformerConfig,
+};
+use regex::Regex;
+
+/// Transformer for Vue 2 event bus patterns
+///
+/// This transformer handles:
+/// - Converting `this.$root.$emit/$on/$off(...)` to `bus.emit/on/off(...)`
+/// - Converting buses installed on the prototype (`this.$bus.$emit(...)`)
+/// - Converting imported bus instances (`EventBus.$on(...)`) and dropping their import
+/// - Adding the configured mitt-based composable (`const bus = useEventBus();`)
+pub struct EventBusTransformer;
+
+impl Default for EventBusTransformer {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl EventBusTransformer {
+  pub fn new() -> Self {
+    Self
+  }
+
+  /// Imported identifiers that are used as an event bus (e.g. `import EventBus from '@/event-bus'`)
+  fn imported_buses(context: &TransformationContext) -> Vec<(String, String)> {
+    let mut buses = Vec::new();
+
+    for import_info in &context.script_state.imports {
+      for item in &import_info.imports {
+        let local_name = item.alias.as_deref().unwrap_or(&item.name);
+        let usage = format!("{}.$", local_name);
+        let is_bus = Self::script_bodies(context).any(|body| {
+          ["emit", "on", "off"]
+            .iter()
+            .any(|method| body.contains(&format!("{}{}(", usage, method)))
+        });
+
+        if is_bus {
+          buses.push((local_name.to_string(), import_info.source.clone()));
+        }
+      }
+    }
+
+    buses
+  }
+
+  /// All script bodies that may contain bus calls
+  fn script_bodies(context: &TransformationContext) -> impl Iterator<Item = &String> {
+    let script_state = &context.script_state;
+    script_state
+      .method_details
+      .iter()
+      .map(|method| &method.body)
+      .chain(
+        script_state
+          .computed_details
+          .iter()
+          .flat_map(|computed| computed.getter.iter().chain(computed.setter.iter())),
+      )
+      .chain(script_state.watchers.iter().map(|watcher| &watcher.handler_body))
+  }
+
+  /// Build the pattern matching bus receivers followed by `.$emit(`, `.$on(` or `.$off(`
+  fn bus_pattern(context: &TransformationContext, bus_config: &EventBusConfig) -> Option<Regex> {
+    let mut receivers: Vec<String> = bus_config
+      .globals
+      .iter()
+      .map(|global| format!(r"this\.{}", regex::escape(global)))
+      .collect();
+
+    for (local_name, _) in Self::imported_buses(context) {
+      receivers.push(format!(r"\b{}", regex::escape(&local_name)));
+    }
+
+    if receivers.is_empty() {
+      return None;
+    }
+
+    Regex::new(&format!(
+      r"(?:{})\s*\.\s*\$(emit|on|off)\s*\(",
+      receivers.join("|")
+    ))
+    .ok()
+  }
+
+  /// Check if the component uses an event bus
+  fn has_event_bus_usage(context: &TransformationContext, bus_config: &EventBusConfig) -> bool {
+    let uses_bus_in_script = Self::bus_pattern(context, bus_config)
+      .is_some_and(|pattern| Self::script_bodies(context).any(|body| pattern.is_match(body)));
+
+    uses_bus_in_script
+      || context
+        .sfc_sections
+        .template_content
+        .as_ref()
+        .is_some_and(|template| {
+          bus_config.globals.iter().any(|global| {
+            ["emit", "on", "off"]
+              .iter()
+              .any(|method| template.contains(&format!("{}.${}(", global, method)))
+          })
+        })
+  }
+}
+
+impl Transformer for EventBusTransformer {
+  fn name(&self) -> &'static str {
+    "event_bus"
+  }
+
+  fn should_transform(&self, context: &TransformationContext, config: &TransformerConfig) -> bool {
+    let default_config = EventBusConfig::default();
+    let bus_config = config.event_bus.as_ref().unwrap_or(&default_config);
+    Self::has_event_bus_usage(context, bus_config)
+  }
+
+  fn transform(
+    &self,
+    context: &TransformationContext,
+    config: &TransformerConfig,
+  ) -> TransformationResult {
+    let mut result = TransformationResult::new();
+    let default_config = EventBusConfig::default();
+    let bus_config = config.event_bus.as_ref().unwrap_or(&default_config);
+
+    // Add the bus composable
+    result.add_import(&bus_config.import_path, &bus_config.composable);
+    result.setup.push(format!(
+      "const {} = {}();",
+      bus_config.variable, bus_config.composable
+    ));
+
+    // Imported bus instances are replaced by the composable
+    for (local_name, source) in Self::imported_buses(context) {
+      result.imports_to_remove.push(source);
+      result.resolved_identifiers.push(local_name);
+    }
+
+    // Template usage goes through the globals, e.g. @click="$root.$emit('refresh')"
+    for global in &bus_config.globals {
+      for method in ["emit", "on", "off"] {
+        result.template_replacements.push(TemplateReplacement {
+          find: format!("{}.${}(", global, method),
+          replace: format!("{}.{}(", bus_config.variable, method),
+          scope: ReplacementScope::Expressions,
+        });
+      }
+    }
+
+    result
+  }
+
+  fn get_body_transform(&self) -> Option<Box<BodyTransformFn>> {
+    Some(Box::new(
+      |body: &str, context: &TransformationContext, config: &TransformerConfig| {
+        // Skip building the bus pattern for bodies without any bus call
+        if !["$emit", "$on", "$off"].iter().any(|call| body.contains(call)) {
+          return body.to_string();
+        }
+
+        let default_config = EventBusConfig::default();
+        let bus_config = config.event_bus.as_ref().unwrap_or(&default_config);
+        let Some(pattern) = EventBusTransformer::bus_pattern(context, bus_config) else {
+          return body.to_string();
+        };
+
+        pattern
+          .replace_all(body, |caps: &regex::Captures| {
+            format!("{}.{}(", bus_config.variable, &caps[1])
+          })
+          .to_string()
+      },
+    ))
+  }
+}
@@ -0,0 +1,159 @@
+use super::Transformer;
+use crate::{
+  Diagnostic, ExposeConfig, SymbolKind, TransformationContext, TransformationResult,
+  TransformerConfig,
+};
+use regex::Regex;
+
+/// Transformer generating `defineExpose()` for members parents use through template refs
+///
+/// `<script setup>` components are closed: `this.$refs.modal.open()` in a parent stops working
+/// unless the child exposes `open`. This transformer exposes:
+/// - The members configured with `[expose]` for component name patterns (`"*Modal" = ["open"]`)
+/// - The members parents were found to use through refs, when the CLI analyzes the project
+/// - With `auto`, the methods the component never calls itself, neither in the template nor
+///   through `this`, reported so the guess can be checked
+pub struct ExposeTransformer;
+
+impl Default for ExposeTransformer {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl ExposeTransformer {
+  pub fn new() -> Self {
+    Self
+  }
+
+  /// Whether a component name matches a pattern where `*` matches any characters
+  fn matches_pattern(pattern: &str, name: &str) -> bool {
+    let pattern = format!(
+      "^{}$",
+      pattern
+        .split('*')
+        .map(regex::escape)
+        .collect::<Vec<_>>()
+        .join(".*")
+    );
+    Regex::new(&pattern).is_ok_and(|pattern| pattern.is_match(name))
+  }
+
+  /// Members configured for the component, from the patterns matching its name in order, then
+  /// the members to expose in any case
+  fn configured_members(context: &TransformationContext, expose: &ExposeConfig) -> Vec<String> {
+    let mut members: Vec<String> = Vec::new();
+    let mut add = |member: &String| {
+      if !members.contains(member) {
+        members.push(member.clone());
+      }
+    };
+
+    if let Some(name) = &context.script_state.component_name {
+      let mut patterns: Vec<&String> = expose
+        .components
+        .keys()
+        .filter(|pattern| Self::matches_pattern(pattern, name))
+        .collect();
+      patterns.sort();
+      patterns
+        .into_iter()
+        .flat_map(|pattern| &expose.components[pattern])
+        .for_each(&mut add);
+    }
+    expose.members.iter().for_each(add);
+
+    members
+  }
+
+  /// Methods that neither the template nor any script code of the component refers to
+  fn uncalled_methods(context: &TransformationContext) -> Vec<String> {
+    let script = context.sfc_sections.script_content.as_deref().unwrap_or("");
+    let template = &context.template_state;
+
+    context
+      .script_state
+      .method_details
+      .iter()
+      .map(|method| method.name.clone())
+      .filter(|name| {
+        let used_in_template =
+          template.identifiers.contains(name) || template.function_calls.contains(name);
+        // `this.name`, `this['name']` or a string handler (`watch: { value: 'name' }`)
+        let reference = Regex::new(&format!(
+          r#"\bthis\s*\.\s*{0}\b|['"]{0}['"]"#,
+          regex::escape(name)
+        ));
+        let used_in_script = reference.is_ok_and(|pattern| pattern.is_match(script));
+        !used_in_template && !used_in_script
+      })
+      .collect()
+  }
+}
+
+impl Transformer for ExposeTransformer {
+  fn name(&self) -> &'static str {
+    "expose"
+  }
+
+  fn should_transform(&self, _context: &TransformationContext, config: &TransformerConfig) -> bool {
+    config.expose.is_some()
+  }
+
+  fn transform(
+    &self,
+    context: &TransformationContext,
+    config: &TransformerConfig,
+  ) -> TransformationResult {
+    let mut result = TransformationResult::new();
+    let Some(expose) = &config.expose else {
+      return result;
+    };
+
+    // Props are exposed by Vue itself, only setup bindings can be passed to `defineExpose()`
+    let symbols = context.script_state.symbols();
+    let mut unknown = Vec::new();
+    for member in Self::configured_members(context, expose) {
+      let is_binding = symbols
+        .iter()
+        .any(|symbol| symbol.name == member && symbol.kind != SymbolKind::Prop);
+      if is_binding {
+        result.exposed.push(member);
+      } else {
+        unknown.push(format!("`{}`", member));
+      }
+    }
+    if !unknown.is_empty() {
+      result.diagnostics.push(Diagnostic::warning(
+        "expose-unknown-member",
+        format!(
+          "Members to expose ({}) are not data, computed properties or methods of the component and were not exposed",
+          unknown.join(", ")
+        ),
+      ));
+    }
+
+    if expose.auto {
+      let uncalled: Vec<String> = Self::uncalled_methods(context)
+        .into_iter()
+        .filter(|method| !result.exposed.contains(method))
+        .collect();
+      if !uncalled.is_empty() {
+        result.diagnostics.push(Diagnostic::info(
+          "expose-uncalled-methods",
+          format!(
+            "Methods the component never calls itself ({}) were exposed with `defineExpose()` for parents calling them through template refs, configure `[expose]` for the component if they are not",
+            uncalled
+              .iter()
+              .map(|method| format!("`{}`", method))
+              .collect::<Vec<_>>()
+              .join(", ")
+          ),
+        ));
+        result.exposed.extend(uncalled);
+      }
+    }
+
+    result
+  }
+}
@@ -65,8 +65,7 @@ impl FiltersTransformer {
   }
 
   /// Get body transformation function for converting filter calls
-  fn get_filters_body_transform(
-  ) -> Box<dyn Fn(&str, &TransformationContext, &TransformerConfig) -> String> {
+  fn get_filters_body_transform() -> Box<super::BodyTransformFn> {
     Box::new(
       |body: &str, _context: &TransformationContext, _config: &TransformerConfig| {
         let mut transformed_body = body.to_string();
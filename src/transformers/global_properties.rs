@@ -0,0 +1,106 @@
+use super::{BodyTransformFn, Transformer};
+use crate::{TransformationContext, TransformationResult, TransformerConfig};
+use regex::Regex;
+
+/// Transformer for prototype globals kept as `app.config.globalProperties`
+///
+/// For teams not ready to replace globals such as `this.$filters` or `this.$constants` with
+/// composables, the configured properties are accessed through the component proxy:
+/// - Adds `const { proxy } = getCurrentInstance();` (marked as transitional)
+/// - Converting `this.$filters.x` to `proxy.$filters.x`
+///
+/// Templates can keep using `$filters` directly since global properties are exposed there.
+pub struct GlobalPropertiesTransformer;
+
+impl Default for GlobalPropertiesTransformer {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl GlobalPropertiesTransformer {
+  pub fn new() -> Self {
+    Self
+  }
+
+  /// Build the pattern matching `this.$property` for the configured properties
+  fn property_pattern(config: &TransformerConfig) -> Option<Regex> {
+    let properties = config.global_properties.as_ref()?;
+    if properties.is_empty() {
+      return None;
+    }
+
+    let alternatives = properties
+      .iter()
+      .map(|property| regex::escape(property))
+      .collect::<Vec<_>>()
+      .join("|");
+
+    Regex::new(&format!(r"this\.({})\b", alternatives)).ok()
+  }
+
+  /// Check if any script body accesses one of the configured global properties
+  fn has_global_property_usage(context: &TransformationContext, config: &TransformerConfig) -> bool {
+    let Some(pattern) = Self::property_pattern(config) else {
+      return false;
+    };
+
+    let script_state = &context.script_state;
+    script_state
+      .method_details
+      .iter()
+      .any(|method| pattern.is_match(&method.body))
+      || script_state.computed_details.iter().any(|computed| {
+        computed
+          .getter
+          .iter()
+          .chain(computed.setter.iter())
+          .any(|body| pattern.is_match(body))
+      })
+      || script_state
+        .watchers
+        .iter()
+        .any(|watcher| pattern.is_match(&watcher.handler_body))
+  }
+}
+
+impl Transformer for GlobalPropertiesTransformer {
+  fn name(&self) -> &'static str {
+    "global_properties"
+  }
+
+  fn should_transform(&self, context: &TransformationContext, config: &TransformerConfig) -> bool {
+    Self::has_global_property_usage(context, config)
+  }
+
+  fn transform(
+    &self,
+    _context: &TransformationContext,
+    _config: &TransformerConfig,
+  ) -> TransformationResult {
+    let mut result = TransformationResult::new();
+
+    result.add_import("vue", "getCurrentInstance");
+    result.setup.push(
+      "// TODO: transitional access to app.config.globalProperties, replace with composables"
+        .to_string(),
+    );
+    result
+      .setup
+      .push("const { proxy } = getCurrentInstance();".to_string());
+
+    result
+  }
+
+  fn get_body_transform(&self) -> Option<Box<BodyTransformFn>> {
+    Some(Box::new(
+      |body: &str, _context: &TransformationContext, config: &TransformerConfig| {
+        let Some(pattern) = GlobalPropertiesTransformer::property_pattern(config) else {
+          return body.to_string();
+        };
+
+        pattern.replace_all(body, "proxy.$1").to_string()
+      },
+    ))
+  }
+}
@@ -1,6 +1,17 @@
 use super::Transformer;
 use super::TransformerOrchestrator;
-use crate::{TransformationContext, TransformationResult, TransformerConfig};
+use crate::{Diagnostic, Target, TransformationContext, TransformationResult, TransformerConfig};
+use lazy_static::lazy_static;
+use regex::Regex;
+
+lazy_static! {
+  // `hid: 'x',` followed by other properties, or `, hid: 'x'` closing the object
+  static ref DEDUPE_KEY_PATTERN: Regex = {
+    let key = r#"(?:'(?:hid|vmid)'|"(?:hid|vmid)"|\b(?:hid|vmid)\b)\s*:\s*"#;
+    let value = r#"(?:'[^']*'|"[^"]*"|`[^`]*`|[\w.$]+)"#;
+    Regex::new(&format!(r"{key}{value}\s*,\s*|,\s*{key}{value}")).unwrap()
+  };
+}
 
 /// Transformer for converting Nuxt.js head() method to Composition API useHead
 ///
@@ -9,6 +20,7 @@ use crate::{TransformationContext, TransformationResult, TransformerConfig};
 /// - Adding `import { useHead } from '@unhead/vue';` import
 /// - Converting the head() method body to a useHead(() => { ... }) call
 /// - Applying i18n and other transformations to the head method body
+/// - Removing the vue-meta `hid` / `vmid` dedupe keys, which unhead does not use
 ///
 /// # Examples
 ///
@@ -85,9 +97,11 @@ impl HeadTransformer {
     context: &TransformationContext,
     config: &TransformerConfig,
   ) -> String {
+    let body = DEDUPE_KEY_PATTERN.replace_all(body, "");
+
     // Use the orchestrator's body transformer which applies all available transforms
     let body_transformer = TransformerOrchestrator::get_body_transformer();
-    let mut transformed_body = body_transformer(body, context, config);
+    let mut transformed_body = body_transformer(&body, context, config);
 
     // Add trailing commas to object properties for better formatting
     transformed_body = self.add_trailing_commas(&transformed_body);
@@ -110,8 +124,8 @@ impl HeadTransformer {
       if trimmed.contains(':') && !trimmed.ends_with(',') && !trimmed.ends_with('{') {
         // Check if the next non-empty line is a closing brace
         let mut found_closing = false;
-        for j in (i + 1)..lines.len() {
-          let next_trimmed = lines[j].trim();
+        for next_line in &lines[(i + 1)..] {
+          let next_trimmed = next_line.trim();
           if !next_trimmed.is_empty() {
             if next_trimmed.starts_with('}') {
               found_closing = true;
@@ -152,8 +166,10 @@ impl Transformer for HeadTransformer {
     let mut result = TransformationResult::new();
 
     if let Some(head_method) = &context.script_state.head_method {
-      // Add useHead import
-      result.add_import("@unhead/vue", "useHead");
+      // Add useHead import, Nuxt 3 auto-imports it
+      if config.target != Target::Nuxt3 {
+        result.add_import("@unhead/vue", "useHead");
+      }
 
       // Transform the head method body
       let transformed_body = self.transform_head_body(&head_method.body, context, config);
@@ -169,6 +185,17 @@ impl Transformer for HeadTransformer {
       }
 
       result.methods.push("});".to_string());
+
+      let dedupe_keys = DEDUPE_KEY_PATTERN.find_iter(&head_method.body).count();
+      if dedupe_keys > 0 {
+        result.diagnostics.push(Diagnostic::info(
+          "head-hid",
+          format!(
+            "{} `hid` / `vmid` key(s) were removed from `head()`: unhead dedupes meta tags by `name` / `property`, add a `key` to other tags that need deduping",
+            dedupe_keys
+          ),
+        ));
+      }
     }
 
     result
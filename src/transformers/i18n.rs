@@ -1,10 +1,48 @@
+use super::template_edits::{edits_to_replacements, element_end, Edit, OPENING_TAG_PATTERN};
+use super::body_transforms::argument_bounds;
 use super::Transformer;
-use crate::{TemplateReplacement, TransformationContext, TransformationResult, TransformerConfig};
+use crate::{
+  Diagnostic, ReplacementScope, SetLocaleStyle, TemplateReplacement, TransformationContext,
+  TransformationResult, TransformerConfig, VTDirectiveStyle,
+};
+use lazy_static::lazy_static;
+use regex::Regex;
+
+lazy_static! {
+  static ref V_T_ATTRIBUTE_PATTERN: Regex =
+    Regex::new(r#"\s+v-t\s*=\s*(?:"([^"]*)"|'([^']*)')"#).unwrap();
+  static ref LOCALE_PATTERN: Regex = Regex::new(r"(?:this\.)?\$i18n\.locale\b").unwrap();
+  static ref LOCALES_PATTERN: Regex = Regex::new(r"(?:this\.)?\$i18n\.locales\b").unwrap();
+  static ref SET_LOCALE_PATTERN: Regex =
+    Regex::new(r"(await\s+)?(?:this\.)?\$i18n\.setLocale\(").unwrap();
+  static ref V_T_OBJECT_PATTERN: Regex =
+    Regex::new(r"^\{\s*path\s*:\s*([^,]+?)\s*(?:,\s*args\s*:\s*(.+?))?\s*,?\s*\}$").unwrap();
+}
+
+/// Vue 2 i18n functions and their `useI18n()` equivalent, in destructuring order. vue-i18n v9
+/// merged `$tc` into `t`.
+pub(crate) const I18N_FUNCTIONS: [(&str, &str); 6] = [
+  ("$t", "t"),
+  ("$tc", "t"),
+  ("$te", "te"),
+  ("$tm", "tm"),
+  ("$n", "n"),
+  ("$d", "d"),
+];
 
 /// Transformer for converting Vue i18n usage from Options API to Composition API
 ///
-/// This transformer handles the conversion of Vue i18n function calls ($t, $n, $d) from the
-/// Options API pattern to the Composition API pattern using useI18n().
+/// This transformer handles the conversion of Vue i18n function calls ($t, $tc, $te, $tm, $n, $d)
+/// from the Options API pattern to the Composition API pattern using useI18n(). `$tc(key, choice,
+/// values)` becomes `t(key, values, choice)`, the argument order of vue-i18n v9.
+///
+/// Nuxt i18n's `$i18n.locales` is destructured from `useI18n()`, and `$i18n.setLocale(code)`
+/// becomes `setLocale(code)` from `useI18nUtils()` or `locale.value = code`, depending on
+/// `set_locale`.
+///
+/// The `v-t="'key'"` directive becomes `{{ t('key') }}` as the element content, unless
+/// `v_t_directive` keeps it. Components with an `<i18n>` custom block use its messages through
+/// `useI18n({ useScope: 'local' })`.
 ///
 /// # Examples
 ///
@@ -102,21 +140,22 @@ impl I18nTransformer {
       return body.to_string();
     }
 
-    let mut result = body.to_string();
+    let mut result = Self::reorder_tc_arguments(body);
 
-    // Transform i18n method calls
-    result = result.replace("this.$t(", "t(");
-    result = result.replace("this.$n(", "n(");
-    result = result.replace("this.$d(", "d(");
+    // Transform i18n method calls, also where 'this.' was already removed by other
+    // transformations
+    for (function, replacement) in I18N_FUNCTIONS {
+      result = result.replace(&format!("this.{}(", function), &format!("{}(", replacement));
+      result = result.replace(&format!("{}(", function), &format!("{}(", replacement));
+    }
 
-    // Also handle cases where 'this.' was already removed by other transformations
-    result = result.replace("$t(", "t(");
-    result = result.replace("$n(", "n(");
-    result = result.replace("$d(", "d(");
+    // Transform $i18n.setLocale() and $i18n.locales, before $i18n.locale
+    result = Self::rewrite_set_locale(&result, config, false);
+    result = LOCALES_PATTERN.replace_all(&result, "locales.value").to_string();
 
     // Transform i18n utils usage
     let i18n_transformer = I18nTransformer::new();
-    if i18n_transformer.has_i18n_utils_usage(context) {
+    if i18n_transformer.has_i18n_utils_usage(context, config) {
       // Transform $i18n.localeProperties to localeProperties
       result = result.replace("this.$i18n.localeProperties", "localeProperties");
       result = result.replace("$i18n.localeProperties", "localeProperties");
@@ -125,39 +164,211 @@ impl I18nTransformer {
     }
 
     // Transform $i18n.locale usage
-    if i18n_transformer.has_i18n_locale_usage(context) {
+    if i18n_transformer.has_i18n_locale_usage(context, config) {
       // Transform this.$i18n.locale to locale.value
-      result = result.replace("this.$i18n.locale", "locale.value");
-      result = result.replace("$i18n.locale", "locale.value");
+      result = LOCALE_PATTERN.replace_all(&result, "locale.value").to_string();
+    }
+
+    result
+  }
+
+  /// Whether the script or the template matches `pattern`
+  fn matches_component(context: &TransformationContext, pattern: &Regex) -> bool {
+    [
+      &context.sfc_sections.script_content,
+      &context.sfc_sections.template_content,
+    ]
+    .into_iter()
+    .flatten()
+    .any(|source| pattern.is_match(source))
+  }
+
+  /// Rewrite `$i18n.setLocale(code)` calls in the script or, with `in_template`, the template
+  fn rewrite_set_locale(code: &str, config: &TransformerConfig, in_template: bool) -> String {
+    let mut result = String::with_capacity(code.len());
+    let mut position = 0;
+
+    while let Some(caps) = SET_LOCALE_PATTERN.captures_at(code, position) {
+      let call = caps.get(0).unwrap();
+      let Some(bounds) = argument_bounds(code, call.end() - 1) else {
+        break;
+      };
+      let close = bounds[bounds.len() - 1];
+      let argument = code[call.end()..close].trim();
+
+      result.push_str(&code[position..call.start()]);
+      match config.set_locale {
+        SetLocaleStyle::Utils => {
+          result.push_str(caps.get(1).map(|m| m.as_str()).unwrap_or_default());
+          result.push_str(&format!("setLocale({})", argument));
+        }
+        // The assignment is synchronous, so an `await` is dropped
+        SetLocaleStyle::Assign if in_template => {
+          result.push_str(&format!("locale = {}", argument));
+        }
+        SetLocaleStyle::Assign => result.push_str(&format!("locale.value = {}", argument)),
+      }
+      position = close + 1;
+    }
+
+    result.push_str(&code[position..]);
+    result
+  }
+
+  /// Check if `$i18n.setLocale()` is called in the component
+  fn uses_set_locale(context: &TransformationContext) -> bool {
+    Self::matches_component(context, &SET_LOCALE_PATTERN)
+  }
+
+  /// Check if `$i18n.locales` is used in the component
+  fn uses_locales(context: &TransformationContext) -> bool {
+    Self::matches_component(context, &LOCALES_PATTERN)
+  }
+
+  /// Whether `v-t` directives are converted to `t()` interpolations
+  fn interpolates_v_t(context: &TransformationContext, config: &TransformerConfig) -> bool {
+    config.v_t_directive == VTDirectiveStyle::Interpolate
+      && context
+        .sfc_sections
+        .template_content
+        .as_ref()
+        .is_some_and(|template| V_T_ATTRIBUTE_PATTERN.is_match(template))
+  }
+
+  /// Whether the component has an `<i18n>` custom block with local messages
+  fn has_local_messages(context: &TransformationContext) -> bool {
+    context.sfc_sections.custom_blocks.iter().any(|block| {
+      let opening_tag = block.split('>').next().unwrap_or_default();
+      (opening_tag == "<i18n" || opening_tag.starts_with("<i18n "))
+        && !opening_tag.split_whitespace().any(|attribute| attribute == "global")
+    })
+  }
+
+  /// `t()` call equivalent to a `v-t` value: `'key'` or `{ path: 'key', args: { ... } }`
+  fn translation_call(value: &str) -> Option<String> {
+    let value = value.trim();
+    if !value.starts_with('{') {
+      return Some(format!("t({})", value));
+    }
+
+    let caps = V_T_OBJECT_PATTERN.captures(value)?;
+    match caps.get(2) {
+      Some(args) => Some(format!("t({}, {})", &caps[1], args.as_str())),
+      None => Some(format!("t({})", &caps[1])),
+    }
+  }
+
+  /// Edits replacing the content of elements with a `v-t` directive by a `t()` interpolation,
+  /// with the region each rewrite changes and the directives that could not be converted
+  fn v_t_edits(template: &str) -> (Vec<Edit>, Vec<(usize, usize)>, Vec<Diagnostic>) {
+    let mut edits = Vec::new();
+    let mut regions = Vec::new();
+    let mut diagnostics = Vec::new();
+
+    for caps in OPENING_TAG_PATTERN.captures_iter(template) {
+      let tag = caps.get(0).unwrap();
+      let Some(attribute) = V_T_ATTRIBUTE_PATTERN.captures(tag.as_str()) else {
+        continue;
+      };
+      let value = attribute
+        .get(1)
+        .or_else(|| attribute.get(2))
+        .map(|value| value.as_str())
+        .unwrap_or_default();
+
+      let Some(call) = Self::translation_call(value) else {
+        diagnostics.push(Diagnostic::warning(
+          "v-t-directive",
+          format!(
+            "`v-t` on `<{}>` uses options other than `path` and `args`, it was kept as-is",
+            &caps[1]
+          ),
+        ));
+        continue;
+      };
+      let Some(end) = element_end(template, &caps) else {
+        continue;
+      };
+
+      let range = attribute.get(0).unwrap().range();
+      let mut opening = tag.as_str().to_string();
+      opening.replace_range(range, "");
+      if &caps[3] == "/" {
+        opening = format!("{}>", opening.trim_end_matches("/>").trim_end());
+      }
+
+      edits.push((
+        tag.start(),
+        end,
+        format!("{}{{{{ {} }}}}</{}>", opening, call, &caps[1]),
+      ));
+      regions.push((tag.start(), end));
     }
 
+    (edits, regions, diagnostics)
+  }
+
+  /// Swap the choice and values of `$tc(key, choice, values)`, so the call matches vue-i18n v9's
+  /// `t(key, values, choice)`
+  fn reorder_tc_arguments(code: &str) -> String {
+    let mut result = String::with_capacity(code.len());
+    let mut position = 0;
+
+    while let Some(found) = code[position..].find("$tc(") {
+      let open = position + found + "$tc".len();
+      let Some(bounds) = argument_bounds(code, open) else {
+        break;
+      };
+      result.push_str(&code[position..=open]);
+
+      if let [key_end, choice_end, values_end] = bounds[..] {
+        result.push_str(&code[open + 1..key_end]);
+        result.push_str(", ");
+        result.push_str(code[choice_end + 1..values_end].trim());
+        result.push_str(", ");
+        result.push_str(code[key_end + 1..choice_end].trim());
+        result.push(')');
+      } else {
+        result.push_str(&code[open + 1..=bounds[bounds.len() - 1]]);
+      }
+      position = bounds[bounds.len() - 1] + 1;
+    }
+
+    result.push_str(&code[position..]);
     result
   }
 
+  /// Check whether the Vue 2 i18n function (e.g. `$tc`) is used in the script or the template
+  fn uses_function(context: &TransformationContext, function: &str) -> bool {
+    let this_function = format!("this.{}", function);
+    let call = format!("{}(", function);
+    let is_call = |candidate: &String| {
+      *candidate == function || *candidate == this_function || candidate.starts_with(&call)
+    };
+
+    context.script_state.function_calls.iter().any(is_call)
+      || context.template_state.function_calls.iter().any(is_call)
+      || context.script_state.identifiers.iter().any(|id| id == function)
+      || context.template_state.identifiers.iter().any(|id| id == function)
+  }
+
   /// Check if i18n functions are used in the component
   fn has_i18n_usage(&self, context: &TransformationContext) -> bool {
-    // Check function calls for $t, $n, $d (but not $set, $delete, etc.)
-    context.script_state.function_calls.iter().any(|call| {
-            call.contains("this.$t(") || call.contains("this.$n(") || call.contains("this.$d(") ||
-            call.contains("$t(") || call.contains("$n(") || call.contains("$d(") ||
-            *call == "$t" || *call == "$n" || *call == "$d"
-        }) ||
-        // Check template for i18n usage
-        context.template_state.function_calls.iter().any(|call| {
-            call.contains("$t(") || call.contains("$n(") || call.contains("$d(") ||
-            *call == "$t" || *call == "$n" || *call == "$d"
-        }) ||
-        // Check identifiers
-        context.script_state.identifiers.iter().any(|id| {
-            id == "$t" || id == "$n" || id == "$d"
-        }) ||
-        context.template_state.identifiers.iter().any(|id| {
-            id == "$t" || id == "$n" || id == "$d"
-        })
+    I18N_FUNCTIONS
+      .iter()
+      .any(|(function, _)| Self::uses_function(context, function))
   }
 
   /// Check if i18n utilities are used (localeProperties, localePath, localeRoute)
-  fn has_i18n_utils_usage(&self, context: &TransformationContext) -> bool {
+  fn has_i18n_utils_usage(
+    &self,
+    context: &TransformationContext,
+    config: &TransformerConfig,
+  ) -> bool {
+    if config.set_locale == SetLocaleStyle::Utils && Self::uses_set_locale(context) {
+      return true;
+    }
+
     // Check for $i18n.localeProperties usage in script
     let has_locale_properties_script = context
       .script_state
@@ -205,24 +416,19 @@ impl I18nTransformer {
     has_locale_properties || has_locale_functions
   }
 
-  /// Check if $i18n.locale is used in the component
-  fn has_i18n_locale_usage(&self, context: &TransformationContext) -> bool {
+  /// Check if $i18n.locale is used in the component, or assigned by a converted `setLocale()`
+  fn has_i18n_locale_usage(
+    &self,
+    context: &TransformationContext,
+    config: &TransformerConfig,
+  ) -> bool {
     context
       .script_state
       .identifiers
       .iter()
       .any(|id| id == "$i18n.locale")
-      || context
-        .script_state
-        .method_details
-        .iter()
-        .any(|method| method.body.contains("$i18n.locale"))
-      || context.script_state.data_properties.iter().any(|prop| {
-        prop
-          .value
-          .as_ref()
-          .is_some_and(|v| v.contains("$i18n.locale"))
-      })
+      || Self::matches_component(context, &LOCALE_PATTERN)
+      || (config.set_locale == SetLocaleStyle::Assign && Self::uses_set_locale(context))
   }
 
   /// Add i18n imports to the result
@@ -236,7 +442,11 @@ impl I18nTransformer {
   }
 
   /// Generate i18n utils setup code
-  fn generate_i18n_utils_setup(&self, context: &TransformationContext) -> Vec<String> {
+  fn generate_i18n_utils_setup(
+    &self,
+    context: &TransformationContext,
+    config: &TransformerConfig,
+  ) -> Vec<String> {
     let mut setup_code = Vec::new();
     let mut needed_utils = Vec::new();
 
@@ -291,6 +501,10 @@ impl I18nTransformer {
       needed_utils.push("localeRoute");
     }
 
+    if config.set_locale == SetLocaleStyle::Utils && Self::uses_set_locale(context) {
+      needed_utils.push("setLocale");
+    }
+
     if !needed_utils.is_empty() {
       let destructured = needed_utils.join(", ");
       setup_code.push(format!("const {{ {} }} = useI18nUtils();", destructured));
@@ -301,53 +515,41 @@ impl I18nTransformer {
   }
 
   /// Generate i18n setup code
-  fn generate_i18n_setup(&self, context: &TransformationContext) -> Vec<String> {
+  fn generate_i18n_setup(
+    &self,
+    context: &TransformationContext,
+    config: &TransformerConfig,
+  ) -> Vec<String> {
     let mut setup_code = Vec::new();
     let mut needed_functions = Vec::new();
 
     // Determine which i18n functions are needed
-    let all_calls: Vec<&String> = context
-      .script_state
-      .function_calls
-      .iter()
-      .chain(context.template_state.function_calls.iter())
-      .collect();
-    let all_identifiers: Vec<&String> = context
-      .script_state
-      .identifiers
-      .iter()
-      .chain(context.template_state.identifiers.iter())
-      .collect();
-    let has_i18n_locale = self.has_i18n_locale_usage(context);
-
-    // Check for $t usage
-    if all_calls.iter().any(|call| call.contains("$t(") || *call == "$t")
-      || all_identifiers.iter().any(|id| *id == "$t")
-    {
+    let has_i18n_locale = self.has_i18n_locale_usage(context, config);
+    if Self::interpolates_v_t(context, config) {
       needed_functions.push("t");
     }
-    
-    // Check for $n usage
-    if all_calls.iter().any(|call| call.contains("$n(") || *call == "$n")
-      || all_identifiers.iter().any(|id| *id == "$n")
-    {
-      needed_functions.push("n");
-    }
-    
-    // Check for $d usage
-    if all_calls.iter().any(|call| call.contains("$d(") || *call == "$d")
-      || all_identifiers.iter().any(|id| *id == "$d")
-    {
-      needed_functions.push("d");
+    for (function, replacement) in I18N_FUNCTIONS {
+      if Self::uses_function(context, function) && !needed_functions.contains(&replacement) {
+        needed_functions.push(replacement);
+      }
     }
 
     if has_i18n_locale {
       needed_functions.push("locale");
     }
 
+    if Self::uses_locales(context) {
+      needed_functions.push("locales");
+    }
+
     if !needed_functions.is_empty() {
       let destructured = needed_functions.join(", ");
-      setup_code.push(format!("const {{ {} }} = useI18n();", destructured));
+      let options = if Self::has_local_messages(context) {
+        "{ useScope: 'local' }"
+      } else {
+        ""
+      };
+      setup_code.push(format!("const {{ {} }} = useI18n({});", destructured, options));
       setup_code.push("".to_string()); // Empty line for readability
     }
 
@@ -359,36 +561,87 @@ impl I18nTransformer {
   fn generate_template_replacements(
     &self,
     context: &TransformationContext,
+    config: &TransformerConfig,
   ) -> Vec<TemplateReplacement> {
     let mut replacements = Vec::new();
 
-    // Replace $t with t, $n with n, $d with d in templates
-    replacements.push(TemplateReplacement {
-      find: "$t(".to_string(),
-      replace: "t(".to_string(),
-    });
-    replacements.push(TemplateReplacement {
-      find: "$n(".to_string(),
-      replace: "n(".to_string(),
-    });
-    replacements.push(TemplateReplacement {
-      find: "$d(".to_string(),
-      replace: "d(".to_string(),
-    });
+    // Replace $i18n.setLocale() calls and $i18n.locales, before $i18n.locale
+    if let Some(template) = &context.sfc_sections.template_content {
+      for caps in SET_LOCALE_PATTERN.captures_iter(template) {
+        let call = caps.get(0).unwrap();
+        let Some(bounds) = argument_bounds(template, call.end() - 1) else {
+          continue;
+        };
+        let find = &template[call.start()..=bounds[bounds.len() - 1]];
+        replacements.push(TemplateReplacement {
+          find: find.to_string(),
+          replace: Self::rewrite_set_locale(find, config, true),
+          scope: ReplacementScope::Expressions,
+        });
+      }
+    }
+    if Self::uses_locales(context) {
+      replacements.push(TemplateReplacement {
+        find: "$i18n.locales".to_string(),
+        replace: "locales".to_string(),
+        scope: ReplacementScope::Expressions,
+      });
+    }
+
+    // Reorder the arguments of $tc(key, choice, values) calls
+    if let Some(template) = &context.sfc_sections.template_content {
+      let mut position = 0;
+      while let Some(found) = template[position..].find("$tc(") {
+        let start = position + found;
+        let Some(bounds) = argument_bounds(template, start + "$tc".len()) else {
+          break;
+        };
+        let end = bounds[bounds.len() - 1] + 1;
+        let call = &template[start..end];
+        let reordered = Self::reorder_tc_arguments(call);
+        let is_new = !replacements
+          .iter()
+          .any(|r: &TemplateReplacement| r.find == call);
+        if reordered != call && is_new {
+          // The call replaces the `$tc(` ones below, rename the functions here
+          let mut replace = reordered;
+          for (function, replacement) in I18N_FUNCTIONS {
+            replace = replace.replace(&format!("{}(", function), &format!("{}(", replacement));
+          }
+          replacements.push(TemplateReplacement {
+            find: call.to_string(),
+            replace,
+            scope: ReplacementScope::Expressions,
+          });
+        }
+        position = end;
+      }
+    }
+
+    // Replace $t with t, $tc with t, $te with te, ... in templates
+    for (function, replacement) in I18N_FUNCTIONS {
+      replacements.push(TemplateReplacement {
+        find: format!("{}(", function),
+        replace: format!("{}(", replacement),
+        scope: ReplacementScope::Expressions,
+      });
+    }
 
     // Replace $i18n.locale with locale in templates
-    if self.has_i18n_locale_usage(context) {
+    if self.has_i18n_locale_usage(context, config) {
       replacements.push(TemplateReplacement {
         find: "$i18n.locale".to_string(),
         replace: "locale".to_string(),
+        scope: ReplacementScope::Expressions,
       });
     }
 
     // Replace $i18n.localeProperties with localeProperties in templates
-    if self.has_i18n_utils_usage(context) {
+    if self.has_i18n_utils_usage(context, config) {
       replacements.push(TemplateReplacement {
         find: "$i18n.localeProperties".to_string(),
         replace: "localeProperties".to_string(),
+        scope: ReplacementScope::Expressions,
       });
     }
 
@@ -404,40 +657,57 @@ impl Transformer for I18nTransformer {
   fn should_transform(&self, context: &TransformationContext, config: &TransformerConfig) -> bool {
     config.enable_i18n
       && (self.has_i18n_usage(context)
-        || self.has_i18n_utils_usage(context)
-        || self.has_i18n_locale_usage(context))
+        || self.has_i18n_utils_usage(context, config)
+        || self.has_i18n_locale_usage(context, config)
+        || Self::uses_locales(context)
+        || Self::interpolates_v_t(context, config))
   }
 
   fn transform(
     &self,
     context: &TransformationContext,
-    _config: &TransformerConfig,
+    config: &TransformerConfig,
   ) -> TransformationResult {
     let mut result = TransformationResult::new();
 
-    // Handle standard i18n usage ($t, $n, $d) or locale usage
-    if self.has_i18n_usage(context) || self.has_i18n_locale_usage(context) {
+    // Convert v-t directives to t() interpolations
+    if Self::interpolates_v_t(context, config) {
+      if let Some(template) = &context.sfc_sections.template_content {
+        let (edits, regions, diagnostics) = Self::v_t_edits(template);
+        result
+          .template_replacements
+          .extend(edits_to_replacements(template, edits, regions));
+        result.diagnostics.extend(diagnostics);
+      }
+    }
+
+    // Handle standard i18n usage ($t, $n, $d), locale usage or converted v-t directives
+    if self.has_i18n_usage(context)
+      || self.has_i18n_locale_usage(context, config)
+      || Self::uses_locales(context)
+      || Self::interpolates_v_t(context, config)
+    {
       // Add imports
       self.add_i18n_imports(&mut result);
 
       // Generate setup code for standard i18n
-      result.setup.extend(self.generate_i18n_setup(context));
+      result.setup.extend(self.generate_i18n_setup(context, config));
 
       // Generate replacements
       result
         .template_replacements
-        .extend(self.generate_template_replacements(context));
+        .extend(self.generate_template_replacements(context, config));
     }
 
     // Handle i18n utils usage (localeProperties, localePath, localeRoute)
-    if self.has_i18n_utils_usage(context) {
+    if self.has_i18n_utils_usage(context, config) {
       self.add_i18n_utils_imports(&mut result);
-      result.setup.extend(self.generate_i18n_utils_setup(context));
+      result.setup.extend(self.generate_i18n_utils_setup(context, config));
       
       // Generate template replacements for utils
       result
         .template_replacements
-        .extend(self.generate_template_replacements(context));
+        .extend(self.generate_template_replacements(context, config));
     }
 
     result
@@ -1,5 +1,9 @@
 use super::Transformer;
-use crate::{TemplateReplacement, TransformationContext, TransformationResult, TransformerConfig};
+use crate::{
+  ReplacementScope, TemplateReplacement, TransformationContext, TransformationResult,
+  TransformerConfig,
+};
+use std::collections::HashMap;
 
 /// Transformer for rewriting imports and component names
 ///
@@ -62,6 +66,14 @@ impl ImportRewriteTransformer {
     false
   }
 
+  /// Entries of a configured map in a fixed order, longest names first so `BNavbar` is replaced
+  /// before `BNav`
+  fn ordered_entries<V>(map: &HashMap<String, V>) -> Vec<(&String, &V)> {
+    let mut entries: Vec<(&String, &V)> = map.iter().collect();
+    entries.sort_by(|(a, _), (b, _)| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+    entries
+  }
+
   /// Generate new imports based on rewrite configuration
   fn generate_rewritten_imports(
     &self,
@@ -120,7 +132,7 @@ impl ImportRewriteTransformer {
     // Add additional imports
     if let Some(additional_imports) = &config.additional_imports {
       if let Some(template_content) = &context.sfc_sections.template_content {
-        for (component_name, import_config) in additional_imports {
+        for (component_name, import_config) in Self::ordered_entries(additional_imports) {
           let should_import = template_content
             .contains(&format!("<{}", component_name.to_lowercase()))
             || template_content.contains(&format!("<{}", component_name));
@@ -149,15 +161,17 @@ impl ImportRewriteTransformer {
       for import_info in &context.script_state.imports {
         if let Some(rewrite_config) = import_rewrites.get(&import_info.source) {
           if let Some(component_rewrites) = &rewrite_config.component_rewrite {
-            for (old_component, new_component) in component_rewrites {
+            for (old_component, new_component) in Self::ordered_entries(component_rewrites) {
               // Replace both PascalCase and kebab-case versions
               replacements.push(TemplateReplacement {
                 find: format!("<{}", old_component),
                 replace: format!("<{}", new_component),
+                scope: ReplacementScope::Template,
               });
               replacements.push(TemplateReplacement {
                 find: format!("</{}>", old_component),
                 replace: format!("</{}>", new_component),
+                scope: ReplacementScope::Template,
               });
 
               // Convert to kebab-case and replace
@@ -166,10 +180,12 @@ impl ImportRewriteTransformer {
               replacements.push(TemplateReplacement {
                 find: format!("<{}", old_kebab),
                 replace: format!("<{}", new_kebab),
+                scope: ReplacementScope::Template,
               });
               replacements.push(TemplateReplacement {
                 find: format!("</{}>", old_kebab),
                 replace: format!("</{}>", new_kebab),
+                scope: ReplacementScope::Template,
               });
             }
           }
@@ -179,16 +195,18 @@ impl ImportRewriteTransformer {
 
     // Handle additional imports with rewrite_to
     if let Some(additional_imports) = &config.additional_imports {
-      for (component_name, import_config) in additional_imports {
+      for (component_name, import_config) in Self::ordered_entries(additional_imports) {
         if let Some(rewrite_to) = &import_config.rewrite_to {
           let old_kebab = to_kebab_case(component_name);
           replacements.push(TemplateReplacement {
             find: format!("<{}", old_kebab),
             replace: format!("<{}", rewrite_to),
+            scope: ReplacementScope::Template,
           });
           replacements.push(TemplateReplacement {
             find: format!("</{}>", old_kebab),
             replace: format!("</{}>", rewrite_to),
+            scope: ReplacementScope::Template,
           });
         }
       }
@@ -233,7 +251,7 @@ impl Transformer for ImportRewriteTransformer {
 
 /// Convert PascalCase to kebab-case
 /// E.g., "BSidebar" -> "b-sidebar"
-fn to_kebab_case(s: &str) -> String {
+pub(crate) fn to_kebab_case(s: &str) -> String {
   let mut result = String::new();
   for (i, c) in s.chars().enumerate() {
     if i > 0 && c.is_uppercase() {
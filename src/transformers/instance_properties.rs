@@ -0,0 +1,184 @@
+use super::{BodyTransformFn, Transformer};
+use crate::{
+  InstancePropertyStyle, TransformationContext, TransformationResult, TransformerConfig,
+};
+use lazy_static::lazy_static;
+use regex::Regex;
+
+lazy_static! {
+  static ref THIS_ASSIGNMENT_PATTERN: Regex =
+    Regex::new(r"this\.([a-zA-Z_][a-zA-Z0-9_$]*)\s*=\s*([^=\s][\w$.]*)?").unwrap();
+}
+
+/// Functions returning a handle that is only stored to be cleared later
+const HANDLE_FUNCTIONS: [&str; 4] = [
+  "setTimeout",
+  "setInterval",
+  "requestAnimationFrame",
+  "requestIdleCallback",
+];
+
+/// Transformer for instance properties assigned without being declared in `data`
+///
+/// Vue 2 components often store non-reactive handles directly on the instance:
+/// - `this.timer = setInterval(...)` with `clearInterval(this.timer)` in `beforeDestroy`
+/// - `this.observer = new IntersectionObserver(...)`
+///
+/// Timer and animation frame handles are always declared in setup, as `let timer;` by default or as
+/// `const timer = ref(null);` with [`InstancePropertyStyle::Ref`]. Declaring every other undeclared
+/// property is opt-in through `instance_properties`, in the same style and marked with a note.
+pub struct InstancePropertiesTransformer;
+
+impl Default for InstancePropertiesTransformer {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl InstancePropertiesTransformer {
+  pub fn new() -> Self {
+    Self
+  }
+
+  /// Names assigned through `this.x = ...` that are not declared anywhere in the component, with
+  /// whether they only store a timer handle. Other properties are only included when opted in.
+  fn undeclared_properties(
+    context: &TransformationContext,
+    config: &TransformerConfig,
+  ) -> Vec<(String, bool)> {
+    let script_state = &context.script_state;
+    let bodies = script_state
+      .method_details
+      .iter()
+      .map(|method| &method.body)
+      .chain(
+        script_state
+          .watchers
+          .iter()
+          .map(|watcher| &watcher.handler_body),
+      )
+      .chain(
+        script_state
+          .computed_details
+          .iter()
+          .flat_map(|computed| computed.setter.iter()),
+      );
+
+    let mut properties: Vec<(String, bool)> = Vec::new();
+
+    for body in bodies {
+      for caps in THIS_ASSIGNMENT_PATTERN.captures_iter(body) {
+        // Comparisons (`this.x == y`) have no assigned value
+        let Some(value) = caps.get(2) else {
+          continue;
+        };
+        let name = caps[1].to_string();
+        let callee = value.as_str().trim_start_matches("window.");
+        let is_handle = HANDLE_FUNCTIONS.contains(&callee);
+
+        let is_declared = script_state
+          .data_properties
+          .iter()
+          .any(|prop| prop.name == name)
+          || script_state.props.iter().any(|prop| prop.name == name)
+          || script_state.computed_properties.contains(&name)
+          || script_state.methods.contains(&name);
+
+        // Properties provided by a mixin composable are resolved by the mixin transformer
+        let is_mixin_property = config.mixins.as_ref().is_some_and(|mixins| {
+          mixins
+            .values()
+            .any(|mixin_config| mixin_config.imports.contains(&name))
+        });
+
+        if is_declared || is_mixin_property {
+          continue;
+        }
+
+        match properties
+          .iter_mut()
+          .find(|(existing, _)| *existing == name)
+        {
+          // A property also assigned something else is not just a handle
+          Some(existing) => existing.1 &= is_handle,
+          None => properties.push((name, is_handle)),
+        }
+      }
+    }
+
+    if config.instance_properties.is_none() {
+      properties.retain(|(_, is_handle)| *is_handle);
+    }
+
+    properties
+  }
+}
+
+impl Transformer for InstancePropertiesTransformer {
+  fn name(&self) -> &'static str {
+    "instance_properties"
+  }
+
+  fn should_transform(&self, context: &TransformationContext, config: &TransformerConfig) -> bool {
+    !Self::undeclared_properties(context, config).is_empty()
+  }
+
+  fn transform(
+    &self,
+    context: &TransformationContext,
+    config: &TransformerConfig,
+  ) -> TransformationResult {
+    let mut result = TransformationResult::new();
+    let style = config.instance_properties.unwrap_or_default();
+
+    for (name, is_handle) in Self::undeclared_properties(context, config) {
+      let declaration = match (style, is_handle) {
+        (InstancePropertyStyle::Let, true) => format!("let {};", name),
+        (InstancePropertyStyle::Let, false) => format!(
+          "// NOTE: `{}` was not declared in data(), added during conversion\nlet {};",
+          name, name
+        ),
+        (InstancePropertyStyle::Ref, true) => {
+          result.add_import("vue", "ref");
+          format!("const {} = ref(null);", name)
+        }
+        (InstancePropertyStyle::Ref, false) => {
+          result.add_import("vue", "ref");
+          format!(
+            "// NOTE: `{}` was not declared in data(), added during conversion\nconst {} = ref(undefined);",
+            name, name
+          )
+        }
+      };
+      result.data_refs.insert(name.clone(), (declaration, 0));
+      result.resolved_identifiers.push(name);
+    }
+
+    result
+  }
+
+  fn get_body_transform(&self) -> Option<Box<BodyTransformFn>> {
+    Some(Box::new(
+      |body: &str, context: &TransformationContext, config: &TransformerConfig| {
+        let style = config.instance_properties.unwrap_or_default();
+        let mut result = body.to_string();
+
+        for (name, _) in InstancePropertiesTransformer::undeclared_properties(context, config) {
+          let Ok(pattern) = Regex::new(&format!(r"this\.{}\b", regex::escape(&name))) else {
+            continue;
+          };
+
+          let replacement = match style {
+            InstancePropertyStyle::Let => name.clone(),
+            InstancePropertyStyle::Ref => format!("{}.value", name),
+          };
+          result = pattern
+            .replace_all(&result, regex::NoExpand(&replacement))
+            .to_string();
+        }
+
+        result
+      },
+    ))
+  }
+}
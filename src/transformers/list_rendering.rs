@@ -0,0 +1,160 @@
+use super::template_edits::{edits_to_replacements, element_end, Edit, OPENING_TAG_PATTERN};
+use super::Transformer;
+use crate::{Diagnostic, Target, TransformationContext, TransformationResult, TransformerConfig};
+use lazy_static::lazy_static;
+use regex::Regex;
+
+lazy_static! {
+  static ref V_FOR_ATTRIBUTE_PATTERN: Regex =
+    Regex::new(r#"\s+v-for\s*=\s*(?:"[^"]*"|'[^']*')"#).unwrap();
+  static ref V_IF_ATTRIBUTE_PATTERN: Regex =
+    Regex::new(r#"\s+v-if\s*=\s*(?:"[^"]*"|'[^']*')"#).unwrap();
+  static ref KEY_ATTRIBUTE_PATTERN: Regex =
+    Regex::new(r#"\s+(?::key|v-bind:key|key)\s*=\s*(?:"[^"]*"|'[^']*')"#).unwrap();
+}
+
+/// Transformer for `v-for` differences between Vue 2 and Vue 3
+///
+/// This transformer handles:
+/// - `v-if` on the same element as `v-for`: Vue 3 evaluates `v-if` first, so the loop moves to a
+///   wrapping `<template v-for>` to keep the Vue 2 behavior
+/// - `<template v-for>`: Vue 2 required the `key` on the children, Vue 3 expects it on the
+///   `<template>` itself, so the first child key is moved there
+pub struct ListRenderingTransformer;
+
+impl Default for ListRenderingTransformer {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl ListRenderingTransformer {
+  pub fn new() -> Self {
+    Self
+  }
+
+  /// Edits for the template, with the region each rewrite changes and the diagnostics to report
+  fn collect_edits(template: &str) -> (Vec<Edit>, Vec<(usize, usize)>, Vec<Diagnostic>) {
+    let mut edits = Vec::new();
+    let mut regions = Vec::new();
+    let mut diagnostics = Vec::new();
+
+    for caps in OPENING_TAG_PATTERN.captures_iter(template) {
+      let tag = caps.get(0).unwrap();
+      let Some(v_for) = V_FOR_ATTRIBUTE_PATTERN.find(tag.as_str()) else {
+        continue;
+      };
+      let Some(end) = element_end(template, &caps) else {
+        continue;
+      };
+
+      if V_IF_ATTRIBUTE_PATTERN.is_match(tag.as_str()) {
+        let key = KEY_ATTRIBUTE_PATTERN.find(tag.as_str());
+        let mut moved: Vec<_> = key.iter().chain(std::iter::once(&v_for)).collect();
+        moved.sort_by_key(|attribute| std::cmp::Reverse(attribute.start()));
+
+        // Remove back to front so the earlier ranges stay valid
+        let mut stripped = tag.as_str().to_string();
+        for attribute in moved {
+          stripped.replace_range(attribute.range(), "");
+        }
+
+        edits.push((
+          tag.start(),
+          tag.end(),
+          format!(
+            "<template{}{}>{}",
+            v_for.as_str(),
+            key.map(|key| key.as_str()).unwrap_or(""),
+            stripped
+          ),
+        ));
+        edits.push((end, end, "</template>".to_string()));
+        regions.push((tag.start(), end));
+
+        diagnostics.push(Diagnostic::info(
+          "v-if-with-v-for",
+          format!(
+            "`<{}>` uses `v-if` together with `v-for`, the loop was moved to a wrapping `<template>`. Consider filtering the list in a computed property instead",
+            &caps[1]
+          ),
+        ));
+        continue;
+      }
+
+      if &caps[1] == "template" && !KEY_ATTRIBUTE_PATTERN.is_match(tag.as_str()) {
+        let keys = Self::child_keys(template, tag.end(), end);
+        let Some((_, first_key)) = keys.first() else {
+          continue;
+        };
+
+        let insert_at = tag.start() + v_for.end();
+        edits.push((insert_at, insert_at, first_key.clone()));
+        for (start, key) in &keys {
+          edits.push((*start, start + key.len(), String::new()));
+        }
+        regions.push((tag.start(), end));
+      }
+    }
+
+    (edits, regions, diagnostics)
+  }
+
+  /// Keys (position, attribute) on the direct children of the element content between `start`
+  /// and `end`. Children with their own `v-for` keep their key.
+  fn child_keys(template: &str, start: usize, end: usize) -> Vec<(usize, String)> {
+    let mut keys = Vec::new();
+    let mut position = start;
+
+    while let Some(caps) = OPENING_TAG_PATTERN.captures_at(template, position) {
+      let child = caps.get(0).unwrap();
+      if child.start() >= end {
+        break;
+      }
+
+      if !V_FOR_ATTRIBUTE_PATTERN.is_match(child.as_str()) {
+        if let Some(key) = KEY_ATTRIBUTE_PATTERN.find(child.as_str()) {
+          keys.push((child.start() + key.start(), key.as_str().to_string()));
+        }
+      }
+
+      position = element_end(template, &caps).unwrap_or(child.end());
+    }
+
+    keys
+  }
+}
+
+impl Transformer for ListRenderingTransformer {
+  fn name(&self) -> &'static str {
+    "list_rendering"
+  }
+
+  fn should_transform(&self, context: &TransformationContext, config: &TransformerConfig) -> bool {
+    // The Vue 2.7 runtime keeps the Vue 2 precedence and key placement
+    config.target != Target::Vue27
+      && context
+        .sfc_sections
+        .template_content
+        .as_ref()
+        .is_some_and(|template| template.contains("v-for"))
+  }
+
+  fn transform(
+    &self,
+    context: &TransformationContext,
+    _config: &TransformerConfig,
+  ) -> TransformationResult {
+    let mut result = TransformationResult::new();
+
+    if let Some(template) = &context.sfc_sections.template_content {
+      let (edits, regions, diagnostics) = Self::collect_edits(template);
+      result
+        .template_replacements
+        .extend(edits_to_replacements(template, edits, regions));
+      result.diagnostics.extend(diagnostics);
+    }
+
+    result
+  }
+}
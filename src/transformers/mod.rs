@@ -1,23 +1,83 @@
-use crate::{TransformationContext, TransformationResult, TransformerConfig};
+use crate::{Diagnostic, TransformationContext, TransformationResult, TransformerConfig};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::cell::OnceCell;
+
+lazy_static! {
+  static ref DESTRUCTURED_SETUP_PATTERN: Regex =
+    Regex::new(r"^const\s*\{([^}]*)\}\s*=\s*(\w+\([^()]*\));$").unwrap();
+}
+
+/// Globals templates can read without the component declaring them
+const TEMPLATE_GLOBALS: [&str; 28] = [
+  "Infinity",
+  "undefined",
+  "NaN",
+  "isFinite",
+  "isNaN",
+  "parseFloat",
+  "parseInt",
+  "decodeURI",
+  "decodeURIComponent",
+  "encodeURI",
+  "encodeURIComponent",
+  "Math",
+  "Number",
+  "Date",
+  "Array",
+  "Object",
+  "Boolean",
+  "String",
+  "RegExp",
+  "Map",
+  "Set",
+  "JSON",
+  "Intl",
+  "BigInt",
+  "console",
+  "Error",
+  "Symbol",
+  "arguments",
+];
 
 // Sub-modules for different types of transformers
+pub mod analysis;
 pub mod assets;
 pub mod axios;
+pub mod compat;
+pub mod components;
 pub mod composition;
+pub mod css_modules;
+pub mod date_plugin;
 pub mod emit;
+pub mod event_bus;
+pub mod expose;
 pub mod filters;
+pub mod global_properties;
 pub mod head;
 pub mod i18n;
 pub mod import_rewrite;
+pub mod instance_properties;
+pub mod list_rendering;
 pub mod mixin;
 pub mod nuxt;
+pub mod options;
 pub mod router;
+pub mod slots;
+pub mod style;
+pub mod template_edits;
+pub mod this_scope;
+pub mod transitions;
+pub mod typescript;
 pub mod vue2;
 pub mod vuex;
 
 /// Function type for transforming method bodies
 pub type BodyTransformFn = dyn Fn(&str, &TransformationContext, &TransformerConfig) -> String;
 
+/// Function type for transforming the `<style>` content
+pub type StyleTransformFn = dyn Fn(&str, &TransformationContext, &TransformerConfig) -> String;
+
 /// Trait that all transformers must implement
 pub trait Transformer {
   /// Name of the transformer for logging/debugging
@@ -42,195 +102,220 @@ pub trait Transformer {
   fn get_body_transform(&self) -> Option<Box<BodyTransformFn>> {
     None
   }
+
+  /// Get style transformation function if this transformer rewrites the `<style>` content
+  fn get_style_transform(&self) -> Option<Box<StyleTransformFn>> {
+    None
+  }
 }
 
 /// Utility functions for common body transformations
 pub mod body_transforms {
-  use crate::{TransformationContext, TransformerConfig};
+  use crate::{SymbolKind, TransformationContext, TransformerConfig};
   use lazy_static::lazy_static;
   use regex::Regex;
 
   lazy_static! {
     static ref THIS_PROPERTY_PATTERN: Regex =
-      Regex::new(r"this\.([a-zA-Z_$][a-zA-Z0-9_$]*)").unwrap();
+      Regex::new(r"(^|[^\w$.]|\.\.\.)this\.([a-zA-Z_$][a-zA-Z0-9_$]*)").unwrap();
+    static ref THIS_STRING_KEY_PATTERN: Regex =
+      Regex::new(r#"(^|[^\w$.]|\.\.\.)this\[\s*(?:'([^']*)'|"([^"]*)")\s*\]"#).unwrap();
   }
 
-  /// Apply reactive reference transformations to a body string
-  pub fn apply_reactive_transforms(
-    body: &str,
-    context: &TransformationContext,
-    config: &TransformerConfig,
-    transformation_result: Option<&crate::TransformationResult>,
-  ) -> String {
-    let mut result = body.to_string();
-
-    // Transform data property accesses (this.prop -> prop.value)
-    // Sort by length (longest first) to prevent substring replacements
-    let mut data_props_sorted = context.script_state.data_properties.clone();
-    data_props_sorted.sort_by(|a, b| b.name.len().cmp(&a.name.len()));
-
-    for data_prop in &data_props_sorted {
-      let this_access = format!("this.{}", data_prop.name);
-      let ref_access = format!("{}.value", data_prop.name);
-      result = result.replace(&this_access, &ref_access);
-    }
-
-    // Transform computed property accesses (this.computed -> computed.value)
-    // Sort by length (longest first) to prevent substring replacements
-    let mut computed_props_sorted = context.script_state.computed_properties.clone();
-    computed_props_sorted.sort_by(|a, b| b.len().cmp(&a.len()));
+  /// Positions of the top-level argument separators of the call opened at `open` (the index of
+  /// its `(`), followed by the position of the closing parenthesis
+  pub fn argument_bounds(code: &str, open: usize) -> Option<Vec<usize>> {
+    let mut bounds = Vec::new();
+    let mut depth = 0;
+    let mut quote: Option<char> = None;
+    let mut escaped = false;
+
+    for (offset, c) in code[open..].char_indices() {
+      let position = open + offset;
+      if let Some(q) = quote {
+        if escaped {
+          escaped = false;
+        } else if c == '\\' {
+          escaped = true;
+        } else if c == q {
+          quote = None;
+        }
+        continue;
+      }
 
-    for computed_prop in &computed_props_sorted {
-      let this_access = format!("this.{}", computed_prop);
-      let ref_access = format!("{}.value", computed_prop);
-      result = result.replace(&this_access, &ref_access);
+      match c {
+        '\'' | '"' | '`' => quote = Some(c),
+        '(' | '[' | '{' => depth += 1,
+        ')' | ']' | '}' => {
+          depth -= 1;
+          if depth == 0 {
+            bounds.push(position);
+            return Some(bounds);
+          }
+        }
+        ',' if depth == 1 => bounds.push(position),
+        _ => {}
+      }
     }
 
-    // Transform prop accesses (this.propName -> props.propName)
-    // Sort by length (longest first) to prevent substring replacements
-    let mut props_sorted = context.script_state.props.clone();
-    props_sorted.sort_by(|a, b| b.name.len().cmp(&a.name.len()));
+    None
+  }
 
-    for prop in &props_sorted {
-      let this_access = format!("this.{}", prop.name);
-      let prop_access = format!("props.{}", prop.name);
-      result = result.replace(&this_access, &prop_access);
+  /// Whether the member access ending at `end` (and starting at `start`) is written to: assigned
+  /// (`=`, `+=`, `??=`, ...), incremented or decremented
+  pub fn is_written(code: &str, start: usize, end: usize) -> bool {
+    let after = code[end..].trim_start();
+    let before = code[..start].trim_end();
+    if after.starts_with("++")
+      || after.starts_with("--")
+      || before.ends_with("++")
+      || before.ends_with("--")
+    {
+      return true;
     }
 
-    // Transform method calls (this.method() -> method())
-    // Sort by length (longest first) to prevent substring replacements
-    let mut methods_sorted = context.script_state.methods.clone();
-    methods_sorted.sort_by(|a, b| b.len().cmp(&a.len()));
-
-    for method in &methods_sorted {
-      let this_call = format!("this.{}(", method);
-      let direct_call = format!("{}(", method);
-      result = result.replace(&this_call, &direct_call);
-    }
+    let operator_end = after
+      .find(|c: char| !"+-*/%&|^<>?=!".contains(c))
+      .unwrap_or(after.len());
+    let operator = &after[..operator_end];
+    operator.ends_with('=') && !matches!(operator, "==" | "===" | "!=" | "!==" | "<=" | ">=")
+  }
 
-    // Transform method references (this.method -> method)
-    // This handles method references (not calls) like in event listeners
-    for method in &methods_sorted {
-      let this_ref = format!("this.{}", method);
-      let direct_ref = method;
-
-      // Use simple replace for method references (not followed by parentheses)
-      // This handles cases like: this.handleEvent -> handleEvent
-      if result.contains(&this_ref) {
-        // Only replace if it's not a method call (not followed by '(')
-        let this_call = format!("this.{}(", method);
-        if !result.contains(&this_call) {
-          result = result.replace(&this_ref, direct_ref);
-        }
+  /// Apply reactive reference transformations to a body string
+  pub fn apply_reactive_transforms(
+    body: &str,
+    context: &TransformationContext,
+    config: &TransformerConfig,
+    transformation_result: Option<&crate::TransformationResult>,
+  ) -> String {
+    // Each `this.x` resolves to exactly one member of the component, so a member whose name
+    // starts with another member's name (`total` and `totalPrice`) is never rewritten twice
+    let symbols = context.script_state.symbols();
+    let renamed = &context.script_state.renamed_members;
+    let renamed_to = |key: &str| {
+      renamed
+        .iter()
+        .find(|(original, _)| original == key)
+        .map(|(_, name)| name.clone())
+    };
+
+    // `this['on-save']` is the same member access as `this.onSave`
+    let body = THIS_STRING_KEY_PATTERN.replace_all(body, |caps: &regex::Captures| {
+      let key = caps.get(2).or(caps.get(3)).map_or("", |key| key.as_str());
+      let name = renamed_to(key)
+        .or_else(|| symbols.iter().any(|symbol| symbol.name == key).then(|| key.to_string()));
+      match name {
+        Some(name) => format!("{}this.{}", &caps[1], name),
+        None => caps[0].to_string(),
       }
-    }
-
-    // Handle any remaining this.* references that weren't transformed
-    // These are likely non-existent variables that should get FIXME comments
-    {
-      let re = &*THIS_PROPERTY_PATTERN;
-      result = re
-        .replace_all(&result, |caps: &regex::Captures| {
-          let var_name = &caps[1];
-
-          // Check if this variable exists in any known scope
-          let exists_in_data = context
-            .script_state
-            .data_properties
-            .iter()
-            .any(|prop| prop.name == var_name);
-          let exists_in_computed = context
-            .script_state
-            .computed_properties
-            .iter()
-            .any(|prop| prop == var_name);
-          let exists_in_props = context
-            .script_state
-            .props
-            .iter()
-            .any(|prop| prop.name == var_name);
-          let exists_in_methods = context
-            .script_state
-            .methods
-            .iter()
-            .any(|method| method == var_name);
-
-          // Check if this identifier was resolved by a transformer
-          let is_resolved_by_transformer = transformation_result
-            .map(|result| {
-              result.resolved_identifiers.contains(&var_name.to_string())
-                || result.skip_data_properties.contains(&var_name.to_string())
-            })
-            .unwrap_or(false);
-
-          // Check if this is a known Vue/framework variable that should be handled by transformers
-          let is_framework_variable = matches!(
-            var_name,
-            "$axios"
-              | "$http"
-              | "$api"
-              | "$fetch"
-              | "$route"
-              | "$router"
-              | "$store"
-              | "$vuex"
-              | "$t"
-              | "$i18n"
-              | "$n"
-              | "$d"
-              | "$config"
-              | "$nextTick"
-              | "$refs"
-              | "$emit"
-              | "$nuxt"
-              | "$options"
-              | "$parent"
-              | "$children"
-              | "$el"
-              | "$data"
-              | "$props"
-              | "$attrs"
-              | "$slots"
-              | "$scopedSlots"
-              | "$set"
-              | "$delete"
-              | "$watch"
-              | "$forceUpdate"
-              | "$destroy"
-          );
-
-          // Check if this property is provided by a mixin composable
-          let is_mixin_property = if let Some(mixin_configs) = &config.mixins {
-            mixin_configs
-              .values()
-              .any(|mixin_config| mixin_config.imports.contains(&var_name.to_string()))
-          } else {
-            false
-          };
+    });
 
-          if exists_in_data
-            || exists_in_computed
-            || exists_in_props
-            || exists_in_methods
-            || is_resolved_by_transformer
+    let is_getter_only = |name: &str| {
+      context
+        .script_state
+        .computed_details
+        .iter()
+        .any(|computed| computed.name == name && computed.setter.is_none())
+    };
+
+    THIS_PROPERTY_PATTERN
+      .replace_all(&body, |caps: &regex::Captures| {
+        let prefix = &caps[1];
+        let var_name = renamed_to(&caps[2]).unwrap_or_else(|| caps[2].to_string());
+        let var_name = var_name.as_str();
+        let access = caps.get(2).map_or(0..0, |name| name.start() - "this.".len()..name.end());
+
+        let kind = symbols
+          .iter()
+          .find(|symbol| symbol.name == var_name)
+          .map(|symbol| symbol.kind);
+        match kind {
+          Some(SymbolKind::Prop) => return format!("{}props.{}", prefix, var_name),
+          Some(SymbolKind::Computed)
+            if is_getter_only(var_name) && is_written(&body, access.start, access.end) =>
           {
-            // This should have been transformed by earlier logic, or is resolved by a transformer
-            // Just remove the 'this.' for now
-            var_name.to_string()
-          } else if is_framework_variable || is_mixin_property {
-            // This is a framework variable or mixin property that should be handled by a transformer
-            // but apparently wasn't - don't add FIXME, just remove 'this.'
-            var_name.to_string()
-          } else {
-            // This variable doesn't exist in the component and isn't a known framework variable
-            // Add FIXME comment
-            format!("/* FIXME: {} */ {}", var_name, var_name)
+            // Writing to a computed without setter throws at runtime, leave it to the developer
+            return format!("{}/* FIXME: {} */ {}.value", prefix, var_name, var_name);
           }
-        })
-        .to_string();
-    }
+          Some(SymbolKind::Data)
+            if super::vue2::Vue2Transformer::observable_data(context, var_name).is_some() =>
+          {
+            return format!("{}{}", prefix, var_name)
+          }
+          Some(SymbolKind::Data | SymbolKind::Computed) => {
+            return format!("{}{}.value", prefix, var_name)
+          }
+          Some(SymbolKind::Method) => return format!("{}{}", prefix, var_name),
+          None => {}
+        }
 
-    result
+        // Check if this identifier was resolved by a transformer
+        let is_resolved_by_transformer = transformation_result
+          .map(|result| {
+            result.resolved_identifiers.contains(&var_name.to_string())
+              || result.skip_data_properties.contains(&var_name.to_string())
+          })
+          .unwrap_or(false);
+
+        // Check if this is a known Vue/framework variable that should be handled by transformers
+        let is_framework_variable = matches!(
+          var_name,
+          "$axios"
+            | "$http"
+            | "$api"
+            | "$fetch"
+            | "$route"
+            | "$router"
+            | "$store"
+            | "$vuex"
+            | "$t"
+            | "$tc"
+            | "$te"
+            | "$tm"
+            | "$i18n"
+            | "$n"
+            | "$d"
+            | "$config"
+            | "$nextTick"
+            | "$refs"
+            | "$emit"
+            | "$nuxt"
+            | "$options"
+            | "$parent"
+            | "$children"
+            | "$el"
+            | "$data"
+            | "$props"
+            | "$attrs"
+            | "$slots"
+            | "$scopedSlots"
+            | "$set"
+            | "$delete"
+            | "$watch"
+            | "$forceUpdate"
+            | "$destroy"
+        );
+
+        // Check if this property is provided by a mixin composable
+        let is_mixin_property = if let Some(mixin_configs) = &config.mixins {
+          mixin_configs
+            .values()
+            .any(|mixin_config| mixin_config.imports.contains(&var_name.to_string()))
+        } else {
+          false
+        };
+
+        if is_resolved_by_transformer || is_framework_variable || is_mixin_property {
+          // Resolved by a transformer, or a framework variable or mixin property that should be
+          // handled by a transformer but apparently wasn't - don't add FIXME, just remove 'this.'
+          format!("{}{}", prefix, var_name)
+        } else {
+          // This variable doesn't exist in the component and isn't a known framework variable
+          // Add FIXME comment
+          format!("{}/* FIXME: {} */ {}", prefix, var_name, var_name)
+        }
+      })
+      .to_string()
   }
 
   /// Apply all common body transformations
@@ -241,7 +326,9 @@ pub mod body_transforms {
     additional_transforms: &[Box<super::BodyTransformFn>],
     transformation_result: Option<&crate::TransformationResult>,
   ) -> String {
-    let mut transformed_body = body.to_string();
+    // Keep strings, comments and `this` of nested callbacks out of the component conversions
+    let (body, literals) = super::this_scope::mask_literals(body);
+    let mut transformed_body = super::this_scope::isolate_callback_this(&body);
 
     // Apply additional transforms from transformers (including i18n)
     for transform_fn in additional_transforms {
@@ -252,7 +339,8 @@ pub mod body_transforms {
     transformed_body =
       apply_reactive_transforms(&transformed_body, context, config, transformation_result);
 
-    transformed_body
+    let transformed_body = super::this_scope::restore_callback_this(&transformed_body);
+    super::this_scope::restore_literals(&transformed_body, &literals)
   }
 }
 
@@ -270,24 +358,46 @@ impl Default for TransformerOrchestrator {
 impl TransformerOrchestrator {
   pub fn new() -> Self {
     let transformers: Vec<Box<dyn Transformer>> = vec![
+      Box::new(slots::SlotsTransformer::new()),
+      Box::new(list_rendering::ListRenderingTransformer::new()),
+      Box::new(transitions::TransitionsTransformer::new()),
+      Box::new(style::StyleTransformer::new()),
       Box::new(axios::AxiosTransformer::new()),
       Box::new(import_rewrite::ImportRewriteTransformer::new()),
       Box::new(mixin::MixinTransformer::new()),
       Box::new(nuxt::NuxtTransformer::new()),
       Box::new(router::RouterTransformer::new()),
       Box::new(vue2::Vue2Transformer::new()),
+      Box::new(components::ComponentsTransformer::new()),
       Box::new(filters::FiltersTransformer::new()),
+      Box::new(date_plugin::DatePluginTransformer::new()),
+      Box::new(css_modules::CssModulesTransformer::new()),
+      Box::new(global_properties::GlobalPropertiesTransformer::new()),
+      Box::new(instance_properties::InstancePropertiesTransformer::new()),
       Box::new(vuex::VuexTransformer::new()),
       Box::new(composition::CompositionTransformer::new()),
+      Box::new(event_bus::EventBusTransformer::new()),
       Box::new(emit::EmitTransformer::new()),
+      Box::new(expose::ExposeTransformer::new()),
+      Box::new(options::OptionsTransformer::new()),
       Box::new(i18n::I18nTransformer::new()),
       Box::new(head::HeadTransformer::new()),
       Box::new(assets::AssetsTransformer::new()),
+      Box::new(analysis::AnalysisTransformer::new()),
+      Box::new(compat::CompatTransformer::new()),
     ];
 
     Self { transformers }
   }
 
+  /// Whether a transformer was not disabled by name (`disable = ["vuex"]`)
+  fn is_enabled(transformer: &dyn Transformer, config: &TransformerConfig) -> bool {
+    !config
+      .disabled_transformers
+      .iter()
+      .any(|name| name == transformer.name())
+  }
+
   /// Collect all body transformation functions from transformers
   pub fn collect_body_transforms(
     &self,
@@ -297,7 +407,7 @@ impl TransformerOrchestrator {
     self
       .transformers
       .iter()
-      .filter(|t| t.should_transform(context, config))
+      .filter(|t| Self::is_enabled(t.as_ref(), config) && t.should_transform(context, config))
       .filter_map(|t| t.get_body_transform())
       .collect()
   }
@@ -332,23 +442,28 @@ impl TransformerOrchestrator {
   }
 
   /// Get a static method for transforming bodies (for use in transformers)
+  ///
+  /// The body transforms of the applicable transformers are collected on the first call, so the
+  /// returned function must only be used for one component; reusing it for the bodies of that
+  /// component avoids checking every transformer again for each body.
   pub fn get_body_transformer(
   ) -> impl Fn(&str, &TransformationContext, &TransformerConfig) -> String {
-    |body: &str, context: &TransformationContext, config: &TransformerConfig| {
-      // Create a temporary orchestrator to get body transforms
-      let orchestrator = TransformerOrchestrator::new();
-      let additional_transforms = orchestrator.collect_body_transforms(context, config);
+    let additional_transforms: OnceCell<Vec<Box<BodyTransformFn>>> = OnceCell::new();
+    move |body: &str, context: &TransformationContext, config: &TransformerConfig| {
+      let additional_transforms = additional_transforms
+        .get_or_init(|| TransformerOrchestrator::new().collect_body_transforms(context, config));
       body_transforms::apply_all_body_transforms(
         body,
         context,
         config,
-        &additional_transforms,
+        additional_transforms,
         None,
       )
     }
   }
 
   /// Transform a Vue component using all applicable transformers
+  #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
   pub fn transform(
     &self,
     context: &TransformationContext,
@@ -360,22 +475,58 @@ impl TransformerOrchestrator {
     let applicable_transformers: Vec<&Box<dyn Transformer>> = self
       .transformers
       .iter()
-      .filter(|t| t.should_transform(context, config))
+      .filter(|t| Self::is_enabled(t.as_ref(), config) && t.should_transform(context, config))
       .collect();
+    log::debug!(
+      "Applying transformers: {}",
+      applicable_transformers
+        .iter()
+        .map(|t| t.name())
+        .collect::<Vec<_>>()
+        .join(", ")
+    );
 
     // Apply each transformer and collect results
     let mut all_results = Vec::new();
-    for transformer in applicable_transformers {
+    for transformer in &applicable_transformers {
+      let _span = trace_span!("transformer", name = transformer.name());
       let transformer_result = transformer.transform(context, config);
+      trace_event!(
+        DEBUG,
+        imports = transformer_result.imports_to_add.values().map(Vec::len).sum::<usize>(),
+        setup = transformer_result.setup.len(),
+        methods = transformer_result.methods.len(),
+        template_replacements = transformer_result.template_replacements.len(),
+        diagnostics = transformer_result.diagnostics.len(),
+        "transformer applied"
+      );
       all_results.push((transformer.name(), transformer_result));
     }
 
     // Merge results with intelligent ordering
+    let _merge = trace_span!("merge");
     self.merge_results_intelligently(&mut result, all_results);
 
     // Fix any remaining FIXME comments for resolved identifiers
     self.fix_remaining_fixme_comments(&mut result);
 
+    // Every binding the template reads needs a declaration, whichever transformer skipped it
+    Self::declare_missing_bindings(context, &mut result);
+
+    // Rewrite the style content, in transformer order
+    if let Some(style) = &context.sfc_sections.style_content {
+      let transformed = applicable_transformers
+        .iter()
+        .filter_map(|t| t.get_style_transform())
+        .fold(style.clone(), |style, transform| {
+          transform(&style, context, config)
+        });
+
+      if transformed != *style {
+        result.style_content = Some(transformed);
+      }
+    }
+
     result
   }
 
@@ -407,6 +558,84 @@ impl TransformerOrchestrator {
     }
   }
 
+  /// Declare the bindings the template reads that no transformer declared (a data property
+  /// skipped by one transformer and not produced by another, a member of a mixin without a config
+  /// entry, ...) as `ref()`s marked with a FIXME, so the gap shows in the output and is reported
+  fn declare_missing_bindings(context: &TransformationContext, result: &mut TransformationResult) {
+    let code = [
+      &result.setup,
+      &result.reactive_state,
+      &result.computed_properties,
+      &result.watchers,
+      &result.methods,
+      &result.lifecycle_hooks,
+      &result.additional_scripts,
+      &crate::format_imports(&result.imports_to_add),
+    ]
+    .iter()
+    .flat_map(|group| group.iter())
+    .map(String::as_str)
+    .collect::<Vec<_>>()
+    .join("\n");
+
+    let is_declared = |name: &str| {
+      let name = regex::escape(name);
+      let declaration = format!(
+        r"\b(?:const|let|var|function|class)\s+{0}\b|\b(?:const|let|var)\s*[{{\[][^=;]*\b{0}\b[^=;]*[}}\]]\s*=|\bimport\b[^;]*\b{0}\b[^;]*\bfrom\b",
+        name
+      );
+      Regex::new(&declaration).is_ok_and(|declaration| declaration.is_match(&code))
+    };
+    // Replacements may rewrite the name away in the template (a renamed model prop, `require()`
+    // of an asset)
+    let is_replaced = |name: &str| {
+      let Ok(word) = Regex::new(&format!(r"(?:^|[^\w$.]){}\b", regex::escape(name))) else {
+        return false;
+      };
+      result
+        .template_replacements
+        .iter()
+        .any(|replacement| word.is_match(&replacement.find) && !word.is_match(&replacement.replace))
+    };
+
+    let mut missing: Vec<&String> = Vec::new();
+    for name in &context.template_state.identifiers {
+      let skipped = name.starts_with('$')
+        || TEMPLATE_GLOBALS.contains(&name.as_str())
+        || context
+          .script_state
+          .props
+          .iter()
+          .any(|prop| &prop.name == name)
+        || missing.contains(&name);
+      if !skipped && !is_replaced(name) && !is_declared(name) {
+        missing.push(name);
+      }
+    }
+    if missing.is_empty() {
+      return;
+    }
+
+    result.add_import("vue", "ref");
+    for name in &missing {
+      result.reactive_state.push(format!(
+        "const {0} = ref(/* FIXME: {0} */ undefined);",
+        name
+      ));
+    }
+    result.diagnostics.push(Diagnostic::warning(
+      "missing-declaration",
+      format!(
+        "No declaration was generated for bindings the template uses ({}), refs marked with a FIXME were added in their place",
+        missing
+          .iter()
+          .map(|name| format!("`{}`", name))
+          .collect::<Vec<_>>()
+          .join(", ")
+      ),
+    ));
+  }
+
   /// Merge results with intelligent ordering using the new structured approach
   fn merge_results_intelligently(
     &self,
@@ -441,11 +670,19 @@ impl TransformerOrchestrator {
       result
         .computed_properties
         .extend(transformer_result.computed_properties);
+      result
+        .define_options
+        .extend(transformer_result.define_options);
       result.methods.extend(transformer_result.methods);
       result.watchers.extend(transformer_result.watchers);
       result
         .lifecycle_hooks
         .extend(transformer_result.lifecycle_hooks);
+      for member in transformer_result.exposed {
+        if !result.exposed.contains(&member) {
+          result.exposed.push(member);
+        }
+      }
 
       // Merge other fields
       result
@@ -461,6 +698,13 @@ impl TransformerOrchestrator {
       result
         .resolved_identifiers
         .extend(transformer_result.resolved_identifiers);
+      result
+        .event_renames
+        .extend(transformer_result.event_renames);
+      result
+        .additional_files
+        .extend(transformer_result.additional_files);
+      result.diagnostics.extend(transformer_result.diagnostics);
 
       // Merge data refs with priority
       for (prop_name, (ref_declaration, priority)) in transformer_result.data_refs {
@@ -478,9 +722,19 @@ impl TransformerOrchestrator {
       }
     }
 
+    result.setup = Self::dedupe_setup(std::mem::take(&mut result.setup));
+
     // Remove imports that should be filtered out
     for package_to_remove in &imports_to_remove {
       result.imports_to_add.remove(package_to_remove);
+
+      // Pre-formatted imports carried over from the original script
+      if let Some(existing) = result.imports_to_add.get_mut("__existing__") {
+        existing.retain(|import| {
+          !import.ends_with(&format!("from '{}';", package_to_remove))
+            && !import.ends_with(&format!("from \"{}\";", package_to_remove))
+        });
+      }
     }
 
     // Process data_refs into reactive_state with proper priority ordering
@@ -504,6 +758,65 @@ impl TransformerOrchestrator {
     }
   }
 
+  /// Remove setup declarations requested by several transformers and merge destructurings of the
+  /// same composable call (`const { a } = useX();` and `const { b } = useX();` become
+  /// `const { a, b } = useX();`). Only single top-level statements are considered, so multi-line
+  /// declarations such as `defineProps({ ... })` are kept as-is.
+  fn dedupe_setup(setup: Vec<String>) -> Vec<String> {
+    let mut deduped: Vec<String> = Vec::new();
+
+    for line in setup {
+      let statement = line.trim();
+      let is_statement = !line.starts_with(char::is_whitespace)
+        && !statement.contains('\n')
+        && statement.ends_with(';');
+      if !is_statement {
+        deduped.push(line);
+        continue;
+      }
+
+      if deduped.iter().any(|existing| existing.trim() == statement) {
+        continue;
+      }
+
+      if let Some(caps) = DESTRUCTURED_SETUP_PATTERN.captures(statement) {
+        let call = &caps[2];
+        let existing = deduped.iter_mut().find(|existing| {
+          DESTRUCTURED_SETUP_PATTERN
+            .captures(existing.trim())
+            .is_some_and(|existing_caps| &existing_caps[2] == call)
+        });
+
+        if let Some(existing) = existing {
+          let existing_caps = DESTRUCTURED_SETUP_PATTERN.captures(existing.trim()).unwrap();
+          let mut names: Vec<&str> = existing_caps[1]
+            .split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .collect();
+          for name in caps[1].split(',').map(str::trim) {
+            if !name.is_empty() && !names.contains(&name) {
+              names.push(name);
+            }
+          }
+
+          let merged = format!("const {{ {} }} = {};", names.join(", "), call);
+          // Keep a trailing line break of the original declaration
+          *existing = if existing.ends_with('\n') {
+            format!("{}\n", merged)
+          } else {
+            merged
+          };
+          continue;
+        }
+      }
+
+      deduped.push(line);
+    }
+
+    deduped
+  }
+
   /// Get a list of all available transformer names
   pub fn available_transformers(&self) -> Vec<&'static str> {
     self.transformers.iter().map(|t| t.name()).collect()
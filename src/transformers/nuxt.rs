@@ -1,10 +1,38 @@
 use super::{BodyTransformFn, Transformer};
-use crate::{TransformationContext, TransformationResult, TransformerConfig};
+use crate::{
+  Diagnostic, NuxtI18nStyle, ReplacementScope, Target, TemplateReplacement, TransformationContext,
+  TransformationResult, TransformerConfig,
+};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::collections::BTreeMap;
+use tree_sitter::Node;
+
+lazy_static! {
+  static ref NUXT_ERROR_PATTERN: Regex =
+    Regex::new(r"(?:this\.)?\$nuxt\.(?:context\.)?error\b").unwrap();
+  static ref QUERY_KEY_PATTERN: Regex = Regex::new(r#"^\s*(?:'([^']*)'|"([^"]*)")\s*$"#).unwrap();
+  static ref IDENTIFIER_PATTERN: Regex = Regex::new(r"^[a-zA-Z_$][\w$]*$").unwrap();
+  static ref FETCH_CALL_PATTERN: Regex = Regex::new(r"(?:this\.)?\$fetch\b").unwrap();
+  static ref FETCH_STATE_PATTERN: Regex =
+    Regex::new(r"(?:this\.)?\$fetchState\.(pending|error)\b").unwrap();
+}
 
 /// Transformer for converting Nuxt.js specific features from Options API to Composition API
 ///
 /// This transformer handles the conversion of Nuxt.js specific methods like `fetch()` and
-/// converts `this.$fetch()` calls to plain `fetch()` calls.
+/// converts `this.$fetch()` calls to plain `fetch()` calls. `$fetchState.pending` and
+/// `$fetchState.error` become refs set by the generated `fetch()`, which is called right away, or
+/// in `onMounted` with `fetchOnServer: false`.
+///
+/// `this.$nuxt.error()` becomes `showError()` from `useNuxtCompat()`, or the function configured
+/// with `nuxt_error`, in the script and the template.
+///
+/// The `watchQuery` page option becomes a watcher on `route.query` re-running `fetch()`, and the
+/// `key` page option is passed to `definePageMeta()` (`define_page_meta`) or reported.
+///
+/// The `nuxtI18n` page options are kept as a whole, either exported as `i18n` from a separate
+/// `<script>` or passed to `defineI18nRoute()` (`nuxt_i18n`).
 pub struct NuxtTransformer;
 
 impl Default for NuxtTransformer {
@@ -18,6 +46,18 @@ impl NuxtTransformer {
     Self
   }
 
+  /// Check if the script or the template calls `$nuxt.error()`
+  fn has_nuxt_error(&self, context: &TransformationContext) -> bool {
+    let sections = &context.sfc_sections;
+    [&sections.script_content, &sections.template_content]
+      .iter()
+      .any(|content| {
+        content
+          .as_ref()
+          .is_some_and(|c| NUXT_ERROR_PATTERN.is_match(c))
+      })
+  }
+
   /// Check if context contains Nuxt fetch method
   fn has_fetch_method(&self, context: &TransformationContext) -> bool {
     context.script_state.fetch_method.is_some()
@@ -42,6 +82,170 @@ impl NuxtTransformer {
         .any(|id| id.contains("$fetch"))
   }
 
+  /// Check if the script or the template reads `$fetchState`
+  fn has_fetch_state(&self, context: &TransformationContext) -> bool {
+    let sections = &context.sfc_sections;
+    [&sections.script_content, &sections.template_content]
+      .iter()
+      .any(|content| content.as_ref().is_some_and(|c| c.contains("$fetchState")))
+  }
+
+  /// Names of the pending and error refs, prefixed with `fetch` when the component already uses
+  /// `pending` or `error`
+  fn fetch_state_names(context: &TransformationContext) -> (String, String) {
+    let state = &context.script_state;
+    let is_taken = |name: &str| {
+      state.data_properties.iter().any(|data| data.name == name)
+        || state.props.iter().any(|prop| prop.name == name)
+        || state.computed_properties.iter().any(|computed| computed == name)
+        || state.methods.iter().any(|method| method == name)
+    };
+
+    let pending = if is_taken("pending") { "fetchPending" } else { "pending" };
+    let error = if is_taken("error") { "fetchError" } else { "error" };
+    (pending.to_string(), error.to_string())
+  }
+
+  /// Rewrite `$fetchState.pending` / `$fetchState.error` to the fetch state refs
+  fn rewrite_fetch_state(code: &str, context: &TransformationContext, in_template: bool) -> String {
+    let (pending, error) = Self::fetch_state_names(context);
+    let suffix = if in_template { "" } else { ".value" };
+
+    FETCH_STATE_PATTERN
+      .replace_all(code, |caps: &regex::Captures| {
+        let name = if &caps[1] == "pending" { &pending } else { &error };
+        format!("{}{}", name, suffix)
+      })
+      .to_string()
+  }
+
+  /// Page option value by name
+  fn page_option<'a>(context: &'a TransformationContext, name: &str) -> Option<&'a str> {
+    context
+      .script_state
+      .page_options
+      .iter()
+      .find(|(key, _)| key == name)
+      .map(|(_, value)| value.as_str())
+  }
+
+  /// Getter for the route query watched by `watchQuery`, `None` when it is a function or lists
+  /// computed keys
+  fn watch_query_source(value: &str) -> Option<String> {
+    let value = value.trim();
+    if value == "true" {
+      return Some("() => route.query".to_string());
+    }
+
+    let items = value.strip_prefix('[')?.strip_suffix(']')?;
+    let accessors = items
+      .split(',')
+      .filter(|item| !item.trim().is_empty())
+      .map(|item| {
+        let caps = QUERY_KEY_PATTERN.captures(item)?;
+        let key = caps.get(1).or_else(|| caps.get(2))?.as_str();
+        Some(if IDENTIFIER_PATTERN.is_match(key) {
+          format!("route.query.{}", key)
+        } else {
+          format!("route.query['{}']", key)
+        })
+      })
+      .collect::<Option<Vec<_>>>()?;
+
+    match accessors.as_slice() {
+      [] => None,
+      [accessor] => Some(format!("() => {}", accessor)),
+      _ => Some(format!("() => [{}]", accessors.join(", "))),
+    }
+  }
+
+  /// Convert the `watchQuery` and `key` page options
+  fn convert_page_options(
+    &self,
+    context: &TransformationContext,
+    config: &TransformerConfig,
+    result: &mut TransformationResult,
+  ) {
+    if let Some(watch_query) = Self::page_option(context, "watchQuery") {
+      // Loaders Nuxt re-runs when the query changes
+      let mut loaders = Vec::new();
+      if self.has_fetch_method(context) {
+        loaders.push("fetch");
+      }
+      if self.has_async_data_method(context) {
+        if config.ssr_target {
+          loaders.push("loadAsyncData");
+        } else {
+          result.diagnostics.push(Diagnostic::warning(
+            "watch-query",
+            "`watchQuery` does not re-run `asyncData`, which is loaded once through `useAsyncData()`",
+          ));
+        }
+      }
+
+      match Self::watch_query_source(watch_query) {
+        Some(source) if !loaders.is_empty() => {
+          result.add_import("vue", "watch");
+          if let Some(module) = config.target.router_module() {
+            result.add_import(module, "useRoute");
+          }
+          result.add_setup("const route = useRoute();".to_string());
+          result.watchers.push(format!("watch({}, () => {{", source));
+          for loader in loaders {
+            result.watchers.push(format!("  {}();", loader));
+          }
+          result.watchers.push("});".to_string());
+          result.watchers.push("".to_string());
+        }
+        Some(_) => {}
+        None => result.diagnostics.push(Diagnostic::warning(
+          "watch-query",
+          format!(
+            "`watchQuery: {}` could not be converted, watch `route.query` manually",
+            watch_query
+          ),
+        )),
+      }
+    }
+
+    if let Some(key) = Self::page_option(context, "key") {
+      if config.define_page_meta {
+        result.setup.push(format!("definePageMeta({{\n  key: {},\n}});", key));
+        result.setup.push("".to_string());
+      } else {
+        result.diagnostics.push(Diagnostic::warning(
+          "page-key",
+          "The `key` page option was dropped, set it on `<router-view :key>` or with `definePageMeta({ key })` in Nuxt 3",
+        ));
+      }
+    }
+  }
+
+  /// Note that component state loaded during SSR is loaded again on the client
+  fn client_fallback_diagnostic(method: &str) -> Diagnostic {
+    Diagnostic::info(
+      "ssr-client-fallback",
+      format!(
+        "`{}` runs in `onServerPrefetch` and again in `onMounted`, since component state is not transferred to the client. Keep the data in a store to load it once",
+        method
+      ),
+    )
+  }
+
+  /// Whether `fetchOnServer` is set to `false`, `None` when it cannot be evaluated
+  fn fetch_on_server(context: &TransformationContext) -> Option<bool> {
+    context
+      .script_state
+      .fetch_options
+      .iter()
+      .find(|(key, _)| key == "fetchOnServer")
+      .map_or(Some(true), |(_, value)| match value.trim() {
+        "true" => Some(true),
+        "false" => Some(false),
+        _ => None,
+      })
+  }
+
   /// Check if context contains nuxtI18n configuration
   fn has_nuxt_i18n(&self, context: &TransformationContext) -> bool {
     context.script_state.nuxt_i18n.is_some()
@@ -91,79 +295,63 @@ impl NuxtTransformer {
         .any(|id| id.contains("$config"))
   }
 
-  /// Generate i18n script block from nuxtI18n configuration
-  fn generate_i18n_script(&self, context: &TransformationContext) -> Option<String> {
-    if let Some(nuxt_i18n_content) = &context.script_state.nuxt_i18n {
-      // Parse the nuxtI18n object to extract paths
-      if let Some(paths_content) = self.extract_paths_from_nuxt_i18n(nuxt_i18n_content) {
-        // Format the paths content properly
-        let formatted_paths = self.format_paths_object(&paths_content);
-        let script_block = format!(
-          "<script>\nexport const i18n = {};\n</script>",
-          formatted_paths
-        );
-        return Some(script_block);
-      }
+  /// Format the `nuxtI18n` value with two-space indentation. It is parsed with tree-sitter, so
+  /// any nesting and formatting is supported; `None` when it is not valid JavaScript.
+  fn format_nuxt_i18n(&self, content: &str) -> Option<String> {
+    let source = format!("const i18n = {};", content);
+    let tree = crate::javascript::parse(&source)?;
+    let root = tree.root_node();
+    if root.has_error() {
+      return None;
     }
-    None
-  }
-
-  /// Extract paths object from nuxtI18n configuration
-  fn extract_paths_from_nuxt_i18n(&self, nuxt_i18n_content: &str) -> Option<String> {
-    // Simple parsing to extract the paths object
-    // Looking for pattern: { paths: { ... }, ... }
-    if let Some(paths_start) = nuxt_i18n_content.find("paths:") {
-      let paths_section = &nuxt_i18n_content[paths_start + 6..];
-
-      // Find the opening brace after "paths:"
-      if let Some(brace_start) = paths_section.find('{') {
-        let mut brace_count = 1;
-        let mut end_pos = brace_start + 1;
-        let chars: Vec<char> = paths_section.chars().collect();
-
-        // Find matching closing brace
-        while end_pos < chars.len() && brace_count > 0 {
-          match chars[end_pos] {
-            '{' => brace_count += 1,
-            '}' => brace_count -= 1,
-            _ => {}
-          }
-          end_pos += 1;
-        }
 
-        if brace_count == 0 {
-          let paths_object = &paths_section[brace_start..end_pos];
-          return Some(paths_object.to_string());
-        }
-      }
+    let declarator = root.named_child(0)?.named_child(0)?;
+    let value = declarator.child_by_field_name("value")?;
+    Some(Self::format_value(&value, &source, 0))
+  }
+
+  /// Format an object or multi-line array one entry per line, other values are kept as written
+  fn format_value(node: &Node, source: &str, indent: usize) -> String {
+    let text = &source[node.byte_range()];
+    let (open, close) = match node.kind() {
+      "object" => ("{", "}"),
+      "array" if text.contains('\n') => ("[", "]"),
+      _ => return text.to_string(),
+    };
+
+    let mut cursor = node.walk();
+    let entries: Vec<Node> = node.named_children(&mut cursor).collect();
+    if entries.is_empty() {
+      return format!("{}{}", open, close);
     }
-    None
-  }
-
-  /// Format the paths object to match expected output
-  fn format_paths_object(&self, paths_content: &str) -> String {
-    // Parse the object and reformat it with proper indentation
-    let mut result = String::from("{\n");
-
-    // Extract key-value pairs from the paths object
-    let content = paths_content
-      .trim()
-      .trim_start_matches('{')
-      .trim_end_matches('}');
-
-    // Split by lines and process each key-value pair
-    for line in content.lines() {
-      let line = line.trim();
-      if !line.is_empty() && line.contains(':') {
-        // Clean up the line and ensure proper formatting
-        let cleaned_line = line.trim_end_matches(',');
-        result.push_str("  ");
-        result.push_str(cleaned_line);
-        result.push_str(",\n");
+
+    let entry_indent = " ".repeat(indent + 2);
+    let mut result = format!("{}\n", open);
+    for entry in entries {
+      result.push_str(&entry_indent);
+      match entry.kind() {
+        "comment" => {
+          result.push_str(&source[entry.byte_range()]);
+          result.push('\n');
+          continue;
+        }
+        "pair" => {
+          let (Some(key), Some(value)) = (
+            entry.child_by_field_name("key"),
+            entry.child_by_field_name("value"),
+          ) else {
+            continue;
+          };
+          result.push_str(&source[key.byte_range()]);
+          result.push_str(": ");
+          result.push_str(&Self::format_value(&value, source, indent + 2));
+        }
+        _ => result.push_str(&Self::format_value(&entry, source, indent + 2)),
       }
+      result.push_str(",\n");
     }
-
-    result.push('}');
+    result.push_str(&" ".repeat(indent));
+    result.push_str(close);
     result
   }
 
@@ -187,14 +375,34 @@ impl NuxtTransformer {
         setup_code.push("const fetch = () => {".to_string());
       }
 
+      // Set the fetch state around the body
+      let fetch_state = self
+        .has_fetch_state(context)
+        .then(|| Self::fetch_state_names(context));
+      let indent = if fetch_state.is_some() { "    " } else { "  " };
+      if let Some((pending, error)) = &fetch_state {
+        setup_code.push(format!("  {}.value = true;", pending));
+        setup_code.push(format!("  {}.value = null;", error));
+        setup_code.push("  try {".to_string());
+      }
+
       // Add the transformed body (with proper indentation)
       for line in transformed_body.lines() {
         if !line.trim().is_empty() {
-          setup_code.push(format!("  {}", line));
+          setup_code.push(format!("{}{}", indent, line));
         }
       }
 
+      if let Some((pending, error)) = &fetch_state {
+        setup_code.push("  } catch (e) {".to_string());
+        setup_code.push(format!("    {}.value = e;", error));
+        setup_code.push("  } finally {".to_string());
+        setup_code.push(format!("    {}.value = false;", pending));
+        setup_code.push("  }".to_string());
+      }
+
       setup_code.push("};".to_string());
+      setup_code.push("".to_string());
     }
 
     setup_code
@@ -209,6 +417,58 @@ impl NuxtTransformer {
     ]
   }
 
+  /// Generate `onServerPrefetch` loading `call`, with an `onMounted` fallback for client-side
+  /// navigation
+  fn generate_ssr_hooks(&self, call: &str) -> Vec<String> {
+    vec![
+      "onServerPrefetch(async () => {".to_string(),
+      format!("  await {}();", call),
+      "});".to_string(),
+      "".to_string(),
+      "onMounted(async () => {".to_string(),
+      format!("  {}();", call),
+      "});".to_string(),
+      "".to_string(),
+    ]
+  }
+
+  /// Generate the asyncData function and a loader assigning its result to the data refs, used
+  /// for SSR targets (`ssr_target`)
+  fn generate_async_data_loader(&self, context: &TransformationContext) -> Vec<String> {
+    let mut setup_code = Vec::new();
+
+    if let Some(async_data_method) = &context.script_state.async_data_method {
+      let params = self.extract_async_data_params(async_data_method);
+      let body = self.extract_async_data_body(async_data_method);
+
+      setup_code.push(format!("const asyncData = async ({}) => {{", params));
+      for line in body.lines() {
+        if !line.trim().is_empty() {
+          setup_code.push(format!("  {}", line));
+        }
+      }
+      setup_code.push("};".to_string());
+      setup_code.push("".to_string());
+
+      setup_code.push("const loadAsyncData = async () => {".to_string());
+      setup_code.push("  const data = await asyncData(context);".to_string());
+      for prop in self.extract_returned_properties(&body) {
+        if context
+          .script_state
+          .data_properties
+          .iter()
+          .any(|dp| dp.name == prop)
+        {
+          setup_code.push(format!("  {}.value = data.{};", prop, prop));
+        }
+      }
+      setup_code.push("};".to_string());
+      setup_code.push("".to_string());
+    }
+
+    setup_code
+  }
+
   /// Generate the asyncData method in Composition API style
   fn generate_async_data_method(
     &self,
@@ -252,9 +512,8 @@ impl NuxtTransformer {
   fn generate_async_data_refs(
     &self,
     context: &TransformationContext,
-  ) -> std::collections::HashMap<String, (String, u8)> {
-    use std::collections::HashMap;
-    let mut data_refs = HashMap::new();
+  ) -> BTreeMap<String, (String, u8)> {
+    let mut data_refs = BTreeMap::new();
 
     if let Some(async_data_method) = &context.script_state.async_data_method {
       let body = self.extract_async_data_body(async_data_method);
@@ -427,6 +686,8 @@ impl Transformer for NuxtTransformer {
       || self.has_nuxt_link_usage(context)
       || self.has_nuxt_redirect(context)
       || self.has_nuxt_refresh(context)
+      || self.has_nuxt_error(context)
+      || !context.script_state.page_options.is_empty()
   }
 
   fn transform(
@@ -453,9 +714,40 @@ impl Transformer for NuxtTransformer {
       used_functions.push("refresh".to_string());
     }
 
-    // Handle $config usage
+    // Handle $nuxt.error usage
+    if self.has_nuxt_error(context) {
+      let nuxt_error = &config.nuxt_error;
+      match &nuxt_error.import_path {
+        Some(import_path) => result.add_import(import_path, &nuxt_error.name),
+        None => used_functions.push(nuxt_error.name.clone()),
+      }
+
+      if let Some(template) = &context.sfc_sections.template_content {
+        for found in NUXT_ERROR_PATTERN.find_iter(template) {
+          let find = found.as_str().to_string();
+          if !result.template_replacements.iter().any(|r| r.find == find) {
+            result.template_replacements.push(crate::TemplateReplacement {
+              find,
+              replace: nuxt_error.name.clone(),
+              scope: crate::ReplacementScope::Expressions,
+            });
+          }
+        }
+      }
+    }
+
+    // The asyncData context comes from the compat composable for SSR targets
+    if config.ssr_target && self.has_async_data_method(context) {
+      used_functions.push("context".to_string());
+    }
+
+    // Handle $config usage, Nuxt 3 provides the runtime config through `useRuntimeConfig()`
     if self.has_config_usage(context) {
-      used_functions.push("runtimeConfig".to_string());
+      if config.target == Target::Nuxt3 {
+        result.add_setup("const runtimeConfig = useRuntimeConfig();".to_string());
+      } else {
+        used_functions.push("runtimeConfig".to_string());
+      }
 
       // Add template replacements for $config
       result
@@ -463,6 +755,7 @@ impl Transformer for NuxtTransformer {
         .push(crate::TemplateReplacement {
           find: "$config".to_string(),
           replace: "runtimeConfig".to_string(),
+          scope: crate::ReplacementScope::Expressions,
         });
     }
 
@@ -477,19 +770,106 @@ impl Transformer for NuxtTransformer {
 
     // Generate fetch method if it exists
     if self.has_fetch_method(context) {
-      // Add onMounted import for fetch
-      result.add_import("vue", "onMounted");
-      
       let fetch_code = self.generate_fetch_method(context, config);
       result.methods.extend(fetch_code);
-      
-      // Add onMounted lifecycle hook to call fetch
-      let fetch_lifecycle = self.generate_fetch_lifecycle_hook();
-      result.lifecycle_hooks.extend(fetch_lifecycle);
+
+      // Call fetch right away like Nuxt does during SSR (in onServerPrefetch for SSR targets), or
+      // once mounted for client-only fetches
+      let on_server = Self::fetch_on_server(context);
+      if on_server.is_none() {
+        result.diagnostics.push(Diagnostic::warning(
+          "fetch-on-server",
+          "`fetchOnServer` could not be evaluated, `fetch()` is treated as fetching on the server",
+        ));
+      }
+      match on_server {
+        Some(false) => {
+          result.add_import("vue", "onMounted");
+          let fetch_lifecycle = self.generate_fetch_lifecycle_hook();
+          result.lifecycle_hooks.extend(fetch_lifecycle);
+        }
+        _ if config.ssr_target => {
+          result.add_imports("vue", &["onMounted", "onServerPrefetch"]);
+          result.lifecycle_hooks.extend(self.generate_ssr_hooks("fetch"));
+          result.diagnostics.push(Self::client_fallback_diagnostic("fetch"));
+        }
+        _ => result.lifecycle_hooks.push("fetch();".to_string()),
+      }
+
+      for (key, _) in &context.script_state.fetch_options {
+        if key != "fetchOnServer" {
+          result.diagnostics.push(Diagnostic::info(
+            "fetch-option",
+            format!("`{}` has no equivalent for the generated `fetch()` and was dropped", key),
+          ));
+        }
+      }
+    }
+
+    // Template calls of $fetch refer to the generated fetch()
+    let template_refetch = context
+      .sfc_sections
+      .template_content
+      .as_ref()
+      .is_some_and(|template| FETCH_CALL_PATTERN.is_match(template));
+    if self.has_fetch_method(context) && template_refetch {
+      result.template_replacements.push(TemplateReplacement {
+        find: "$fetch".to_string(),
+        replace: "fetch".to_string(),
+        scope: ReplacementScope::Identifiers,
+      });
+    }
+
+    // Generate refs for $fetchState
+    if self.has_fetch_state(context) {
+      let (pending, error) = Self::fetch_state_names(context);
+      result.add_import("vue", "ref");
+      for (name, value) in [(pending, "false"), (error, "null")] {
+        result
+          .data_refs
+          .insert(name.clone(), (format!("const {} = ref({});", name, value), 0));
+        result.resolved_identifiers.push(name);
+      }
+
+      if let Some(template) = &context.sfc_sections.template_content {
+        for caps in FETCH_STATE_PATTERN.captures_iter(template) {
+          let find = caps[0].to_string();
+          if !result.template_replacements.iter().any(|r| r.find == find) {
+            result.template_replacements.push(TemplateReplacement {
+              replace: Self::rewrite_fetch_state(&find, context, true),
+              find,
+              scope: ReplacementScope::Expressions,
+            });
+          }
+        }
+      }
+
+      let sections = &context.sfc_sections;
+      let has_leftovers = [&sections.script_content, &sections.template_content]
+        .iter()
+        .filter_map(|content| content.as_ref())
+        .any(|content| Self::rewrite_fetch_state(content, context, true).contains("$fetchState"));
+      if has_leftovers {
+        result.diagnostics.push(Diagnostic::warning(
+          "fetch-state",
+          "`$fetchState` is only converted for `pending` and `error`, other uses (e.g. `timestamp`) were left as-is",
+        ));
+      }
     }
 
     // Generate asyncData method if it exists
-    if self.has_async_data_method(context) {
+    if self.has_async_data_method(context) && config.ssr_target {
+      result.add_imports("vue", &["onMounted", "onServerPrefetch"]);
+      result
+        .methods
+        .extend(self.generate_async_data_loader(context));
+      result
+        .lifecycle_hooks
+        .extend(self.generate_ssr_hooks("loadAsyncData"));
+      result
+        .diagnostics
+        .push(Self::client_fallback_diagnostic("asyncData"));
+    } else if self.has_async_data_method(context) {
       result.add_import("@/composables/useAsyncData", "useAsyncData");
 
       let async_data_code = self.generate_async_data_method(context, config);
@@ -500,27 +880,40 @@ impl Transformer for NuxtTransformer {
       result.data_refs.extend(async_data_refs);
     }
 
-    // Generate i18n script block if nuxtI18n exists
-    if self.has_nuxt_i18n(context) {
-      // Add imports for i18n
-      if let Some(i18n_script) = self.generate_i18n_script(context) {
-        result.additional_scripts.push(i18n_script);
+    // Keep the nuxtI18n page options
+    if let Some(nuxt_i18n_content) = &context.script_state.nuxt_i18n {
+      let options = self
+        .format_nuxt_i18n(nuxt_i18n_content)
+        .unwrap_or_else(|| nuxt_i18n_content.trim().to_string());
+      match config.nuxt_i18n {
+        NuxtI18nStyle::Export => result.additional_scripts.push(format!(
+          "<script>\nexport const i18n = {};\n</script>",
+          options
+        )),
+        NuxtI18nStyle::DefineI18nRoute => {
+          result.setup.push(format!("defineI18nRoute({});", options));
+          result.setup.push("".to_string());
+        }
       }
     }
 
-    // Handle nuxt-link to router-link transformation
-    if self.has_nuxt_link_usage(context) {
+    self.convert_page_options(context, config, &mut result);
+
+    // Handle nuxt-link to router-link transformation, Nuxt 2 still renders `<nuxt-link>` with Vue 2.7
+    if self.has_nuxt_link_usage(context) && config.target != Target::Vue27 {
       result
         .template_replacements
         .push(crate::TemplateReplacement {
           find: "nuxt-link".to_string(),
           replace: "router-link".to_string(),
+          scope: crate::ReplacementScope::Template,
         });
       result
         .template_replacements
         .push(crate::TemplateReplacement {
           find: "NuxtLink".to_string(),
           replace: "router-link".to_string(),
+          scope: crate::ReplacementScope::Template,
         });
     }
 
@@ -529,15 +922,21 @@ impl Transformer for NuxtTransformer {
 
   fn get_body_transform(&self) -> Option<Box<BodyTransformFn>> {
     Some(Box::new(
-      |body: &str, context: &TransformationContext, _config: &TransformerConfig| {
+      |body: &str, context: &TransformationContext, config: &TransformerConfig| {
         let nuxt_transformer = NuxtTransformer::new();
         let mut transformed_body = body.to_string();
 
+        // Transform this.$fetchState reads to the fetch state refs
+        if nuxt_transformer.has_fetch_state(context) {
+          transformed_body =
+            NuxtTransformer::rewrite_fetch_state(&transformed_body, context, false);
+        }
+
         // Transform this.$fetch() calls to fetch() calls
         if nuxt_transformer.has_fetch_calls(context) {
-          transformed_body = transformed_body.replace("this.$fetch", "fetch");
-          // Also handle cases where 'this.' was already removed by other transformations
-          transformed_body = transformed_body.replace("$fetch", "fetch");
+          transformed_body = FETCH_CALL_PATTERN
+            .replace_all(&transformed_body, "fetch")
+            .to_string();
         }
 
         // Transform $nuxt event bus calls to eventBus calls
@@ -564,6 +963,13 @@ impl Transformer for NuxtTransformer {
           transformed_body = transformed_body.replace("this.$nuxt.refresh", "refresh");
         }
 
+        // Transform $nuxt.error usage in script
+        if nuxt_transformer.has_nuxt_error(context) {
+          transformed_body = NUXT_ERROR_PATTERN
+            .replace_all(&transformed_body, regex::NoExpand(&config.nuxt_error.name))
+            .to_string();
+        }
+
         transformed_body
       },
     ))
@@ -0,0 +1,280 @@
+use super::{Transformer, TransformerOrchestrator};
+use crate::{
+  CustomOptionConfig, Diagnostic, TransformationContext, TransformationResult, TransformerConfig,
+};
+use lazy_static::lazy_static;
+use regex::Regex;
+
+lazy_static! {
+  static ref IDENTIFIER_PATTERN: Regex = Regex::new(r"[A-Za-z_$][\w$]*").unwrap();
+  // Method shorthand: (1) async, (2) parameters, (3) body
+  static ref METHOD_OPTION_PATTERN: Regex =
+    Regex::new(r"^(async\s+)?[\w$]+\s*\(([^)]*)\)\s*(\{[\s\S]*\})$").unwrap();
+}
+
+/// What the conversion does with a component option no parser handles
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OptionDisposition {
+  /// Converted by another transformer (the emitted events, the mixin composables, ...)
+  Converted,
+  /// Kept as is with `defineOptions()`
+  DefineOptions,
+  /// Dropped, with a warning saying what to do instead
+  Dropped(&'static str),
+}
+
+/// Dispositions of the options the parser does not handle itself, anything else is reported as
+/// unknown
+pub const OPTION_DISPOSITIONS: [(&str, OptionDisposition); 24] = [
+  ("name", OptionDisposition::DefineOptions),
+  ("inheritAttrs", OptionDisposition::DefineOptions),
+  ("emits", OptionDisposition::Converted),
+  ("filters", OptionDisposition::Converted),
+  ("mixins", OptionDisposition::Converted),
+  (
+    "serverCacheKey",
+    OptionDisposition::Dropped("component caching was removed from the Vue 3 server renderer"),
+  ),
+  (
+    "delimiters",
+    OptionDisposition::Dropped("set `compilerOptions.delimiters` in the build configuration"),
+  ),
+  (
+    "comments",
+    OptionDisposition::Dropped("set `compilerOptions.comments` in the build configuration"),
+  ),
+  (
+    "model",
+    OptionDisposition::Dropped(
+      "Vue 3 binds `v-model` to `modelValue`, configure the prop and event with `[emit_mapping]`",
+    ),
+  ),
+  (
+    "provide",
+    OptionDisposition::Dropped("call `provide()` in the script instead"),
+  ),
+  (
+    "inject",
+    OptionDisposition::Dropped("call `inject()` in the script instead"),
+  ),
+  (
+    "extends",
+    OptionDisposition::Dropped("merge the extended component or turn it into a composable"),
+  ),
+  (
+    "directives",
+    OptionDisposition::Dropped("declare local directives as `vName` variables in the script"),
+  ),
+  (
+    "functional",
+    OptionDisposition::Dropped("functional components are plain functions in Vue 3"),
+  ),
+  (
+    "template",
+    OptionDisposition::Dropped("move the template into the `<template>` block"),
+  ),
+  (
+    "render",
+    OptionDisposition::Dropped(
+      "move the render function into a `<template>` or a `.jsx` component",
+    ),
+  ),
+  (
+    "setup",
+    OptionDisposition::Dropped("merge its code into the generated script"),
+  ),
+  (
+    "errorCaptured",
+    OptionDisposition::Dropped("move the hook into `onErrorCaptured()`"),
+  ),
+  (
+    "serverPrefetch",
+    OptionDisposition::Dropped("move the hook into `onServerPrefetch()`"),
+  ),
+  (
+    "layout",
+    OptionDisposition::Dropped("set it with `definePageMeta()` in Nuxt 3"),
+  ),
+  (
+    "middleware",
+    OptionDisposition::Dropped("set it with `definePageMeta()` in Nuxt 3"),
+  ),
+  (
+    "transition",
+    OptionDisposition::Dropped("set `pageTransition` with `definePageMeta()` in Nuxt 3"),
+  ),
+  (
+    "scrollToTop",
+    OptionDisposition::Dropped("set `scrollToTop` with `definePageMeta()` in Nuxt 3"),
+  ),
+  (
+    "validate",
+    OptionDisposition::Dropped("set `validate` with `definePageMeta()` in Nuxt 3"),
+  ),
+];
+
+/// Transformer handling the component options the conversion has no dedicated support for
+///
+/// Each option gets the disposition of [`OPTION_DISPOSITIONS`]:
+/// - Options `<script setup>` can still declare (`inheritAttrs`) are kept with `defineOptions()`
+/// - Options without a Vue 3 equivalent (`serverCacheKey`) or that are not converted (`provide`,
+///   `extends`) are dropped with a warning
+/// - Options converted elsewhere are left alone, except for mixins without a `[mixins]` entry,
+///   which are reported
+/// - Any other option is reported as unknown
+///
+/// Options with a `[custom_options]` entry are converted to its code instead, e.g.
+/// `analytics: { page: 'cart' }` to `useAnalytics({ page: 'cart' });` after the methods.
+pub struct OptionsTransformer;
+
+impl Default for OptionsTransformer {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl OptionsTransformer {
+  pub fn new() -> Self {
+    Self
+  }
+
+  pub fn disposition(option: &str) -> Option<OptionDisposition> {
+    OPTION_DISPOSITIONS
+      .iter()
+      .find(|(name, _)| *name == option)
+      .map(|(_, disposition)| *disposition)
+  }
+
+  /// Code of an option with a `[custom_options]` entry, method shorthands are passed as
+  /// arrow functions (`track() { ... }` becomes `() => { ... }`)
+  fn custom_option_code(
+    custom_option: &CustomOptionConfig,
+    value: &str,
+    context: &TransformationContext,
+    config: &TransformerConfig,
+  ) -> String {
+    let value = match METHOD_OPTION_PATTERN.captures(value.trim()) {
+      Some(caps) => format!(
+        "{}({}) => {}",
+        caps.get(1).map_or("", |m| m.as_str()),
+        &caps[2],
+        &caps[3]
+      ),
+      None => value.trim().to_string(),
+    };
+    let body_transformer = TransformerOrchestrator::get_body_transformer();
+    let body = body_transformer(&value, context, config);
+
+    let code = custom_option.emit.trim().replace("{body}", &body);
+    match code.ends_with(';') || code.ends_with('}') {
+      true => code,
+      false => format!("{};", code),
+    }
+  }
+
+  /// Mixins of the `mixins` option whose import matches no `[mixins]` entry
+  fn unconfigured_mixins(
+    context: &TransformationContext,
+    config: &TransformerConfig,
+    value: &str,
+  ) -> Vec<String> {
+    IDENTIFIER_PATTERN
+      .find_iter(value)
+      .map(|identifier| identifier.as_str().to_string())
+      .filter(|identifier| {
+        let source = context.script_state.imports.iter().find(|import_info| {
+          import_info
+            .imports
+            .iter()
+            .any(|item| item.alias.as_ref().unwrap_or(&item.name) == identifier)
+        });
+        !source.is_some_and(|import_info| {
+          config.mixins.as_ref().is_some_and(|mixins| {
+            mixins
+              .keys()
+              .any(|mixin| import_info.source.contains(mixin.as_str()))
+          })
+        })
+      })
+      .collect()
+  }
+}
+
+impl Transformer for OptionsTransformer {
+  fn name(&self) -> &'static str {
+    "options"
+  }
+
+  fn should_transform(&self, context: &TransformationContext, _config: &TransformerConfig) -> bool {
+    !context.script_state.other_options.is_empty()
+  }
+
+  fn transform(
+    &self,
+    context: &TransformationContext,
+    config: &TransformerConfig,
+  ) -> TransformationResult {
+    let mut result = TransformationResult::new();
+
+    for (option, value) in &context.script_state.other_options {
+      let custom_option = config
+        .custom_options
+        .as_ref()
+        .and_then(|custom_options| custom_options.get(option));
+      if let Some(custom_option) = custom_option {
+        if let Some(import_path) = &custom_option.import_path {
+          let imports: Vec<&str> = custom_option.imports.iter().map(String::as_str).collect();
+          result.add_imports(import_path, &imports);
+        }
+        // After the declarations of the component, which the value may refer to, like `useHead()`
+        result.methods.push(Self::custom_option_code(
+          custom_option,
+          value,
+          context,
+          config,
+        ));
+        continue;
+      }
+
+      match Self::disposition(option) {
+        Some(OptionDisposition::DefineOptions) => {
+          result.define_options.push(format!("{}: {}", option, value));
+        }
+        Some(OptionDisposition::Dropped(hint)) => {
+          result.diagnostics.push(Diagnostic::warning(
+            "option-dropped",
+            format!("The `{}` option was dropped, {}", option, hint),
+          ));
+        }
+        Some(OptionDisposition::Converted) if option == "mixins" => {
+          let unconfigured = Self::unconfigured_mixins(context, config, value);
+          if !unconfigured.is_empty() {
+            result.diagnostics.push(Diagnostic::warning(
+              "mixin-not-configured",
+              format!(
+                "Mixins without a `[mixins]` entry ({}) were dropped, configure the composables replacing them",
+                unconfigured
+                  .iter()
+                  .map(|mixin| format!("`{}`", mixin))
+                  .collect::<Vec<_>>()
+                  .join(", ")
+              ),
+            ));
+          }
+        }
+        Some(OptionDisposition::Converted) => {}
+        None => {
+          result.diagnostics.push(Diagnostic::warning(
+            "option-unknown",
+            format!(
+              "The `{}` option is not supported and was dropped, convert it by hand",
+              option
+            ),
+          ));
+        }
+      }
+    }
+
+    result
+  }
+}
@@ -1,17 +1,47 @@
+use super::body_transforms::argument_bounds;
 use super::{BodyTransformFn, Transformer};
-use crate::{TemplateReplacement, TransformationContext, TransformationResult, TransformerConfig};
+use crate::{
+  template_expressions, Diagnostic, ReplacementScope, TemplateReplacement, TransformationContext,
+  TransformationResult, TransformerConfig,
+};
+use lazy_static::lazy_static;
+use regex::Regex;
+
+lazy_static! {
+  // `\b` keeps `$route` from matching `$router`
+  static ref ROUTE_PATTERN: Regex = Regex::new(r"(?:\bthis\.)?\$route\b").unwrap();
+  static ref ROUTER_PATTERN: Regex = Regex::new(r"(?:\bthis\.)?\$router\b").unwrap();
+  static ref CURRENT_ROUTE_PATTERN: Regex =
+    Regex::new(r"(?:\bthis\.)?\$router\.currentRoute\b").unwrap();
+  static ref ROUTER_APP_PATTERN: Regex = Regex::new(r"\$router\.app\b").unwrap();
+  // Matches both `$router.push(` and `router.push(`
+  static ref NAVIGATION_CALL_PATTERN: Regex = Regex::new(r"\brouter\.(?:push|replace)\(").unwrap();
+  // An empty handler, the Vue Router 3 workaround for duplicate navigation errors
+  static ref NOOP_CATCH_PATTERN: Regex = Regex::new(
+    r"^\s*\.catch\(\s*(?:(?:\(\s*[\w$]*\s*\)|[\w$]+)\s*=>\s*(?:\{\s*\}|null|undefined|void 0)|function\s*\(\s*[\w$]*\s*\)\s*\{\s*\})\s*\)"
+  )
+  .unwrap();
+}
 
 /// Transformer for converting Vue Router usage from Options API to Composition API
 ///
 /// This transformer handles the conversion of:
 /// - `this.$route` -> `route` (from useRoute())
 /// - `this.$router` -> `router` (from useRouter())
+/// - `this.$router.currentRoute` -> `router.currentRoute.value`
+/// - `.catch(() => {})` after `push`/`replace` is removed, since Vue Router 4 resolves duplicate
+///   navigations instead of rejecting them
+///
+/// `$router.app` has no Vue Router 4 equivalent and is reported. For the `nuxt3` target the
+/// composables are auto-imported, so no `vue-router` import is added.
+/// Template usage in mustaches and directive values (`v-if="$route.params.id"`,
+/// `:to="{ query: $route.query }"`) also adds the composable, and is rewritten as an identifier.
 pub struct RouterTransformer;
 
 impl Default for RouterTransformer {
-    fn default() -> Self {
-        Self::new()
-    }
+  fn default() -> Self {
+    Self::new()
+  }
 }
 
 impl RouterTransformer {
@@ -35,12 +65,12 @@ impl RouterTransformer {
       .script_state
       .identifiers
       .iter()
-      .any(|id| id.contains("$route"))
+      .any(|id| ROUTE_PATTERN.is_match(id))
       || context
         .script_state
         .function_calls
         .iter()
-        .any(|call| call.contains("$route"))
+        .any(|call| ROUTE_PATTERN.is_match(call))
   }
 
   /// Check for $router in identifiers and function calls
@@ -49,12 +79,12 @@ impl RouterTransformer {
       .script_state
       .identifiers
       .iter()
-      .any(|id| id.contains("$router"))
+      .any(|id| ROUTER_PATTERN.is_match(id))
       || context
         .script_state
         .function_calls
         .iter()
-        .any(|call| call.contains("$router"))
+        .any(|call| ROUTER_PATTERN.is_match(call))
   }
 
   /// Check for $route usage in method bodies
@@ -63,7 +93,7 @@ impl RouterTransformer {
       .script_state
       .method_details
       .iter()
-      .any(|method| method.body.contains("$route"))
+      .any(|method| ROUTE_PATTERN.is_match(&method.body))
   }
 
   /// Check for $router usage in method bodies
@@ -72,25 +102,53 @@ impl RouterTransformer {
       .script_state
       .method_details
       .iter()
-      .any(|method| method.body.contains("$router"))
+      .any(|method| ROUTER_PATTERN.is_match(&method.body))
   }
 
-  /// Check for $route usage in template
+  /// Check for $route usage in template expressions
   fn has_route_in_template(&self, context: &TransformationContext) -> bool {
-    if let Some(template_content) = &context.sfc_sections.template_content {
-      template_content.contains("$route")
-    } else {
-      false
-    }
+    Self::template_matches(context, &ROUTE_PATTERN)
   }
 
-  /// Check for $router usage in template
+  /// Check for $router usage in template expressions
   fn has_router_in_template(&self, context: &TransformationContext) -> bool {
-    if let Some(template_content) = &context.sfc_sections.template_content {
-      template_content.contains("$router")
-    } else {
-      false
+    Self::template_matches(context, &ROUTER_PATTERN)
+  }
+
+  /// Remove empty `.catch()` handlers chained to `router.push()`/`router.replace()`, returning the
+  /// code and the number of handlers removed
+  fn strip_navigation_catches(code: &str) -> (String, usize) {
+    let mut result = code.to_string();
+    let mut removed = 0;
+    let mut search_from = 0;
+
+    while let Some(call) = NAVIGATION_CALL_PATTERN.find_at(&result, search_from) {
+      let Some(close) =
+        argument_bounds(&result, call.end() - 1).and_then(|bounds| bounds.last().copied())
+      else {
+        break;
+      };
+      search_from = close + 1;
+      if let Some(catch) = NOOP_CATCH_PATTERN.find(&result[search_from..]) {
+        result.replace_range(search_from..search_from + catch.end(), "");
+        removed += 1;
+      }
     }
+
+    (result, removed)
+  }
+
+  /// Whether a mustache or directive value matches the pattern, ignoring plain text and comments
+  fn template_matches(context: &TransformationContext, pattern: &Regex) -> bool {
+    context
+      .sfc_sections
+      .template_content
+      .as_deref()
+      .is_some_and(|template| {
+        template_expressions(template)
+          .iter()
+          .any(|expression| pattern.is_match(expression))
+      })
   }
 }
 
@@ -106,11 +164,11 @@ impl Transformer for RouterTransformer {
   fn transform(
     &self,
     context: &TransformationContext,
-    _config: &TransformerConfig,
+    config: &TransformerConfig,
   ) -> TransformationResult {
     let mut result = TransformationResult::default();
 
-    // Add vue-router imports
+    // Add vue-router imports, Nuxt 3 auto-imports its own versions
     let mut imports = Vec::new();
     if self.has_route_usage(context) {
       imports.push("useRoute");
@@ -119,8 +177,8 @@ impl Transformer for RouterTransformer {
       imports.push("useRouter");
     }
 
-    if !imports.is_empty() {
-      result.add_imports("vue-router", &imports);
+    if let Some(module) = config.target.router_module().filter(|_| !imports.is_empty()) {
+      result.add_imports(module, &imports);
     }
 
     // Add composable setup code
@@ -136,18 +194,46 @@ impl Transformer for RouterTransformer {
       result.add_setup("".to_string());
     }
 
+    let script = context.sfc_sections.script_content.as_deref().unwrap_or("");
+    let (_, removed_catches) = Self::strip_navigation_catches(script);
+    if removed_catches > 0 {
+      result.diagnostics.push(Diagnostic::info(
+        "router-duplicate-navigation",
+        format!(
+          "Removed {} empty `.catch()` handler(s) on router navigation, Vue Router 4 no longer rejects duplicate navigations",
+          removed_catches
+        ),
+      ));
+    }
+
+    let template = context.sfc_sections.template_content.as_deref().unwrap_or("");
+    if ROUTER_APP_PATTERN.is_match(script) || ROUTER_APP_PATTERN.is_match(template) {
+      result.diagnostics.push(Diagnostic::warning(
+        "router-app",
+        "`$router.app` has no Vue Router 4 equivalent, use `getCurrentInstance()` or pass the app explicitly",
+      ));
+    }
+
     // Add template replacements for $route and $router
     if self.has_route_in_template(context) {
       result.template_replacements.push(TemplateReplacement {
         find: "$route".to_string(),
         replace: "route".to_string(),
+        scope: ReplacementScope::Identifiers,
       });
     }
 
     if self.has_router_in_template(context) {
+      // Refs nested in the router are not unwrapped in templates
+      result.template_replacements.push(TemplateReplacement {
+        find: "$router.currentRoute".to_string(),
+        replace: "router.currentRoute.value".to_string(),
+        scope: ReplacementScope::Expressions,
+      });
       result.template_replacements.push(TemplateReplacement {
         find: "$router".to_string(),
         replace: "router".to_string(),
+        scope: ReplacementScope::Identifiers,
       });
     }
 
@@ -157,18 +243,29 @@ impl Transformer for RouterTransformer {
   fn get_body_transform(&self) -> Option<Box<BodyTransformFn>> {
     Some(Box::new(
       |body: &str, context: &TransformationContext, _config: &TransformerConfig| {
+        // Every pattern needs one of these, checking the whole component for usage is costly
+        if !body.contains("$route") && !body.contains("router.") {
+          return body.to_string();
+        }
+
         let router_transformer = RouterTransformer::new();
         let mut transformed_body = body.to_string();
 
         // Transform $route and $router usage
         if router_transformer.has_route_usage(context) {
-          transformed_body = transformed_body.replace("this.$route", "route");
-          transformed_body = transformed_body.replace("$route", "route");
+          transformed_body = ROUTE_PATTERN
+            .replace_all(&transformed_body, "route")
+            .to_string();
         }
 
         if router_transformer.has_router_usage(context) {
-          transformed_body = transformed_body.replace("this.$router", "router");
-          transformed_body = transformed_body.replace("$router", "router");
+          transformed_body = CURRENT_ROUTE_PATTERN
+            .replace_all(&transformed_body, "router.currentRoute.value")
+            .to_string();
+          transformed_body = ROUTER_PATTERN
+            .replace_all(&transformed_body, "router")
+            .to_string();
+          transformed_body = RouterTransformer::strip_navigation_catches(&transformed_body).0;
         }
 
         transformed_body
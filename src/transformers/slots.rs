@@ -0,0 +1,166 @@
+use super::template_edits::{edits_to_replacements, element_end, Edit, OPENING_TAG_PATTERN};
+use super::Transformer;
+use crate::{TransformationContext, TransformationResult, TransformerConfig};
+use lazy_static::lazy_static;
+use regex::Regex;
+
+lazy_static! {
+  static ref SLOT_ATTRIBUTE_PATTERN: Regex =
+    Regex::new(r#"\s+(v-bind:slot|:slot|slot-scope|slot|scope)\s*=\s*(?:"([^"]*)"|'([^']*)')"#)
+      .unwrap();
+}
+
+/// Transformer for the deprecated slot syntax
+///
+/// Vue 3 only supports `v-slot`, so the Vue 2 attributes are rewritten:
+/// - `<template slot="header" slot-scope="props">` becomes `<template #header="props">`
+/// - `<template slot-scope="{ item }">` becomes `<template #default="{ item }">`
+/// - `<template :slot="name">` becomes `<template #[name]>`
+/// - Slot attributes placed directly on an element or component (`<span slot="title">`) are
+///   moved to a `<template>` wrapping it, since `v-slot` is only allowed there
+pub struct SlotsTransformer;
+
+impl Default for SlotsTransformer {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+/// A slot attribute found on an opening tag
+struct SlotAttribute {
+  start: usize, // Offset within the opening tag
+  end: usize,
+  kind: String,
+  value: String,
+}
+
+impl SlotsTransformer {
+  pub fn new() -> Self {
+    Self
+  }
+
+  /// Slot attributes of an opening tag, `scope` only counting on `<template>`
+  fn slot_attributes(tag_name: &str, tag: &str) -> Vec<SlotAttribute> {
+    SLOT_ATTRIBUTE_PATTERN
+      .captures_iter(tag)
+      .filter(|caps| &caps[1] != "scope" || tag_name == "template")
+      .map(|caps| {
+        let whole = caps.get(0).unwrap();
+        SlotAttribute {
+          start: whole.start(),
+          end: whole.end(),
+          kind: caps[1].to_string(),
+          value: caps
+            .get(2)
+            .or_else(|| caps.get(3))
+            .map(|value| value.as_str().to_string())
+            .unwrap_or_default(),
+        }
+      })
+      .collect()
+  }
+
+  /// The `v-slot` shorthand equivalent to the slot attributes
+  fn slot_directive(attributes: &[SlotAttribute]) -> String {
+    let mut name = "default".to_string();
+    let mut scope = None;
+
+    for attribute in attributes {
+      match attribute.kind.as_str() {
+        "slot" => name = attribute.value.clone(),
+        ":slot" | "v-bind:slot" => name = format!("[{}]", attribute.value),
+        _ => scope = Some(attribute.value.clone()),
+      }
+    }
+
+    match scope {
+      Some(scope) => format!("#{}=\"{}\"", name, scope),
+      None => format!("#{}", name),
+    }
+  }
+
+  /// Edits converting every slot attribute in the template, with the
+  /// region of the template each conversion changes
+  fn collect_edits(template: &str) -> (Vec<Edit>, Vec<(usize, usize)>) {
+    let mut edits = Vec::new();
+    let mut regions = Vec::new();
+
+    for caps in OPENING_TAG_PATTERN.captures_iter(template) {
+      let tag = caps.get(0).unwrap();
+      let tag_name = &caps[1];
+      let attributes = Self::slot_attributes(tag_name, tag.as_str());
+      if attributes.is_empty() {
+        continue;
+      }
+
+      let directive = Self::slot_directive(&attributes);
+
+      // Opening tag without the slot attributes
+      let mut stripped = String::new();
+      let mut position = 0;
+      for attribute in &attributes {
+        stripped.push_str(&tag.as_str()[position..attribute.start]);
+        position = attribute.end;
+      }
+      stripped.push_str(&tag.as_str()[position..]);
+
+      if tag_name == "template" {
+        let insert_at = attributes[0].start;
+        let opening = format!(
+          "{} {}{}",
+          &stripped[..insert_at],
+          directive,
+          &stripped[insert_at..]
+        );
+        edits.push((tag.start(), tag.end(), opening));
+        regions.push((tag.start(), tag.end()));
+        continue;
+      }
+
+      let Some(element_end) = element_end(template, &caps) else {
+        continue;
+      };
+
+      edits.push((
+        tag.start(),
+        tag.end(),
+        format!("<template {}>{}", directive, stripped),
+      ));
+      edits.push((element_end, element_end, "</template>".to_string()));
+      regions.push((tag.start(), element_end));
+    }
+
+    (edits, regions)
+  }
+}
+
+impl Transformer for SlotsTransformer {
+  fn name(&self) -> &'static str {
+    "slots"
+  }
+
+  fn should_transform(&self, context: &TransformationContext, _config: &TransformerConfig) -> bool {
+    context
+      .sfc_sections
+      .template_content
+      .as_ref()
+      .is_some_and(|template| !Self::collect_edits(template).0.is_empty())
+  }
+
+  fn transform(
+    &self,
+    context: &TransformationContext,
+    _config: &TransformerConfig,
+  ) -> TransformationResult {
+    let mut result = TransformationResult::new();
+
+    if let Some(template) = &context.sfc_sections.template_content {
+      let (edits, regions) = Self::collect_edits(template);
+      result
+        .template_replacements
+        .extend(edits_to_replacements(template, edits, regions));
+    }
+
+    result
+  }
+}
@@ -0,0 +1,108 @@
+use super::{StyleTransformFn, Transformer};
+use crate::{Diagnostic, TransformationContext, TransformationResult, TransformerConfig};
+use lazy_static::lazy_static;
+use regex::Regex;
+
+lazy_static! {
+  static ref PSEUDO_FUNCTION_PATTERN: Regex = Regex::new(r"::v-(deep|slotted|global)\(").unwrap();
+  static ref DEEP_COMBINATOR_PATTERN: Regex =
+    Regex::new(r"(?:/deep/|>>>|::v-deep)(\s*)([^{},;]*?)(\s*)([{,])").unwrap();
+}
+
+/// Transformer for scoped style selectors, enabled by default and controlled with
+/// `enable_style_transforms`
+///
+/// This transformer handles:
+/// - Deep selectors: `.a /deep/ .b`, `.a >>> .b` and `.a ::v-deep .b` become `.a :deep(.b)`
+/// - The function forms `::v-deep(.b)`, `::v-slotted(.b)` and `::v-global(.b)` become
+///   `:deep(.b)`, `:slotted(.b)` and `:global(.b)`
+///
+/// Deep combinators used as a nesting block (`::v-deep { .b { } }`) are reported, since they have
+/// no direct Vue 3 equivalent.
+pub struct StyleTransformer;
+
+impl Default for StyleTransformer {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl StyleTransformer {
+  pub fn new() -> Self {
+    Self
+  }
+
+  /// Rewrite deep, slotted and global selectors to the Vue 3 pseudo-classes
+  fn rewrite_selectors(style: &str) -> String {
+    let result = PSEUDO_FUNCTION_PATTERN.replace_all(style, ":$1(");
+
+    DEEP_COMBINATOR_PATTERN
+      .replace_all(&result, |caps: &regex::Captures| {
+        // A nesting block (`::v-deep {`) has no selector to wrap
+        if caps[2].is_empty() {
+          return caps[0].to_string();
+        }
+        format!(":deep({}){}{}", &caps[2], &caps[3], &caps[4])
+      })
+      .to_string()
+  }
+
+  /// Deep combinators left in the style after rewriting
+  fn unconverted_combinators(style: &str) -> usize {
+    let rewritten = Self::rewrite_selectors(style);
+    ["/deep/", ">>>", "::v-deep"]
+      .iter()
+      .map(|combinator| rewritten.matches(combinator).count())
+      .sum()
+  }
+}
+
+impl Transformer for StyleTransformer {
+  fn name(&self) -> &'static str {
+    "style"
+  }
+
+  fn should_transform(&self, context: &TransformationContext, config: &TransformerConfig) -> bool {
+    config.enable_style_transforms
+      && context
+        .sfc_sections
+        .style_content
+        .as_ref()
+        .is_some_and(|style| {
+          ["/deep/", ">>>", "::v-deep", "::v-slotted", "::v-global"]
+            .iter()
+            .any(|selector| style.contains(selector))
+        })
+  }
+
+  fn transform(
+    &self,
+    context: &TransformationContext,
+    _config: &TransformerConfig,
+  ) -> TransformationResult {
+    let mut result = TransformationResult::new();
+
+    if let Some(style) = &context.sfc_sections.style_content {
+      let unconverted = Self::unconverted_combinators(style);
+      if unconverted > 0 {
+        result.diagnostics.push(Diagnostic::warning(
+          "deep-selector",
+          format!(
+            "{} deep selector block(s) were not converted, move the nested selectors into `:deep(...)` manually",
+            unconverted
+          ),
+        ));
+      }
+    }
+
+    result
+  }
+
+  fn get_style_transform(&self) -> Option<Box<StyleTransformFn>> {
+    Some(Box::new(
+      |style: &str, _context: &TransformationContext, _config: &TransformerConfig| {
+        StyleTransformer::rewrite_selectors(style)
+      },
+    ))
+  }
+}
@@ -0,0 +1,83 @@
+//! Helpers for template passes that rewrite elements rather than single expressions.
+//!
+//! Passes collect positional [`Edit`]s against the original template together with the region
+//! of the template each rewrite touches, and [`edits_to_replacements`] turns them into literal
+//! [`TemplateReplacement`]s, one per outermost region.
+
+use crate::{find_closing_tag, ReplacementScope, TemplateReplacement};
+use lazy_static::lazy_static;
+use regex::{Captures, Regex};
+
+lazy_static! {
+  /// Opening tag: (1) tag name, (2) attributes, (3) `/` when self-closing
+  pub static ref OPENING_TAG_PATTERN: Regex =
+    Regex::new(r#"<([a-zA-Z][\w.-]*)((?:[^>"']|"[^"]*"|'[^']*')*?)(/?)>"#).unwrap();
+}
+
+/// Template edit: (start, end, replacement)
+pub type Edit = (usize, usize, String);
+
+/// End of the element opened by an [`OPENING_TAG_PATTERN`] match, after its closing tag
+pub fn element_end(template: &str, opening_tag: &Captures) -> Option<usize> {
+  let tag = opening_tag.get(0)?;
+  if &opening_tag[3] == "/" {
+    return Some(tag.end());
+  }
+
+  let tag_name = &opening_tag[1];
+  find_closing_tag(template, tag.end(), tag_name)
+    .map(|close| close + format!("</{}>", tag_name).len())
+}
+
+/// Literal replacements covering the edits, one per outermost changed region
+pub fn edits_to_replacements(
+  template: &str,
+  mut edits: Vec<Edit>,
+  mut regions: Vec<(usize, usize)>,
+) -> Vec<TemplateReplacement> {
+  edits.sort_by_key(|(start, end, _)| (*start, *end));
+
+  // Merge nested regions into the outermost one
+  regions.sort_by_key(|(start, end)| (*start, std::cmp::Reverse(*end)));
+  let mut outermost: Vec<(usize, usize)> = Vec::new();
+  for (start, end) in regions {
+    match outermost.last_mut() {
+      Some(last) if start < last.1 => last.1 = last.1.max(end),
+      _ => outermost.push((start, end)),
+    }
+  }
+
+  let mut replacements: Vec<TemplateReplacement> = outermost
+    .into_iter()
+    .map(|(region_start, region_end)| {
+      let mut replace = String::new();
+      let mut position = region_start;
+      for (start, end, replacement) in &edits {
+        // Skip edits outside the region, including an element closed right where it starts
+        if *start < region_start
+          || *end > region_end
+          || *start < position
+          || (start == end && *start == region_start)
+        {
+          continue;
+        }
+        replace.push_str(&template[position..*start]);
+        replace.push_str(replacement);
+        position = *end;
+      }
+      replace.push_str(&template[position..region_end]);
+
+      TemplateReplacement {
+        find: template[region_start..region_end].to_string(),
+        replace,
+        scope: ReplacementScope::Template,
+      }
+    })
+    .collect();
+
+  // Larger regions first, so identical smaller ones inside them are not replaced beforehand
+  replacements.sort_by_key(|replacement| std::cmp::Reverse(replacement.find.len()));
+  replacements.dedup_by(|a, b| a.find == b.find);
+
+  replacements
+}
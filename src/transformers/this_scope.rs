@@ -0,0 +1,471 @@
+//! Scoping of `this` inside method bodies.
+//!
+//! Body transforms treat every `this.` as a reference to the component. That is wrong inside
+//! nested regular functions, where `this` is whatever the callback is invoked with, and misses
+//! component references made through an alias (`const self = this;`). Before the body transforms
+//! run, [`isolate_callback_this`]:
+//! - converts functions bound to the component (`function () {}.bind(this)` or passed with a
+//!   `thisArg`, as in `items.forEach(function () {}, this)`) to arrow functions
+//! - masks `this` inside the remaining regular functions and object methods
+//! - rewrites `self.x` to `this.x` for `self/vm/that = this` aliases and drops the alias
+//!
+//! [`restore_callback_this`] puts the masked `this` back once the body transforms are done.
+//!
+//! Body transforms are string replacements, which would also rewrite `this.save()` inside
+//! `'please call this.save()'`. [`mask_literals`] replaces comments, regular expressions and the
+//! strings and template literal parts mentioning `this` with placeholders first, and
+//! [`restore_literals`] puts them back. Other strings are left for the transforms reading them
+//! (`this.$emit('input')`, `this.$store.dispatch('cart/add')`).
+
+use lazy_static::lazy_static;
+use regex::Regex;
+use tree_sitter::Node;
+
+lazy_static! {
+  static ref THIS_ALIAS_PATTERN: Regex = Regex::new(
+    r"(?m)^[ \t]*(?:const|let|var)\s+([a-zA-Z_$][a-zA-Z0-9_$]*)\s*=\s*this\s*;?[ \t]*\n?"
+  )
+  .unwrap();
+  static ref THIS_WORD_PATTERN: Regex = Regex::new(r"\bthis\b").unwrap();
+}
+
+/// Stand-in for a literal masked by [`mask_literals`], numbered by its position in the literals.
+/// Comments are masked as a block comment holding it, so the masked body still parses the same.
+const LITERAL_PLACEHOLDER: &str = "__literal_";
+
+/// Stand-in for a `this` that does not refer to the component
+const CALLBACK_THIS: &str = "__callback_this__";
+
+/// Wrapper making the body a valid program (`return` and `await` are allowed in functions)
+const BODY_PREFIX: &str = "async function __body__() {\n";
+const BODY_SUFFIX: &str = "\n}";
+
+/// Prepare a method body so that only component `this` references are converted
+pub fn isolate_callback_this(body: &str) -> String {
+  if !body.contains("this") {
+    return body.to_string();
+  }
+
+  let masked = mask_callback_this(body).unwrap_or_else(|| body.to_string());
+  resolve_this_aliases(&masked)
+}
+
+/// Restore `this` references masked by [`isolate_callback_this`]
+pub fn restore_callback_this(body: &str) -> String {
+  body.replace(CALLBACK_THIS, "this")
+}
+
+/// Replace the comments, regular expressions and strings mentioning `this` of a body with
+/// placeholders, returning the masked body and the literals to restore
+pub fn mask_literals(body: &str) -> (String, Vec<String>) {
+  if !body.contains("this") && !body.contains('/') {
+    return (body.to_string(), Vec::new());
+  }
+
+  let source = format!("{}{}{}", BODY_PREFIX, body, BODY_SUFFIX);
+  let Some(tree) = crate::javascript::parse(&source) else {
+    return (body.to_string(), Vec::new());
+  };
+
+  let mut ranges = Vec::new();
+  collect_literals(&tree.root_node(), &source, &mut ranges);
+  let ranges: Vec<(usize, usize)> = ranges
+    .into_iter()
+    .filter(|(start, end)| *start >= BODY_PREFIX.len() && *end <= BODY_PREFIX.len() + body.len())
+    .map(|(start, end)| (start - BODY_PREFIX.len(), end - BODY_PREFIX.len()))
+    .collect();
+
+  let mut masked = String::with_capacity(body.len());
+  let mut literals = Vec::new();
+  let mut position = 0;
+  for (start, end) in ranges {
+    let literal = &body[start..end];
+    masked.push_str(&body[position..start]);
+    if literal.starts_with("//") || literal.starts_with("/*") {
+      masked.push_str(&format!("/*{}{}__*/", LITERAL_PLACEHOLDER, literals.len()));
+    } else {
+      masked.push_str(&format!("{}{}__", LITERAL_PLACEHOLDER, literals.len()));
+    }
+    literals.push(literal.to_string());
+    position = end;
+  }
+  masked.push_str(&body[position..]);
+
+  (masked, literals)
+}
+
+/// Put back the literals masked by [`mask_literals`]
+pub fn restore_literals(body: &str, literals: &[String]) -> String {
+  let mut result = body.to_string();
+  for (index, literal) in literals.iter().enumerate().rev() {
+    let placeholder = format!("{}{}__", LITERAL_PLACEHOLDER, index);
+    result = result.replace(&format!("/*{}*/", placeholder), literal);
+    result = result.replace(&placeholder, literal);
+  }
+  result
+}
+
+/// Collect the byte ranges of the literals [`mask_literals`] masks, in order
+fn collect_literals(node: &Node, source: &str, ranges: &mut Vec<(usize, usize)>) {
+  let mentions_this = |node: &Node| THIS_WORD_PATTERN.is_match(&source[node.byte_range()]);
+  match node.kind() {
+    "comment" | "regex" => ranges.push((node.start_byte(), node.end_byte())),
+    // Only the text of template literals, their `${}` parts are code
+    "string" | "string_fragment" if mentions_this(node) => {
+      ranges.push((node.start_byte(), node.end_byte()))
+    }
+    "string" => {}
+    _ => {
+      let mut cursor = node.walk();
+      for child in node.children(&mut cursor) {
+        collect_literals(&child, source, ranges);
+      }
+    }
+  }
+}
+
+/// Convert bound functions to arrow functions and mask `this` inside unbound regular functions
+fn mask_callback_this(body: &str) -> Option<String> {
+  let source = format!("{}{}{}", BODY_PREFIX, body, BODY_SUFFIX);
+  let tree = crate::javascript::parse(&source)?;
+
+  // The wrapper function itself is the component scope
+  let wrapper = tree.root_node().child(0)?;
+  let wrapper_body = wrapper.child_by_field_name("body")?;
+
+  let mut edits = Vec::new();
+  collect_edits(&wrapper_body, &source, &mut edits);
+
+  if edits.is_empty() {
+    return None;
+  }
+
+  edits.sort_by_key(|(start, _, _)| *start);
+
+  let mut result = String::with_capacity(source.len());
+  let mut position = 0;
+  for (start, end, replacement) in edits {
+    if start < position {
+      continue;
+    }
+    result.push_str(&source[position..start]);
+    result.push_str(&replacement);
+    position = end;
+  }
+  result.push_str(&source[position..]);
+
+  let inner = result
+    .strip_prefix(BODY_PREFIX)?
+    .strip_suffix(BODY_SUFFIX)?;
+  Some(inner.to_string())
+}
+
+/// Collect (start, end, replacement) edits for the component scope rooted at `node`
+fn collect_edits(node: &Node, source: &str, edits: &mut Vec<(usize, usize, String)>) {
+  let mut cursor = node.walk();
+  for child in node.children(&mut cursor) {
+    match child.kind() {
+      "function_expression" | "function" => {
+        if let Some(binding_edit) = component_binding(&child, source) {
+          convert_to_arrow(&child, source, edits);
+          edits.push(binding_edit);
+          // The body keeps the component `this`
+          if let Some(body) = child.child_by_field_name("body") {
+            collect_edits(&body, source, edits);
+          }
+        } else {
+          mask_this(&child, edits);
+        }
+      }
+      "function_declaration"
+      | "generator_function"
+      | "generator_function_declaration"
+      | "method_definition"
+      | "class_declaration"
+      | "class" => mask_this(&child, edits),
+      _ => collect_edits(&child, source, edits),
+    }
+  }
+}
+
+/// If the function is bound to the component, the edit removing the binding
+/// (`.bind(this)` or the `thisArg` argument)
+fn component_binding(function: &Node, source: &str) -> Option<(usize, usize, String)> {
+  let text = |node: &Node| &source[node.start_byte()..node.end_byte()];
+
+  // Skip parentheses around the function: (function () {}).bind(this)
+  let mut target = *function;
+  while let Some(parent) = target.parent() {
+    if parent.kind() != "parenthesized_expression" {
+      break;
+    }
+    target = parent;
+  }
+
+  let parent = target.parent()?;
+
+  // function () {}.bind(this)
+  if parent.kind() == "member_expression"
+    && parent.child_by_field_name("object") == Some(target)
+    && parent
+      .child_by_field_name("property")
+      .is_some_and(|property| text(&property) == "bind")
+  {
+    let call = parent.parent()?;
+    let arguments = call.child_by_field_name("arguments")?;
+    let mut cursor = arguments.walk();
+    let args: Vec<Node> = arguments.named_children(&mut cursor).collect();
+
+    if call.kind() == "call_expression" && args.len() == 1 && args[0].kind() == "this" {
+      return Some((target.end_byte(), call.end_byte(), String::new()));
+    }
+    return None;
+  }
+
+  // items.forEach(function () {}, this)
+  if parent.kind() == "arguments" {
+    let mut cursor = parent.walk();
+    let args: Vec<Node> = parent.named_children(&mut cursor).collect();
+    let position = args.iter().position(|arg| *arg == target)?;
+
+    if position + 2 == args.len() && args[position + 1].kind() == "this" {
+      return Some((
+        target.end_byte(),
+        args[position + 1].end_byte(),
+        String::new(),
+      ));
+    }
+  }
+
+  None
+}
+
+/// Replace the `function name` keyword and name with an arrow: `function (a) {` -> `(a) => {`
+fn convert_to_arrow(function: &Node, source: &str, edits: &mut Vec<(usize, usize, String)>) {
+  let (Some(parameters), Some(body)) = (
+    function.child_by_field_name("parameters"),
+    function.child_by_field_name("body"),
+  ) else {
+    return;
+  };
+
+  let header = &source[function.start_byte()..parameters.start_byte()];
+  let prefix = if header.trim_start().starts_with("async") {
+    "async "
+  } else {
+    ""
+  };
+
+  edits.push((
+    function.start_byte(),
+    parameters.start_byte(),
+    prefix.to_string(),
+  ));
+  edits.push((parameters.end_byte(), body.start_byte(), " => ".to_string()));
+}
+
+/// Mask every `this` in the subtree, which belongs to a scope other than the component
+fn mask_this(node: &Node, edits: &mut Vec<(usize, usize, String)>) {
+  if node.kind() == "this" {
+    edits.push((
+      node.start_byte(),
+      node.end_byte(),
+      CALLBACK_THIS.to_string(),
+    ));
+    return;
+  }
+
+  let mut cursor = node.walk();
+  for child in node.children(&mut cursor) {
+    mask_this(&child, edits);
+  }
+}
+
+/// Rewrite `self.x` to `this.x` for aliases declared as `const self = this;`.
+///
+/// The alias declaration is dropped when every usage is a member access, otherwise it is kept.
+fn resolve_this_aliases(body: &str) -> String {
+  let mut result = body.to_string();
+
+  let aliases: Vec<(String, String)> = THIS_ALIAS_PATTERN
+    .captures_iter(body)
+    .map(|caps| (caps[0].to_string(), caps[1].to_string()))
+    .collect();
+
+  for (declaration, alias) in aliases {
+    let without_declaration = result.replacen(&declaration, "", 1);
+    let Ok(usage) = Regex::new(&format!(r"(^|[^\w$.]){}\b(\s*\.)?", regex::escape(&alias))) else {
+      continue;
+    };
+
+    let only_member_access = usage
+      .captures_iter(&without_declaration)
+      .all(|caps| caps.get(2).is_some());
+    if !only_member_access {
+      continue;
+    }
+
+    result = usage
+      .replace_all(&without_declaration, |caps: &regex::Captures| {
+        format!("{}this.", &caps[1])
+      })
+      .to_string();
+  }
+
+  result
+}
+
+/// Rename the references a body makes to module bindings (`limit` -> `importedLimit`)
+///
+/// Member names and object keys are left alone, shorthand properties keep their key
+/// (`{ limit }` -> `{ limit: importedLimit }`) and a name the body declares itself is not renamed
+/// in it. A body parsing as an expression (a data value) is renamed as one.
+pub fn rename_bindings(body: &str, renames: &[(String, String)]) -> String {
+  rename_function_bindings("", body, renames)
+}
+
+/// [`rename_bindings`] for the body of a function, whose parameters are not renamed either
+pub fn rename_function_bindings(
+  parameters: &str,
+  body: &str,
+  renames: &[(String, String)],
+) -> String {
+  let renames: Vec<&(String, String)> = renames
+    .iter()
+    .filter(|(name, _)| body.contains(name.as_str()))
+    .collect();
+  if renames.is_empty() {
+    return body.to_string();
+  }
+
+  let expression = format!("(\n{}\n)", body);
+  let (source, prefix_length, tree) = match crate::javascript::parse(&expression) {
+    Some(tree) if parameters.is_empty() && !tree.root_node().has_error() => (expression, 2, tree),
+    _ => {
+      let prefix = format!("async function __body__({}) {{\n", parameters);
+      let source = format!("{}{}{}", prefix, body, BODY_SUFFIX);
+      let Some(tree) = crate::javascript::parse(&source) else {
+        return body.to_string();
+      };
+      (source, prefix.len(), tree)
+    }
+  };
+
+  let mut declared = Vec::new();
+  collect_declared_names(&tree.root_node(), &source, false, &mut declared);
+  let renames: Vec<&(String, String)> = renames
+    .into_iter()
+    .filter(|(name, _)| !declared.contains(name))
+    .collect();
+
+  let mut edits = Vec::new();
+  collect_binding_references(&tree.root_node(), &source, &renames, &mut edits);
+
+  let mut result = String::with_capacity(body.len());
+  let mut position = 0;
+  for (start, end, replacement) in edits {
+    let (Some(start), Some(end)) = (
+      start.checked_sub(prefix_length),
+      end.checked_sub(prefix_length),
+    ) else {
+      continue;
+    };
+    if start < position || end > body.len() {
+      continue;
+    }
+    result.push_str(&body[position..start]);
+    result.push_str(&replacement);
+    position = end;
+  }
+  result.push_str(&body[position..]);
+
+  result
+}
+
+/// Collect the names declared by variables, parameters, functions and classes
+fn collect_declared_names(node: &Node, source: &str, in_pattern: bool, names: &mut Vec<String>) {
+  let text = || source[node.byte_range()].to_string();
+  match node.kind() {
+    "identifier" | "shorthand_property_identifier_pattern" if in_pattern => names.push(text()),
+    "variable_declarator" => {
+      if let Some(name) = node.child_by_field_name("name") {
+        collect_declared_names(&name, source, true, names);
+      }
+      if let Some(value) = node.child_by_field_name("value") {
+        collect_declared_names(&value, source, false, names);
+      }
+    }
+    "formal_parameters" => {
+      let mut cursor = node.walk();
+      for parameter in node.named_children(&mut cursor) {
+        collect_declared_names(&parameter, source, true, names);
+      }
+    }
+    // Default values and computed keys of patterns are expressions
+    "assignment_pattern" | "object_assignment_pattern" if in_pattern => {
+      if let Some(left) = node.child_by_field_name("left") {
+        collect_declared_names(&left, source, true, names);
+      }
+      if let Some(right) = node.child_by_field_name("right") {
+        collect_declared_names(&right, source, false, names);
+      }
+    }
+    "pair_pattern" if in_pattern => {
+      if let Some(value) = node.child_by_field_name("value") {
+        collect_declared_names(&value, source, true, names);
+      }
+    }
+    _ => {
+      let declared = match node.kind() {
+        "function_declaration" | "function_expression" | "class_declaration" => {
+          node.child_by_field_name("name")
+        }
+        "arrow_function" => node.child_by_field_name("parameter"),
+        "catch_clause" => node.child_by_field_name("parameter"),
+        _ => None,
+      };
+      if let Some(declared) = declared {
+        collect_declared_names(&declared, source, true, names);
+      }
+
+      let mut cursor = node.walk();
+      for child in node.named_children(&mut cursor) {
+        if Some(child) != declared {
+          collect_declared_names(&child, source, in_pattern, names);
+        }
+      }
+    }
+  }
+}
+
+/// Collect the edits renaming the references to the renamed bindings
+fn collect_binding_references(
+  node: &Node,
+  source: &str,
+  renames: &[&(String, String)],
+  edits: &mut Vec<(usize, usize, String)>,
+) {
+  let text = &source[node.byte_range()];
+  let alias = renames
+    .iter()
+    .find(|(name, _)| name == text)
+    .map(|(_, alias)| alias);
+  match (node.kind(), alias) {
+    ("identifier", Some(alias)) => {
+      edits.push((node.start_byte(), node.end_byte(), alias.clone()));
+    }
+    ("shorthand_property_identifier", Some(alias)) => {
+      edits.push((
+        node.start_byte(),
+        node.end_byte(),
+        format!("{}: {}", text, alias),
+      ));
+    }
+    _ => {
+      let mut cursor = node.walk();
+      for child in node.children(&mut cursor) {
+        collect_binding_references(&child, source, renames, edits);
+      }
+    }
+  }
+}
@@ -0,0 +1,168 @@
+use super::template_edits::OPENING_TAG_PATTERN;
+use super::{StyleTransformFn, Transformer};
+use crate::{
+  Diagnostic, ReplacementScope, TemplateReplacement, TransformationContext, TransformationResult,
+  TransformerConfig,
+};
+use lazy_static::lazy_static;
+use regex::Regex;
+
+lazy_static! {
+  static ref NAME_ATTRIBUTE_PATTERN: Regex =
+    Regex::new(r#"\s(:|v-bind:)?name\s*=\s*(?:"([^"]*)"|'([^']*)')"#).unwrap();
+  static ref TAG_ATTRIBUTE_PATTERN: Regex = Regex::new(r#"\s(:|v-bind:)?tag\s*="#).unwrap();
+}
+
+/// `<transition>` class props renamed in Vue 3
+const RENAMED_CLASS_PROPS: [(&str, &str); 2] = [
+  ("enter-class", "enter-from-class"),
+  ("leave-class", "leave-from-class"),
+];
+
+/// Transformer for transitions, enabled with `enable_transition_transforms`
+///
+/// This transformer handles:
+/// - Renaming the Vue 2 transition classes in `<style>`: `.fade-enter` to `.fade-enter-from` and
+///   `.fade-leave` to `.fade-leave-from` (`.v-enter` for transitions without a name)
+/// - Renaming the `enter-class` / `leave-class` props of `<transition>`
+/// - Reporting `<transition-group>` without a `tag`, which no longer renders a wrapping `<span>`
+pub struct TransitionsTransformer;
+
+impl Default for TransitionsTransformer {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl TransitionsTransformer {
+  pub fn new() -> Self {
+    Self
+  }
+
+  /// Opening tags of `<transition>` and `<transition-group>` (also in PascalCase)
+  fn transition_tags(template: &str) -> Vec<(bool, &str)> {
+    OPENING_TAG_PATTERN
+      .captures_iter(template)
+      .filter_map(|caps| {
+        let is_group = match &caps[1] {
+          "transition" | "Transition" => false,
+          "transition-group" | "TransitionGroup" => true,
+          _ => return None,
+        };
+        Some((is_group, caps.get(0).unwrap().as_str()))
+      })
+      .collect()
+  }
+
+  /// Transition names used in the template; unnamed transitions use the `v` prefix. Dynamic names
+  /// cannot be resolved and are skipped.
+  fn transition_names(template: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+
+    for (_, tag) in Self::transition_tags(template) {
+      let name = match NAME_ATTRIBUTE_PATTERN.captures(tag) {
+        Some(caps) if caps.get(1).is_some() => continue,
+        Some(caps) => caps
+          .get(2)
+          .or_else(|| caps.get(3))
+          .map(|name| name.as_str().to_string())
+          .unwrap_or_default(),
+        None => "v".to_string(),
+      };
+
+      if !name.is_empty() && !names.contains(&name) {
+        names.push(name);
+      }
+    }
+
+    names
+  }
+
+  /// Rename `.name-enter` and `.name-leave` selectors to their `-from` variant
+  fn rename_transition_classes(style: &str, names: &[String]) -> String {
+    let mut result = style.to_string();
+
+    for name in names {
+      let Ok(pattern) = Regex::new(&format!(
+        r"\.({}-(?:enter|leave))([^\w-]|$)",
+        regex::escape(name)
+      )) else {
+        continue;
+      };
+      result = pattern.replace_all(&result, ".${1}-from${2}").to_string();
+    }
+
+    result
+  }
+}
+
+impl Transformer for TransitionsTransformer {
+  fn name(&self) -> &'static str {
+    "transitions"
+  }
+
+  fn should_transform(&self, context: &TransformationContext, config: &TransformerConfig) -> bool {
+    config.enable_transition_transforms
+      && context
+        .sfc_sections
+        .template_content
+        .as_ref()
+        .is_some_and(|template| !Self::transition_tags(template).is_empty())
+  }
+
+  fn transform(
+    &self,
+    context: &TransformationContext,
+    _config: &TransformerConfig,
+  ) -> TransformationResult {
+    let mut result = TransformationResult::new();
+    let Some(template) = &context.sfc_sections.template_content else {
+      return result;
+    };
+
+    for (is_group, tag) in Self::transition_tags(template) {
+      if is_group {
+        if !TAG_ATTRIBUTE_PATTERN.is_match(tag) {
+          result.diagnostics.push(Diagnostic::warning(
+            "transition-group-tag",
+            format!(
+              "`{}` has no `tag`: Vue 3 no longer renders a wrapping `<span>`, add `tag=\"span\"` if the styling relies on it",
+              tag
+            ),
+          ));
+        }
+        continue;
+      }
+
+      for (old, new) in RENAMED_CLASS_PROPS {
+        for prefix in [" ", ":"] {
+          let find = format!("{}{}=", prefix, old);
+          if tag.contains(&find) && !result.template_replacements.iter().any(|r| r.find == find) {
+            result.template_replacements.push(TemplateReplacement {
+              replace: format!("{}{}=", prefix, new),
+              find,
+              scope: ReplacementScope::Template,
+            });
+          }
+        }
+      }
+    }
+
+    result
+  }
+
+  fn get_style_transform(&self) -> Option<Box<StyleTransformFn>> {
+    Some(Box::new(
+      |style: &str, context: &TransformationContext, _config: &TransformerConfig| {
+        let Some(template) = &context.sfc_sections.template_content else {
+          return style.to_string();
+        };
+
+        TransitionsTransformer::rename_transition_classes(
+          style,
+          &TransitionsTransformer::transition_names(template),
+        )
+      },
+    ))
+  }
+}
@@ -0,0 +1,347 @@
+//! Best-effort TypeScript types for the `typescript` option.
+//!
+//! Nothing is type-checked: types are read from what the component declares or initializes.
+//! - Props from their runtime type (`String` -> `string`, `[String, Number]` -> `string | number`,
+//!   `Array as PropType<Item[]>` -> `Item[]`), with the default filling in array elements
+//! - Emit payloads from the expressions they are emitted with (`this.count` -> the type of the
+//!   `count` prop or data property, `true` -> `boolean`)
+//! - Refs from their initial value, only where TypeScript would infer a type too narrow to assign
+//!   to (`null`, `[]`, `{}`)
+//!
+//! What can not be inferred is typed [`UNKNOWN`], which keeps a `FIXME` comment in the output.
+
+use crate::{PropInfo, TransformationContext};
+use lazy_static::lazy_static;
+use regex::Regex;
+use tree_sitter::Node;
+
+lazy_static! {
+  static ref PROP_TYPE_CAST_PATTERN: Regex =
+    Regex::new(r"(?s)\bas\s+PropType\s*<(.+)>\s*$").unwrap();
+  static ref PAYLOAD_NAME_PATTERN: Regex =
+    Regex::new(r"^(?:this\.)?(?:[A-Za-z_$][\w$]*\.)*([A-Za-z_$][\w$]*)$").unwrap();
+}
+
+/// Type written where inference fails
+pub const UNKNOWN: &str = "/* FIXME: type */ unknown";
+
+/// Type of function props and function values
+const FUNCTION: &str = "(...args: any[]) => any";
+
+/// Type of an expression, `None` when it can not be inferred. `resolve` types the component
+/// members the expression refers to (`count` or `this.count`).
+pub fn expression_type(
+  expression: &str,
+  resolve: &dyn Fn(&str) -> Option<String>,
+) -> Option<String> {
+  let source = format!("({});", expression.trim());
+  let tree = crate::javascript::parse(&source)?;
+  let statement = tree.root_node().named_child(0)?;
+  if tree.root_node().has_error() || statement.kind() != "expression_statement" {
+    return None;
+  }
+
+  node_type(statement.named_child(0)?, &source, resolve)
+}
+
+/// The type a component member has: the type of its prop or the type of its initial data value
+pub fn member_type(context: &TransformationContext, name: &str) -> Option<String> {
+  let no_members = |_: &str| None;
+  if let Some(prop) = context
+    .script_state
+    .props
+    .iter()
+    .find(|prop| prop.name == name)
+  {
+    return Some(prop_type(prop, &no_members)).filter(|ts_type| ts_type != UNKNOWN);
+  }
+  context
+    .script_state
+    .data_properties
+    .iter()
+    .find(|data| data.name == name)
+    .and_then(|data| data.value.as_deref())
+    .and_then(|value| expression_type(value, &no_members))
+}
+
+/// Type of a prop, from its runtime type or its default
+pub fn prop_type(prop: &PropInfo, resolve: &dyn Fn(&str) -> Option<String>) -> String {
+  let default_type = prop
+    .default_value
+    .as_deref()
+    .map(factory_value)
+    .and_then(|default| expression_type(default, resolve));
+
+  let Some(runtime_type) = prop.prop_type.as_deref() else {
+    return default_type.unwrap_or_else(|| UNKNOWN.to_string());
+  };
+  if let Some(caps) = PROP_TYPE_CAST_PATTERN.captures(runtime_type) {
+    return caps[1].trim().to_string();
+  }
+
+  let constructors: Vec<&str> = runtime_type
+    .trim()
+    .trim_start_matches('[')
+    .trim_end_matches(']')
+    .split(',')
+    .map(str::trim)
+    .filter(|constructor| !constructor.is_empty())
+    .collect();
+  let mut types: Vec<String> = Vec::new();
+  for constructor in constructors {
+    let ts_type = match constructor {
+      "String" => "string".to_string(),
+      "Number" => "number".to_string(),
+      "Boolean" => "boolean".to_string(),
+      "Symbol" => "symbol".to_string(),
+      "Function" => FUNCTION.to_string(),
+      "Promise" => "Promise<unknown>".to_string(),
+      "Array" => default_type
+        .clone()
+        .filter(|default| default.ends_with("[]"))
+        .unwrap_or_else(|| format!("{}[]", UNKNOWN)),
+      "Object" => default_type
+        .clone()
+        .filter(|default| default.starts_with('{'))
+        .unwrap_or_else(|| "Record<string, unknown>".to_string()),
+      "null" => continue,
+      other if is_identifier(other) => other.to_string(),
+      _ => UNKNOWN.to_string(),
+    };
+    if !types.contains(&ts_type) {
+      types.push(ts_type);
+    }
+  }
+
+  match types.len() {
+    0 => default_type.unwrap_or_else(|| UNKNOWN.to_string()),
+    _ => union(&types),
+  }
+}
+
+/// Type argument of a `ref()` initialized with `value`, when TypeScript's own inference would be
+/// too narrow to assign anything to (`null`, `undefined`, `[]`, `{}`)
+pub fn ref_type(value: &str) -> Option<String> {
+  match value.trim() {
+    "null" | "undefined" => Some(UNKNOWN.to_string()),
+    "[]" => Some(format!("{}[]", UNKNOWN)),
+    "{}" => Some("Record<string, unknown>".to_string()),
+    _ => None,
+  }
+}
+
+/// The call signatures of `defineEmits<{ ... }>()` for events and the argument lists they are
+/// emitted with (one list per call site)
+pub fn emit_signatures(
+  events: &[(String, Vec<Vec<String>>)],
+  resolve: &dyn Fn(&str) -> Option<String>,
+) -> Vec<String> {
+  events
+    .iter()
+    .map(|(event, calls)| {
+      let required = calls.iter().map(Vec::len).min().unwrap_or(0);
+      let count = calls.iter().map(Vec::len).max().unwrap_or(0);
+
+      let mut parameters = vec![format!("e: '{}'", event)];
+      let mut names: Vec<String> = vec!["e".to_string()];
+      for position in 0..count {
+        let arguments: Vec<&str> = calls
+          .iter()
+          .filter_map(|call| call.get(position))
+          .map(String::as_str)
+          .collect();
+
+        let mut types: Vec<String> = Vec::new();
+        for argument in &arguments {
+          let ts_type = expression_type(argument, resolve).unwrap_or_else(|| UNKNOWN.to_string());
+          if !types.contains(&ts_type) {
+            types.push(ts_type);
+          }
+        }
+
+        let base = arguments
+          .first()
+          .and_then(|argument| PAYLOAD_NAME_PATTERN.captures(argument.trim()))
+          .map(|caps| caps[1].trim_start_matches('$').to_string())
+          .filter(|name| !name.is_empty() && name != "e")
+          .unwrap_or_else(|| "value".to_string());
+        let mut name = base.clone();
+        let mut suffix = 2;
+        while names.contains(&name) {
+          name = format!("{}{}", base, suffix);
+          suffix += 1;
+        }
+        names.push(name.clone());
+
+        let optional = if position >= required { "?" } else { "" };
+        parameters.push(format!("{}{}: {}", name, optional, union(&types)));
+      }
+
+      format!("({}): void;", parameters.join(", "))
+    })
+    .collect()
+}
+
+/// The value a prop default factory (`() => []`, `() => ({ ... })`) returns, or the default itself
+fn factory_value(default: &str) -> &str {
+  let default = default.trim();
+  match default.strip_prefix("()").map(str::trim_start) {
+    Some(rest) => match rest.strip_prefix("=>").map(str::trim) {
+      Some(body) if !body.starts_with('{') => body,
+      _ => default,
+    },
+    None => default,
+  }
+}
+
+fn union(types: &[String]) -> String {
+  if types.iter().any(|ts_type| ts_type == UNKNOWN) {
+    return UNKNOWN.to_string();
+  }
+  types.join(" | ")
+}
+
+fn is_identifier(text: &str) -> bool {
+  PAYLOAD_NAME_PATTERN.is_match(text) && !text.contains('.')
+}
+
+fn node_type(node: Node, source: &str, resolve: &dyn Fn(&str) -> Option<String>) -> Option<String> {
+  let text = &source[node.start_byte()..node.end_byte()];
+  match node.kind() {
+    "number" => Some("number".to_string()),
+    "string" | "template_string" => Some("string".to_string()),
+    "true" | "false" => Some("boolean".to_string()),
+    "regex" => Some("RegExp".to_string()),
+    "arrow_function" | "function_expression" => Some(FUNCTION.to_string()),
+    "parenthesized_expression" => node_type(node.named_child(0)?, source, resolve),
+    "identifier" => resolve(text),
+    "member_expression" => {
+      let object = node.child_by_field_name("object")?;
+      let property = node.child_by_field_name("property")?;
+      if object.kind() == "this" {
+        resolve(&source[property.start_byte()..property.end_byte()])
+      } else {
+        None
+      }
+    }
+    "array" => {
+      let mut cursor = node.walk();
+      let elements: Vec<Node> = node
+        .named_children(&mut cursor)
+        .filter(|element| element.kind() != "comment")
+        .collect();
+      let mut types: Vec<String> = Vec::new();
+      for element in elements {
+        let ts_type = node_type(element, source, resolve)?;
+        if !types.contains(&ts_type) {
+          types.push(ts_type);
+        }
+      }
+      Some(match types.len() {
+        0 => format!("{}[]", UNKNOWN),
+        1 if !types[0].contains(' ') => format!("{}[]", types[0]),
+        _ => format!("({})[]", types.join(" | ")),
+      })
+    }
+    "object" => {
+      let mut cursor = node.walk();
+      let mut fields = Vec::new();
+      for member in node.named_children(&mut cursor) {
+        match member.kind() {
+          "pair" => {
+            let key = member.child_by_field_name("key")?;
+            let value = member.child_by_field_name("value")?;
+            let ts_type = node_type(value, source, resolve).unwrap_or_else(|| UNKNOWN.to_string());
+            fields.push(format!(
+              "{}: {}",
+              &source[key.start_byte()..key.end_byte()],
+              ts_type
+            ));
+          }
+          "shorthand_property_identifier" => {
+            let name = &source[member.start_byte()..member.end_byte()];
+            fields.push(format!(
+              "{}: {}",
+              name,
+              resolve(name).unwrap_or_else(|| UNKNOWN.to_string())
+            ));
+          }
+          "method_definition" => {
+            let name = member.child_by_field_name("name")?;
+            fields.push(format!(
+              "{}: {}",
+              &source[name.start_byte()..name.end_byte()],
+              FUNCTION
+            ));
+          }
+          "comment" => {}
+          _ => return Some("Record<string, unknown>".to_string()),
+        }
+      }
+      Some(if fields.is_empty() {
+        "Record<string, unknown>".to_string()
+      } else {
+        format!("{{ {} }}", fields.join("; "))
+      })
+    }
+    "unary_expression" => match node.child_by_field_name("operator")?.kind() {
+      "!" => Some("boolean".to_string()),
+      "-" | "+" | "~" => Some("number".to_string()),
+      "typeof" => Some("string".to_string()),
+      _ => None,
+    },
+    "binary_expression" => {
+      let operator = node.child_by_field_name("operator")?.kind();
+      match operator {
+        "==" | "===" | "!=" | "!==" | "<" | ">" | "<=" | ">=" | "instanceof" | "in" => {
+          Some("boolean".to_string())
+        }
+        "-" | "*" | "/" | "%" | "**" | "|" | "&" | "^" | "<<" | ">>" | ">>>" => {
+          Some("number".to_string())
+        }
+        "+" | "&&" | "||" | "??" => {
+          let left = node_type(node.child_by_field_name("left")?, source, resolve);
+          let right = node_type(node.child_by_field_name("right")?, source, resolve);
+          if operator == "+"
+            && [&left, &right]
+              .iter()
+              .any(|side| side.as_deref() == Some("string"))
+          {
+            return Some("string".to_string());
+          }
+          left.filter(|left| Some(left) == right.as_ref())
+        }
+        _ => None,
+      }
+    }
+    "ternary_expression" => {
+      let consequence = node_type(node.child_by_field_name("consequence")?, source, resolve)?;
+      let alternative = node_type(node.child_by_field_name("alternative")?, source, resolve)?;
+      (consequence == alternative).then_some(consequence)
+    }
+    "assignment_expression" => node_type(node.child_by_field_name("right")?, source, resolve),
+    "new_expression" => {
+      let constructor = node.child_by_field_name("constructor")?;
+      let name = &source[constructor.start_byte()..constructor.end_byte()];
+      match name {
+        "Map" | "WeakMap" => Some(format!("{}<unknown, unknown>", name)),
+        "Set" | "WeakSet" | "Promise" => Some(format!("{}<unknown>", name)),
+        _ if is_identifier(name) => Some(name.to_string()),
+        _ => None,
+      }
+    }
+    "call_expression" => {
+      let function = node.child_by_field_name("function")?;
+      match &source[function.start_byte()..function.end_byte()] {
+        "String" | "JSON.stringify" => Some("string".to_string()),
+        "Number" | "parseInt" | "parseFloat" | "Date.now" | "Math.round" | "Math.floor"
+        | "Math.ceil" | "Math.max" | "Math.min" | "Math.abs" | "Math.random" => {
+          Some("number".to_string())
+        }
+        "Boolean" | "Array.isArray" => Some("boolean".to_string()),
+        _ => None,
+      }
+    }
+    _ => None,
+  }
+}
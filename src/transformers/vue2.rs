@@ -1,24 +1,46 @@
 use super::{Transformer, TransformerOrchestrator};
-use crate::{TransformationContext, TransformationResult, TransformerConfig};
+use crate::{
+  ReplacementScope, Target, TemplateRefStyle, TemplateReplacement, TransformationContext,
+  TransformationResult, TransformerConfig,
+};
 use lazy_static::lazy_static;
 use regex::Regex;
+use std::ops::Range;
 
 lazy_static! {
-    static ref VUE2_SET_THIS_PATTERN: Regex = Regex::new(r"this\.\$set\(this\.(\w+),\s*([^,]+),\s*([^)]+)\)").unwrap();
-    static ref VUE2_SET_VALUE_PATTERN: Regex = Regex::new(r"this\.\$set\((\w+)\.value,\s*([^,]+),\s*([^)]+)\)").unwrap();
-    static ref VUE2_DELETE_PATTERN: Regex = Regex::new(r"this\.\$delete\(this\.(\w+),\s*([^)]+)\)").unwrap();
     static ref VUE2_REFS_DOT_PATTERN: Regex = Regex::new(r"\$refs\.([a-zA-Z_$][a-zA-Z0-9_$]*)").unwrap();
     static ref VUE2_REFS_BRACKET_PATTERN: Regex = Regex::new(r#"\$refs\[['"]([^'"]+)['"]\]"#).unwrap();
+    static ref VUE_GLOBAL_SET_PATTERN: Regex = Regex::new(r"\bVue\.set\(").unwrap();
+    static ref VUE_GLOBAL_DELETE_PATTERN: Regex = Regex::new(r"\bVue\.delete\(").unwrap();
+    static ref VUE_GLOBAL_OBSERVABLE_PATTERN: Regex = Regex::new(r"\bVue\.observable\(").unwrap();
+    static ref VUE_GLOBAL_NEXT_TICK_PATTERN: Regex = Regex::new(r"\bVue\.nextTick\(").unwrap();
+    static ref IDENTIFIER_PATTERN: Regex = Regex::new(r"^[a-zA-Z_$][a-zA-Z0-9_$]*$").unwrap();
+    static ref INSTANCE_SET_PATTERN: Regex = Regex::new(r"(?:\bthis\.)?\$set\(").unwrap();
+    static ref INSTANCE_DELETE_PATTERN: Regex = Regex::new(r"(?:\bthis\.)?\$delete\(").unwrap();
 }
 
 /// Transformer for Vue 2 specific methods that need to be converted for Vue 3
 ///
 /// This transformer handles:
-/// - Converting `this.$set(obj, key, value)` to `obj.value[key] = value`
-/// - Converting `this.$delete(obj, key)` to `delete obj.value[key]`
+/// - Converting `this.$set(target, key, value)` to `target[key] = value` and
+///   `this.$delete(target, key)` to `delete target[key]`, for any target expression, which then
+///   gets `.value` or `props.` like any other member access
 /// - Adding `onMounted` import when needed
+///
+/// Template refs are declared with `useTemplateRef()`, or with `template_ref_style = "ref"` as
+/// `ref(null)`s the `ref` attributes are renamed to, for Vue versions before 3.5.
+///
+/// With the `vue2.7` target the runtime is still Vue 2, whose reactivity can not detect added
+/// properties: `$set`/`$delete` become the `set()`/`del()` functions of Vue 2.7 instead, and
+/// template refs are always `ref(null)`s.
 pub struct Vue2Transformer;
 
+/// A call found by [`Vue2Transformer::find_calls`], with the byte ranges of its arguments
+struct Call {
+  range: Range<usize>,
+  arguments: Vec<Range<usize>>,
+}
+
 impl Default for Vue2Transformer {
     fn default() -> Self {
         Self::new()
@@ -31,71 +53,26 @@ impl Vue2Transformer {
   }
 
   /// Get body transformation function for converting $set and $delete calls
-  fn get_vue2_body_transform(
-  ) -> Box<dyn Fn(&str, &TransformationContext, &TransformerConfig) -> String> {
+  fn get_vue2_body_transform() -> Box<super::BodyTransformFn> {
     Box::new(
-      |body: &str, context: &TransformationContext, _config: &TransformerConfig| {
-        let mut transformed_body = body.to_string();
-
-        // Transform $set calls: this.$set(this.obj, key, value) -> obj.value[key] = value
-        // Also handle already transformed: this.$set(obj.value, key, value) -> obj.value[key] = value
-        let set_pattern1 = &*VUE2_SET_THIS_PATTERN;
-        transformed_body = set_pattern1
-          .replace_all(&transformed_body, |caps: &regex::Captures| {
-            let obj_name = &caps[1];
-            let key = &caps[2];
-            let value = &caps[3];
-
-            // Check if the key is a string literal or a template literal
-            if key.starts_with("'") || key.starts_with("\"") {
-              // String literal key: obj.value.key = value
-              let clean_key = key.trim_matches(|c| c == '\'' || c == '"');
-              format!("{}.value.{} = {}", obj_name, clean_key, value)
-            } else {
-              // Dynamic key: obj.value[key] = value
-              format!("{}.value[{}] = {}", obj_name, key, value)
-            }
-          })
-          .to_string();
-
-        // Handle already transformed reactive references: this.$set(obj.value, key, value) -> obj.value[key] = value
-        let set_pattern2 = &*VUE2_SET_VALUE_PATTERN;
-        transformed_body = set_pattern2
-          .replace_all(&transformed_body, |caps: &regex::Captures| {
-            let obj_name = &caps[1];
-            let key = &caps[2];
-            let value = &caps[3];
-
-            // Check if the key is a string literal or a template literal
-            if key.starts_with("'") || key.starts_with("\"") {
-              // String literal key: obj.value.key = value
-              let clean_key = key.trim_matches(|c| c == '\'' || c == '"');
-              format!("{}.value.{} = {}", obj_name, clean_key, value)
-            } else {
-              // Dynamic key: obj.value[key] = value
-              format!("{}.value[{}] = {}", obj_name, key, value)
-            }
-          })
-          .to_string();
-
-        // Transform $delete calls: this.$delete(this.obj, key) -> delete obj.value[key]
-        let delete_pattern1 = &*VUE2_DELETE_PATTERN;
-        transformed_body = delete_pattern1
-          .replace_all(&transformed_body, |caps: &regex::Captures| {
-            let obj_name = &caps[1];
-            let key = &caps[2];
-
-            // Check if the key is a string literal or a template literal
-            if key.starts_with("'") || key.starts_with("\"") {
-              // String literal key: delete obj.value.key
-              let clean_key = key.trim_matches(|c| c == '\'' || c == '"');
-              format!("delete {}.value.{}", obj_name, clean_key)
-            } else {
-              // Dynamic key: delete obj.value[key]
-              format!("delete {}.value[{}]", obj_name, key)
-            }
-          })
-          .to_string();
+      |body: &str, context: &TransformationContext, config: &TransformerConfig| {
+        // Transform global API calls: Vue.set(...), Vue.delete(...), Vue.observable(...)
+        let mut transformed_body = Vue2Transformer::transform_global_api(body, config.target);
+
+        // Transform instance calls: this.$set(target, key, value) -> target[key] = value, the
+        // target is left for the reactive transforms to access through `.value` or `props`
+        transformed_body = Vue2Transformer::rewrite_calls(
+          &transformed_body,
+          &INSTANCE_SET_PATTERN,
+          3,
+          |arguments| Vue2Transformer::set_call(arguments, config.target),
+        );
+        transformed_body = Vue2Transformer::rewrite_calls(
+          &transformed_body,
+          &INSTANCE_DELETE_PATTERN,
+          2,
+          |arguments| Vue2Transformer::delete_call(arguments, config.target),
+        );
 
         // Transform $nextTick calls: this.$nextTick(...) -> nextTick(...)
         transformed_body = transformed_body.replace("this.$nextTick(", "nextTick(");
@@ -104,7 +81,11 @@ impl Vue2Transformer {
         transformed_body = transformed_body.replace("$nextTick(", "nextTick(");
 
         // Transform $refs usage: this.$refs.name -> nameRef.value and this.$refs['name'] -> nameRef.value
-        let template_refs = Vue2Transformer::extract_template_refs(context);
+        let template_refs = if transformed_body.contains("$refs") {
+          Vue2Transformer::extract_template_refs(context)
+        } else {
+          Vec::new()
+        };
         for ref_name in &template_refs {
           let var_name = Vue2Transformer::ref_name_to_variable(ref_name);
 
@@ -139,9 +120,208 @@ impl Vue2Transformer {
     )
   }
 
+  /// Transform Vue 2 global API calls into their Vue 3 equivalents
+  ///
+  /// - `Vue.set(obj, key, value)` -> `obj[key] = value` (`set(obj, key, value)` for Vue 2.7)
+  /// - `Vue.delete(obj, key)` -> `delete obj[key]` (`del(obj, key)` for Vue 2.7)
+  /// - `Vue.observable({...})` -> `reactive({...})`
+  /// - `Vue.nextTick(...)` -> `nextTick(...)`
+  pub(crate) fn transform_global_api(body: &str, target: Target) -> String {
+    let mut transformed_body = Self::rewrite_calls(body, &VUE_GLOBAL_SET_PATTERN, 3, |arguments| {
+      Self::set_call(arguments, target)
+    });
+    transformed_body = Self::rewrite_calls(
+      &transformed_body,
+      &VUE_GLOBAL_DELETE_PATTERN,
+      2,
+      |arguments| Self::delete_call(arguments, target),
+    );
+
+    transformed_body = VUE_GLOBAL_OBSERVABLE_PATTERN
+      .replace_all(&transformed_body, "reactive(")
+      .to_string();
+
+    VUE_GLOBAL_NEXT_TICK_PATTERN
+      .replace_all(&transformed_body, "nextTick(")
+      .to_string()
+  }
+
+  /// Rewrite the calls matched by `pattern` taking `arity` arguments with `rewrite`
+  ///
+  /// The arguments are the nodes of the parsed call, so any expression can be passed (nested
+  /// paths, calls, template literals, regular expressions), each with the comments before it.
+  /// Calls on another object (`vm.$set()`) or with another number of arguments are left alone.
+  fn rewrite_calls(
+    body: &str,
+    pattern: &Regex,
+    arity: usize,
+    rewrite: impl Fn(&[&str]) -> String,
+  ) -> String {
+    let mut result = String::with_capacity(body.len());
+    let mut position = 0;
+
+    for call in Self::find_calls(body, pattern) {
+      // Calls in the arguments of a rewritten call are kept as they are
+      if call.range.start < position || call.arguments.len() != arity {
+        continue;
+      }
+      let arguments: Vec<&str> = call
+        .arguments
+        .iter()
+        .map(|argument| body[argument.clone()].trim())
+        .collect();
+
+      result.push_str(&body[position..call.range.start]);
+      result.push_str(&rewrite(&arguments));
+      position = call.range.end;
+    }
+
+    result.push_str(&body[position..]);
+    result
+  }
+
+  /// The calls of `code` whose callee is matched by `pattern` (ending with the opening
+  /// parenthesis), skipping matches in strings or comments and calls on another object
+  /// (`vm.$set()`)
+  fn find_calls(code: &str, pattern: &Regex) -> Vec<Call> {
+    if !pattern.is_match(code) {
+      return Vec::new();
+    }
+    let Some((tree, offset)) = crate::javascript::parse_fragment(code) else {
+      return Vec::new();
+    };
+
+    pattern
+      .find_iter(code)
+      .filter_map(|found| {
+        let open = offset + found.end() - 1;
+        let arguments = tree
+          .root_node()
+          .descendant_for_byte_range(open, open + 1)?
+          .parent()
+          .filter(|arguments| arguments.kind() == "arguments" && arguments.start_byte() == open)?;
+        let call = arguments
+          .parent()
+          .filter(|call| call.kind() == "call_expression")?;
+        let function = call.child_by_field_name("function")?;
+        if function.start_byte() != offset + found.start() {
+          return None;
+        }
+
+        // Each argument starts after the previous comma, keeping the comments before it
+        let mut ranges = Vec::new();
+        let mut start = open + 1;
+        let mut cursor = arguments.walk();
+        for child in arguments.children(&mut cursor) {
+          if child.kind() == "," {
+            start = child.end_byte();
+          } else if child.is_named() && child.kind() != "comment" {
+            ranges.push(start - offset..child.end_byte() - offset);
+          }
+        }
+        Some(Call {
+          range: found.start()..call.end_byte() - offset,
+          arguments: ranges,
+        })
+      })
+      .collect()
+  }
+
+  /// `set(target, key, value)` as an assignment, kept as a `set()` call for Vue 2.7
+  fn set_call(arguments: &[&str], target: Target) -> String {
+    match target {
+      Target::Vue27 => format!("set({})", arguments.join(", ")),
+      _ => format!(
+        "{}{} = {}",
+        arguments[0],
+        Self::member_access(arguments[1]),
+        arguments[2]
+      ),
+    }
+  }
+
+  /// `delete(target, key)` as a `delete` expression, kept as a `del()` call for Vue 2.7
+  fn delete_call(arguments: &[&str], target: Target) -> String {
+    match target {
+      Target::Vue27 => format!("del({})", arguments.join(", ")),
+      _ => format!(
+        "delete {}{}",
+        arguments[0],
+        Self::member_access(arguments[1])
+      ),
+    }
+  }
+
+  /// Build a property access for a key: `'name'` -> `.name`, anything else -> `[key]`
+  fn member_access(key: &str) -> String {
+    let unquoted = key.trim_matches(|c| c == '\'' || c == '"');
+    if unquoted.len() + 2 == key.len() && IDENTIFIER_PATTERN.is_match(unquoted) {
+      format!(".{}", unquoted)
+    } else {
+      format!("[{}]", key)
+    }
+  }
+
+  /// The object a data property is initialized to observe (`state: Vue.observable({ a: 1 })` ->
+  /// `{ a: 1 }`). Such a property is declared with `reactive()` and accessed without `.value`.
+  pub(crate) fn observable_data<'a>(
+    context: &'a TransformationContext,
+    name: &str,
+  ) -> Option<&'a str> {
+    let value = context
+      .script_state
+      .data_properties
+      .iter()
+      .find(|data| data.name == name)?
+      .value
+      .as_deref()?
+      .trim();
+    let call = Self::find_calls(value, &VUE_GLOBAL_OBSERVABLE_PATTERN)
+      .into_iter()
+      .next()
+      .filter(|call| call.range == (0..value.len()))?;
+    match call.arguments.as_slice() {
+      [argument] => Some(value[argument.clone()].trim()),
+      _ => None,
+    }
+  }
+
+  /// Functions imported from `vue` by the global API calls of `code` once converted
+  pub(crate) fn global_api_imports(code: &str, target: Target) -> Vec<&'static str> {
+    let mut imports = Vec::new();
+    if VUE_GLOBAL_OBSERVABLE_PATTERN.is_match(code) {
+      imports.push("reactive");
+    }
+    if VUE_GLOBAL_NEXT_TICK_PATTERN.is_match(code) {
+      imports.push("nextTick");
+    }
+    if target == Target::Vue27 {
+      if code.contains("Vue.set(") || !Self::find_calls(code, &INSTANCE_SET_PATTERN).is_empty() {
+        imports.push("set");
+      }
+      if code.contains("Vue.delete(")
+        || !Self::find_calls(code, &INSTANCE_DELETE_PATTERN).is_empty()
+      {
+        imports.push("del");
+      }
+    }
+    imports
+  }
+
+  /// Check if the script uses Vue 2 global APIs that are converted
+  pub(crate) fn has_global_api_usage(script: &str) -> bool {
+    VUE_GLOBAL_SET_PATTERN.is_match(script)
+      || VUE_GLOBAL_DELETE_PATTERN.is_match(script)
+      || VUE_GLOBAL_OBSERVABLE_PATTERN.is_match(script)
+      || VUE_GLOBAL_NEXT_TICK_PATTERN.is_match(script)
+  }
+
   /// Check if the body contains Vue 2 reactivity methods
   fn has_vue2_methods(body: &str) -> bool {
-    body.contains("$set(") || body.contains("$delete(") || body.contains("$nextTick(")
+    body.contains("$set(")
+      || body.contains("$delete(")
+      || body.contains("$nextTick(")
+      || Self::has_global_api_usage(body)
   }
 
   /// Check if the body contains $refs usage
@@ -222,6 +402,12 @@ impl Vue2Transformer {
     refs
   }
 
+  /// Whether template refs are `ref(null)`s bound by their variable name, since
+  /// `useTemplateRef()` requires Vue 3.5
+  fn uses_plain_refs(config: &TransformerConfig) -> bool {
+    config.template_ref_style == TemplateRefStyle::Ref || config.target == Target::Vue27
+  }
+
   /// Convert a ref name to a valid variable name with Ref suffix
   fn ref_name_to_variable(ref_name: &str) -> String {
     // Convert kebab-case to camelCase
@@ -292,53 +478,20 @@ impl Transformer for Vue2Transformer {
       let mut vue_imports = vec![];
 
       for method_detail in &context.script_state.method_details {
-        match method_detail.name.as_str() {
-          "beforeMount" => {
-            if !vue_imports.contains(&"onBeforeMount") {
-              vue_imports.push("onBeforeMount");
-            }
-          }
-          "mounted" => {
-            if !vue_imports.contains(&"onMounted") {
-              vue_imports.push("onMounted");
-            }
-          }
-          "beforeUpdate" => {
-            if !vue_imports.contains(&"onBeforeUpdate") {
-              vue_imports.push("onBeforeUpdate");
-            }
-          }
-          "updated" => {
-            if !vue_imports.contains(&"onUpdated") {
-              vue_imports.push("onUpdated");
-            }
-          }
-          "beforeUnmount" => {
-            if !vue_imports.contains(&"onBeforeUnmount") {
-              vue_imports.push("onBeforeUnmount");
-            }
-          }
-          "beforeDestroy" => {
-            if !vue_imports.contains(&"onBeforeUnmount") {
-              vue_imports.push("onBeforeUnmount");
-            }
-          }
-          "destroyed" | "unmounted" => {
-            if !vue_imports.contains(&"onUnmounted") {
-              vue_imports.push("onUnmounted");
-            }
-          }
-          "activated" => {
-            if !vue_imports.contains(&"onActivated") {
-              vue_imports.push("onActivated");
-            }
-          }
-          "deactivated" => {
-            if !vue_imports.contains(&"onDeactivated") {
-              vue_imports.push("onDeactivated");
-            }
-          }
-          _ => {}
+        let hook = match method_detail.name.as_str() {
+          "beforeMount" => "onBeforeMount",
+          "mounted" => "onMounted",
+          "beforeUpdate" => "onBeforeUpdate",
+          "updated" => "onUpdated",
+          "beforeUnmount" | "beforeDestroy" => "onBeforeUnmount",
+          "destroyed" | "unmounted" => "onUnmounted",
+          "activated" => "onActivated",
+          "deactivated" => "onDeactivated",
+          _ => continue,
+        };
+
+        if !vue_imports.contains(&hook) {
+          vue_imports.push(hook);
         }
       }
 
@@ -391,40 +544,63 @@ impl Transformer for Vue2Transformer {
     ];
 
     // Generate lifecycle code in deterministic order
+    let body_transformer = TransformerOrchestrator::get_body_transformer();
     for vue3_hook in &hook_order {
       if let Some(methods) = lifecycle_groups.get(vue3_hook) {
         if vue3_hook == &"setup" {
-          // beforeCreate and created run directly in setup
+          // beforeCreate and created run directly in setup, async ones in an async IIFE so the
+          // component does not turn into an async setup that needs <Suspense>
           for method_detail in methods {
-            let body_transformer = TransformerOrchestrator::get_body_transformer();
             let transformed_body = body_transformer(&method_detail.body, context, config);
 
+            if method_detail.is_async {
+              result.lifecycle_hooks.push("(async () => {".to_string());
+            }
             for line in transformed_body.lines() {
               if !line.trim().is_empty() {
-                result.lifecycle_hooks.push(line.to_string());
+                let indent = if method_detail.is_async { "  " } else { "" };
+                result.lifecycle_hooks.push(format!("{}{}", indent, line));
               }
             }
+            if method_detail.is_async {
+              result.lifecycle_hooks.push("})();".to_string());
+            }
             result.lifecycle_hooks.push("".to_string()); // Add blank line
           }
         } else {
-          // Other lifecycle hooks are wrapped in their Vue 3 equivalent
-          result
-            .lifecycle_hooks
-            .push(format!("{}(() => {{", vue3_hook));
-
-          for method_detail in methods {
-            let body_transformer = TransformerOrchestrator::get_body_transformer();
-            let transformed_body = body_transformer(&method_detail.body, context, config);
+          // Other lifecycle hooks are wrapped in their Vue 3 equivalent. Hooks mapping to the
+          // same Vue 3 hook share a wrapper, unless one of them is async: merging would make the
+          // others wait for its awaits, so each one is registered on its own
+          let wrappers: Vec<Vec<&crate::MethodDetail>> =
+            if methods.iter().any(|method| method.is_async) {
+              methods.iter().map(|method| vec![*method]).collect()
+            } else {
+              vec![methods.clone()]
+            };
 
-            for line in transformed_body.lines() {
-              if !line.trim().is_empty() {
-                result.lifecycle_hooks.push(format!("  {}", line));
+          for wrapper in wrappers {
+            let async_part = if wrapper.iter().any(|method| method.is_async) {
+              "async "
+            } else {
+              ""
+            };
+            result
+              .lifecycle_hooks
+              .push(format!("{}({}() => {{", vue3_hook, async_part));
+
+            for method_detail in wrapper {
+              let transformed_body = body_transformer(&method_detail.body, context, config);
+
+              for line in transformed_body.lines() {
+                if !line.trim().is_empty() {
+                  result.lifecycle_hooks.push(format!("  {}", line));
+                }
               }
             }
-          }
 
-          result.lifecycle_hooks.push("});".to_string());
-          result.lifecycle_hooks.push("".to_string()); // Add blank line
+            result.lifecycle_hooks.push("});".to_string());
+            result.lifecycle_hooks.push("".to_string()); // Add blank line
+          }
         }
       }
     }
@@ -453,9 +629,34 @@ impl Transformer for Vue2Transformer {
       result.add_import("vue", "nextTick");
     }
 
+    // Global API calls in methods need their Vue 3 counterparts imported
+    for method_detail in &context.script_state.method_details {
+      for import in Self::global_api_imports(&method_detail.body, config.target) {
+        result.add_import("vue", import);
+      }
+    }
+
     // Handle template refs
     let template_refs = Self::extract_template_refs(context);
-    if !template_refs.is_empty() {
+    if !template_refs.is_empty() && Self::uses_plain_refs(config) {
+      result.add_import("vue", "ref");
+      for ref_name in &template_refs {
+        let var_name = Self::ref_name_to_variable(ref_name);
+        result
+          .reactive_state
+          .push(format!("const {} = ref(null);", var_name));
+        // The leading space leaves bound `:ref` attributes alone
+        if var_name != *ref_name {
+          for quote in ['"', '\''] {
+            result.template_replacements.push(TemplateReplacement {
+              find: format!(" ref={0}{1}{0}", quote, ref_name),
+              replace: format!(" ref={0}{1}{0}", quote, var_name),
+              scope: ReplacementScope::Template,
+            });
+          }
+        }
+      }
+    } else if !template_refs.is_empty() {
       result.add_import("vue", "useTemplateRef");
 
       // Generate template ref declarations in reactive_state section (after regular refs)
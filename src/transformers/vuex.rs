@@ -1,7 +1,7 @@
-use super::Transformer;
+use super::{Transformer, TransformerOrchestrator};
 use crate::{
-  FunctionCallDetail, TemplateReplacement, TransformationContext, TransformationResult,
-  TransformerConfig,
+  Diagnostic, FunctionCallDetail, ReplacementScope, TemplateReplacement, TransformationContext,
+  TransformationResult, TransformerConfig,
 };
 use std::collections::HashSet;
 use lazy_static::lazy_static;
@@ -16,6 +16,7 @@ lazy_static! {
     static ref VUEX_DISPATCH_PATTERN: Regex = Regex::new(r#"this\.\$store\.dispatch\(['"]([a-zA-Z_]\w*)/([a-zA-Z_]\w*)['"](?:,\s*([^)]+))?\)"#).unwrap();
     static ref VUEX_STATE_PATTERN: Regex = Regex::new(r#"this\.\$store\.state\.([a-zA-Z_]\w*)\.([a-zA-Z_]\w*)"#).unwrap();
     static ref VUEX_TEMPLATE_STATE_PATTERN: Regex = Regex::new(r#"\$store\.state\.([a-zA-Z_]\w*)\.([a-zA-Z_]\w*)"#).unwrap();
+    static ref VUEX_GETTER_PATTERN: Regex = Regex::new(r#"(?:this\.)?\$store\.getters\[\s*['"`]([a-zA-Z_]\w*)/([a-zA-Z_]\w*)['"`]\s*\]"#).unwrap();
 }
 
 /// Transformer for Vuex to Pinia store usage
@@ -24,10 +25,23 @@ lazy_static! {
 /// - Converting `this.$store.commit('namespace/action')` to `namespaceStore.action()`
 /// - Converting `this.$store.dispatch('namespace/action')` to `namespaceStore.action()`
 /// - Converting `this.$store.state.namespace.property` to `namespaceStore.property`
+/// - Converting `this.$store.getters['namespace/getter']` to `namespaceStore.getter`, keeping the
+///   arguments of getters returning a function (`productsStore.byId(id)`)
+/// - Converting calls of mapGetters getter factories (`this.productById(id)`) to store calls
+/// - Converting function-style `mapState` entries (`total: state => state.cart.total`) to computed
+///   properties reading the store inferred from the state path (`cartStore.total`)
 /// - Adding appropriate Pinia store imports
 /// - Extracting namespaces from Vuex usage patterns
 pub struct VuexTransformer;
 
+/// A `mapState` entry with a function value
+struct StateFunction {
+  alias: String,
+  namespace: Option<String>, // Namespace argument of `mapState('cart', { ... })`
+  parameter: String,         // Name of the state parameter
+  body: String,              // Expression or block (with braces) of the function
+}
+
 impl Default for VuexTransformer {
   fn default() -> Self {
     Self::new()
@@ -62,11 +76,25 @@ impl VuexTransformer {
       self.extract_namespaces_from_identifier(identifier, &mut namespaces);
     }
 
+    // Extract namespaces from bracket getter access in the template
+    if let Some(template_content) = &context.sfc_sections.template_content {
+      for captures in VUEX_GETTER_PATTERN.captures_iter(template_content) {
+        namespaces.insert(captures[1].to_string());
+      }
+    }
+
     // Extract namespaces from Vuex map functions if needed (fallback)
     if let Some(script_content) = &context.sfc_sections.script_content {
       self.extract_namespaces_from_map_functions(script_content, &mut namespaces);
     }
 
+    // Extract namespaces read by function-style mapState entries
+    for function in self.extract_state_functions(context) {
+      if self.is_property_used(&function.alias, context) {
+        namespaces.extend(Self::translate_state_access(&function).1);
+      }
+    }
+
     namespaces
   }
 
@@ -180,8 +208,7 @@ impl VuexTransformer {
   }
 
   /// Get body transformation function for converting store calls and computed properties
-  fn get_vuex_body_transform(
-  ) -> Box<dyn Fn(&str, &TransformationContext, &TransformerConfig) -> String> {
+  fn get_vuex_body_transform() -> Box<super::BodyTransformFn> {
     Box::new(
       |body: &str, context: &TransformationContext, _config: &TransformerConfig| {
         let mut transformed_body = body.to_string();
@@ -190,8 +217,16 @@ impl VuexTransformer {
         let transformer = VuexTransformer::new();
 
         // Transform computed properties created by Vuex map functions to .value syntax
-        let aliased_getters = transformer.extract_aliased_getters(context);
-        for (alias, _namespace, _getter, _is_array_syntax) in &aliased_getters {
+        let aliased_getters = last_alias_wins(transformer.extract_aliased_getters(context));
+        for (alias, namespace, getter, _is_array_syntax) in &aliased_getters {
+          // Getter factories are called on the store: this.productById(id) -> productsStore.byId(id)
+          let call_pattern = format!("\\bthis\\.{}\\s*\\(", regex::escape(alias));
+          if let Ok(regex_pattern) = regex::Regex::new(&call_pattern) {
+            transformed_body = regex_pattern
+              .replace_all(&transformed_body, format!("{}Store.{}(", namespace, getter))
+              .to_string();
+          }
+
           let pattern = format!("\\bthis\\.{}\\b", regex::escape(alias));
           if let Ok(regex_pattern) = regex::Regex::new(&pattern) {
             transformed_body = regex_pattern
@@ -200,7 +235,14 @@ impl VuexTransformer {
           }
         }
 
-        let aliased_state = transformer.extract_aliased_state_properties(context);
+        let mut aliased_state =
+          last_alias_wins(transformer.extract_aliased_state_properties(context));
+        aliased_state.extend(
+          transformer
+            .extract_state_functions(context)
+            .into_iter()
+            .map(|function| (function.alias, String::new(), String::new(), false)),
+        );
         for (alias, _namespace, _property, _is_array_syntax) in &aliased_state {
           let pattern = format!("\\bthis\\.{}\\b", regex::escape(alias));
           if let Ok(regex_pattern) = regex::Regex::new(&pattern) {
@@ -211,27 +253,26 @@ impl VuexTransformer {
         }
 
         // Extract aliased methods from map functions
-        let aliased_actions = transformer.extract_aliased_actions(context);
-        let aliased_mutations = transformer.extract_aliased_mutations(context);
-        let _aliased_getters = transformer.extract_aliased_getters(context);
-        let _aliased_state = transformer.extract_aliased_state_properties(context);
-
-        // Transform calls to aliased actions: this.fetchUser() -> userStore.fetchUser()
-        for (alias, namespace, _action_name, _is_array_syntax) in &aliased_actions {
-          let pattern = format!("this\\.{}\\(", regex::escape(alias));
+        let aliased_actions = last_alias_wins(transformer.extract_aliased_actions(context));
+        let aliased_mutations = last_alias_wins(transformer.extract_aliased_mutations(context));
+
+        // Transform calls to aliased actions: this.fetchUser() -> userStore.fetchUser(), and
+        // references passed as callbacks: this.fetchUser -> userStore.fetchUser
+        for (alias, namespace, action_name, _is_array_syntax) in &aliased_actions {
+          let pattern = format!("this\\.{}\\b", regex::escape(alias));
           if let Ok(regex_pattern) = regex::Regex::new(&pattern) {
             transformed_body = regex_pattern
-              .replace_all(&transformed_body, format!("{}Store.{}(", namespace, alias))
+              .replace_all(&transformed_body, format!("{}Store.{}", namespace, action_name))
               .to_string();
           }
         }
 
         // Transform calls to aliased mutations: this.setUser() -> userStore.setUser()
-        for (alias, namespace, _mutation_name, _is_array_syntax) in &aliased_mutations {
-          let pattern = format!("this\\.{}\\(", regex::escape(alias));
+        for (alias, namespace, mutation_name, _is_array_syntax) in &aliased_mutations {
+          let pattern = format!("this\\.{}\\b", regex::escape(alias));
           if let Ok(regex_pattern) = regex::Regex::new(&pattern) {
             transformed_body = regex_pattern
-              .replace_all(&transformed_body, format!("{}Store.{}(", namespace, alias))
+              .replace_all(&transformed_body, format!("{}Store.{}", namespace, mutation_name))
               .to_string();
           }
         }
@@ -276,6 +317,11 @@ impl VuexTransformer {
           })
           .to_string();
 
+        // Transform getter access: this.$store.getters['namespace/getter'] -> namespaceStore.getter
+        transformed_body = VUEX_GETTER_PATTERN
+          .replace_all(&transformed_body, "${1}Store.${2}")
+          .to_string();
+
         // Transform state access: this.$store.state.namespace.property -> namespaceStore.property
         let state_pattern = &*VUEX_STATE_PATTERN;
         transformed_body = state_pattern
@@ -311,6 +357,19 @@ impl VuexTransformer {
           replacements.push(TemplateReplacement {
             find: full_match.to_string(),
             replace: format!("{}Store.{}", namespace, property),
+            scope: ReplacementScope::Expressions,
+          });
+        }
+      }
+
+      // Replace $store.getters['namespace/getter'] with namespaceStore.getter
+      for captures in VUEX_GETTER_PATTERN.captures_iter(template_content) {
+        let find = captures[0].to_string();
+        if !replacements.iter().any(|replacement| replacement.find == find) {
+          replacements.push(TemplateReplacement {
+            replace: format!("{}Store.{}", &captures[1], &captures[2]),
+            find,
+            scope: ReplacementScope::Expressions,
           });
         }
       }
@@ -403,12 +462,7 @@ impl VuexTransformer {
     full_call: &str,
     results: &mut Vec<(String, String, String, bool)>,
   ) {
-    let mut parser = tree_sitter::Parser::new();
-    parser
-      .set_language(&tree_sitter_javascript::LANGUAGE.into())
-      .expect("Error loading JavaScript grammar");
-
-    if let Some(tree) = parser.parse(full_call, None) {
+    if let Some(tree) = crate::javascript::parse(full_call) {
       let root_node = tree.root_node();
       // Use unified parsing for all map functions
       self.extract_from_map_call(&root_node, full_call, results);
@@ -434,21 +488,17 @@ impl VuexTransformer {
         for i in 0..arguments.child_count() {
           if let Some(child) = arguments.child(i) {
             match child.kind() {
-              "string" => {
-                if first_string_arg.is_none() {
-                  first_string_arg = Some(child);
-                  let arg_text = self.get_node_text(&child, source);
-                  let cleaned = arg_text.trim_matches('\'').trim_matches('"');
-                  // If it doesn't contain '/', it's likely a namespace
-                  if !cleaned.contains('/') {
-                    namespace = Some(cleaned.to_string());
-                  }
+              "string" if first_string_arg.is_none() => {
+                first_string_arg = Some(child);
+                let arg_text = self.get_node_text(&child, source);
+                let cleaned = arg_text.trim_matches('\'').trim_matches('"');
+                // If it doesn't contain '/', it's likely a namespace
+                if !cleaned.contains('/') {
+                  namespace = Some(cleaned.to_string());
                 }
               }
-              "object" | "array" => {
-                if array_or_object_arg.is_none() {
-                  array_or_object_arg = Some(child);
-                }
+              "object" | "array" if array_or_object_arg.is_none() => {
+                array_or_object_arg = Some(child);
               }
               _ => {
                 // Skip punctuation and other nodes
@@ -582,16 +632,8 @@ impl VuexTransformer {
 
   /// Check if a property is actually used in the template or script
   fn is_property_used(&self, property_name: &str, context: &TransformationContext) -> bool {
-    // Check template usage
-    if let Some(template_content) = &context.sfc_sections.template_content {
-      if template_content.contains(&format!("{{ {}", property_name))
-        || template_content.contains(&format!("{{{{{}", property_name))
-        || template_content.contains(&format!(" {}", property_name))
-        || template_content.contains(&format!("\"{}\"", property_name))
-        || template_content.contains(&format!("'{}'", property_name))
-      {
-        return true;
-      }
+    if self.is_used_in_template(property_name, context) || self.is_watched(property_name, context) {
+      return true;
     }
 
     // Check script usage - look for this.propertyName usage
@@ -608,14 +650,205 @@ impl VuexTransformer {
       }
     }
 
-    // Check identifiers in template state
-    for identifier in &context.template_state.identifiers {
-      if identifier == property_name {
-        return true;
+    false
+  }
+
+  /// Check if a property is used in the template, where loop variables and slot props of the
+  /// same name shadow it
+  fn is_used_in_template(&self, property_name: &str, context: &TransformationContext) -> bool {
+    context
+      .template_state
+      .identifiers
+      .iter()
+      .any(|identifier| identifier == property_name)
+  }
+
+  /// Check if a watcher watches a property or a path under it (`items.length`)
+  fn is_watched(&self, property_name: &str, context: &TransformationContext) -> bool {
+    context
+      .script_state
+      .watchers
+      .iter()
+      .any(|watcher| watcher.watched_property.split('.').next() == Some(property_name))
+  }
+
+  /// Check if a property is called in the template (`byId(3)`)
+  fn is_called_in_template(&self, property_name: &str, context: &TransformationContext) -> bool {
+    let Some(template_content) = &context.sfc_sections.template_content else {
+      return false;
+    };
+    Regex::new(&format!(r"(?:^|[^\w$.]){}\s*\(", regex::escape(property_name)))
+      .is_ok_and(|call| call.is_match(template_content))
+  }
+
+  /// Check if a mapGetters alias is read as a value, not only called as a getter factory (which
+  /// is rewritten to a store call)
+  fn is_getter_read(&self, alias: &str, context: &TransformationContext) -> bool {
+    if self.is_used_in_template(alias, context) || self.is_watched(alias, context) {
+      return true;
+    }
+
+    let Some(script_content) = &context.sfc_sections.script_content else {
+      return false;
+    };
+    let escaped = regex::escape(alias);
+    let (Ok(access), Ok(call)) = (
+      Regex::new(&format!(r"\bthis\.{}\b", escaped)),
+      Regex::new(&format!(r"\bthis\.{}\s*\(", escaped)),
+    ) else {
+      return true;
+    };
+
+    access.find_iter(script_content).count() > call.find_iter(script_content).count()
+  }
+
+  /// Extract the function-style entries of mapState calls
+  fn extract_state_functions(&self, context: &TransformationContext) -> Vec<StateFunction> {
+    let mut functions = Vec::new();
+    for function_call in &context.script_state.function_call_details {
+      if function_call.name != "mapState" {
+        continue;
       }
+      let source = function_call.full_call.as_str();
+      let Some(tree) = crate::javascript::parse(source) else {
+        continue;
+      };
+      self.collect_state_functions(&tree.root_node(), source, &mut functions);
     }
 
-    false
+    functions
+  }
+
+  /// Collect the function values of the mapState object argument
+  fn collect_state_functions(
+    &self,
+    node: &tree_sitter::Node,
+    source: &str,
+    functions: &mut Vec<StateFunction>,
+  ) {
+    if node.kind() == "call_expression" {
+      if let Some(arguments) = node.child_by_field_name("arguments") {
+        let mut cursor = arguments.walk();
+        let arguments: Vec<_> = arguments.named_children(&mut cursor).collect();
+        let namespace = arguments
+          .iter()
+          .find(|argument| argument.kind() == "string")
+          .map(|argument| {
+            let text = self.get_node_text(argument, source);
+            text.trim_matches(|c| c == '\'' || c == '"').to_string()
+          });
+
+        if let Some(object) = arguments.iter().find(|argument| argument.kind() == "object") {
+          let mut cursor = object.walk();
+          for entry in object.named_children(&mut cursor) {
+            let (key, function) = match entry.kind() {
+              "pair" => (entry.child_by_field_name("key"), entry.child_by_field_name("value")),
+              "method_definition" => (entry.child_by_field_name("name"), Some(entry)),
+              _ => continue,
+            };
+            let (Some(key), Some(function)) = (key, function) else {
+              continue;
+            };
+            if !matches!(
+              function.kind(),
+              "arrow_function" | "function_expression" | "function" | "method_definition"
+            ) {
+              continue;
+            }
+
+            let parameter = function
+              .child_by_field_name("parameter")
+              .or_else(|| {
+                function
+                  .child_by_field_name("parameters")
+                  .and_then(|parameters| parameters.named_child(0))
+              })
+              .map(|parameter| self.get_node_text(&parameter, source));
+            let (Some(parameter), Some(body)) = (parameter, function.child_by_field_name("body"))
+            else {
+              continue;
+            };
+
+            functions.push(StateFunction {
+              alias: self
+                .get_node_text(&key, source)
+                .trim_matches(|c| c == '\'' || c == '"')
+                .to_string(),
+              namespace: namespace.clone(),
+              parameter,
+              body: self.get_node_text(&body, source),
+            });
+          }
+        }
+        return;
+      }
+    }
+
+    for i in 0..node.child_count() {
+      if let Some(child) = node.child(i) {
+        self.collect_state_functions(&child, source, functions);
+      }
+    }
+  }
+
+  /// Rewrite the state parameter accesses of a mapState function to the Pinia stores, returning
+  /// the rewritten code and the namespaces it reads. `state.cart.total` becomes `cartStore.total`,
+  /// or `state.total` with a namespace argument.
+  fn translate_state_access(function: &StateFunction) -> (String, Vec<String>) {
+    let mut namespaces = Vec::new();
+    let parameter = regex::escape(&function.parameter);
+
+    let translated = match &function.namespace {
+      Some(namespace) => {
+        let Ok(pattern) = Regex::new(&format!(r"(^|[^\w$.]){}\.", parameter)) else {
+          return (function.body.clone(), namespaces);
+        };
+        namespaces.push(namespace.clone());
+        pattern
+          .replace_all(&function.body, format!("${{1}}{}Store.", namespace))
+          .to_string()
+      }
+      None => {
+        let Ok(pattern) = Regex::new(&format!(
+          r"(^|[^\w$.]){}\.([a-zA-Z_]\w*)(\.)?",
+          parameter
+        )) else {
+          return (function.body.clone(), namespaces);
+        };
+        pattern
+          .replace_all(&function.body, |caps: &regex::Captures| {
+            let namespace = caps[2].to_string();
+            if !namespaces.contains(&namespace) {
+              namespaces.push(namespace.clone());
+            }
+            match caps.get(3) {
+              Some(_) => format!("{}{}Store.", &caps[1], namespace),
+              None => format!("{}{}Store.$state", &caps[1], namespace),
+            }
+          })
+          .to_string()
+      }
+    };
+
+    (translated, namespaces)
+  }
+
+  /// Aliases of the computed properties generated for mapState and mapGetters entries
+  pub(crate) fn mapped_computed_aliases(context: &TransformationContext) -> Vec<String> {
+    let transformer = Self::new();
+    let mut aliases: Vec<String> = transformer
+      .extract_aliased_getters(context)
+      .into_iter()
+      .chain(transformer.extract_aliased_state_properties(context))
+      .map(|(alias, _, _, _)| alias)
+      .collect();
+    aliases.extend(
+      transformer
+        .extract_state_functions(context)
+        .into_iter()
+        .map(|function| function.alias),
+    );
+    aliases
   }
 
   /// Extract aliased state properties from mapState function calls
@@ -649,7 +882,7 @@ impl Transformer for VuexTransformer {
   fn transform(
     &self,
     context: &TransformationContext,
-    _config: &TransformerConfig,
+    config: &TransformerConfig,
   ) -> TransformationResult {
     let mut result = TransformationResult::new();
 
@@ -684,11 +917,50 @@ impl Transformer for VuexTransformer {
       }
     }
 
+    // Report aliases mapped from more than one namespace, only the last mapping is kept
+    for (map_function, entries) in [
+      ("mapState", self.extract_aliased_state_properties(context)),
+      ("mapGetters", self.extract_aliased_getters(context)),
+      ("mapActions", self.extract_aliased_actions(context)),
+      ("mapMutations", self.extract_aliased_mutations(context)),
+    ] {
+      for (alias, sources) in alias_collisions(&entries) {
+        result.diagnostics.push(Diagnostic::warning(
+          "vuex-alias-collision",
+          format!(
+            "`{}` is mapped by {} from {}, only the last mapping ({}) is kept as in Vue 2. Rename the alias to keep the others",
+            alias,
+            map_function,
+            sources.join(", "),
+            sources.last().unwrap()
+          ),
+        ));
+      }
+    }
+
+    // Computed properties generated so far, aliases shared between map functions keep the first
+    let mut computed_aliases: HashSet<String> = HashSet::new();
+    let mut claim_alias = |alias: &str, result: &mut TransformationResult| {
+      if computed_aliases.insert(alias.to_string()) {
+        return true;
+      }
+      result.diagnostics.push(Diagnostic::warning(
+        "vuex-alias-collision",
+        format!(
+          "`{}` is mapped by more than one of mapState and mapGetters, only the first computed property was generated",
+          alias
+        ),
+      ));
+      false
+    };
+
     // Generate computed properties from mapGetters (only if used)
-    let aliased_getters = self.extract_aliased_getters(context);
+    let aliased_getters = last_alias_wins(self.extract_aliased_getters(context));
     for (alias, namespace, getter, is_array_syntax) in aliased_getters {
-      if self.is_property_used(&alias, context) {
-        let parentheses = if is_array_syntax { "()" } else { "" };
+      if self.is_getter_read(&alias, context) && claim_alias(&alias, &mut result) {
+        // Getter factories called in the template (`byId(3)`) are passed their arguments there
+        let is_factory = self.is_called_in_template(&alias, context);
+        let parentheses = if is_array_syntax && !is_factory { "()" } else { "" };
         result.computed_properties.push(format!(
           "const {} = computed(() => {}Store.{}{});",
           alias, namespace, getter, parentheses
@@ -697,9 +969,9 @@ impl Transformer for VuexTransformer {
     }
 
     // Generate computed properties from mapState (only if used)
-    let aliased_state = self.extract_aliased_state_properties(context);
+    let aliased_state = last_alias_wins(self.extract_aliased_state_properties(context));
     for (alias, namespace, property, _is_array_syntax) in aliased_state {
-      if self.is_property_used(&alias, context) {
+      if self.is_property_used(&alias, context) && claim_alias(&alias, &mut result) {
         result.computed_properties.push(format!(
           "const {} = computed(() => {}Store.{});",
           alias, namespace, property
@@ -707,11 +979,53 @@ impl Transformer for VuexTransformer {
       }
     }
 
+    // Generate computed properties from function-style mapState entries (only if used)
+    for function in self.extract_state_functions(context) {
+      if !self.is_property_used(&function.alias, context)
+        || !claim_alias(&function.alias, &mut result)
+      {
+        continue;
+      }
+
+      let (translated, _) = Self::translate_state_access(&function);
+      let parameter = regex::escape(&function.parameter);
+      let leftover = Regex::new(&format!(r"(^|[^\w$.]){}\b", parameter))
+        .is_ok_and(|pattern| pattern.is_match(&translated));
+      if leftover {
+        result.diagnostics.push(Diagnostic::warning(
+          "map-state-function",
+          format!(
+            "`{}` in mapState uses `{}` other than to read a module property, check the generated computed",
+            function.alias, function.parameter
+          ),
+        ));
+      }
+
+      let body_transformer = TransformerOrchestrator::get_body_transformer();
+      let body = body_transformer(&translated, context, config);
+      result
+        .computed_properties
+        .push(format!("const {} = computed(() => {});", function.alias, body.trim()));
+    }
+
     // Generate template replacements for $store usage
     result
       .template_replacements
       .extend(self.generate_template_replacements(context));
 
+    // Mapped actions and mutations live on the store, handlers (`@click="save"`) reference it
+    let mut aliased_methods = last_alias_wins(self.extract_aliased_actions(context));
+    aliased_methods.extend(last_alias_wins(self.extract_aliased_mutations(context)));
+    for (alias, namespace, method, _is_array_syntax) in aliased_methods {
+      if self.is_used_in_template(&alias, context) {
+        result.template_replacements.push(TemplateReplacement {
+          find: alias,
+          replace: format!("{}Store.{}", namespace, method),
+          scope: ReplacementScope::Identifiers,
+        });
+      }
+    }
+
     // Remove Vuex imports since we're converting to Pinia
     result.imports_to_remove.push("vuex".to_string());
 
@@ -723,8 +1037,42 @@ impl Transformer for VuexTransformer {
   }
 }
 
+/// Keep the last mapping of each alias: the map function results are spread into `computed` or
+/// `methods`, so later keys win
+fn last_alias_wins(
+  entries: Vec<(String, String, String, bool)>,
+) -> Vec<(String, String, String, bool)> {
+  let mut seen = HashSet::new();
+  let mut kept: Vec<_> = entries
+    .into_iter()
+    .rev()
+    .filter(|(alias, ..)| seen.insert(alias.clone()))
+    .collect();
+  kept.reverse();
+  kept
+}
+
+/// Aliases mapped from more than one `namespace/name` source, with the sources in mapping order
+fn alias_collisions(entries: &[(String, String, String, bool)]) -> Vec<(String, Vec<String>)> {
+  let mut collisions: Vec<(String, Vec<String>)> = Vec::new();
+
+  for (alias, namespace, name, _) in entries {
+    let source = format!("`{}/{}`", namespace, name);
+    match collisions.iter_mut().find(|(existing, _)| existing == alias) {
+      Some((_, sources)) => {
+        sources.retain(|existing| existing != &source);
+        sources.push(source);
+      }
+      None => collisions.push((alias.clone(), vec![source])),
+    }
+  }
+
+  collisions.retain(|(_, sources)| sources.len() > 1);
+  collisions
+}
+
 /// Capitalize the first letter of a string
-fn capitalize_first_letter(s: &str) -> String {
+pub(crate) fn capitalize_first_letter(s: &str) -> String {
   let mut chars = s.chars();
   match chars.next() {
     None => String::new(),
@@ -0,0 +1,111 @@
+use std::collections::HashMap;
+use vue_options_to_composition::analyze::{analyze_component, Difficulty};
+use vue_options_to_composition::{MixinConfig, RewriteOptions};
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use pretty_assertions::assert_eq;
+
+  #[test]
+  fn test_should_inventory_component_features() {
+    let sfc = r#"<template>
+      <p>{{ price | currency }} {{ $auth.user.name }}</p>
+    </template>
+    <script>
+    import priceMixin from '@/mixins/price';
+    import tracking from '@/mixins/tracking';
+    import { mapGetters } from 'vuex';
+
+    export default {
+      mixins: [priceMixin, tracking],
+      inject: ['theme', 'locale'],
+      computed: {
+        ...mapGetters({ total: 'cart/total' })
+      },
+      data() {
+        return {
+          price: 1
+        };
+      },
+      asyncData({ params }) {
+        return { id: params.id };
+      },
+      mounted() {
+        this.$auth.fetch();
+        this.$refs.input.focus();
+      }
+    }
+    </script>"#;
+
+    let mut mixins = HashMap::new();
+    mixins.insert(
+      "price".to_string(),
+      MixinConfig {
+        name: "usePrice".to_string(),
+        imports: vec!["formatPrice".to_string()],
+      },
+    );
+    let options = RewriteOptions {
+      mixins: Some(mixins),
+      ..Default::default()
+    };
+
+    let inventory = analyze_component(sfc, Some(options)).unwrap();
+
+    assert_eq!(
+      inventory.features,
+      vec!["refs", "vuex", "mixins", "inject", "asyncData", "filters"]
+    );
+    assert_eq!(inventory.mixins, vec!["priceMixin", "tracking"]);
+    assert_eq!(inventory.unconfigured_mixins, vec!["tracking"]);
+    assert_eq!(inventory.injections, vec!["theme", "locale"]);
+    assert_eq!(inventory.unknown_injections, vec!["$auth"]);
+    assert_eq!(inventory.unresolved, 1);
+    assert_eq!(inventory.difficulty, Difficulty::Hard);
+  }
+
+  #[test]
+  fn test_should_rate_simple_component_easy() {
+    let sfc = r#"<template><button @click="increment">{{ count }}</button></template>
+    <script>
+    export default {
+      data() {
+        return {
+          count: 0
+        };
+      },
+      methods: {
+        increment() {
+          this.count++;
+        }
+      }
+    }
+    </script>"#;
+
+    let inventory = analyze_component(sfc, None).unwrap();
+
+    assert!(inventory.features.is_empty());
+    assert_eq!(inventory.unresolved, 0);
+    assert_eq!(inventory.warnings, 0);
+    assert_eq!(inventory.difficulty, Difficulty::Easy);
+  }
+
+  #[test]
+  fn test_should_rate_class_component_hard() {
+    let sfc = r#"<template><p>{{ message }}</p></template>
+    <script>
+    import { Component, Vue } from 'vue-property-decorator';
+
+    @Component
+    export default class Hello extends Vue {
+      message = 'Hello';
+    }
+    </script>"#;
+
+    let inventory = analyze_component(sfc, None).unwrap();
+
+    assert_eq!(inventory.features, vec!["class-component"]);
+    assert_eq!(inventory.difficulty, Difficulty::Hard);
+  }
+}
@@ -0,0 +1,94 @@
+//! Allocation budget for converting the "huge" benchmark component. Allocations are counted rather
+//! than timed so the budget holds on any machine and in debug builds. Both Rust allocations and the
+//! ones tree-sitter makes in C are counted, so building a parser per snippet shows up as well.
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::ffi::c_void;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+mod common;
+
+/// About 10% above the 5.5M allocations the conversion currently makes
+const BUDGET: usize = 6_000_000;
+
+/// Counts the allocations made while `COUNTING` is set
+struct CountingAllocator;
+
+static COUNTING: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+  unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+    if COUNTING.load(Ordering::Relaxed) != 0 {
+      ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    }
+    System.alloc(layout)
+  }
+
+  unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+    System.dealloc(ptr, layout)
+  }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+extern "C" {
+  fn malloc(size: usize) -> *mut c_void;
+  fn calloc(count: usize, size: usize) -> *mut c_void;
+  fn realloc(ptr: *mut c_void, size: usize) -> *mut c_void;
+}
+
+/// Counts an allocation made by tree-sitter while `COUNTING` is set
+fn count_tree_sitter_allocation() {
+  if COUNTING.load(Ordering::Relaxed) != 0 {
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+  }
+}
+
+unsafe extern "C" fn counting_malloc(size: usize) -> *mut c_void {
+  count_tree_sitter_allocation();
+  malloc(size)
+}
+
+unsafe extern "C" fn counting_calloc(count: usize, size: usize) -> *mut c_void {
+  count_tree_sitter_allocation();
+  calloc(count, size)
+}
+
+unsafe extern "C" fn counting_realloc(ptr: *mut c_void, size: usize) -> *mut c_void {
+  count_tree_sitter_allocation();
+  realloc(ptr, size)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use vue_options_to_composition::{rewrite_sfc, RewriteOptions};
+
+  #[test]
+  fn test_should_convert_the_huge_component_within_the_allocation_budget() {
+    let sfc = common::component(120);
+    unsafe {
+      tree_sitter::set_allocator(
+        Some(counting_malloc),
+        Some(counting_calloc),
+        Some(counting_realloc),
+        None,
+      );
+    }
+
+    COUNTING.store(1, Ordering::Relaxed);
+    let result = rewrite_sfc(&sfc, Some(RewriteOptions::default()));
+    COUNTING.store(0, Ordering::Relaxed);
+
+    let result = result.unwrap();
+    assert!(result.contains("<script setup>"), "{}", result);
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    assert!(
+      allocations < BUDGET,
+      "converting the huge component made {} allocations, the budget is {}",
+      allocations,
+      BUDGET
+    );
+  }
+}
@@ -0,0 +1,87 @@
+//! Fixtures shared by the benchmarks and the integration tests
+
+/// A component with `members` data properties, computed properties, methods and watchers, and
+/// a template using each of them
+pub fn component(members: usize) -> String {
+  let mut template = String::new();
+  let mut data = String::new();
+  let mut computed = String::new();
+  let mut methods = String::new();
+  let mut watch = String::new();
+
+  for i in 0..members {
+    template.push_str(&format!(
+      r#"    <div v-if="visible{i}" :class="{{ active: active{i} }}" ref="item{i}">
+      <input v-model="value{i}" @input="$emit('change{i}', value{i})" />
+      <p>{{{{ $t('label.{i}') }}}} {{{{ total{i} | currency }}}}</p>
+      <b-button @click="save{i}(value{i})">{{{{ $t('save') }}}}</b-button>
+    </div>
+"#
+    ));
+    data.push_str(&format!(
+      "      value{i}: '',\n      visible{i}: true,\n      active{i}: false,\n"
+    ));
+    computed.push_str(&format!(
+      r#"    total{i}() {{
+      return this.value{i}.length + this.$store.state.cart.count;
+    }},
+"#
+    ));
+    methods.push_str(&format!(
+      r#"    async save{i}(value) {{
+      this.active{i} = true;
+      await this.$axios.post('/items/{i}', {{ value }});
+      this.$refs.item{i}.focus();
+      this.$nextTick(() => {{
+        this.$router.push({{ name: 'item', params: {{ id: {i} }} }});
+      }});
+    }},
+"#
+    ));
+    watch.push_str(&format!(
+      r#"    value{i}(newValue) {{
+      this.visible{i} = newValue !== '';
+    }},
+"#
+    ));
+  }
+
+  format!(
+    r#"<template>
+  <div>
+{template}  </div>
+</template>
+
+<script>
+import {{ mapGetters }} from 'vuex';
+
+export default {{
+  name: 'Generated',
+  props: {{
+    title: {{ type: String, default: '' }},
+  }},
+  data() {{
+    return {{
+{data}    }};
+  }},
+  computed: {{
+    ...mapGetters('user', ['isLoggedIn']),
+{computed}  }},
+  watch: {{
+{watch}  }},
+  mounted() {{
+    this.$bus.$on('refresh', this.save0);
+  }},
+  methods: {{
+{methods}  }},
+}};
+</script>
+
+<style scoped>
+.active /deep/ p {{
+  color: red;
+}}
+</style>
+"#
+  )
+}
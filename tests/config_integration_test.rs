@@ -0,0 +1,222 @@
+use std::path::Path;
+use vue_options_to_composition::config::{
+  check_config, parse_config, parse_config_file, relativize_import_paths,
+};
+use vue_options_to_composition::{
+  rewrite_sfc_with_report, RewriteOptions, Target, VTDirectiveStyle,
+};
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use pretty_assertions::assert_eq;
+
+  #[test]
+  fn test_should_parse_the_example_config() {
+    let options = parse_config(include_str!("../config.example.toml")).unwrap();
+
+    assert!(options.mixins.is_some_and(|mixins| !mixins.is_empty()));
+    assert!(options.imports_rewrite.is_some());
+  }
+
+  #[test]
+  fn test_should_parse_config_enums_and_report_unknown_values() {
+    let options = parse_config(
+      r#"
+      target = "nuxt3"
+      v_t_directive = "keep"
+      "#,
+    )
+    .unwrap();
+    assert_eq!(options.target, Some(Target::Nuxt3));
+    assert_eq!(options.v_t_directive, Some(VTDirectiveStyle::Keep));
+
+    let error = parse_config(r#"target = "vue4""#).unwrap_err();
+    assert!(error.to_string().contains("unknown variant `vue4`"));
+  }
+
+  #[test]
+  fn test_should_interpolate_env_vars_and_resolve_relative_import_paths() {
+    std::env::set_var("VOTC_TEST_SHARED_DIR", "../shared");
+    let config = r#"
+      [http]
+      import_path = "${VOTC_TEST_SHARED_DIR}/composables/useHttp"
+
+      [event_bus]
+      import_path = "@/composables/useEventBus"
+      "#;
+
+    let mut options = parse_config_file(config, Path::new("/repo/packages/web")).unwrap();
+    assert_eq!(
+      options.http.as_ref().unwrap().import_path,
+      "/repo/packages/shared/composables/useHttp"
+    );
+    assert_eq!(
+      options.event_bus.as_ref().unwrap().import_path,
+      "@/composables/useEventBus"
+    );
+
+    relativize_import_paths(&mut options, Path::new("/repo/packages/web/src/components"));
+    assert_eq!(
+      options.http.unwrap().import_path,
+      "../../../shared/composables/useHttp"
+    );
+
+    let error = parse_config(r#"aliases = { "@" = "${VOTC_TEST_UNSET_VAR}/src" }"#).unwrap_err();
+    assert!(error.to_string().contains(
+      "environment variable `VOTC_TEST_UNSET_VAR` used in `${VOTC_TEST_UNSET_VAR}/src` is not set"
+    ));
+  }
+
+  #[test]
+  fn test_should_report_config_entries_the_components_do_not_use() {
+    let options = parse_config(
+      r#"
+      import_keeplist = ["vue", "lodsh", "@/utils/format"]
+
+      [mixins.price]
+      name = "usePrice"
+      imports = ["price"]
+
+      [mixins.cart]
+      name = "useCart"
+      imports = ["cart"]
+
+      [imports_rewrite.bootstrap-vue]
+      name = "bootstrap-vue-next"
+      "#,
+    )
+    .unwrap();
+    let import_sources = vec![
+      "@/mixins/price".to_string(),
+      "lodash".to_string(),
+      "@/utils/format".to_string(),
+    ];
+
+    let messages: Vec<String> = check_config(&options, &import_sources)
+      .into_iter()
+      .map(|d| format!("{}: {}", d.code, d.message))
+      .collect();
+    assert_eq!(
+      messages,
+      vec![
+        "config-unused-mixin: `[mixins.cart]` matches no mixin imported by the components",
+        "config-unused-import-rewrite: `[imports_rewrite.bootstrap-vue]` is never imported by the components",
+        "config-keeplist-typo: `lodsh` in `import_keeplist` matches no import of the components, did you mean `lodash`?",
+      ]
+    );
+  }
+
+  #[test]
+  fn test_should_apply_the_overrides_of_a_votc_comment() {
+    let sfc = r#"<!-- votc: disable=vuex,i18n, typescript=true, target=vue4, layout=default -->
+<template>
+  <p>{{ $t('title') }} {{ count }}</p>
+</template>
+<script>
+export default {
+  data() {
+    return { count: null };
+  },
+  created() {
+    this.$store.dispatch('cart/load');
+  },
+};
+</script>"#;
+
+    let options = RewriteOptions {
+      disable: Some(vec!["expose".to_string()]),
+      ..Default::default()
+    };
+    let report = rewrite_sfc_with_report(sfc, Some(options)).unwrap();
+
+    // The comment is dropped, typescript is enabled and vuex and i18n are left alone
+    assert!(report.code.starts_with("<template>"));
+    assert!(report.code.contains("<script setup lang=\"ts\">"));
+    assert!(report.code.contains("const count = ref<"));
+    assert!(report.code.contains("$store.dispatch('cart/load');"));
+    assert!(!report.code.contains("useCartStore"));
+    assert!(report.code.contains("{{ $t('title') }}"));
+
+    let messages: Vec<String> = report
+      .diagnostics
+      .into_iter()
+      .filter(|d| d.code == "file-override")
+      .map(|d| d.message)
+      .collect();
+    assert_eq!(messages.len(), 2);
+    assert!(
+      messages[0].starts_with("`target` in the votc comment was ignored: unknown variant `vue4`")
+    );
+    assert_eq!(
+      messages[1],
+      "`layout` can not be overridden in the votc comment, configure it in the configuration file"
+    );
+  }
+
+  #[test]
+  fn test_should_report_unknown_disabled_transformers() {
+    let options = parse_config(r#"disable = ["vuex", "pinia"]"#).unwrap();
+    let report = rewrite_sfc_with_report(
+      "<template><p>{{ title }}</p></template><script>export default { props: ['title'] };</script>",
+      Some(options),
+    )
+    .unwrap();
+
+    assert_eq!(report.diagnostics.len(), 1);
+    assert_eq!(report.diagnostics[0].code, "unknown-transformer");
+    assert!(report.diagnostics[0]
+      .message
+      .starts_with("`pinia` is not a transformer and was not disabled, available transformers: "));
+  }
+
+  #[test]
+  fn test_should_convert_custom_options_with_their_config() {
+    let options = parse_config(
+      r#"
+      [custom_options.analytics]
+      emit = "useAnalytics({body})"
+      import_path = "@/composables/useAnalytics"
+      imports = ["useAnalytics"]
+
+      [custom_options.tracking]
+      emit = "useTracking({body});"
+      import_path = "@/composables/useTracking"
+      imports = ["useTracking"]
+      "#,
+    )
+    .unwrap();
+    let sfc = r#"<template><p>{{ total }}</p></template>
+<script>
+export default {
+  analytics: { page: 'cart' },
+  tracking() {
+    return { total: this.total };
+  },
+  data() {
+    return { total: 0 };
+  },
+};
+</script>"#;
+
+    let report = rewrite_sfc_with_report(sfc, Some(options)).unwrap();
+
+    let expected = r#"<template>
+<p>{{ total }}</p>
+</template>
+<script setup>
+import { ref } from 'vue';
+import { useAnalytics } from '@/composables/useAnalytics';
+import { useTracking } from '@/composables/useTracking';
+
+const total = ref(0);
+
+useAnalytics({ page: 'cart' });
+useTracking(() => {
+    return { total: total.value };
+  });
+</script>"#;
+    assert_eq!(report.code, expected);
+    assert!(report.diagnostics.is_empty());
+  }
+}
@@ -0,0 +1,158 @@
+use vue_options_to_composition::config::parse_config;
+use vue_options_to_composition::rewrite_sfc_with_report;
+
+const CONFIG: &str = r#"
+[mixins.price]
+name = "usePrice"
+imports = ["price"]
+
+[imports_rewrite.bootstrap-vue]
+name = "bootstrap-vue-next"
+
+[imports_rewrite.bootstrap-vue.component_rewrite]
+BNav = "BNavigation"
+BNavbar = "BNavbarNext"
+BButton = "BBtn"
+
+[additional_imports.ClientOnly]
+import_path = "import ClientOnly from '@/components/ClientOnly.vue';"
+
+[additional_imports.LazyImage]
+import_path = "import LazyImage from '@/components/LazyImage.vue';"
+
+[additional_imports.NuxtLink]
+rewrite_to = "router-link"
+
+[event_bus]
+import_path = "@/lib/useEventBus"
+
+[http]
+import_path = "@/plugins/useHttp"
+"#;
+
+const COMPONENTS: [&str; 3] = [
+  r#"<template>
+  <div>
+    <b-navbar><b-nav>{{ $t('title') }}</b-nav></b-navbar>
+    <client-only><p>{{ price }} {{ items.length }}</p></client-only>
+    <lazy-image src="/a.png" />
+    <nuxt-link to="/">Home</nuxt-link>
+  </div>
+</template>
+<script>
+import { mapGetters, mapState } from 'vuex';
+import { BNavbar, BNav } from 'bootstrap-vue';
+import priceMixin from '@/mixins/price';
+
+export default {
+  components: { BNavbar, BNav },
+  mixins: [priceMixin],
+  data() {
+    return { zeta: 1, alpha: 2, count: 0 };
+  },
+  computed: {
+    ...mapGetters('user', ['isLoggedIn']),
+    ...mapState('cart', ['items']),
+  },
+  async mounted() {
+    this.$bus.$on('refresh', this.load);
+    const { data } = await this.$axios.get('/api');
+    this.zeta = data;
+  },
+  methods: {
+    load() {
+      this.count++;
+      this.$router.push('/');
+    },
+  },
+};
+</script>"#,
+  r#"<template>
+  <b-button @click="save">{{ total | currency }}</b-button>
+</template>
+<script>
+import { mapActions } from 'vuex';
+import { BButton } from 'bootstrap-vue';
+
+export default {
+  components: { BButton },
+  filters: {
+    currency(value) {
+      return `$${value}`;
+    },
+  },
+  props: ['total'],
+  methods: {
+    ...mapActions('cart', ['checkout']),
+    ...mapActions('order', ['submit']),
+    save() {
+      this.checkout();
+      this.submit(this.$route.params.id);
+    },
+  },
+};
+</script>"#,
+  r#"<template>
+  <client-only>
+    <lazy-image :src="src" />
+  </client-only>
+</template>
+<script>
+export default {
+  data: () => ({ src: '', width: 0, height: 0 }),
+  watch: {
+    src() {
+      this.$bus.$emit('resized', this.width, this.height);
+    },
+  },
+};
+</script>"#,
+];
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use pretty_assertions::assert_eq;
+
+  fn convert(sfc: &str) -> String {
+    let options = parse_config(CONFIG).unwrap();
+    let report = rewrite_sfc_with_report(sfc, Some(options)).unwrap();
+    let diagnostics: Vec<String> = report
+      .diagnostics
+      .iter()
+      .map(|d| format!("[{}] {}", d.code, d.message))
+      .collect();
+    format!("{}\n{}", report.code, diagnostics.join("\n"))
+  }
+
+  #[test]
+  fn test_should_produce_the_same_output_on_every_run() {
+    // Every map gets its own hash seed, repeated runs would expose any iteration order leaking
+    // into the output
+    for sfc in COMPONENTS {
+      let expected = convert(sfc);
+      for _ in 0..20 {
+        assert_eq!(convert(sfc), expected);
+      }
+    }
+  }
+
+  #[test]
+  fn test_should_produce_the_same_output_when_converting_in_parallel() {
+    let expected: Vec<String> = COMPONENTS.iter().map(|sfc| convert(sfc)).collect();
+
+    let outputs: Vec<Vec<String>> = std::thread::scope(|scope| {
+      let handles: Vec<_> = (0..8)
+        .map(|_| scope.spawn(|| COMPONENTS.iter().map(|sfc| convert(sfc)).collect()))
+        .collect();
+      handles
+        .into_iter()
+        .map(|handle| handle.join().unwrap())
+        .collect()
+    });
+
+    for output in outputs {
+      assert_eq!(output, expected);
+    }
+  }
+}
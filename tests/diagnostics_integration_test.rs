@@ -0,0 +1,382 @@
+use std::collections::HashMap;
+use vue_options_to_composition::{
+  rewrite_sfc_with_report, AdditionalImport, DiagnosticSeverity, ImportRewrite, RewriteOptions,
+};
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use pretty_assertions::assert_eq;
+
+  #[test]
+  fn test_should_warn_about_impure_computed() {
+    let sfc = r#"<template><p>{{ greeting }} {{ label }}</p></template>
+    <script>
+    export default {
+      data() {
+        return {
+          name: 'World',
+        };
+      },
+      computed: {
+        greeting() {
+          const hour = new Date().getHours();
+          return hour < 12 ? 'Good morning' : 'Hello';
+        },
+        label() {
+          return `${this.name}-${Math.random()}-${Date.now()}`;
+        },
+        upperName() {
+          return this.name.toUpperCase();
+        },
+      },
+    };
+    </script>"#;
+
+    let report = rewrite_sfc_with_report(sfc, None).unwrap();
+
+    let diagnostics: Vec<(DiagnosticSeverity, String, String)> = report
+      .diagnostics
+      .into_iter()
+      .map(|d| (d.severity, d.code, d.message))
+      .collect();
+
+    assert_eq!(
+      diagnostics,
+      vec![
+        (
+          DiagnosticSeverity::Warning,
+          "impure-computed".to_string(),
+          "computed `greeting` calls new Date(); its value is cached and may differ between server and client, consider a method or a ref".to_string()
+        ),
+        (
+          DiagnosticSeverity::Warning,
+          "impure-computed".to_string(),
+          "computed `label` calls Math.random(), Date.now(); its value is cached and may differ between server and client, consider a method or a ref".to_string()
+        ),
+      ]
+    );
+
+    // Analysis never changes the output
+    assert!(report.code.contains("const label = computed(() => {"));
+  }
+
+  #[test]
+  fn test_should_warn_about_listeners_without_cleanup() {
+    let sfc = r#"<template><div /></template>
+    <script>
+    export default {
+      mounted() {
+        window.addEventListener('resize', this.onResize);
+        window.addEventListener('scroll', this.onScroll, { passive: true });
+        document.addEventListener('keydown', this.onKeydown);
+      },
+      beforeDestroy() {
+        window.removeEventListener('resize', this.onResize);
+        document.removeEventListener('keyup', this.onKeydown);
+      },
+      methods: {
+        onResize() {},
+        onScroll() {},
+        onKeydown() {},
+      },
+    };
+    </script>"#;
+
+    let report = rewrite_sfc_with_report(sfc, None).unwrap();
+
+    let messages: Vec<String> = report
+      .diagnostics
+      .into_iter()
+      .filter(|d| d.code == "listener-cleanup")
+      .map(|d| d.message)
+      .collect();
+
+    assert_eq!(
+      messages,
+      vec![
+        "`window.addEventListener('scroll', onScroll)` in `mounted` has no matching removeEventListener in onBeforeUnmount, the listener will leak".to_string(),
+        "`document.addEventListener('keydown', onKeydown)` in `mounted` has no matching removeEventListener in onBeforeUnmount, the listener will leak".to_string(),
+      ]
+    );
+  }
+
+  #[test]
+  fn test_should_report_vue_3_incompatibilities() {
+    let sfc = r#"<template>
+    <div>
+      <chart @hook:mounted="onChartMounted" />
+      <price-list inline-template><p>{{ price | currency }}</p></price-list>
+    </div>
+    </template>
+    <script>
+    import Vue from 'vue';
+
+    Vue.prototype.$http = fetch;
+
+    export default {
+      filters: {
+        currency(value) {
+          return `€${value}`;
+        },
+      },
+      mounted() {
+        this.$once('ready', this.onReady);
+        this.$on('hook:beforeDestroy', this.cleanup);
+        this.$children.forEach((child) => child.reset());
+      },
+      methods: {
+        onChartMounted() {},
+        onReady() {},
+        cleanup() {},
+      },
+    };
+    </script>"#;
+
+    let report = rewrite_sfc_with_report(sfc, None).unwrap();
+
+    let compat: Vec<_> = report
+      .diagnostics
+      .iter()
+      .filter(|d| d.code.starts_with("compat-"))
+      .collect();
+
+    assert_eq!(
+      compat.iter().map(|d| d.code.as_str()).collect::<Vec<_>>(),
+      vec![
+        "compat-children",
+        "compat-once",
+        "compat-hook-events",
+        "compat-hook-events",
+        "compat-prototype",
+        "compat-filters",
+        "compat-filters",
+        "compat-inline-template",
+      ]
+    );
+    assert!(compat
+      .iter()
+      .all(|d| d.severity == DiagnosticSeverity::Warning
+        && d
+          .url
+          .as_deref()
+          .is_some_and(|url| url.starts_with("https://v3-migration.vuejs.org/"))));
+    assert!(compat[4].message.contains("`$http`"));
+    assert!(compat[7].message.contains("`price-list`"));
+  }
+
+  #[test]
+  fn test_should_flag_writes_to_getter_only_computed() {
+    let sfc = r#"<template><p>{{ fullName }}</p></template>
+    <script>
+    export default {
+      data() {
+        return { first: 'a', last: 'b', count: 0 };
+      },
+      computed: {
+        fullName() {
+          return `${this.first} ${this.last}`;
+        },
+        total: {
+          get() {
+            return this.count;
+          },
+          set(value) {
+            this.count = value;
+          },
+        },
+      },
+      watch: {
+        first(value) {
+          this.fullName = value;
+        },
+      },
+      methods: {
+        reset() {
+          this.total = 0;
+          if (this.fullName === 'x') {
+            this.fullName.trim();
+          }
+        },
+      },
+    };
+    </script>"#;
+
+    let report = rewrite_sfc_with_report(sfc, None).unwrap();
+
+    let messages: Vec<String> = report
+      .diagnostics
+      .into_iter()
+      .filter(|d| d.code == "computed-write")
+      .map(|d| d.message)
+      .collect();
+
+    assert_eq!(
+      messages,
+      vec!["The `first` watcher assigns `this.fullName`, but the computed property has no setter and Vue 3 rejects the write at runtime. Add a setter or turn it into a ref".to_string()]
+    );
+    assert!(report
+      .code
+      .contains("/* FIXME: fullName */ fullName.value = value;"));
+    assert!(report.code.contains("total.value = 0;"));
+    assert!(report.code.contains("if (fullName.value === 'x') {"));
+  }
+
+  #[test]
+  fn test_should_apply_option_dispositions() {
+    let sfc = r#"<template><p v-bind="$attrs">{{ label }}</p></template>
+    <script>
+    import priceMixin from '@/mixins/price';
+
+    export default {
+      name: 'PriceLabel',
+      inheritAttrs: false,
+      mixins: [priceMixin],
+      serverCacheKey: (props) => props.label,
+      delimiters: ['${', '}'],
+      comments: true,
+      analytics: { page: 'cart' },
+      props: {
+        label: String,
+      },
+    };
+    </script>"#;
+
+    let report = rewrite_sfc_with_report(sfc, None).unwrap();
+
+    assert!(report
+      .code
+      .contains("defineOptions({ inheritAttrs: false });"));
+
+    let diagnostics: Vec<(String, String)> = report
+      .diagnostics
+      .into_iter()
+      .filter(|d| d.severity == DiagnosticSeverity::Warning)
+      .map(|d| (d.code, d.message))
+      .collect();
+
+    assert_eq!(
+      diagnostics,
+      vec![
+        (
+          "mixin-not-configured".to_string(),
+          "Mixins without a `[mixins]` entry (`priceMixin`) were dropped, configure the composables replacing them".to_string()
+        ),
+        (
+          "option-dropped".to_string(),
+          "The `serverCacheKey` option was dropped, component caching was removed from the Vue 3 server renderer".to_string()
+        ),
+        (
+          "option-dropped".to_string(),
+          "The `delimiters` option was dropped, set `compilerOptions.delimiters` in the build configuration".to_string()
+        ),
+        (
+          "option-dropped".to_string(),
+          "The `comments` option was dropped, set `compilerOptions.comments` in the build configuration".to_string()
+        ),
+        (
+          "option-unknown".to_string(),
+          "The `analytics` option is not supported and was dropped, convert it by hand".to_string()
+        ),
+      ]
+    );
+  }
+
+  #[test]
+  fn test_should_declare_template_bindings_missing_from_the_output() {
+    let sfc = r#"<template>
+  <p :title="label">{{ formatPrice(total) }} {{ Math.round(total) }}</p>
+</template>
+<script>
+import priceMixin from '@/mixins/price';
+
+export default {
+  mixins: [priceMixin],
+  data() {
+    return { total: 0 };
+  },
+};
+</script>"#;
+
+    let report = rewrite_sfc_with_report(sfc, None).unwrap();
+
+    assert!(report.code.contains("import { ref } from 'vue';"));
+    assert!(report.code.contains("const total = ref(0);"));
+    assert!(report
+      .code
+      .contains("const label = ref(/* FIXME: label */ undefined);"));
+    assert!(report
+      .code
+      .contains("const formatPrice = ref(/* FIXME: formatPrice */ undefined);"));
+
+    let messages: Vec<String> = report
+      .diagnostics
+      .into_iter()
+      .filter(|d| d.code == "missing-declaration")
+      .map(|d| d.message)
+      .collect();
+    assert_eq!(
+      messages,
+      vec!["No declaration was generated for bindings the template uses (`label`, `formatPrice`), refs marked with a FIXME were added in their place".to_string()]
+    );
+  }
+
+  #[test]
+  fn test_should_warn_about_conflicting_template_replacements() {
+    let sfc = r#"<template>
+  <b-sidebar :visible="open"></b-sidebar>
+</template>
+<script>
+import { BSidebar } from 'bootstrap-vue';
+
+export default {
+  components: { BSidebar },
+  data() {
+    return { open: false };
+  },
+};
+</script>"#;
+
+    let mut component_rewrite = HashMap::new();
+    component_rewrite.insert("BSidebar".to_string(), "BOffcanvas".to_string());
+    let mut imports_rewrite = HashMap::new();
+    imports_rewrite.insert(
+      "bootstrap-vue".to_string(),
+      ImportRewrite {
+        name: "bootstrap-vue-next".to_string(),
+        component_rewrite: Some(component_rewrite),
+        directives: None,
+      },
+    );
+    let mut additional_imports = HashMap::new();
+    additional_imports.insert(
+      "BSidebar".to_string(),
+      AdditionalImport {
+        import_path: None,
+        rewrite_to: Some("app-sidebar".to_string()),
+      },
+    );
+    let options = RewriteOptions {
+      imports_rewrite: Some(imports_rewrite),
+      additional_imports: Some(additional_imports),
+      ..Default::default()
+    };
+
+    let report = rewrite_sfc_with_report(sfc, Some(options)).unwrap();
+
+    assert!(report
+      .code
+      .contains(r#"<b-offcanvas :visible="open"></b-offcanvas>"#));
+    let conflicts: Vec<_> = report
+      .diagnostics
+      .iter()
+      .filter(|d| d.code == "template-replacement-conflict")
+      .collect();
+    assert_eq!(conflicts.len(), 2);
+    assert_eq!(conflicts[0].severity, DiagnosticSeverity::Warning);
+    assert_eq!(
+      conflicts[0].message,
+      "`<b-sidebar` is replaced with both `<b-offcanvas` and `<app-sidebar` in the template, only the first was applied"
+    );
+  }
+}
@@ -0,0 +1,50 @@
+#![cfg(feature = "ffi")]
+
+use std::ffi::{CStr, CString};
+use std::ptr;
+use vue_options_to_composition::ffi::{
+  votc_free_string, votc_rewrite_sfc, VOTC_ERROR, VOTC_INVALID_ARGUMENT, VOTC_OK,
+};
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use pretty_assertions::assert_eq;
+
+  #[test]
+  fn test_should_rewrite_sfc_through_the_c_abi() {
+    let sfc = CString::new(
+      "<template><p>{{ count }}</p></template>\n<script>\nexport default { data() { return { count: 0 } } }\n</script>",
+    )
+    .unwrap();
+    let mut out = ptr::null_mut();
+    let mut err = ptr::null_mut();
+
+    let status = unsafe { votc_rewrite_sfc(sfc.as_ptr(), ptr::null(), &mut out, &mut err) };
+
+    assert_eq!(status, VOTC_OK);
+    assert!(err.is_null());
+    let code = unsafe { CStr::from_ptr(out) }.to_str().unwrap().to_string();
+    assert!(code.contains("const count = ref(0);"));
+    unsafe { votc_free_string(out) };
+  }
+
+  #[test]
+  fn test_should_report_invalid_options_and_arguments() {
+    let sfc = CString::new("<script>export default {}</script>").unwrap();
+    let options = CString::new(r#"target = "vue4""#).unwrap();
+    let mut out = ptr::null_mut();
+    let mut err = ptr::null_mut();
+
+    let status = unsafe { votc_rewrite_sfc(sfc.as_ptr(), options.as_ptr(), &mut out, &mut err) };
+
+    assert_eq!(status, VOTC_ERROR);
+    assert!(out.is_null());
+    let message = unsafe { CStr::from_ptr(err) }.to_str().unwrap().to_string();
+    assert!(message.starts_with("Invalid options: "));
+    unsafe { votc_free_string(err) };
+
+    let status = unsafe { votc_rewrite_sfc(ptr::null(), ptr::null(), &mut out, &mut err) };
+    assert_eq!(status, VOTC_INVALID_ARGUMENT);
+  }
+}
@@ -1,6 +1,9 @@
 use std::collections::HashMap;
 use vue_options_to_composition::{
-  rewrite_sfc, AdditionalImport, ImportRewrite, MixinConfig, RewriteOptions,
+  convert_mixin, rewrite_sfc, rewrite_sfc_with_report, scaffold_mixin_config, AdditionalImport,
+  EmitMappingConfig, ExposeConfig, HttpConfig, ImportOrderConfig, ImportRewrite,
+  InstancePropertyStyle, MixinConfig, RequireComponentStyle, RewriteOptions, ScriptStyle,
+  SelfReferenceStyle, SetLocaleStyle, TemplateRefStyle, VTDirectiveStyle,
 };
 
 fn trim_whitespace(s: &str) -> String {
@@ -57,6 +60,490 @@ const increment = () => {
     assert_eq!(trim_whitespace(&result), trim_whitespace(expected));
   }
 
+  #[test]
+  fn test_should_convert_object_data_to_refs() {
+    let sfc = r#"<template><h1>{{ count }}</h1></template>
+    <script>
+    export default {
+      data: {
+        count: 0,
+        'label': 'Clicks',
+      },
+      methods: {
+        increment() {
+          this.count++;
+          console.log(this.label);
+        }
+      }
+    }
+    </script>"#;
+
+    let report = rewrite_sfc_with_report(sfc, None).unwrap();
+
+    let expected = r#"
+<template>
+  <h1>{{ count }}</h1>
+</template>
+<script setup>
+import { ref } from 'vue';
+
+const count = ref(0);
+const label = ref('Clicks');
+
+const increment = () => {
+  count.value++;
+  console.log(label.value);
+};
+</script>"#;
+
+    assert_eq!(trim_whitespace(&report.code), trim_whitespace(expected));
+    assert_eq!(
+      report
+        .diagnostics
+        .iter()
+        .map(|d| d.code.as_str())
+        .collect::<Vec<_>>(),
+      vec!["data-object"]
+    );
+  }
+
+  #[test]
+  fn test_should_expand_spreads_of_module_objects_in_data() {
+    let sfc = r#"<template><p>{{ page }} / {{ size }} {{ query }}</p></template>
+    <script>
+    import { createFilters } from '@/utils/filters';
+
+    const defaults = {
+      page: 1,
+      size: 20
+    };
+
+    export default {
+      data() {
+        return {
+          ...defaults,
+          ...createFilters(),
+          size: 50,
+          query: ''
+        };
+      }
+    }
+    </script>"#;
+
+    let report = rewrite_sfc_with_report(sfc, None).unwrap();
+
+    let expected = r#"
+<template>
+  <p>{{ page }} / {{ size }} {{ query }}</p>
+</template>
+<script setup>
+import { ref } from 'vue';
+
+const defaults = {
+  page: 1,
+  size: 20
+};
+
+const page = ref(1);
+const query = ref('');
+const size = ref(50);
+</script>"#;
+
+    assert_eq!(trim_whitespace(&report.code), trim_whitespace(expected));
+    assert_eq!(
+      report
+        .diagnostics
+        .iter()
+        .map(|d| d.code.as_str())
+        .collect::<Vec<_>>(),
+      vec!["data-spread"]
+    );
+  }
+
+  #[test]
+  fn test_should_alias_imports_redeclared_by_component_members() {
+    let sfc = r#"<template><p>{{ limit }} {{ summary }}</p></template>
+    <script>
+    import { limit, format } from '@/utils/limits';
+
+    const max = limit * 2;
+
+    export default {
+      data() {
+        return {
+          limit: limit,
+          format: 'short'
+        };
+      },
+      computed: {
+        summary() {
+          const options = { limit, max };
+          return format(this.limit, options, this.format);
+        }
+      },
+      methods: {
+        clamp(limit) {
+          return Math.min(limit, max);
+        },
+        reset() {
+          this.limit = limit;
+        }
+      }
+    }
+    </script>"#;
+
+    let result = rewrite_sfc(sfc, None).unwrap();
+
+    let expected = r#"
+<template>
+  <p>{{ limit }} {{ summary }}</p>
+</template>
+<script setup>
+import { computed, ref } from 'vue';
+import { limit as importedLimit, format as importedFormat } from '@/utils/limits';
+
+const max = importedLimit * 2;
+
+const format = ref('short');
+const limit = ref(importedLimit);
+
+const summary = computed(() => {
+  const options = { limit: importedLimit, max };
+  return importedFormat(limit.value, options, format.value);
+});
+
+const clamp = (limit) => {
+  return Math.min(limit, max);
+};
+const reset = () => {
+  limit.value = importedLimit;
+};
+</script>"#;
+
+    assert_eq!(trim_whitespace(&result), trim_whitespace(expected));
+  }
+
+  #[test]
+  fn test_should_keep_method_parameter_patterns() {
+    let sfc = r#"<template><p>{{ page }}</p></template>
+    <script>
+    export default {
+      data() {
+        return { page: 1 };
+      },
+      methods: {
+        submit({ id, qty } = {}, ...rest) {
+          console.log(id, qty, rest);
+        },
+        load(size = this.page, [first, second] = []) {
+          return size + first + second;
+        },
+        double: value => {
+          return value * 2;
+        },
+      },
+    };
+    </script>"#;
+
+    let result = rewrite_sfc(sfc, None).unwrap();
+
+    let expected = r#"
+<template>
+  <p>{{ page }}</p>
+</template>
+<script setup>
+import { ref } from 'vue';
+
+const page = ref(1);
+
+const submit = ({ id, qty } = {}, ...rest) => {
+  console.log(id, qty, rest);
+};
+const load = (size = page.value, [first, second] = []) => {
+  return size + first + second;
+};
+const double = (value) => {
+  return value * 2;
+};
+</script>"#;
+
+    assert_eq!(trim_whitespace(&result), trim_whitespace(expected));
+  }
+
+  #[test]
+  fn test_should_rename_string_keyed_members_and_report_computed_keys() {
+    let sfc = r#"<template><button @click="this['on-save']()">{{ this["full-label"] }}</button></template>
+    <script>
+    const SAVE = 'save';
+
+    export default {
+      data() {
+        return { label: 'x' };
+      },
+      computed: {
+        'full-label'() {
+          return this.label + '!';
+        },
+      },
+      methods: {
+        'on-save'() {
+          this['update-value'](this['label']);
+          this.delete();
+        },
+        "update-value": function (value) {
+          console.log(value);
+        },
+        delete() {
+          this.label = '';
+        },
+        [SAVE]() {
+          console.log('saved');
+        },
+      },
+    };
+    </script>"#;
+
+    let report = rewrite_sfc_with_report(sfc, None).unwrap();
+
+    let expected = r#"
+<template>
+  <button @click="onSave()">{{ fullLabel }}</button>
+</template>
+<script setup>
+import { computed, ref } from 'vue';
+
+const SAVE = 'save';
+
+const label = ref('x');
+
+const fullLabel = computed(() => {
+  return label.value + '!';
+});
+
+const onSave = () => {
+  updateValue(label.value);
+  _delete();
+};
+const updateValue = (value) => {
+  console.log(value);
+};
+const _delete = () => {
+  label.value = '';
+};
+</script>"#;
+
+    assert_eq!(trim_whitespace(&report.code), trim_whitespace(expected));
+    assert_eq!(
+      report
+        .diagnostics
+        .iter()
+        .map(|d| d.code.as_str())
+        .collect::<Vec<_>>(),
+      vec![
+        "member-renamed",
+        "member-renamed",
+        "member-renamed",
+        "member-renamed",
+        "member-computed-key"
+      ]
+    );
+  }
+
+  #[test]
+  fn test_should_keep_member_comments() {
+    let sfc = r#"<template><p>{{ total }}</p></template>
+<script>
+export default {
+  props: {
+    /** Maximum number of items */
+    limit: { type: Number, default: 10 },
+    // Currency code
+    // (ISO 4217)
+    currency: String,
+  },
+  data() { return { items: [] }; },
+  computed: {
+    /**
+     * Sum of all item prices.
+     * @returns {number}
+     */
+    total() {
+      return this.items.reduce((sum, item) => sum + item.price, 0);
+    },
+  },
+  methods: {
+    /**
+     * Add an item.
+     * @param {object} item
+     */
+    add(item) {
+      this.items.push(item);
+    }, // trailing note
+    clear: function () {
+      this.items = [];
+    },
+  },
+};
+</script>"#;
+
+    let result = rewrite_sfc(sfc, None).unwrap();
+
+    let expected = r#"
+<template>
+<p>{{ total }}</p>
+</template>
+<script setup>
+import { computed, ref } from 'vue';
+
+const props = defineProps({
+  /** Maximum number of items */
+  limit: {
+    type: Number,
+    default: 10,
+  },
+  // Currency code
+  // (ISO 4217)
+  currency: {
+    type: String,
+  },
+});
+
+const items = ref([]);
+
+/**
+ * Sum of all item prices.
+ * @returns {number}
+ */
+const total = computed(() => {
+  return items.value.reduce((sum, item) => sum + item.price, 0);
+});
+
+/**
+ * Add an item.
+ * @param {object} item
+ */
+const add = (item) => {
+  items.value.push(item);
+};
+const clear = () => {
+  items.value = [];
+};
+</script>"#;
+
+    assert_eq!(trim_whitespace(&result), trim_whitespace(expected));
+  }
+
+  #[test]
+  fn test_should_resolve_each_member_access_once() {
+    let sfc = r#"<template><p>{{ total }}</p></template>
+    <script>
+    export default {
+      props: { totalLimit: Number },
+      data() {
+        return { total: 0, items: [] };
+      },
+      computed: {
+        totalPrice() {
+          return this.total * 2 + this.totalLimit;
+        },
+      },
+      watch: {
+        totalPrice(value) {
+          this.calcTotal(value);
+        },
+        totalLimit() {
+          this.reset();
+        },
+        'items.length'(length) {
+          console.log(length);
+        },
+      },
+      methods: {
+        calcTotal(value) {
+          this.total = value + this.totalPrice;
+          return this.calcTotalLater(this.calcTotal);
+        },
+        calcTotalLater(fn) {
+          return fn;
+        },
+        reset() {
+          this.items = [];
+        },
+      },
+    };
+    </script>"#;
+
+    let result = rewrite_sfc(sfc, None).unwrap();
+
+    let expected = r#"
+<template>
+  <p>{{ total }}</p>
+</template>
+<script setup>
+import { computed, ref, watch } from 'vue';
+
+const props = defineProps({
+  totalLimit: {
+    type: Number,
+  },
+});
+
+const items = ref([]);
+const total = ref(0);
+
+const totalPrice = computed(() => {
+  return total.value * 2 + props.totalLimit;
+});
+
+watch(totalPrice, (value, oldVal) => {
+  calcTotal(value);
+});
+watch(() => props.totalLimit, (newVal, oldVal) => {
+  reset();
+});
+watch(() => items.value.length, (length, oldVal) => {
+  console.log(length);
+});
+
+const calcTotal = (value) => {
+  total.value = value + totalPrice.value;
+  return calcTotalLater(calcTotal);
+};
+const calcTotalLater = (fn) => {
+  return fn;
+};
+const reset = () => {
+  items.value = [];
+};
+</script>"#;
+
+    assert_eq!(trim_whitespace(&result), trim_whitespace(expected));
+  }
+
+  #[test]
+  fn test_should_report_members_declared_in_several_options() {
+    let sfc = r#"<template><p>{{ label }}</p></template>
+    <script>
+    export default {
+      props: ['label'],
+      data() {
+        return { label: '' };
+      },
+    };
+    </script>"#;
+
+    let report = rewrite_sfc_with_report(sfc, None).unwrap();
+
+    assert_eq!(
+      report
+        .diagnostics
+        .iter()
+        .map(|d| d.message.as_str())
+        .collect::<Vec<_>>(),
+      vec!["`label` is declared in props and data, `this.label` was converted as the props member. Rename the others, they would redeclare the same constant in <script setup>"]
+    );
+  }
+
   #[test]
   fn test_should_handle_i18n_methods() {
     let sfc = r#"<template>
@@ -163,6 +650,197 @@ const { t, d } = useI18n();
     assert_eq!(trim_whitespace(&result), trim_whitespace(expected));
   }
 
+  #[test]
+  fn test_should_handle_i18n_plural_and_message_functions() {
+    let sfc = r#"<template>
+    <p>{{ $tc('apple', count) }} {{ $te('car') ? $tc('car', count, { n: count }) : '' }}</p>
+    </template>
+    <script>
+    export default {
+      props: ['count'],
+      methods: {
+        items() {
+          return this.$tm('list').concat(this.$tc('item', 2, { n: 2 }))
+        }
+      }
+    }
+    </script>"#;
+
+    let result = rewrite_sfc(sfc, None).unwrap();
+
+    let expected = r#"
+<template>
+  <p>{{ t('apple', count) }} {{ te('car') ? t('car', { n: count }, count) : '' }}</p>
+</template>
+<script setup>
+import { useI18n } from 'vue-i18n';
+
+const props = defineProps(['count']);
+
+const { t, te, tm } = useI18n();
+
+const items = () => {
+  return tm('list').concat(t('item', { n: 2 }, 2))
+};
+</script>"#;
+
+    assert_eq!(trim_whitespace(&result), trim_whitespace(expected));
+  }
+
+  #[test]
+  fn test_should_handle_i18n_locale_switching() {
+    let sfc = r#"<template>
+    <div>
+      <button v-for="l in $i18n.locales" :key="l.code" @click="$i18n.setLocale(l.code)">{{ l.name }}</button>
+    </div>
+    </template>
+    <script>
+    export default {
+      methods: {
+        async switchTo(code) {
+          await this.$i18n.setLocale(code)
+        },
+        otherLocales() {
+          return this.$i18n.locales.filter((l) => l.code !== this.$i18n.locale)
+        }
+      }
+    }
+    </script>"#;
+
+    let result = rewrite_sfc(sfc, None).unwrap();
+
+    let expected = r#"
+<template>
+  <div>
+    <button v-for="l in locales" :key="l.code" @click="setLocale(l.code)">{{ l.name }}</button>
+  </div>
+</template>
+<script setup>
+import { useI18n } from 'vue-i18n';
+import { useI18nUtils } from '@/composables/useI18nUtils';
+
+const { locale, locales } = useI18n();
+
+const { setLocale } = useI18nUtils();
+
+const switchTo = async (code) => {
+  await setLocale(code)
+};
+const otherLocales = () => {
+  return locales.value.filter((l) => l.code !== locale.value)
+};
+</script>"#;
+
+    assert_eq!(trim_whitespace(&result), trim_whitespace(expected));
+  }
+
+  #[test]
+  fn test_should_assign_locale_when_configured() {
+    let sfc = r#"<template>
+    <div>
+      <button v-for="l in $i18n.locales" :key="l.code" @click="$i18n.setLocale(l.code)">{{ l.name }}</button>
+    </div>
+    </template>
+    <script>
+    export default {
+      methods: {
+        async switchTo(code) {
+          await this.$i18n.setLocale(code)
+        },
+        otherLocales() {
+          return this.$i18n.locales.filter((l) => l.code !== this.$i18n.locale)
+        }
+      }
+    }
+    </script>"#;
+
+    let options = RewriteOptions {
+      set_locale: Some(SetLocaleStyle::Assign),
+      ..Default::default()
+    };
+    let result = rewrite_sfc(sfc, Some(options)).unwrap();
+
+    assert!(result.contains(r#"@click="locale = l.code""#));
+    assert!(result.contains("const switchTo = async (code) => {\n  locale.value = code\n};"));
+    assert!(result.contains("const { locale, locales } = useI18n();"));
+    assert!(!result.contains("useI18nUtils"));
+  }
+
+  #[test]
+  fn test_should_convert_v_t_directives() {
+    let sfc = r#"<template>
+    <div>
+      <h1 v-t="'title'"></h1>
+      <p class="lead" v-t="{ path: 'greeting', args: { name } }">placeholder</p>
+      <span v-t="'short'" />
+    </div>
+    </template>
+    <script>
+    export default {
+      props: ['name']
+    }
+    </script>
+
+<i18n lang="json">
+{
+  "en": { "title": "Hello" }
+}
+</i18n>"#;
+
+    let result = rewrite_sfc(sfc, None).unwrap();
+
+    let expected = r#"
+<template>
+  <div>
+    <h1>{{ t('title') }}</h1>
+    <p class="lead">{{ t('greeting', { name }) }}</p>
+    <span>{{ t('short') }}</span>
+  </div>
+</template>
+<script setup>
+import { useI18n } from 'vue-i18n';
+
+const props = defineProps(['name']);
+
+const { t } = useI18n({ useScope: 'local' });
+</script>
+<i18n lang="json">
+{
+  "en": { "title": "Hello" }
+}
+</i18n>"#;
+
+    assert_eq!(trim_whitespace(&result), trim_whitespace(expected));
+  }
+
+  #[test]
+  fn test_should_keep_v_t_directives_and_custom_blocks() {
+    let sfc = r#"<template>
+  <h1 v-t="'title'"></h1>
+</template>
+<script>
+export default {
+  data() {
+    return { count: 0 }
+  }
+}
+</script>
+
+<docs>
+Shows the page title.
+</docs>"#;
+
+    let options = RewriteOptions {
+      v_t_directive: Some(VTDirectiveStyle::Keep),
+      ..Default::default()
+    };
+    let result = rewrite_sfc(sfc, Some(options)).unwrap();
+
+    assert!(result.contains(r#"<h1 v-t="'title'"></h1>"#));
+    assert!(!result.contains("useI18n"));
+    assert!(result.ends_with("<docs>\nShows the page title.\n</docs>"));
+  }
+
   #[test]
   fn test_should_handle_nuxt_fetch() {
     let sfc = r#"<template><h1 @click="clickHandler">{{ data }}</h1></template>
@@ -195,7 +873,7 @@ const { t, d } = useI18n();
   <h1 @click="clickHandler">{{ data }}</h1>
 </template>
 <script setup>
-import { onMounted, ref } from 'vue';
+import { ref } from 'vue';
 import { useHttp } from '@/composables/useHttp';
 
 const http = useHttp();
@@ -214,252 +892,249 @@ const clickHandler = () => {
   fetch();
 };
 
-onMounted(async () => {
-  fetch();
-});
+fetch();
 </script>"#;
 
     assert_eq!(trim_whitespace(&result), trim_whitespace(expected));
   }
 
   #[test]
-  fn test_should_handle_custom_mixins() {
-    let sfc = r#"<template><h1>{{ title }}{{ priceRaw(100) }}</h1></template>
-    <script>
-    import pp from 'ninth-dimension';
-    import priceMixin from '@/mixins/price';
-
-    export default {
-      mixins: [priceMixin],
-      data() {
-        return {
-          title: 'Hello World',
-          yolo: []
-        };
-      },
-      computed: {
-        cartSum() {
-          return this.yolo.reduce(
-            (p, v) =>
-              p +
-              this.priceRaw(
-                v.price,
-              ) *
-                v.quantity,
-            0
-          );
-        }
-      },
-      methods: {
-        featureDetection() {
-          const paymentRequest = pp.paymentRequest({
-            currency: this.currency.toLowerCase(),
-            total: {
-              label: 'Total',
-              amount: price(100, 2),
-            },
-          });
-        },
-        claw() {
-          this.price(
-            100,
-            10
-          );
-        }
-      }
-    }
-    </script>"#;
-
-    let mut mixins = HashMap::new();
-    mixins.insert(
-      "price".to_string(),
-      MixinConfig {
-        name: "usePrice".to_string(),
-        imports: vec![
-          "currency".to_string(),
-          "maximumFractionDigits".to_string(),
-          "priceRaw".to_string(),
-          "priceDiscountRaw".to_string(),
-          "filterPrices".to_string(),
-          "lowestPrice".to_string(),
-          "fromPrice".to_string(),
-          "price".to_string(),
-          "discountPrice".to_string(),
-          "priceRound".to_string(),
-        ],
-      },
-    );
+  fn test_should_group_imports_by_configured_order() {
+    let sfc = r#"<template><p>{{ format(item.date) }}</p></template>
+<script lang="ts">
+import './styles.css';
+import type { Item } from '@/types';
+import { format } from 'date-fns';
+export default {
+  props: { item: Object as PropType<Item> },
+  methods: { format },
+  mounted() {
+    this.$router.push('/');
+  },
+};
+</script>"#;
 
     let options = RewriteOptions {
-      mixins: Some(mixins),
+      import_order: Some(ImportOrderConfig {
+        groups: vec![
+          "type".to_string(),
+          "^vue".to_string(),
+          "^@/".to_string(),
+          "side-effect".to_string(),
+        ],
+        separate_groups: true,
+      }),
       ..Default::default()
     };
 
     let result = rewrite_sfc(sfc, Some(options)).unwrap();
 
-    let expected = r#"
-<template>
-  <h1>{{ title }}{{ priceRaw(100) }}</h1>
-</template>
-<script setup>
-import { computed, ref } from 'vue';
-import pp from 'ninth-dimension';
-import { usePrice } from '@/composables/usePrice';
-
-const { priceRaw, currency, price } = usePrice();
-
-const title = ref('Hello World');
-const yolo = ref([]);
+    assert!(result.contains(
+      r#"<script setup lang="ts">
+import type { Item } from '@/types';
 
-const cartSum = computed(() => {
-  return yolo.value.reduce(
-    (p, v) =>
-      p +
-      priceRaw(
-        v.price,
-      ) *
-      v.quantity,
-      0
-    );
-});
+import { PropType, onMounted } from 'vue';
+import { useRouter } from 'vue-router';
 
-const featureDetection = () => {
-  const paymentRequest = pp.paymentRequest({
-    currency: currency.toLowerCase(),
-    total: {
-      label: 'Total',
-      amount: price(100, 2),
-    },
-  });
-};
+import './styles.css';
 
-const claw = () => {
-  price(
-    100,
-    10
-  );
-};
-</script>"#;
+import { format } from 'date-fns';
 
-    assert_eq!(trim_whitespace(&result), trim_whitespace(expected));
+const router = useRouter();"#
+    ));
   }
 
   #[test]
-  fn test_should_handle_import_rewrites() {
-    let sfc = r#"
-<template>
-  <ClientOnly>
-    <h1 v-b-toggle>{{ title }}</h1>
-  </ClientOnly>
-  <nuxt-link :to="{ name: 'index' }">link</nuxt-link>
-  <b-button></b-button>
-  <BButton></BButton>
-  <BSidebar></BSidebar>
-  <b-sidebar></b-sidebar>
-</template>
+  fn test_should_separate_script_sections_with_single_blank_lines() {
+    let sfc = r#"<template><button @click="save">{{ label }}</button></template>
 <script>
-import { BSidebar, BButton } from 'bootstrap-vue';
-
 export default {
-  components: {
-    BSidebar,
-    BButton
+  props: {
+    label: String,
   },
   data() {
-    return {
-      title: 'Hello Bootstrap Vue'
-    };
-  }
-}
+    return { saving: false };
+  },
+  methods: {
+    save() {
+      this.saving = true;
+    },
+  },
+  mounted() {
+    this.$router.push('/');
+  },
+};
 </script>"#;
 
-    let mut component_rewrite = HashMap::new();
-    component_rewrite.insert("BSidebar".to_string(), "BOffcanvas".to_string());
+    let expected = r#"<template>
+<button @click="save">{{ label }}</button>
+</template>
+<script setup>
+import { onMounted, ref } from 'vue';
+import { useRouter } from 'vue-router';
 
-    let mut directives = HashMap::new();
-    directives.insert("v-b-toggle".to_string(), "vBToggle".to_string());
+const router = useRouter();
 
-    let mut imports_rewrite = HashMap::new();
-    imports_rewrite.insert(
-      "bootstrap-vue".to_string(),
-      ImportRewrite {
-        name: "bootstrap-vue-next".to_string(),
-        component_rewrite: Some(component_rewrite),
-        directives: Some(directives),
-      },
+const props = defineProps({
+  label: {
+    type: String,
+  },
+});
+
+const saving = ref(false);
+
+const save = () => {
+  saving.value = true;
+};
+
+onMounted(() => {
+  router.push('/');
+});
+</script>"#;
+
+    let result = rewrite_sfc(sfc, None).unwrap();
+
+    assert_eq!(result, expected);
+  }
+
+  #[test]
+  fn test_should_scaffold_mixin_config() {
+    let source = r#"import { format } from '@/utils/format';
+
+export default {
+  props: ['currency'],
+  data() {
+    return {
+      amount: 0,
+      discount: null
+    };
+  },
+  computed: {
+    total() {
+      return this.amount - this.discount;
+    }
+  },
+  methods: {
+    formatPrice(value) {
+      return format(value, this.currency);
+    },
+    async reload() {}
+  }
+};"#;
+
+    let config = scaffold_mixin_config("price-mixin", source).unwrap();
+
+    assert_eq!(config.name, "usePrice");
+    assert_eq!(
+      config.imports,
+      vec!["amount", "discount", "total", "formatPrice", "reload"]
     );
+  }
 
-    let mut additional_imports = HashMap::new();
-    additional_imports.insert(
-      "ClientOnly".to_string(),
-      AdditionalImport {
-        import_path: Some("import ClientOnly from '@/components/ClientOnly.vue';".to_string()),
-        rewrite_to: None,
+  #[test]
+  fn test_should_convert_mixin_to_composable() {
+    let source = r#"import { format } from '@/utils/format';
+
+export default {
+  props: ['currency'],
+  data() {
+    return {
+      amount: 0
+    };
+  },
+  computed: {
+    total() {
+      return this.amount * 2;
+    }
+  },
+  mounted() {
+    this.$emit('ready');
+  },
+  methods: {
+    formatPrice(value) {
+      return format(value, this.currency);
+    }
+  }
+};"#;
+
+    let expected = r#"import { computed, onMounted, ref } from 'vue';
+import { format } from '@/utils/format';
+
+export function usePrice(props, emit) {
+  const amount = ref(0);
+
+  const total = computed(() => {
+    return amount.value * 2;
+  });
+
+  const formatPrice = (value) => {
+    return format(value, props.currency);
+  };
+
+  onMounted(() => {
+    emit('ready');
+  });
+
+  return { amount, total, formatPrice };
+}
+"#;
+
+    let composable = convert_mixin("price", source, None).unwrap();
+
+    assert_eq!(composable.name, "usePrice");
+    assert_eq!(composable.parameters, vec!["props", "emit"]);
+    assert_eq!(composable.code, expected);
+  }
+
+  #[test]
+  fn test_should_merge_setup_of_mixins_sharing_a_composable() {
+    let sfc = r#"<template><p>{{ priceRaw(total) }} {{ currency }}</p></template>
+<script>
+import priceMixin from '@/mixins/price';
+import currencyMixin from '@/mixins/currency';
+
+export default {
+  mixins: [priceMixin, currencyMixin],
+  props: {
+    total: Number,
+  },
+};
+</script>"#;
+
+    let mut mixins = HashMap::new();
+    mixins.insert(
+      "price".to_string(),
+      MixinConfig {
+        name: "usePrice".to_string(),
+        imports: vec!["priceRaw".to_string()],
       },
     );
-    additional_imports.insert(
-      "NuxtLink".to_string(),
-      AdditionalImport {
-        import_path: None,
-        rewrite_to: Some("router-link".to_string()),
+    mixins.insert(
+      "currency".to_string(),
+      MixinConfig {
+        name: "usePrice".to_string(),
+        imports: vec!["currency".to_string()],
       },
     );
 
     let options = RewriteOptions {
-      imports_rewrite: Some(imports_rewrite),
-      additional_imports: Some(additional_imports),
+      mixins: Some(mixins),
       ..Default::default()
     };
 
     let result = rewrite_sfc(sfc, Some(options)).unwrap();
 
-    let expected = r#"
-<template>
-  <ClientOnly>
-    <h1 v-b-toggle>{{ title }}</h1>
-  </ClientOnly>
-  <router-link :to="{ name: 'index' }">link</router-link>
-  <b-button></b-button>
-  <BButton></BButton>
-  <BOffcanvas></BOffcanvas>
-  <b-offcanvas></b-offcanvas>
+    let expected = r#"<template>
+<p>{{ priceRaw(total) }} {{ currency }}</p>
 </template>
 <script setup>
-import { ref } from 'vue';
-import { BButton, BOffcanvas, vBToggle } from 'bootstrap-vue-next';
-import ClientOnly from '@/components/ClientOnly.vue';
-
-const title = ref('Hello Bootstrap Vue');
-</script>"#;
-
-    assert_eq!(trim_whitespace(&result), trim_whitespace(expected));
-  }
-
-  #[test]
-  fn test_should_handle_props() {
-    let sfc = r#"<template><h1>{{ title }}</h1></template>
-    <script>
-    export default {
-      props: {
-        title: {
-          type: String,
-          required: true
-        }
-      }
-    }
-    </script>"#;
+import { usePrice } from '@/composables/usePrice';
 
-    let result = rewrite_sfc(sfc, None).unwrap();
+const { priceRaw, currency } = usePrice();
 
-    let expected = r#"
-<template>
-  <h1>{{ title }}</h1>
-</template>
-<script setup>
 const props = defineProps({
-  title: {
-    type: String,
-    required: true,
+  total: {
+    type: Number,
   },
 });
 </script>"#;
@@ -468,62 +1143,363 @@ const props = defineProps({
   }
 
   #[test]
-  fn test_should_handle_computed_properties() {
-    let sfc = r#"<template><h1>{{ fullName }}</h1></template>
+  fn test_should_handle_custom_mixins() {
+    let sfc = r#"<template><h1>{{ title }}{{ priceRaw(100) }}</h1></template>
     <script>
+    import pp from 'ninth-dimension';
+    import priceMixin from '@/mixins/price';
+
     export default {
+      mixins: [priceMixin],
       data() {
         return {
-          firstName: 'John',
-          lastName: 'Doe'
+          title: 'Hello World',
+          yolo: []
         };
       },
       computed: {
-        fullName: {
-          get() {
-            return `${this.firstName} ${this.lastName}`;
-          },
-          set(v) {
-            const names = v.split(' ');
-            this.firstName = names[0];
-            this.lastName = names[1];
-          }
+        cartSum() {
+          return this.yolo.reduce(
+            (p, v) =>
+              p +
+              this.priceRaw(
+                v.price,
+              ) *
+                v.quantity,
+            0
+          );
+        }
+      },
+      methods: {
+        featureDetection() {
+          const paymentRequest = pp.paymentRequest({
+            currency: this.currency.toLowerCase(),
+            total: {
+              label: 'Total',
+              amount: price(100, 2),
+            },
+          });
+        },
+        claw() {
+          this.price(
+            100,
+            10
+          );
         }
       }
     }
     </script>"#;
 
-    let result = rewrite_sfc(sfc, None).unwrap();
+    let mut mixins = HashMap::new();
+    mixins.insert(
+      "price".to_string(),
+      MixinConfig {
+        name: "usePrice".to_string(),
+        imports: vec![
+          "currency".to_string(),
+          "maximumFractionDigits".to_string(),
+          "priceRaw".to_string(),
+          "priceDiscountRaw".to_string(),
+          "filterPrices".to_string(),
+          "lowestPrice".to_string(),
+          "fromPrice".to_string(),
+          "price".to_string(),
+          "discountPrice".to_string(),
+          "priceRound".to_string(),
+        ],
+      },
+    );
+
+    let options = RewriteOptions {
+      mixins: Some(mixins),
+      ..Default::default()
+    };
+
+    let result = rewrite_sfc(sfc, Some(options)).unwrap();
 
     let expected = r#"
 <template>
-  <h1>{{ fullName }}</h1>
+  <h1>{{ title }}{{ priceRaw(100) }}</h1>
 </template>
 <script setup>
 import { computed, ref } from 'vue';
+import pp from 'ninth-dimension';
+import { usePrice } from '@/composables/usePrice';
 
-const firstName = ref('John');
-const lastName = ref('Doe');
-
-const fullName = computed({
-  get() {
-    return `${firstName.value} ${lastName.value}`;
-  },
-  set(v) {
-    const names = v.split(' ');
-    firstName.value = names[0];
-    lastName.value = names[1];
-  },
-});
-</script>"#;
+const { priceRaw, currency, price } = usePrice();
 
-    assert_eq!(trim_whitespace(&result), trim_whitespace(expected));
-  }
+const title = ref('Hello World');
+const yolo = ref([]);
 
-  #[test]
-  fn test_should_keep_computed_setter_name() {
-    let sfc = r#"<template><h1>{{ fullName }}</h1></template>
-    <script>
+const cartSum = computed(() => {
+  return yolo.value.reduce(
+    (p, v) =>
+      p +
+      priceRaw(
+        v.price,
+      ) *
+      v.quantity,
+      0
+    );
+});
+
+const featureDetection = () => {
+  const paymentRequest = pp.paymentRequest({
+    currency: currency.toLowerCase(),
+    total: {
+      label: 'Total',
+      amount: price(100, 2),
+    },
+  });
+};
+
+const claw = () => {
+  price(
+    100,
+    10
+  );
+};
+</script>"#;
+
+    assert_eq!(trim_whitespace(&result), trim_whitespace(expected));
+  }
+
+  #[test]
+  fn test_should_handle_import_rewrites() {
+    let sfc = r#"
+<template>
+  <ClientOnly>
+    <h1 v-b-toggle>{{ title }}</h1>
+  </ClientOnly>
+  <nuxt-link :to="{ name: 'index' }">link</nuxt-link>
+  <b-button></b-button>
+  <BButton></BButton>
+  <BSidebar></BSidebar>
+  <b-sidebar></b-sidebar>
+</template>
+<script>
+import { BSidebar, BButton } from 'bootstrap-vue';
+
+export default {
+  components: {
+    BSidebar,
+    BButton
+  },
+  data() {
+    return {
+      title: 'Hello Bootstrap Vue'
+    };
+  }
+}
+</script>"#;
+
+    let mut component_rewrite = HashMap::new();
+    component_rewrite.insert("BSidebar".to_string(), "BOffcanvas".to_string());
+
+    let mut directives = HashMap::new();
+    directives.insert("v-b-toggle".to_string(), "vBToggle".to_string());
+
+    let mut imports_rewrite = HashMap::new();
+    imports_rewrite.insert(
+      "bootstrap-vue".to_string(),
+      ImportRewrite {
+        name: "bootstrap-vue-next".to_string(),
+        component_rewrite: Some(component_rewrite),
+        directives: Some(directives),
+      },
+    );
+
+    let mut additional_imports = HashMap::new();
+    additional_imports.insert(
+      "ClientOnly".to_string(),
+      AdditionalImport {
+        import_path: Some("import ClientOnly from '@/components/ClientOnly.vue';".to_string()),
+        rewrite_to: None,
+      },
+    );
+    additional_imports.insert(
+      "NuxtLink".to_string(),
+      AdditionalImport {
+        import_path: None,
+        rewrite_to: Some("router-link".to_string()),
+      },
+    );
+
+    let options = RewriteOptions {
+      imports_rewrite: Some(imports_rewrite),
+      additional_imports: Some(additional_imports),
+      ..Default::default()
+    };
+
+    let result = rewrite_sfc(sfc, Some(options)).unwrap();
+
+    let expected = r#"
+<template>
+  <ClientOnly>
+    <h1 v-b-toggle>{{ title }}</h1>
+  </ClientOnly>
+  <router-link :to="{ name: 'index' }">link</router-link>
+  <b-button></b-button>
+  <BButton></BButton>
+  <BOffcanvas></BOffcanvas>
+  <b-offcanvas></b-offcanvas>
+</template>
+<script setup>
+import { ref } from 'vue';
+import { BButton, BOffcanvas, vBToggle } from 'bootstrap-vue-next';
+import ClientOnly from '@/components/ClientOnly.vue';
+
+const title = ref('Hello Bootstrap Vue');
+</script>"#;
+
+    assert_eq!(trim_whitespace(&result), trim_whitespace(expected));
+  }
+
+  #[test]
+  fn test_should_not_rewrite_replaced_component_tags_again() {
+    let sfc = r#"
+<template>
+  <b-navbar>
+    <b-nav :small="true">{{ $t('home') }}</b-nav>
+  </b-navbar>
+</template>
+<script>
+import { BNav, BNavbar } from 'bootstrap-vue';
+
+export default {
+  components: {
+    BNav,
+    BNavbar
+  }
+}
+</script>"#;
+
+    let mut component_rewrite = HashMap::new();
+    component_rewrite.insert("BNav".to_string(), "BNavigation".to_string());
+    component_rewrite.insert("BNavbar".to_string(), "BNavbarNext".to_string());
+
+    let mut imports_rewrite = HashMap::new();
+    imports_rewrite.insert(
+      "bootstrap-vue".to_string(),
+      ImportRewrite {
+        name: "bootstrap-vue-next".to_string(),
+        component_rewrite: Some(component_rewrite),
+        directives: None,
+      },
+    );
+
+    let options = RewriteOptions {
+      imports_rewrite: Some(imports_rewrite),
+      ..Default::default()
+    };
+
+    let result = rewrite_sfc(sfc, Some(options)).unwrap();
+
+    let expected = r#"
+<template>
+  <b-navbar-next>
+    <b-navigation :small="true">{{ t('home') }}</b-navigation>
+  </b-navbar-next>
+</template>
+<script setup>
+import { BNavbarNext, BNavigation } from 'bootstrap-vue-next';
+import { useI18n } from 'vue-i18n';
+
+const { t } = useI18n();
+</script>"#;
+
+    assert_eq!(trim_whitespace(&result), trim_whitespace(expected));
+  }
+
+  #[test]
+  fn test_should_handle_props() {
+    let sfc = r#"<template><h1>{{ title }}</h1></template>
+    <script>
+    export default {
+      props: {
+        title: {
+          type: String,
+          required: true
+        }
+      }
+    }
+    </script>"#;
+
+    let result = rewrite_sfc(sfc, None).unwrap();
+
+    let expected = r#"
+<template>
+  <h1>{{ title }}</h1>
+</template>
+<script setup>
+const props = defineProps({
+  title: {
+    type: String,
+    required: true,
+  },
+});
+</script>"#;
+
+    assert_eq!(trim_whitespace(&result), trim_whitespace(expected));
+  }
+
+  #[test]
+  fn test_should_handle_computed_properties() {
+    let sfc = r#"<template><h1>{{ fullName }}</h1></template>
+    <script>
+    export default {
+      data() {
+        return {
+          firstName: 'John',
+          lastName: 'Doe'
+        };
+      },
+      computed: {
+        fullName: {
+          get() {
+            return `${this.firstName} ${this.lastName}`;
+          },
+          set(v) {
+            const names = v.split(' ');
+            this.firstName = names[0];
+            this.lastName = names[1];
+          }
+        }
+      }
+    }
+    </script>"#;
+
+    let result = rewrite_sfc(sfc, None).unwrap();
+
+    let expected = r#"
+<template>
+  <h1>{{ fullName }}</h1>
+</template>
+<script setup>
+import { computed, ref } from 'vue';
+
+const firstName = ref('John');
+const lastName = ref('Doe');
+
+const fullName = computed({
+  get() {
+    return `${firstName.value} ${lastName.value}`;
+  },
+  set(v) {
+    const names = v.split(' ');
+    firstName.value = names[0];
+    lastName.value = names[1];
+  },
+});
+</script>"#;
+
+    assert_eq!(trim_whitespace(&result), trim_whitespace(expected));
+  }
+
+  #[test]
+  fn test_should_keep_computed_setter_name() {
+    let sfc = r#"<template><h1>{{ fullName }}</h1></template>
+    <script>
     export default {
       data() {
         return {
@@ -666,8 +1642,75 @@ useHead(() => {
   }
 
   #[test]
-  fn test_should_handle_simple_components() {
-    let sfc = r#"<template><h1>Dummy content</h1></template>
+  fn test_should_remove_head_dedupe_keys() {
+    let sfc = r#"<template><h1>{{ title }}</h1></template>
+    <script>
+    export default {
+      data() {
+        return {
+          title: 'Hello World'
+        };
+      },
+      head() {
+        return {
+          title: this.title,
+          meta: [
+            { hid: 'description', name: 'description', content: this.buildDescription() },
+            { property: 'og:title', content: this.title, 'vmid': 'og:title' },
+            {
+              hid: 'robots',
+              name: 'robots',
+              content: 'noindex'
+            }
+          ]
+        };
+      },
+      methods: {
+        buildDescription() {
+          return `About ${this.title}`;
+        }
+      }
+    }
+    </script>"#;
+
+    let expected = r#"
+<template>
+  <h1>{{ title }}</h1>
+</template>
+<script setup>
+import { ref } from 'vue';
+import { useHead } from '@unhead/vue';
+
+const title = ref('Hello World');
+
+const buildDescription = () => {
+  return `About ${title.value}`;
+};
+useHead(() => {
+  return {
+    title: title.value,
+    meta: [
+      { name: 'description', content: buildDescription() },
+      { property: 'og:title', content: title.value },
+      {
+        name: 'robots',
+        content: 'noindex',
+      }
+    ]
+  };
+});
+</script>"#;
+
+    let report = rewrite_sfc_with_report(sfc, None).unwrap();
+
+    assert_eq!(trim_whitespace(&report.code), trim_whitespace(expected));
+    let codes: Vec<&str> = report.diagnostics.iter().map(|d| d.code.as_str()).collect();
+    assert_eq!(codes, vec!["head-hid"]);
+  }
+
+  #[test]
+  fn test_should_handle_simple_components() {
+    let sfc = r#"<template><h1>Dummy content</h1></template>
     <script>
     export default {
       name: 'DummyComponent',
@@ -875,6 +1918,158 @@ onMounted(() => {
     assert_eq!(trim_whitespace(&result), trim_whitespace(expected));
   }
 
+  #[test]
+  fn test_should_handle_route_in_template_directives() {
+    let sfc = r#"<template>
+    <router-link v-if="$route.params.id" :to="{ query: $route.query }">Next</router-link>
+    <p>Uses $route in plain text</p>
+    </template>
+    <script>
+    export default {
+      methods: {
+        goTo(page) {
+          this.$router.replace({ query: { ...this.$route.query, page } });
+        }
+      }
+    }
+    </script>"#;
+
+    let expected = r##"
+<template>
+  <router-link v-if="route.params.id" :to="{ query: route.query }">Next</router-link>
+  <p>Uses $route in plain text</p>
+</template>
+<script setup>
+import { useRoute, useRouter } from 'vue-router';
+
+const route = useRoute();
+const router = useRouter();
+
+const goTo = (page) => {
+  router.replace({ query: { ...route.query, page } });
+};
+</script>"##;
+
+    let result = rewrite_sfc(sfc, None).unwrap();
+
+    assert_eq!(trim_whitespace(&result), trim_whitespace(expected));
+  }
+
+  #[test]
+  fn test_should_not_add_use_route_for_router_only_usage() {
+    let sfc = r#"<template>
+    <button @click="$router.back()">Back</button>
+    <p>No $route here</p>
+    </template>
+    <script>
+    export default {
+      methods: {
+        goHome() {
+          this.$router.push('/');
+        }
+      }
+    }
+    </script>"#;
+
+    let expected = r##"
+<template>
+  <button @click="router.back()">Back</button>
+  <p>No $route here</p>
+</template>
+<script setup>
+import { useRouter } from 'vue-router';
+
+const router = useRouter();
+
+const goHome = () => {
+  router.push('/');
+};
+</script>"##;
+
+    let result = rewrite_sfc(sfc, None).unwrap();
+
+    assert_eq!(trim_whitespace(&result), trim_whitespace(expected));
+  }
+
+  #[test]
+  fn test_should_migrate_vue_router_3_apis() {
+    let sfc = r#"<template>
+    <p>{{ $router.currentRoute.path }}</p>
+    </template>
+    <script>
+    export default {
+      methods: {
+        goTo(page) {
+          console.log(this.$router.currentRoute.name, this.$router.app);
+          this.$router.push({ query: { page } }).catch(() => {});
+          this.$router.replace('/home').catch((err) => {
+            console.error(err);
+          });
+        }
+      }
+    }
+    </script>"#;
+
+    let expected = r##"
+<template>
+  <p>{{ router.currentRoute.value.path }}</p>
+</template>
+<script setup>
+import { useRouter } from 'vue-router';
+
+const router = useRouter();
+
+const goTo = (page) => {
+  console.log(router.currentRoute.value.name, router.app);
+  router.push({ query: { page } });
+  router.replace('/home').catch((err) => {
+    console.error(err);
+  });
+};
+</script>"##;
+
+    let report = rewrite_sfc_with_report(sfc, None).unwrap();
+
+    assert_eq!(trim_whitespace(&report.code), trim_whitespace(expected));
+    let codes: Vec<&str> = report.diagnostics.iter().map(|d| d.code.as_str()).collect();
+    assert_eq!(codes, vec!["router-duplicate-navigation", "router-app"]);
+  }
+
+  #[test]
+  fn test_should_not_rewrite_loop_variables_shadowing_component_bindings() {
+    let sfc = r#"<template>
+    <select :value="value">
+      <option v-for="(value, key) in options" :key="key" :value="value">{{ value }}</option>
+    </select>
+    </template>
+    <script>
+    export default {
+      props: ['value', 'options']
+    }
+    </script>"#;
+
+    let expected = r##"
+<template>
+  <select :value="modelValue">
+    <option v-for="(value, key) in options" :key="key" :value="value">{{ value }}</option>
+  </select>
+</template>
+<script setup>
+const props = defineProps(['modelValue', 'options']);
+</script>"##;
+
+    let options = RewriteOptions {
+      emit_mapping: Some(EmitMappingConfig {
+        model_prop: Some("modelValue".to_string()),
+        ..Default::default()
+      }),
+      ..Default::default()
+    };
+    let result = rewrite_sfc(sfc, Some(options)).unwrap();
+
+    assert_eq!(trim_whitespace(&result), trim_whitespace(expected));
+  }
+
   #[test]
   fn test_should_handle_event_listeners() {
     let sfc = r#"<template><h1>{{ title }}</h1></template>
@@ -1095,19 +2290,19 @@ const handleClick = () => {
   }
 
   #[test]
-  fn test_should_handle_regex_data() {
-    let sfc = r#"<template><h1>{{ title }}</h1></template>
+  fn test_should_unwrap_axios_shortcut_responses() {
+    let sfc = r#"<template><div>{{ items.length }}</div></template>
     <script>
     export default {
       data() {
         return {
-          title: 'Hello world',
-          regex: /\\d+/g
+          items: []
         };
       },
       methods: {
-        testRegex() {
-          return this.regex.test('123');
+        async load() {
+          this.items = await this.$axios.$get('/api/items', { params: { page: 1 } });
+          await this.$axios.$delete(`/api/items/${this.items[0].id}`);
         }
       }
     }
@@ -1115,16 +2310,19 @@ const handleClick = () => {
 
     let expected = r#"
 <template>
-  <h1>{{ title }}</h1>
+  <div>{{ items.length }}</div>
 </template>
 <script setup>
 import { ref } from 'vue';
+import { useHttp } from '@/composables/useHttp';
 
-const regex = ref(/\\d+/g);
-const title = ref('Hello world');
+const http = useHttp();
 
-const testRegex = () => {
-  return regex.value.test('123');
+const items = ref([]);
+
+const load = async () => {
+  items.value = await http.get('/api/items', { params: { page: 1 } }).then((response) => response.data);
+  await http.delete(`/api/items/${items.value[0].id}`).then((response) => response.data);
 };
 </script>"#;
 
@@ -1132,4 +2330,1134 @@ const testRegex = () => {
 
     assert_eq!(trim_whitespace(&result), trim_whitespace(expected));
   }
+
+  #[test]
+  fn test_should_wrap_axios_shortcuts_in_data_helper() {
+    let sfc = r#"<template><div>{{ items.length }}</div></template>
+    <script>
+    export default {
+      data() {
+        return {
+          items: []
+        };
+      },
+      methods: {
+        async save(item) {
+          this.$axios.setToken('secret', 'Bearer');
+          this.items = await this.$axios.$post('/api/items', item);
+        }
+      }
+    }
+    </script>"#;
+
+    let expected = r#"
+<template>
+  <div>{{ items.length }}</div>
+</template>
+<script setup>
+import { ref } from 'vue';
+import { httpData, useHttp } from '@/composables/useHttp';
+
+const http = useHttp();
+
+const items = ref([]);
+
+const save = async (item) => {
+  http.setToken('secret', 'Bearer');
+  items.value = await httpData(http.post('/api/items', item));
+};
+</script>"#;
+
+    let options = RewriteOptions {
+      axios_data_helper: Some("httpData".to_string()),
+      ..Default::default()
+    };
+    let report = rewrite_sfc_with_report(sfc, Some(options)).unwrap();
+
+    assert_eq!(trim_whitespace(&report.code), trim_whitespace(expected));
+    let codes: Vec<&str> = report.diagnostics.iter().map(|d| d.code.as_str()).collect();
+    assert_eq!(codes, vec!["axios-instance-config"]);
+  }
+
+  #[test]
+  fn test_should_keep_axios_as_http_client() {
+    let sfc = r#"<template><div>{{ items.length }}</div></template>
+    <script>
+    export default {
+      data() {
+        return {
+          items: []
+        };
+      },
+      methods: {
+        async load() {
+          this.items = await this.$axios.$get('/api/items');
+          await this.$axios.put('/api/items', this.items);
+        }
+      }
+    }
+    </script>"#;
+
+    let expected = r#"
+<template>
+  <div>{{ items.length }}</div>
+</template>
+<script setup>
+import { ref } from 'vue';
+import axios from '@/plugins/axios';
+
+const items = ref([]);
+
+const load = async () => {
+  items.value = await axios.get('/api/items').then((response) => response.data);
+  await axios.put('/api/items', items.value);
+};
+</script>"#;
+
+    let options = RewriteOptions {
+      http: Some(HttpConfig {
+        import_path: "@/plugins/axios".to_string(),
+        ..HttpConfig::keep_axios()
+      }),
+      ..Default::default()
+    };
+    let result = rewrite_sfc(sfc, Some(options)).unwrap();
+
+    assert_eq!(trim_whitespace(&result), trim_whitespace(expected));
+  }
+
+  #[test]
+  fn test_should_convert_axios_to_custom_composable() {
+    let sfc = r#"<template><div>{{ user.name }}</div></template>
+    <script>
+    export default {
+      data() {
+        return {
+          user: {}
+        };
+      },
+      methods: {
+        async loadUser() {
+          const { data } = await this.$axios.get('/api/user');
+          this.user = data;
+        }
+      }
+    }
+    </script>"#;
+
+    let expected = r#"
+<template>
+  <div>{{ user.name }}</div>
+</template>
+<script setup>
+import { ref } from 'vue';
+import { useApi } from '@/composables/api';
+
+const api = useApi();
+
+const user = ref({});
+
+const loadUser = async () => {
+  const { data } = await api.get('/api/user');
+  user.value = data;
+};
+</script>"#;
+
+    let options = RewriteOptions {
+      http: Some(HttpConfig {
+        import_path: "@/composables/api".to_string(),
+        name: "api".to_string(),
+        composable: Some("useApi".to_string()),
+      }),
+      ..Default::default()
+    };
+    let result = rewrite_sfc(sfc, Some(options)).unwrap();
+
+    assert_eq!(trim_whitespace(&result), trim_whitespace(expected));
+  }
+
+  #[test]
+  fn test_should_handle_regex_data() {
+    let sfc = r#"<template><h1>{{ title }}</h1></template>
+    <script>
+    export default {
+      data() {
+        return {
+          title: 'Hello world',
+          regex: /\\d+/g
+        };
+      },
+      methods: {
+        testRegex() {
+          return this.regex.test('123');
+        }
+      }
+    }
+    </script>"#;
+
+    let expected = r#"
+<template>
+  <h1>{{ title }}</h1>
+</template>
+<script setup>
+import { ref } from 'vue';
+
+const regex = ref(/\\d+/g);
+const title = ref('Hello world');
+
+const testRegex = () => {
+  return regex.value.test('123');
+};
+</script>"#;
+
+    let result = rewrite_sfc(sfc, None).unwrap();
+
+    assert_eq!(trim_whitespace(&result), trim_whitespace(expected));
+  }
+
+  #[test]
+  fn test_should_remove_imports_no_longer_used() {
+    let sfc = r#"<template><p>{{ user.name }}</p></template>
+    <script>
+    import Vue from 'vue';
+    import { cloneDeep, isEqual } from 'lodash';
+    import { unusedHelper } from '@/utils/helpers';
+
+    export default {
+      data() {
+        return {
+          user: {},
+        };
+      },
+      methods: {
+        update(nextUser) {
+          if (!isEqual(nextUser, this.user)) {
+            Vue.set(this.user, 'name', nextUser.name);
+          }
+        },
+      },
+    };
+    </script>"#;
+
+    let expected = r#"
+<template>
+  <p>{{ user.name }}</p>
+</template>
+<script setup>
+import { ref } from 'vue';
+import { cloneDeep, isEqual } from 'lodash';
+import { unusedHelper } from '@/utils/helpers';
+
+const user = ref({});
+
+const update = (nextUser) => {
+  if (!isEqual(nextUser, user.value)) {
+    user.value.name = nextUser.name;
+  }
+};
+</script>"#;
+
+    let result = rewrite_sfc(sfc, None).unwrap();
+    assert_eq!(trim_whitespace(&result), trim_whitespace(expected));
+
+    // Sources in the keeplist are never touched
+    let options = RewriteOptions {
+      import_keeplist: Some(vec!["vue".to_string()]),
+      ..Default::default()
+    };
+    let result = rewrite_sfc(sfc, Some(options)).unwrap();
+    assert!(result.contains("import Vue from 'vue';"));
+  }
+
+  #[test]
+  fn test_should_access_configured_global_properties_through_proxy() {
+    let sfc = r#"<template><p>{{ $filters.currency(total) }}</p></template>
+<script>
+export default {
+  data() {
+    return { total: 0 };
+  },
+  computed: {
+    maxItems() {
+      return this.$constants.MAX_ITEMS;
+    },
+  },
+  methods: {
+    format() {
+      return this.$filters.currency(this.total);
+    },
+  },
+};
+</script>"#;
+
+    let expected = r#"
+<template>
+<p>{{ $filters.currency(total) }}</p>
+</template>
+<script setup>
+import { computed, getCurrentInstance, ref } from 'vue';
+
+// TODO: transitional access to app.config.globalProperties, replace with composables
+const { proxy } = getCurrentInstance();
+
+const total = ref(0);
+
+const maxItems = computed(() => {
+  return proxy.$constants.MAX_ITEMS;
+});
+
+
+const format = () => {
+  return proxy.$filters.currency(total.value);
+};
+</script>"#;
+
+    let options = RewriteOptions {
+      global_properties: Some(vec!["$filters".to_string(), "$constants".to_string()]),
+      ..Default::default()
+    };
+
+    let result = rewrite_sfc(sfc, Some(options)).unwrap();
+    assert_eq!(trim_whitespace(&result), trim_whitespace(expected));
+  }
+
+  #[test]
+  fn test_should_declare_undeclared_instance_properties() {
+    let sfc = r#"<template><p>{{ count }}</p></template>
+<script>
+export default {
+  data() {
+    return { count: 0 };
+  },
+  mounted() {
+    this.timer = setInterval(() => {
+      this.count++;
+    }, 1000);
+  },
+  beforeDestroy() {
+    clearInterval(this.timer);
+  },
+};
+</script>"#;
+
+    let expected = r#"
+<template>
+<p>{{ count }}</p>
+</template>
+<script setup>
+import { onBeforeUnmount, onMounted, ref } from 'vue';
+
+const count = ref(0);
+let timer;
+
+onMounted(() => {
+  timer = setInterval(() => {
+    count.value++;
+  }, 1000);
+});
+
+onBeforeUnmount(() => {
+  clearInterval(timer);
+});
+</script>"#;
+
+    let result = rewrite_sfc(sfc, None).unwrap();
+    assert_eq!(trim_whitespace(&result), trim_whitespace(expected));
+  }
+
+  #[test]
+  fn test_should_declare_undeclared_instance_properties_as_refs() {
+    let sfc = r#"<template><div /></template>
+<script>
+export default {
+  mounted() {
+    this.observer = new IntersectionObserver(this.onIntersect);
+    this.observer.observe(this.$el);
+  },
+  beforeDestroy() {
+    this.observer.disconnect();
+  },
+  methods: {
+    onIntersect(entries) {
+      console.log(entries);
+    },
+  },
+};
+</script>"#;
+
+    let options = RewriteOptions {
+      instance_properties: Some(InstancePropertyStyle::Ref),
+      ..Default::default()
+    };
+
+    let result = rewrite_sfc(sfc, Some(options)).unwrap();
+    assert!(result.contains(
+      "// NOTE: `observer` was not declared in data(), added during conversion\nconst observer = ref(undefined);"
+    ));
+    assert!(result.contains("observer.value = new IntersectionObserver(onIntersect);"));
+    assert!(result.contains("observer.value.disconnect();"));
+    assert!(!result.contains("FIXME"));
+  }
+
+  #[test]
+  fn test_should_declare_timer_handles_in_the_configured_style() {
+    let sfc = r#"<template><div /></template>
+<script>
+export default {
+  mounted() {
+    this.timer = setInterval(this.poll, 1000);
+    this.loading = true;
+  },
+  beforeDestroy() {
+    clearInterval(this.timer);
+  },
+  methods: {
+    poll() {},
+  },
+};
+</script>"#;
+
+    let options = RewriteOptions {
+      instance_properties: Some(InstancePropertyStyle::Ref),
+      ..Default::default()
+    };
+
+    let result = rewrite_sfc(sfc, Some(options)).unwrap();
+    assert!(result.contains("const timer = ref(null);"), "{}", result);
+    assert!(result.contains("timer.value = setInterval(poll, 1000);"));
+    assert!(result.contains("clearInterval(timer.value);"));
+    assert!(result.contains("const loading = ref(undefined);"));
+    assert!(result.contains("loading.value = true;"));
+
+    let options = RewriteOptions {
+      instance_properties: Some(InstancePropertyStyle::Let),
+      ..Default::default()
+    };
+
+    let result = rewrite_sfc(sfc, Some(options)).unwrap();
+    assert!(result.contains("let timer;"), "{}", result);
+    assert!(result.contains("timer = setInterval(poll, 1000);"));
+    assert!(result.contains("clearInterval(timer);"));
+    assert!(result.contains("let loading;"), "{}", result);
+
+    let result = rewrite_sfc(sfc, None).unwrap();
+    assert!(result.contains("let timer;"), "{}", result);
+    assert!(result.contains("clearInterval(timer);"));
+    assert!(!result.contains("let loading;"), "{}", result);
+  }
+
+  #[test]
+  fn test_should_only_declare_undeclared_flags_when_opted_in() {
+    let sfc = r#"<template><div /></template>
+<script>
+export default {
+  methods: {
+    submit() {
+      this.isSubmitting = true;
+      this.retryTimer = setTimeout(this.submit, 1000);
+    },
+  },
+};
+</script>"#;
+
+    let result = rewrite_sfc(sfc, None).unwrap();
+    assert!(result.contains("let retryTimer;"));
+    assert!(result.contains("/* FIXME: isSubmitting */ isSubmitting = true;"));
+
+    let options = RewriteOptions {
+      instance_properties: Some(InstancePropertyStyle::Let),
+      ..Default::default()
+    };
+
+    let result = rewrite_sfc(sfc, Some(options)).unwrap();
+    assert!(result.contains(
+      "// NOTE: `isSubmitting` was not declared in data(), added during conversion\nlet isSubmitting;"
+    ));
+    assert!(result.contains("isSubmitting = true;"));
+    assert!(!result.contains("FIXME"));
+  }
+
+  #[test]
+  fn test_should_only_convert_component_this_in_nested_functions() {
+    let sfc = r#"<template><div /></template>
+<script>
+export default {
+  data() {
+    return { items: [], total: 0 };
+  },
+  methods: {
+    add(it) {
+      this.total += it;
+    },
+    run() {
+      const self = this;
+      this.items.forEach(function (it) {
+        self.add(it);
+      });
+      this.items.forEach(function (it) {
+        this.add(it);
+      }, this);
+      this.items.forEach(function (it) {
+        this.add(it);
+      }.bind(this));
+      $('.item').each(function () {
+        $(this).hide();
+        self.total++;
+      });
+    },
+  },
+};
+</script>"#;
+
+    let expected = r#"
+<template>
+<div />
+</template>
+<script setup>
+import { ref } from 'vue';
+
+const items = ref([]);
+const total = ref(0);
+
+const add = (it) => {
+  total.value += it;
+};
+const run = () => {
+  items.value.forEach(function (it) {
+    add(it);
+  });
+  items.value.forEach((it) => {
+    add(it);
+  });
+  items.value.forEach((it) => {
+    add(it);
+  });
+  $('.item').each(function () {
+    $(this).hide();
+    total.value++;
+  });
+};
+</script>"#;
+
+    let result = rewrite_sfc(sfc, None).unwrap();
+    assert_eq!(trim_whitespace(&result), trim_whitespace(expected));
+  }
+
+  #[test]
+  fn test_should_only_rewrite_framework_identifiers_in_template_expressions() {
+    let sfc = r#"<template>
+  <div :title="$t('title')">
+    <!-- uses $t('title') and $route.params -->
+    <p>Call $t('x') or read $route in templates</p>
+    <p>{{ $t('greeting') }} {{ $route.params.id }}</p>
+    <button @click="$router.push('/')">Home</button>
+  </div>
+</template>
+<script>
+export default {};
+</script>"#;
+
+    let result = rewrite_sfc(sfc, None).unwrap();
+
+    assert!(result.contains(r#"<div :title="t('title')">"#));
+    assert!(result.contains("<!-- uses $t('title') and $route.params -->"));
+    assert!(result.contains("<p>Call $t('x') or read $route in templates</p>"));
+    assert!(result.contains("<p>{{ t('greeting') }} {{ route.params.id }}</p>"));
+    assert!(result.contains(r#"<button @click="router.push('/')">Home</button>"#));
+  }
+
+  #[test]
+  fn test_should_resolve_registered_and_dynamic_components() {
+    let sfc = r#"<template>
+  <div>
+    <span v-text="message"></span>
+    <div v-html="body"></div>
+    <component :is="currentView" />
+    <component is="tab-home" />
+  </div>
+</template>
+<script>
+import TabHome from './TabHome.vue';
+import TabPosts from './TabPosts.vue';
+
+export default {
+  components: { TabHome, Posts: TabPosts, TabArchive: () => import('./TabArchive.vue') },
+  data() {
+    return { message: 'hi', body: '<b>x</b>', currentView: 'TabHome' };
+  },
+  methods: {
+    showArchive() {
+      this.currentView = 'TabArchive';
+    },
+  },
+};
+</script>"#;
+
+    let expected = r#"<template>
+  <div>
+    <span v-text="message"></span>
+    <div v-html="body"></div>
+    <component :is="dynamicComponents[currentView]" />
+    <component :is="TabHome" />
+  </div>
+</template>
+<script setup>
+import { defineAsyncComponent, ref } from 'vue';
+import TabHome from './TabHome.vue';
+import TabPosts from './TabPosts.vue';
+
+const Posts = TabPosts;
+const TabArchive = defineAsyncComponent(() => import('./TabArchive.vue'));
+const dynamicComponents = { TabHome, Posts, TabArchive };
+
+const body = ref('<b>x</b>');
+const currentView = ref('TabHome');
+const message = ref('hi');
+
+const showArchive = () => {
+  currentView.value = 'TabArchive';
+};
+</script>"#;
+
+    let result = rewrite_sfc(sfc, None).unwrap();
+    assert_eq!(trim_whitespace(&result), trim_whitespace(expected));
+  }
+
+  #[test]
+  fn test_should_import_components_registered_with_require() {
+    let sfc = r#"<template>
+  <div>
+    <tab-home />
+    <TabPosts />
+    <tab-archive />
+  </div>
+</template>
+<script>
+export default {
+  components: {
+    TabHome: require('./TabHome.vue').default,
+    TabPosts: require('@/components/TabPosts.vue'),
+    TabArchive: resolve => require(['./TabArchive.vue'], resolve),
+  },
+};
+</script>"#;
+
+    let expected = r#"<template>
+  <div>
+    <tab-home />
+    <TabPosts />
+    <tab-archive />
+  </div>
+</template>
+<script setup>
+import { defineAsyncComponent } from 'vue';
+import TabHome from './TabHome.vue';
+import TabPosts from '@/components/TabPosts.vue';
+
+const TabArchive = defineAsyncComponent(() => import('./TabArchive.vue'));
+</script>"#;
+
+    let result = rewrite_sfc(sfc, None).unwrap();
+    assert_eq!(trim_whitespace(&result), trim_whitespace(expected));
+
+    let options = RewriteOptions {
+      require_components: Some(RequireComponentStyle::Async),
+      ..Default::default()
+    };
+    let result = rewrite_sfc(sfc, Some(options)).unwrap();
+    assert!(result.contains("const TabHome = defineAsyncComponent(() => import('./TabHome.vue'));"));
+    assert!(result.contains(
+      "const TabPosts = defineAsyncComponent(() => import('@/components/TabPosts.vue'));"
+    ));
+    assert!(!result.contains("require("));
+  }
+
+  #[test]
+  fn test_should_emit_typescript_with_inferred_types() {
+    let sfc = r#"<template>
+  <div @click="$emit('select', item)">{{ title }}</div>
+</template>
+
+<script>
+export default {
+  props: {
+    title: { type: String, required: true },
+    count: { type: Number, default: 0 },
+    items: { type: Array, default: () => [1, 2] },
+    mode: [String, Number],
+    item: {},
+  },
+  data() {
+    return { selected: null, open: false }
+  },
+  methods: {
+    toggle() {
+      this.$emit('toggle', this.open, this.count + 1)
+      this.$emit('toggle', false)
+    }
+  }
+}
+</script>"#;
+
+    let options = RewriteOptions {
+      typescript: true,
+      ..Default::default()
+    };
+    let result = rewrite_sfc(sfc, Some(options)).unwrap();
+
+    assert!(result.contains(r#"<script setup lang="ts">"#));
+    let expected_props = r#"const props = withDefaults(defineProps<{
+  title: string;
+  count?: number;
+  items?: number[];
+  mode?: string | number;
+  item?: /* FIXME: type */ unknown;
+}>(), {
+  count: 0,
+  items: () => [1, 2],
+});"#;
+    assert!(result.contains(expected_props), "{}", result);
+    let expected_emits = r#"const emit = defineEmits<{
+  (e: 'select', item: /* FIXME: type */ unknown): void;
+  (e: 'toggle', open: boolean, value?: number): void;
+}>();"#;
+    assert!(result.contains(expected_emits), "{}", result);
+    assert!(result.contains("const selected = ref</* FIXME: type */ unknown>(null);"));
+    assert!(result.contains("const open = ref(false);"));
+  }
+
+  #[test]
+  fn test_should_type_props_from_prop_type_casts_in_typescript() {
+    let sfc = r#"<script lang="ts">
+import { CartItem } from '@/types/cart';
+
+export default {
+  props: {
+    cfg: { type: Object as PropType<CartItem>, default: () => ({}) },
+    id: { type: [String, Number] as PropType<string | number>, required: true },
+    onSelect: Function as PropType<(item: CartItem) => void>,
+  },
+}
+</script>"#;
+
+    let options = RewriteOptions {
+      typescript: true,
+      ..Default::default()
+    };
+    let result = rewrite_sfc(sfc, Some(options)).unwrap();
+
+    let expected_props = r#"const props = withDefaults(defineProps<{
+  cfg?: CartItem;
+  id: string | number;
+  onSelect?: (item: CartItem) => void;
+}>(), {
+  cfg: () => ({}),
+});"#;
+    assert!(result.contains(expected_props), "{}", result);
+    assert!(!result.contains("PropType"), "{}", result);
+  }
+
+  #[test]
+  fn test_should_keep_runtime_props_with_validators_in_typescript() {
+    let sfc = r#"<script>
+export default {
+  props: {
+    size: { type: String, validator: (value) => ['s', 'm'].includes(value) },
+  },
+}
+</script>"#;
+
+    let options = RewriteOptions {
+      typescript: true,
+      ..Default::default()
+    };
+    let report = rewrite_sfc_with_report(sfc, Some(options)).unwrap();
+
+    assert!(report.code.contains("const props = defineProps({"));
+    assert!(report
+      .diagnostics
+      .iter()
+      .any(|diagnostic| diagnostic.code == "typescript-runtime-props"));
+  }
+
+  #[test]
+  fn test_should_generate_define_model_for_passthrough_computed() {
+    let sfc = r#"<template>
+  <input v-model="model">
+</template>
+
+<script>
+export default {
+  props: {
+    value: { type: String, default: '' },
+    label: String,
+  },
+  computed: {
+    model: {
+      get() { return this.value },
+      set(v) { this.$emit('input', v) }
+    }
+  },
+  methods: {
+    close() {
+      this.$emit('close')
+    }
+  }
+}
+</script>"#;
+
+    let expected = r#"<template>
+<input v-model="model">
+</template>
+<script setup>
+const props = defineProps({
+  label: {
+    type: String,
+  },
+});
+
+const model = defineModel('value', { type: String, default: '' });
+
+const emit = defineEmits(['close']);
+
+const close = () => {
+  emit('close')
+};
+</script>"#;
+
+    let options = RewriteOptions {
+      prefer_define_model: true,
+      ..Default::default()
+    };
+    let result = rewrite_sfc(sfc, Some(options)).unwrap();
+    assert_eq!(trim_whitespace(&result), trim_whitespace(expected));
+  }
+
+  #[test]
+  fn test_should_keep_model_prop_used_outside_the_passthrough_computed() {
+    let sfc = r#"<script>
+export default {
+  props: ['modelValue'],
+  computed: {
+    model: {
+      get() { return this.modelValue },
+      set(v) { this.$emit('update:modelValue', v) }
+    },
+    empty() {
+      return !this.modelValue
+    }
+  }
+}
+</script>"#;
+
+    let options = RewriteOptions {
+      prefer_define_model: true,
+      ..Default::default()
+    };
+    let result = rewrite_sfc(sfc, Some(options)).unwrap();
+
+    assert!(!result.contains("defineModel"));
+    assert!(result.contains("const props = defineProps(['modelValue']);"));
+    assert!(result.contains("const emit = defineEmits(['update:modelValue']);"));
+  }
+
+  #[test]
+  fn test_should_expose_configured_and_uncalled_methods() {
+    let sfc = r#"<template>
+  <div v-if="visible" @click="close">{{ label }}</div>
+</template>
+
+<script>
+export default {
+  name: 'DsModal',
+  data() {
+    return { visible: false }
+  },
+  computed: {
+    label() { return 'Close' }
+  },
+  watch: {
+    visible: 'onVisible'
+  },
+  methods: {
+    open() { this.visible = true },
+    close() { this.visible = false },
+    reset() { this.close() },
+    onVisible() {}
+  }
+}
+</script>"#;
+
+    let options = RewriteOptions {
+      expose: Some(ExposeConfig {
+        components: HashMap::from([(
+          "Ds*".to_string(),
+          vec!["label".to_string(), "missing".to_string()],
+        )]),
+        auto: true,
+        ..Default::default()
+      }),
+      ..Default::default()
+    };
+    let report = rewrite_sfc_with_report(sfc, Some(options)).unwrap();
+
+    assert!(report
+      .code
+      .ends_with("defineExpose({ label, open, reset });\n</script>"));
+    let codes: Vec<&str> = report
+      .diagnostics
+      .iter()
+      .map(|diagnostic| diagnostic.code.as_str())
+      .collect();
+    assert!(codes.contains(&"expose-unknown-member"));
+    assert!(codes.contains(&"expose-uncalled-methods"));
+
+    // Without `auto`, nothing is exposed for components no pattern matches
+    let options = RewriteOptions {
+      expose: Some(ExposeConfig {
+        components: HashMap::from([("Other".to_string(), vec!["label".to_string()])]),
+        auto: false,
+        ..Default::default()
+      }),
+      ..Default::default()
+    };
+    let result = rewrite_sfc(sfc, Some(options)).unwrap();
+    assert!(!result.contains("defineExpose"));
+  }
+
+  #[test]
+  fn test_should_leave_strings_comments_and_regexes_alone_in_bodies() {
+    let sfc = r#"<template>
+  <button @click="save">{{ count }}</button>
+</template>
+<script>
+export default {
+  data() {
+    return { count: 0 };
+  },
+  methods: {
+    save() {
+      // this.count is reset by this.reset()
+      const message = "please call this.save()";
+      const label = 'this.count';
+      const template = `this.count is ${this.count}`;
+      const pattern = /this\.count/g;
+      /* this.count */
+      this.count++;
+      return [message, label, template, pattern];
+    },
+  },
+};
+</script>"#;
+
+    let expected = r#"<template>
+  <button @click="save">{{ count }}</button>
+</template>
+<script setup>
+import { ref } from 'vue';
+
+const count = ref(0);
+
+const save = () => {
+  // this.count is reset by this.reset()
+  const message = "please call this.save()";
+  const label = 'this.count';
+  const template = `this.count is ${count.value}`;
+  const pattern = /this\.count/g;
+  /* this.count */
+  count.value++;
+  return [message, label, template, pattern];
+};
+</script>"#;
+
+    let result = rewrite_sfc(sfc, None).unwrap();
+    assert_eq!(trim_whitespace(&result), trim_whitespace(expected));
+  }
+
+  #[test]
+  fn test_should_still_convert_string_arguments_of_instance_calls() {
+    let sfc = r#"<template>
+  <input :value="value" @input="update" />
+</template>
+<script>
+export default {
+  props: ['value'],
+  methods: {
+    update(event) {
+      this.$emit('input', event.target.value);
+      this.$refs['field'].focus();
+    },
+  },
+};
+</script>"#;
+
+    let result = rewrite_sfc(sfc, None).unwrap();
+    assert!(result.contains("emit('update:value', event.target.value);"));
+    assert!(result.contains("fieldRef.value.focus();"));
+  }
+
+  #[test]
+  fn test_should_keep_recursive_components_rendering_themselves() {
+    let sfc = r#"<template>
+  <li>
+    {{ item.label }}
+    <ul v-if="item.children">
+      <tree-item v-for="child in item.children" :key="child.id" :item="child" />
+    </ul>
+  </li>
+</template>
+
+<script>
+export default {
+  name: 'TreeItem',
+  props: ['item'],
+}
+</script>"#;
+
+    let result = rewrite_sfc(sfc, None).unwrap();
+    assert!(result.contains("defineOptions({ name: 'TreeItem' });"));
+    assert!(result.contains("<tree-item v-for"));
+
+    let options = RewriteOptions {
+      self_reference: Some(SelfReferenceStyle::FileName),
+      file_name: Some("TreeNode.vue".to_string()),
+      ..Default::default()
+    };
+    let result = rewrite_sfc(sfc, Some(options)).unwrap();
+    assert!(!result.contains("defineOptions"));
+    assert!(result.contains(r#"<TreeNode v-for="child in item.children""#));
+
+    // The file name is all a component needs to render itself when it matches the name
+    let options = RewriteOptions {
+      self_reference: Some(SelfReferenceStyle::FileName),
+      file_name: Some("TreeItem.vue".to_string()),
+      ..Default::default()
+    };
+    let result = rewrite_sfc(sfc, Some(options)).unwrap();
+    assert!(!result.contains("defineOptions"));
+    assert!(result.contains("<tree-item v-for"));
+  }
+
+  #[test]
+  fn test_should_assemble_a_setup_function() {
+    let sfc = r#"<template>
+  <div>
+    <tab-home :title="title" @click="save" />
+    <span>{{ total }}</span>
+  </div>
+</template>
+
+<script>
+import TabHome from './TabHome.vue';
+
+export default {
+  name: 'Checkout',
+  components: { TabHome, TabOther: () => import('./TabOther.vue') },
+  props: ['title'],
+  data() {
+    return { count: 1 };
+  },
+  computed: {
+    total() {
+      return this.count * 2;
+    },
+  },
+  methods: {
+    save() {
+      this.$emit('saved', this.title);
+    },
+  },
+}
+</script>"#;
+
+    let options = RewriteOptions {
+      script_style: Some(ScriptStyle::SetupFunction),
+      ..Default::default()
+    };
+    let result = rewrite_sfc(sfc, Some(options)).unwrap();
+
+    let expected = r#"<script>
+import { computed, defineAsyncComponent, defineComponent, ref } from 'vue';
+import TabHome from './TabHome.vue';
+
+export default defineComponent({
+  name: 'Checkout',
+  components: { TabHome, TabOther: defineAsyncComponent(() => import('./TabOther.vue')) },
+  props: ['title'],
+  emits: ['saved'],
+  setup(props, { emit }) {
+    const count = ref(1);
+
+    const total = computed(() => {
+      return count.value * 2;
+    });
+
+    const save = () => {
+      emit('saved', props.title);
+    };
+
+    return { total, save };
+  },
+});
+</script>"#;
+    assert_eq!(result[result.find("<script>").unwrap()..], *expected);
+  }
+
+  #[test]
+  fn test_should_only_return_bindings_the_template_expressions_use() {
+    let sfc = r#"<template>
+  <div class="selected">
+    <h1 ref="title">Total: {{ total }}</h1>
+    <p>count of results</p>
+    <input v-model="query">
+    <li v-for="item in items" :key="item.id" @click="onSelect(item)">{{ item.label }}</li>
+  </div>
+</template>
+
+<script>
+export default {
+  data() {
+    return { query: '', count: 0, selected: null, item: null };
+  },
+  computed: {
+    total() {
+      return this.count * 2;
+    },
+    items() {
+      return [];
+    },
+  },
+  methods: {
+    onSelect(item) {
+      this.selected = item;
+      this.$refs.title.focus();
+    },
+  },
+}
+</script>"#;
+
+    let options = RewriteOptions {
+      script_style: Some(ScriptStyle::SetupFunction),
+      ..Default::default()
+    };
+    let result = rewrite_sfc(sfc, Some(options)).unwrap();
+    assert!(
+      result.contains("    return { query, total, items, onSelect };\n"),
+      "{}",
+      result
+    );
+
+    // String refs bind the template refs declared in setup
+    let options = RewriteOptions {
+      script_style: Some(ScriptStyle::SetupFunction),
+      template_ref_style: Some(TemplateRefStyle::Ref),
+      ..Default::default()
+    };
+    let result = rewrite_sfc(sfc, Some(options)).unwrap();
+    assert!(
+      result.contains("    return { titleRef, query, total, items, onSelect };\n"),
+      "{}",
+      result
+    );
+  }
 }
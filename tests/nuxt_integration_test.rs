@@ -1,4 +1,6 @@
-use vue_options_to_composition::rewrite_sfc;
+use vue_options_to_composition::{
+  rewrite_sfc, NuxtErrorConfig, NuxtI18nStyle, RewriteOptions, Target,
+};
 
 fn trim_whitespace(s: &str) -> String {
   s.lines()
@@ -152,17 +154,62 @@ const title = ref('Hello world');
 </script>
 <script>
 export const i18n = {
-  no: '/produkt/:slug',
-  sv: '/produkt/:slug',
-  fi: '/tuote/:slug',
-  da: '/produkt/:slug',
-  nl: '/product/:slug',
+  paths: {
+    no: '/produkt/:slug',
+    sv: '/produkt/:slug',
+    fi: '/tuote/:slug',
+    da: '/produkt/:slug',
+    nl: '/product/:slug',
+  },
 };
 </script>"#;
 
     assert_eq!(trim_whitespace(&result), trim_whitespace(expected));
   }
 
+  #[test]
+  fn test_should_keep_all_nuxt_i18n_options_in_define_i18n_route() {
+    let sfc = r#"<template><h1>{{ title }}</h1></template>
+    <script>
+    export default {
+      nuxtI18n: { paths: { en: '/product/:slug', sv: '/produkt/:slug' }, seo: false,
+        locales: ['en', 'sv'] },
+      data() {
+        return {
+          title: 'Hello world'
+        };
+      }
+    }
+    </script>"#;
+
+    let options = RewriteOptions {
+      nuxt_i18n: Some(NuxtI18nStyle::DefineI18nRoute),
+      ..Default::default()
+    };
+    let result = rewrite_sfc(sfc, Some(options)).unwrap();
+
+    let expected = r#"
+<template>
+  <h1>{{ title }}</h1>
+</template>
+<script setup>
+import { ref } from 'vue';
+
+defineI18nRoute({
+  paths: {
+    en: '/product/:slug',
+    sv: '/produkt/:slug',
+  },
+  seo: false,
+  locales: ['en', 'sv'],
+});
+
+const title = ref('Hello world');
+</script>"#;
+
+    assert_eq!(trim_whitespace(&result), trim_whitespace(expected));
+  }
+
   #[test]
   fn test_should_handle_nuxt_event_bus() {
     let sfc = r#"<template><h1>{{ title }}</h1></template>
@@ -253,4 +300,295 @@ const title = ref(runtimeConfig[locale.value].appName);
 
     assert_eq!(trim_whitespace(&result), trim_whitespace(expected));
   }
+
+  #[test]
+  fn test_should_convert_fetch_state() {
+    let sfc = r#"<template>
+  <div>
+    <p v-if="$fetchState.pending">Loading</p>
+    <p v-else-if="$fetchState.error">{{ $fetchState.error.message }}</p>
+    <ul v-else><li v-for="post in posts" :key="post.id">{{ post.title }}</li></ul>
+    <button @click="$fetch">Refresh</button>
+  </div>
+</template>
+    <script>
+    export default {
+      data() {
+        return {
+          posts: []
+        };
+      },
+      fetchOnServer: false,
+      async fetch() {
+        this.posts = await this.$axios.get('/api/posts');
+      },
+      methods: {
+        refresh() {
+          if (!this.$fetchState.pending) this.$fetch();
+        }
+      }
+    }
+    </script>"#;
+
+    let expected = r#"
+<template>
+<div>
+    <p v-if="pending">Loading</p>
+    <p v-else-if="error">{{ error.message }}</p>
+    <ul v-else><li v-for="post in posts" :key="post.id">{{ post.title }}</li></ul>
+    <button @click="fetch">Refresh</button>
+  </div>
+</template>
+<script setup>
+import { onMounted, ref } from 'vue';
+import { useHttp } from '@/composables/useHttp';
+
+const http = useHttp();
+
+const error = ref(null);
+const pending = ref(false);
+const posts = ref([]);
+
+const fetch = async () => {
+  pending.value = true;
+  error.value = null;
+  try {
+    posts.value = await http.get('/api/posts');
+  } catch (e) {
+    error.value = e;
+  } finally {
+    pending.value = false;
+  }
+};
+const refresh = () => {
+  if (!pending.value) fetch();
+};
+
+onMounted(async () => {
+  fetch();
+});
+</script>"#;
+
+    let result = rewrite_sfc(sfc, None).unwrap();
+
+    assert_eq!(trim_whitespace(&result), trim_whitespace(expected));
+  }
+
+  #[test]
+  fn test_should_prefetch_on_server_for_ssr_target() {
+    let sfc = r#"<template><h1>{{ title }} {{ posts.length }}</h1></template>
+    <script>
+    export default {
+      data() {
+        return {
+          title: '',
+          posts: []
+        };
+      },
+      async asyncData({ params }) {
+        const page = await getPage(params.slug);
+        return {
+          title: page.title
+        };
+      },
+      async fetch() {
+        this.posts = await getPosts();
+      }
+    }
+    </script>"#;
+
+    let expected = r#"
+<template>
+  <h1>{{ title }} {{ posts.length }}</h1>
+</template>
+<script setup>
+import { onMounted, onServerPrefetch, ref } from 'vue';
+import { useNuxtCompat } from '@/composables/useNuxtCompat';
+
+const { context } = useNuxtCompat();
+
+const posts = ref([]);
+const title = ref('');
+
+const fetch = async () => {
+  posts.value = await getPosts();
+};
+
+const asyncData = async ({ params }) => {
+  const page = await getPage(params.slug);
+  return {
+    title: page.title
+  };
+};
+
+const loadAsyncData = async () => {
+  const data = await asyncData(context);
+  title.value = data.title;
+};
+
+onServerPrefetch(async () => {
+  await fetch();
+});
+
+onMounted(async () => {
+  fetch();
+});
+
+onServerPrefetch(async () => {
+  await loadAsyncData();
+});
+
+onMounted(async () => {
+  loadAsyncData();
+});
+</script>"#;
+
+    let options = RewriteOptions {
+      ssr_target: true,
+      ..Default::default()
+    };
+    let result = rewrite_sfc(sfc, Some(options)).unwrap();
+
+    assert_eq!(trim_whitespace(&result), trim_whitespace(expected));
+  }
+
+  #[test]
+  fn test_should_convert_nuxt_error() {
+    let sfc = r#"<template><button @click="$nuxt.error({ statusCode: 500 })">{{ title }}</button></template>
+    <script>
+    export default {
+      data() {
+        return {
+          title: 'Retry'
+        };
+      },
+      methods: {
+        notFound() {
+          this.$nuxt.error({ statusCode: 404, message: 'Not found' });
+        }
+      }
+    }
+    </script>"#;
+
+    let expected = r#"
+<template>
+  <button @click="showError({ statusCode: 500 })">{{ title }}</button>
+</template>
+<script setup>
+import { ref } from 'vue';
+import { useNuxtCompat } from '@/composables/useNuxtCompat';
+
+const { showError } = useNuxtCompat();
+
+const title = ref('Retry');
+
+const notFound = () => {
+  showError({ statusCode: 404, message: 'Not found' });
+};
+</script>"#;
+
+    let result = rewrite_sfc(sfc, None).unwrap();
+    assert_eq!(trim_whitespace(&result), trim_whitespace(expected));
+
+    let options = RewriteOptions {
+      nuxt_error: Some(NuxtErrorConfig {
+        name: "showError".to_string(),
+        import_path: Some("#app".to_string()),
+      }),
+      ..Default::default()
+    };
+    let result = rewrite_sfc(sfc, Some(options)).unwrap();
+    assert!(result.contains("import { showError } from '#app';"));
+    assert!(!result.contains("useNuxtCompat"));
+  }
+
+  #[test]
+  fn test_should_convert_watch_query_and_page_key() {
+    let sfc = r#"<template><p>{{ posts.length }}</p></template>
+    <script>
+    export default {
+      data() {
+        return {
+          posts: []
+        };
+      },
+      watchQuery: ['page', 'sort-by'],
+      key: (route) => route.fullPath,
+      async fetch() {
+        this.posts = await getPosts(this.$route.query.page);
+      }
+    }
+    </script>"#;
+
+    let expected = r#"
+<template>
+  <p>{{ posts.length }}</p>
+</template>
+<script setup>
+import { ref, watch } from 'vue';
+import { useRoute } from 'vue-router';
+
+const route = useRoute();
+definePageMeta({
+  key: (route) => route.fullPath,
+});
+
+const posts = ref([]);
+
+watch(() => [route.query.page, route.query['sort-by']], () => {
+  fetch();
+});
+
+const fetch = async () => {
+  posts.value = await getPosts(route.query.page);
+};
+
+fetch();
+</script>"#;
+
+    let options = RewriteOptions {
+      define_page_meta: true,
+      ..Default::default()
+    };
+    let result = rewrite_sfc(sfc, Some(options)).unwrap();
+
+    assert_eq!(trim_whitespace(&result), trim_whitespace(expected));
+  }
+
+  #[test]
+  fn test_should_rely_on_nuxt_3_auto_imports() {
+    let sfc = r#"<template><a :href="$config.baseUrl + $route.path">{{ title }}</a></template>
+    <script>
+    export default {
+      data() {
+        return {
+          title: 'Hello'
+        };
+      },
+      head() {
+        return { title: this.title };
+      },
+      methods: {
+        back() {
+          this.$router.back();
+        }
+      }
+    }
+    </script>"#;
+
+    let options = RewriteOptions {
+      target: Some(Target::Nuxt3),
+      ..Default::default()
+    };
+    let result = rewrite_sfc(sfc, Some(options)).unwrap();
+
+    assert!(result.contains(r#"<a :href="runtimeConfig.baseUrl + route.path">"#));
+    assert!(result.contains("const runtimeConfig = useRuntimeConfig();"));
+    assert!(result.contains("const route = useRoute();"));
+    assert!(result.contains("const router = useRouter();"));
+    assert!(result.contains("useHead(() => {"));
+    assert!(!result.contains("vue-router"));
+    assert!(!result.contains("@unhead/vue"));
+    assert!(!result.contains("useNuxtCompat"));
+  }
 }
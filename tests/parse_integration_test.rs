@@ -0,0 +1,157 @@
+use vue_options_to_composition::parse_component;
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use pretty_assertions::assert_eq;
+
+  #[test]
+  fn test_should_parse_component_model() {
+    let sfc = r#"<template><button @click="increment">{{ label }}</button></template>
+    <script>
+    export default {
+      props: {
+        step: { type: Number, default: 1 },
+      },
+      data() {
+        return {
+          count: 0,
+        };
+      },
+      computed: {
+        label() {
+          return `Count: ${this.count}`;
+        },
+      },
+      watch: {
+        count(newVal) {
+          console.log(newVal);
+        },
+      },
+      methods: {
+        increment() {
+          this.count += this.step;
+        },
+      },
+    };
+    </script>"#;
+
+    let model = parse_component(sfc).unwrap();
+
+    let props: Vec<&str> = model
+      .script_state
+      .props
+      .iter()
+      .map(|prop| prop.name.as_str())
+      .collect();
+    let data: Vec<&str> = model
+      .script_state
+      .data_properties
+      .iter()
+      .map(|data| data.name.as_str())
+      .collect();
+    assert_eq!(props, vec!["step"]);
+    assert_eq!(data, vec!["count"]);
+    assert_eq!(model.script_state.computed_properties, vec!["label"]);
+    assert_eq!(model.script_state.methods, vec!["increment"]);
+    assert_eq!(model.script_state.watchers[0].watched_property, "count");
+    assert_eq!(model.template_state.vue_directives[0].name, "@click");
+  }
+
+  #[test]
+  fn test_should_serialize_component_model_to_json() {
+    let sfc = r#"<template><p>{{ message }}</p></template>
+    <script>
+    export default {
+      data() {
+        return {
+          message: 'Hello',
+        };
+      },
+    };
+    </script>"#;
+
+    let model = parse_component(sfc).unwrap();
+    let json: serde_json::Value = serde_json::to_value(&model).unwrap();
+
+    assert_eq!(
+      json["script_state"]["data_properties"][0]["name"],
+      serde_json::json!("message")
+    );
+    assert_eq!(
+      json["template_state"]["mustache_expressions"][0]["content"],
+      serde_json::json!("message")
+    );
+    assert!(json["sfc_sections"]["template_content"].is_string());
+  }
+
+  #[test]
+  fn test_should_exclude_loop_variables_and_slot_props_from_identifiers() {
+    let sfc = r#"<template>
+    <ul>
+      <li v-for="(item, index) in items" :key="item.id" @click="select(index)">{{ item.name }}</li>
+    </ul>
+    <user-list>
+      <template #row="{ user, remove }">
+        <button @click="remove(user)">{{ user.name }}</button>
+      </template>
+    </user-list>
+    <Item v-for="entry in entries" :entry="entry" />
+    <p>{{ user }} {{ entry }}</p>
+    </template>
+    <script>
+    export default {};
+    </script>"#;
+
+    let model = parse_component(sfc).unwrap();
+    let template = &model.template_state;
+
+    assert_eq!(
+      template.identifiers,
+      vec!["items", "select", "entries", "user", "entry"]
+    );
+    assert_eq!(template.function_calls, vec!["select"]);
+    let names: Vec<&Vec<String>> = template.scopes.iter().map(|scope| &scope.names).collect();
+    assert_eq!(
+      names,
+      vec![
+        &vec!["item".to_string(), "index".to_string()],
+        &vec!["user".to_string(), "remove".to_string()],
+        &vec!["entry".to_string()],
+      ]
+    );
+  }
+
+  #[test]
+  fn test_should_capture_long_and_multi_line_mustaches_once() {
+    let long_sum = (0..300)
+      .map(|i| format!("value{}", i))
+      .collect::<Vec<_>>()
+      .join(" + ");
+    let sfc = format!(
+      r#"<template>
+    <p>{{{{ {} }}}} and {{{{
+      items
+        .filter((item) => item.active)
+        .length
+    }}}}</p>
+    </template>
+    <script>
+    export default {{}};
+    </script>"#,
+      long_sum
+    );
+
+    let model = parse_component(&sfc).unwrap();
+    let template = &model.template_state;
+
+    assert_eq!(template.mustache_expressions.len(), 2);
+    assert_eq!(template.mustache_expressions[0].content, long_sum);
+    assert!(template.mustache_expressions[1]
+      .content
+      .starts_with("items\n"));
+    assert_eq!(template.identifiers.len(), 302);
+    assert_eq!(template.identifiers[299], "value299");
+    assert_eq!(template.identifiers[300..], ["items", "item"]);
+  }
+}
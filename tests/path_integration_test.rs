@@ -1,4 +1,5 @@
-use vue_options_to_composition::rewrite_sfc;
+use std::collections::HashMap;
+use vue_options_to_composition::{rewrite_sfc, rewrite_sfc_with_report, AssetMode, RewriteOptions};
 
 fn trim_whitespace(s: &str) -> String {
   s.lines()
@@ -42,6 +43,238 @@ export default {
   <img src="@/assets/logo.svg?url" alt="Logo" />
 </template>
 <script setup>
+</script>"#;
+
+    assert_eq!(trim_whitespace(&result), trim_whitespace(expected));
+  }
+
+  #[test]
+  fn test_should_rewrite_configured_aliases() {
+    let sfc = r#"
+<template>
+  <Card>
+    <img src="~/assets/logo.png" alt="Logo" />
+  </Card>
+</template>
+<script>
+import api from '~~/lib/api'
+import Card from '~/components/Card.vue'
+
+export default {
+  components: { Card },
+  methods: {
+    async load() {
+      const { format } = await import('~/utils/format')
+      return format(await api.get())
+    }
+  }
+}
+</script>
+<style>
+.hero { background: url(~/assets/hero.png); }
+</style>"#;
+
+    let options = RewriteOptions {
+      aliases: Some(HashMap::from([
+        ("~~/".to_string(), "#/".to_string()),
+        ("~/".to_string(), "@/".to_string()),
+      ])),
+      ..Default::default()
+    };
+    let result = rewrite_sfc(sfc, Some(options)).unwrap();
+
+    assert!(result.contains(r#"<img src="@/assets/logo.png""#));
+    assert!(result.contains("import api from '#/lib/api';"));
+    assert!(result.contains("import Card from '@/components/Card.vue';"));
+    assert!(result.contains("await import('@/utils/format')"));
+    assert!(result.contains("url(@/assets/hero.png)"));
+  }
+
+  #[test]
+  fn test_should_keep_paths_without_aliases() {
+    let sfc = r#"
+<template>
+  <Card>
+    <img src="~/assets/logo.png" alt="Logo" />
+  </Card>
+</template>
+<script>
+import Card from '~/components/Card.vue'
+
+export default {
+  components: { Card },
+  data() {
+    return { title: 'Logo' }
+  }
+}
+</script>"#;
+
+    let options = RewriteOptions {
+      aliases: Some(HashMap::new()),
+      ..Default::default()
+    };
+    let result = rewrite_sfc(sfc, Some(options)).unwrap();
+
+    assert!(result.contains(r#"<img src="~/assets/logo.png""#));
+    assert!(result.contains("import Card from '~/components/Card.vue';"));
+  }
+
+  #[test]
+  fn test_should_hoist_required_assets_to_imports() {
+    let sfc = r#"
+<template>
+  <div :style="{ backgroundImage: `url(${require('~/assets/bg.jpg')})` }">
+    <img :src="require('~/assets/logo.png')" alt="Logo" />
+    <img :src="require('@/assets/icon.svg')" />
+  </div>
+</template>
+<script>
+export default {
+  data() {
+    return {
+      images: [require('~/assets/logo.png'), require('@/assets/other.webp')]
+    }
+  }
+}
+</script>
+<style>
+.hero { background: url(~assets/hero.png); }
+</style>"#;
+
+    let options = RewriteOptions {
+      asset_mode: Some(AssetMode::Import),
+      ..Default::default()
+    };
+    let result = rewrite_sfc(sfc, Some(options)).unwrap();
+
+    let expected = r#"
+<template>
+  <div :style="{ backgroundImage: `url(${imgBg})` }">
+    <img :src="imgLogo" alt="Logo" />
+    <img :src="imgIcon" />
+  </div>
+</template>
+<script setup>
+import { ref } from 'vue';
+import imgBg from '@/assets/bg.jpg';
+import imgLogo from '@/assets/logo.png';
+import imgIcon from '@/assets/icon.svg?url';
+import imgOther from '@/assets/other.webp';
+
+const images = ref([imgLogo, imgOther]);
+</script>
+<style>
+.hero { background: url(@/assets/hero.png); }
+</style>"#;
+
+    assert_eq!(trim_whitespace(&result), trim_whitespace(expected));
+  }
+
+  #[test]
+  fn test_should_hoist_required_assets_to_urls() {
+    let sfc = r#"
+<template>
+  <img :src="require('./logo.png')" alt="Logo" />
+</template>
+<script>
+export default {
+  methods: {
+    icon(name) {
+      return require(`./icons/${name}.svg`)
+    }
+  }
+}
+</script>"#;
+
+    let options = RewriteOptions {
+      asset_mode: Some(AssetMode::Url),
+      ..Default::default()
+    };
+    let report = rewrite_sfc_with_report(sfc, Some(options)).unwrap();
+
+    assert!(report.code.contains(r#"<img :src="imgLogo" alt="Logo" />"#));
+    assert!(report
+      .code
+      .contains("const imgLogo = new URL('./logo.png', import.meta.url).href;"));
+    let codes: Vec<&str> = report.diagnostics.iter().map(|d| d.code.as_str()).collect();
+    assert_eq!(codes, vec!["dynamic-asset-require"]);
+  }
+
+  #[test]
+  fn test_should_report_script_requires_in_path_mode() {
+    let sfc = r#"
+<template>
+  <img :src="logo" alt="Logo" />
+</template>
+<script>
+export default {
+  data() {
+    return {
+      logo: require('~/assets/logo.png')
+    }
+  }
+}
+</script>"#;
+
+    let report = rewrite_sfc_with_report(sfc, None).unwrap();
+
+    assert!(report
+      .code
+      .contains("const logo = ref(require('@/assets/logo.png'));"));
+    let codes: Vec<&str> = report.diagnostics.iter().map(|d| d.code.as_str()).collect();
+    assert_eq!(codes, vec!["asset-require"]);
+  }
+
+  #[test]
+  fn test_should_share_hoisted_assets_between_references() {
+    let sfc = r#"
+<template>
+  <div>
+    <img :src="require('~/assets/logo.png')" alt="Logo" />
+    <img src="~/assets/logo.png" />
+    <img src="@/assets/hero-banner@2x.jpg" />
+    <img src="@/assets/hero-banner@2x.jpg" />
+    <img src="~/assets/single.png" />
+    <img :src="require('~/assets/icons/logo.svg')" />
+  </div>
+</template>
+<script>
+export default {
+  data() {
+    return {
+      imgLogo: 'Logo',
+      files: [require('~/assets/logo.png'), require('~/assets/terms.pdf')]
+    }
+  }
+}
+</script>"#;
+
+    let options = RewriteOptions {
+      asset_mode: Some(AssetMode::Import),
+      ..Default::default()
+    };
+    let result = rewrite_sfc(sfc, Some(options)).unwrap();
+
+    let expected = r#"
+<template>
+  <div>
+    <img :src="imgLogo2" alt="Logo" />
+    <img :src="imgLogo2" />
+    <img :src="imgHeroBanner2x" />
+    <img :src="imgHeroBanner2x" />
+    <img src="@/assets/single.png" />
+    <img :src="imgLogo3" />
+  </div>
+</template>
+<script setup>
+import { ref } from 'vue';
+import imgLogo2 from '@/assets/logo.png';
+import imgHeroBanner2x from '@/assets/hero-banner@2x.jpg';
+import imgLogo3 from '@/assets/icons/logo.svg?url';
+import assetTerms from '@/assets/terms.pdf';
+
+const files = ref([imgLogo2, assetTerms]);
+const imgLogo = ref('Logo');
 </script>"#;
 
     assert_eq!(trim_whitespace(&result), trim_whitespace(expected));
@@ -0,0 +1,121 @@
+use vue_options_to_composition::{
+  apply_transformers, assemble_sfc, parse_component, parse_sfc_sections, rewrite_sfc,
+  FormatOptions, ReplacementScope, RewriteOptions, ScriptStyle, TemplateReplacement,
+  TransformerConfig,
+};
+
+const SFC: &str = r#"<template>
+  <div>
+    <my-counter :count="count" @click="increment">{{ $t('count', { count }) }}</my-counter>
+  </div>
+</template>
+<script>
+import MyCounter from '@/components/MyCounter.vue';
+
+export default {
+  components: { MyCounter },
+  data() {
+    return {
+      count: 0,
+    };
+  },
+  methods: {
+    increment() {
+      this.count++;
+    },
+  },
+};
+</script>
+<style scoped>
+div { color: red; }
+</style>"#;
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use pretty_assertions::assert_eq;
+
+  #[test]
+  fn test_should_rewrite_like_rewrite_sfc_when_run_pass_by_pass() {
+    for script_style in [ScriptStyle::SetupSugar, ScriptStyle::SetupFunction] {
+      let options = RewriteOptions {
+        script_style: Some(script_style),
+        ..Default::default()
+      };
+
+      let context = parse_component(SFC).unwrap();
+      let config = TransformerConfig::from(options.clone());
+      let result = apply_transformers(&context, &config);
+      let report =
+        assemble_sfc(result, &context.sfc_sections, &FormatOptions::from(&config)).unwrap();
+
+      assert_eq!(report.code, rewrite_sfc(SFC, Some(options)).unwrap());
+    }
+  }
+
+  #[test]
+  fn test_should_assemble_from_the_result_without_parsing_the_script_again() {
+    let options = RewriteOptions {
+      script_style: Some(ScriptStyle::SetupFunction),
+      ..Default::default()
+    };
+    let context = parse_component(SFC).unwrap();
+    let config = TransformerConfig::from(options.clone());
+    let result = apply_transformers(&context, &config);
+
+    // The registered components and the references of the original script come with the result
+    let mut sections = context.sfc_sections.clone();
+    sections.script_content = Some("export default {".to_string());
+    let report = assemble_sfc(result, &sections, &FormatOptions::from(&config)).unwrap();
+
+    assert_eq!(report.code, rewrite_sfc(SFC, Some(options)).unwrap());
+  }
+
+  #[test]
+  fn test_should_run_custom_steps_between_the_passes() {
+    // Before parsing: a change to the template of the component
+    let mut sections = parse_sfc_sections(SFC).unwrap();
+    sections.template_content = sections.template_content.map(|template| {
+      template
+        .replace("<div>", "<section>")
+        .replace("</div>", "</section>")
+    });
+    let context = parse_component(sections).unwrap();
+
+    // Before assembly: a template codemod applied with the replacements of the transformers
+    let config = TransformerConfig::from(RewriteOptions::default());
+    let mut result = apply_transformers(&context, &config);
+    result.template_replacements.push(TemplateReplacement {
+      find: "my-counter".to_string(),
+      replace: "MyCounter".to_string(),
+      scope: ReplacementScope::Template,
+    });
+
+    let report =
+      assemble_sfc(result, &context.sfc_sections, &FormatOptions::from(&config)).unwrap();
+
+    let expected = r#"<template>
+<section>
+    <MyCounter :count="count" @click="increment">{{ t('count', { count }) }}</MyCounter>
+  </section>
+</template>
+<script setup>
+import { ref } from 'vue';
+import MyCounter from '@/components/MyCounter.vue';
+import { useI18n } from 'vue-i18n';
+
+const { t } = useI18n();
+
+const count = ref(0);
+
+const increment = () => {
+  count.value++;
+};
+</script>
+<style scoped>
+div { color: red; }
+</style>"#;
+    assert_eq!(report.code, expected);
+    assert!(report.diagnostics.is_empty());
+  }
+}
@@ -0,0 +1,172 @@
+use vue_options_to_composition::project::{
+  component_tag_names, ref_member_uses, rewrite_component_listeners, RefMemberUse,
+};
+use vue_options_to_composition::{rewrite_sfc_with_report, ExposeConfig, RewriteOptions};
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use pretty_assertions::assert_eq;
+
+  #[test]
+  fn test_should_report_renamed_events() {
+    let sfc = r#"<template><input :value="value" @input="$emit('input', $event.target.value)" /></template>
+    <script>
+    export default {
+      props: ['value'],
+      methods: {
+        clear() {
+          this.$emit('input', '');
+          this.$emit('cleared');
+        },
+      },
+    };
+    </script>"#;
+
+    let report = rewrite_sfc_with_report(sfc, None).unwrap();
+
+    assert_eq!(
+      report.event_renames,
+      vec![("input".to_string(), "update:value".to_string())]
+    );
+    assert!(report.code.contains("emit('update:value', '')"));
+  }
+
+  #[test]
+  fn test_should_derive_component_tag_names() {
+    assert_eq!(
+      component_tag_names("UserCard"),
+      vec!["UserCard".to_string(), "user-card".to_string()]
+    );
+    assert_eq!(
+      component_tag_names("user-card"),
+      vec!["UserCard".to_string(), "user-card".to_string()]
+    );
+    assert_eq!(
+      component_tag_names("input"),
+      vec!["Input".to_string(), "input".to_string()]
+    );
+  }
+
+  #[test]
+  fn test_should_rewrite_listeners_on_renamed_component() {
+    let sfc = r#"<template>
+  <div>
+    <UserCard :value="name" @input="name = $event" />
+    <user-card v-on:input.native="onInput" @cleared="reset" />
+    <input @input="onRawInput" />
+    <OtherCard @input="onOther" />
+  </div>
+</template>
+
+<script setup>
+const label = '<UserCard @input="x" />';
+</script>"#;
+
+    let (output, applied) = rewrite_component_listeners(
+      sfc,
+      &component_tag_names("UserCard"),
+      &[("input".to_string(), "update:value".to_string())],
+    );
+
+    let expected = r#"<template>
+  <div>
+    <UserCard :value="name" @update:value="name = $event" />
+    <user-card v-on:update:value.native="onInput" @cleared="reset" />
+    <input @input="onRawInput" />
+    <OtherCard @input="onOther" />
+  </div>
+</template>
+
+<script setup>
+const label = '<UserCard @input="x" />';
+</script>"#;
+
+    assert_eq!(output, expected);
+    assert_eq!(applied.len(), 2);
+    assert_eq!(applied[1].component, "user-card");
+  }
+
+  #[test]
+  fn test_should_not_touch_attribute_values() {
+    let sfc = r#"<template><UserCard title="@input is renamed" @input="save" /></template>"#;
+
+    let (output, applied) = rewrite_component_listeners(
+      sfc,
+      &component_tag_names("UserCard"),
+      &[("input".to_string(), "update:value".to_string())],
+    );
+
+    assert_eq!(
+      output,
+      r#"<template><UserCard title="@input is renamed" @update:value="save" /></template>"#
+    );
+    assert_eq!(applied.len(), 1);
+  }
+
+  #[test]
+  fn test_should_find_members_used_through_refs() {
+    let sfc = r#"<template>
+  <div>
+    <ds-modal ref="modal" />
+    <UserForm ref="form" />
+    <input ref="input">
+    <button @click="$refs.modal.close()">Close</button>
+  </div>
+</template>
+<script>
+import DsModal from '@/components/DsModal.vue'
+import UserForm from './UserForm'
+export default {
+  components: { DsModal, UserForm },
+  mounted() {
+    this.$refs.modal.open()
+    this.$refs['form']?.validate()
+    this.$refs.input.focus()
+    this.$refs.modal.$el.scrollIntoView()
+  }
+}
+</script>"#;
+
+    assert_eq!(
+      ref_member_uses(sfc),
+      vec![
+        RefMemberUse {
+          source: "@/components/DsModal.vue".to_string(),
+          members: vec!["open".to_string(), "close".to_string()],
+        },
+        RefMemberUse {
+          source: "./UserForm".to_string(),
+          members: vec!["validate".to_string()],
+        },
+      ]
+    );
+  }
+
+  #[test]
+  fn test_should_expose_members_used_by_parents() {
+    let sfc = r#"<script>
+export default {
+  methods: {
+    open() {},
+    close() {},
+  },
+};
+</script>"#;
+
+    let options = RewriteOptions {
+      expose: Some(ExposeConfig {
+        members: vec!["open".to_string(), "reset".to_string()],
+        ..Default::default()
+      }),
+      ..Default::default()
+    };
+    let report = rewrite_sfc_with_report(sfc, Some(options)).unwrap();
+
+    assert!(report.code.contains("defineExpose({ open });"));
+    assert!(report
+      .diagnostics
+      .iter()
+      .any(|diagnostic| diagnostic.code == "expose-unknown-member"));
+  }
+}
@@ -0,0 +1,70 @@
+use serde_json::{json, Value};
+use vue_options_to_composition::server::{Server, INVALID_PARAMS, METHOD_NOT_FOUND, PARSE_ERROR};
+
+fn response(server: &mut Server, request: Value) -> Value {
+  serde_json::from_str(&server.handle(&request.to_string()).unwrap()).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use pretty_assertions::assert_eq;
+
+  #[test]
+  fn test_should_answer_rewrite_requests() {
+    let mut server = Server::new(None);
+    let sfc = "<template>\n<p>{{ count }}</p>\n</template>\n<script>\nexport default {\n  data() {\n    return { count: 0 };\n  },\n  mounted() {\n    this.$children.forEach((child) => child.reset());\n  },\n};\n</script>";
+
+    let reply = response(
+      &mut server,
+      json!({ "jsonrpc": "2.0", "id": 1, "method": "rewrite", "params": { "sfc": sfc } }),
+    );
+
+    assert_eq!(reply["id"], json!(1));
+    assert!(reply["result"]["code"]
+      .as_str()
+      .unwrap()
+      .contains("const count = ref(0);"));
+    assert_eq!(
+      reply["result"]["diagnostics"][0]["code"],
+      json!("compat-children")
+    );
+    assert_eq!(
+      reply["result"]["diagnostics"][0]["severity"],
+      json!("warning")
+    );
+  }
+
+  #[test]
+  fn test_should_report_protocol_errors_and_shut_down() {
+    let mut server = Server::new(None);
+
+    let reply: Value = serde_json::from_str(&server.handle("{").unwrap()).unwrap();
+    assert_eq!(reply["error"]["code"], json!(PARSE_ERROR));
+
+    let reply = response(
+      &mut server,
+      json!({ "jsonrpc": "2.0", "id": 2, "method": "format" }),
+    );
+    assert_eq!(reply["error"]["code"], json!(METHOD_NOT_FOUND));
+
+    let reply = response(
+      &mut server,
+      json!({ "jsonrpc": "2.0", "id": 3, "method": "rewrite", "params": { "sfc": "", "options": { "target": "vue4" } } }),
+    );
+    assert_eq!(reply["id"], json!(3));
+    assert_eq!(reply["error"]["code"], json!(INVALID_PARAMS));
+
+    // Notifications are not answered
+    let notification = json!({ "jsonrpc": "2.0", "method": "rewrite", "params": { "sfc": "" } });
+    assert_eq!(server.handle(&notification.to_string()), None);
+
+    assert!(!server.is_shut_down());
+    let reply = response(
+      &mut server,
+      json!({ "jsonrpc": "2.0", "id": 4, "method": "shutdown" }),
+    );
+    assert_eq!(reply["result"], Value::Null);
+    assert!(server.is_shut_down());
+  }
+}
@@ -0,0 +1,166 @@
+use vue_options_to_composition::store_modules::convert_store_module;
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use pretty_assertions::assert_eq;
+
+  #[test]
+  fn test_should_convert_store_module() {
+    let source = r#"import api from '@/api';
+
+const TAX = 0.2;
+
+const state = () => ({
+  items: [],
+  loading: false
+});
+
+const getters = {
+  count: (state) => state.items.length,
+  total(state, getters) {
+    return getters.subtotal * (1 + TAX);
+  },
+  subtotal: (state) => state.items.reduce((sum, item) => sum + item.price, 0)
+};
+
+const mutations = {
+  setItems(state, items) {
+    state.items = items;
+  },
+  setLoading: (state, loading) => (state.loading = loading)
+};
+
+const actions = {
+  async fetchItems({ commit, state }, params) {
+    commit('setLoading', true);
+    commit('setItems', await api.get('/cart', params));
+    commit('setLoading', false);
+    return state.items;
+  },
+  checkout({ dispatch }) {
+    dispatch('orders/create', null, { root: true });
+  },
+  clear: (context) => context.commit('setItems', [])
+};
+
+export default {
+  namespaced: true,
+  state,
+  getters,
+  mutations,
+  actions
+};
+"#;
+
+    let expected = r#"import { defineStore } from 'pinia';
+import api from '@/api';
+import { useOrdersStore } from '@/stores/orders';
+
+const TAX = 0.2;
+
+export const useCartStore = defineStore('cart', {
+  state: () => ({
+    items: [],
+    loading: false
+  }),
+
+  getters: {
+    count: (state) => state.items.length,
+    total(state) {
+      return this.subtotal * (1 + TAX);
+    },
+    subtotal: (state) => state.items.reduce((sum, item) => sum + item.price, 0),
+  },
+
+  actions: {
+    setItems(items) {
+      this.items = items;
+    },
+    setLoading(loading) {
+      this.loading = loading;
+    },
+    async fetchItems(params) {
+      this.setLoading(true);
+      this.setItems(await api.get('/cart', params));
+      this.setLoading(false);
+      return this.items;
+    },
+    checkout() {
+      useOrdersStore().create(null);
+    },
+    clear() {
+      return this.setItems([]);
+    },
+  },
+});
+"#;
+
+    let conversion = convert_store_module("cart", source).unwrap();
+
+    assert_eq!(conversion.name, "useCartStore");
+    assert_eq!(conversion.code, expected);
+    assert!(conversion.diagnostics.is_empty());
+  }
+
+  #[test]
+  fn test_should_convert_nuxt_store_module() {
+    let source = r#"export const state = () => ({
+  name: ''
+});
+
+export const getters = {
+  greeting: (state, getters, rootState) => `${rootState.locale.hello} ${state.name}`
+};
+
+export const mutations = {
+  setName(state, name) {
+    state.name = name;
+  }
+};
+
+export const actions = {
+  setName({ commit }, name) {
+    commit('setName', name.trim());
+  }
+};
+"#;
+
+    let expected = r#"import { defineStore } from 'pinia';
+
+export const useUserStore = defineStore('user', {
+  state: () => ({
+    name: ''
+  }),
+
+  getters: {
+    greeting: (state) => `${rootState.locale.hello} ${state.name}`,
+  },
+
+  actions: {
+    setNameMutation(name) {
+      this.name = name;
+    },
+    setName(name) {
+      this.setNameMutation(name.trim());
+    },
+  },
+});
+"#;
+
+    let conversion = convert_store_module("user", source).unwrap();
+    assert_eq!(conversion.code, expected);
+
+    let codes: Vec<&str> = conversion
+      .diagnostics
+      .iter()
+      .map(|diagnostic| diagnostic.code.as_str())
+      .collect();
+    assert_eq!(codes, vec!["store-root-access", "store-name-collision"]);
+  }
+
+  #[test]
+  fn test_should_skip_files_without_module_sections() {
+    assert!(convert_store_module("api", "export const get = (url) => fetch(url);\n").is_none());
+  }
+}
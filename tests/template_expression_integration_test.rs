@@ -0,0 +1,84 @@
+use vue_options_to_composition::parse_component;
+use vue_options_to_composition::template_expression::{split_filters, FilterCall, VueExpression};
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use pretty_assertions::assert_eq;
+
+  #[test]
+  fn test_should_split_v_for_and_slot_declarations() {
+    let v_for = VueExpression::from_directive("v-for", "({ row }, index) of rows");
+    assert_eq!(v_for.expressions, vec!["rows"]);
+    assert_eq!(v_for.declarations, vec!["row", "index"]);
+
+    let renamed = VueExpression::from_directive("v-for", "{ id: key, label = fallback } in items");
+    assert_eq!(renamed.expressions, vec!["items"]);
+    assert_eq!(renamed.declarations, vec!["key", "label"]);
+
+    let slot = VueExpression::from_directive("#item", "{ item, index }");
+    assert!(slot.expressions.is_empty());
+    assert_eq!(slot.declarations, vec!["item", "index"]);
+
+    let slot_scope = VueExpression::from_directive("slot-scope", "props");
+    assert_eq!(slot_scope.declarations, vec!["props"]);
+  }
+
+  #[test]
+  fn test_should_split_filters() {
+    let mustache =
+      VueExpression::from_mustache("price * quantity | currency('EUR', digits) | upper");
+    assert_eq!(
+      mustache.expressions,
+      vec!["price * quantity", "['EUR', digits]"]
+    );
+    assert_eq!(
+      mustache.filters,
+      vec![
+        FilterCall {
+          name: "currency".to_string(),
+          arguments: "'EUR', digits".to_string(),
+        },
+        FilterCall {
+          name: "upper".to_string(),
+          arguments: String::new(),
+        },
+      ]
+    );
+
+    // Logical and bitwise operators, strings and regular expressions are not filters
+    assert_eq!(split_filters("a || b"), vec!["a || b"]);
+    assert_eq!(split_filters("'a|b' + `c|d`"), vec!["'a|b' + `c|d`"]);
+    assert_eq!(
+      split_filters("/a|b/.test(value)"),
+      vec!["/a|b/.test(value)"]
+    );
+    assert_eq!(
+      split_filters("fn(a | b) | round"),
+      vec!["fn(a | b) ", " round"]
+    );
+
+    let click = VueExpression::from_directive("@click", "flags = flags | 1");
+    assert!(click.filters.is_empty());
+    assert_eq!(click.expressions, vec!["flags = flags | 1"]);
+  }
+
+  #[test]
+  fn test_should_collect_template_filters_and_their_identifiers() {
+    let sfc = r#"<template>
+    <p :title="name | capitalize">{{ total | currency(locale) }}</p>
+    <tr v-for="({ row }) of rows" :key="row.id"><td>{{ row.label }}</td></tr>
+    </template>
+    <script>
+    export default {};
+    </script>"#;
+
+    let model = parse_component(sfc).unwrap();
+
+    assert_eq!(model.template_state.filters, vec!["capitalize", "currency"]);
+    assert_eq!(
+      model.template_state.identifiers,
+      vec!["name", "rows", "total", "locale"]
+    );
+  }
+}
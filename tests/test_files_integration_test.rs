@@ -0,0 +1,79 @@
+use vue_options_to_composition::test_files::rewrite_test_file;
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use pretty_assertions::assert_eq;
+
+  #[test]
+  fn test_should_rewrite_local_vue_and_mounting_options() {
+    let source = r#"import { createLocalVue, mount } from '@vue/test-utils';
+import Vuex from 'vuex';
+import Button from './Button.vue';
+
+const localVue = createLocalVue();
+localVue.use(Vuex);
+
+describe('Button', () => {
+  it('renders', () => {
+    const wrapper = mount(Button, {
+      localVue,
+      propsData: { label: 'Go' },
+      mocks: { $t: (key) => key },
+      stubs: ['router-link'],
+    });
+    expect(wrapper.text()).toBe('Go');
+  });
+});
+"#;
+
+    let expected = r#"import { mount } from '@vue/test-utils';
+import Vuex from 'vuex';
+import Button from './Button.vue';
+
+describe('Button', () => {
+  it('renders', () => {
+    const wrapper = mount(Button, {
+      global: { plugins: [Vuex], mocks: { $t: (key) => key }, stubs: ['router-link'] },
+      props: { label: 'Go' },
+    });
+    expect(wrapper.text()).toBe('Go');
+  });
+});
+"#;
+
+    let rewrite = rewrite_test_file(source);
+    assert_eq!(rewrite.code, expected);
+    assert!(rewrite
+      .diagnostics
+      .iter()
+      .any(|diagnostic| diagnostic.code == "test-plugins"));
+  }
+
+  #[test]
+  fn test_should_report_vm_access() {
+    let source = r#"import { shallowMount } from '@vue/test-utils';
+import Form from './Form.vue';
+
+it('submits', async () => {
+  const wrapper = shallowMount(Form, { propsData: { id: 1 }, mocks: { $t: (k) => k } });
+  await wrapper.vm.submit();
+  expect(wrapper.vm.isLoading).toBe(false);
+});
+"#;
+
+    let rewrite = rewrite_test_file(source);
+    assert!(rewrite.code.contains(
+      "shallowMount(Form, { props: { id: 1 }, global: { mocks: { $t: (k) => k } } })"
+    ));
+
+    let vm_accesses: Vec<&str> = rewrite
+      .diagnostics
+      .iter()
+      .filter(|diagnostic| diagnostic.code == "test-vm-access")
+      .map(|diagnostic| diagnostic.message.as_str())
+      .collect();
+    assert_eq!(vm_accesses.len(), 2);
+    assert!(vm_accesses[0].contains("wrapper.vm.submit"));
+  }
+}
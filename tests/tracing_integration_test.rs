@@ -0,0 +1,118 @@
+#![cfg(feature = "tracing")]
+
+use std::sync::{Arc, Mutex};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+use vue_options_to_composition::rewrite_sfc;
+
+/// Records the spans created (with their `name` field) and the messages of events
+#[derive(Clone, Default)]
+struct Recorder {
+  spans: Arc<Mutex<Vec<String>>>,
+  events: Arc<Mutex<Vec<String>>>,
+}
+
+#[derive(Default)]
+struct Fields {
+  name: Option<String>,
+  message: Option<String>,
+}
+
+impl Visit for Fields {
+  fn record_str(&mut self, field: &Field, value: &str) {
+    if field.name() == "name" {
+      self.name = Some(value.to_string());
+    }
+  }
+
+  fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+    if field.name() == "message" {
+      self.message = Some(format!("{:?}", value));
+    }
+  }
+}
+
+impl Subscriber for Recorder {
+  fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+    true
+  }
+
+  fn new_span(&self, span: &Attributes<'_>) -> Id {
+    let mut fields = Fields::default();
+    span.record(&mut fields);
+    let mut spans = self.spans.lock().unwrap();
+    spans.push(match fields.name {
+      Some(name) => format!("{}:{}", span.metadata().name(), name),
+      None => span.metadata().name().to_string(),
+    });
+    Id::from_u64(spans.len() as u64)
+  }
+
+  fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+  fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+  fn event(&self, event: &Event<'_>) {
+    let mut fields = Fields::default();
+    event.record(&mut fields);
+    if let Some(message) = fields.message {
+      self.events.lock().unwrap().push(message);
+    }
+  }
+
+  fn enter(&self, _span: &Id) {}
+
+  fn exit(&self, _span: &Id) {}
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use pretty_assertions::assert_eq;
+
+  #[test]
+  fn test_should_trace_phases_and_transformers() {
+    let sfc = r#"<template>
+    <p>{{ $t('count') }}: {{ count }}</p>
+    </template>
+    <script>
+    export default {
+      data() {
+        return { count: 0 };
+      },
+    };
+    </script>"#;
+
+    let recorder = Recorder::default();
+    tracing::subscriber::with_default(recorder.clone(), || rewrite_sfc(sfc, None).unwrap());
+
+    let spans = recorder.spans.lock().unwrap().clone();
+    for phase in [
+      "rewrite_sfc_with_report",
+      "parse_component",
+      "parse_sfc_sections",
+      "parse_script_section",
+      "parse_template_section",
+      "transform",
+      "transformer:composition",
+      "merge",
+      "generate",
+      "template",
+    ] {
+      assert!(
+        spans.iter().any(|span| span == phase),
+        "no `{}` span",
+        phase
+      );
+    }
+
+    let events = recorder.events.lock().unwrap().clone();
+    assert!(events.iter().any(|event| event == "transformer applied"));
+    assert!(events.iter().any(|event| event == "template replacement"));
+    assert_eq!(
+      spans.first().map(String::as_str),
+      Some("rewrite_sfc_with_report")
+    );
+  }
+}
@@ -1,4 +1,8 @@
-use vue_options_to_composition::rewrite_sfc;
+use vue_options_to_composition::{
+  rewrite_sfc, rewrite_sfc_with_report, DatePluginConfig, EmitMappingConfig, EventBusConfig,
+  RewriteOptions, Target, TemplateRefStyle,
+};
+use std::collections::HashMap;
 
 fn trim_whitespace(s: &str) -> String {
   s.lines()
@@ -271,6 +275,138 @@ onMounted(() => {
     assert_eq!(trim_whitespace(&result), trim_whitespace(expected));
   }
 
+  #[test]
+  fn test_should_handle_set_and_delete_on_any_target() {
+    let sfc = r#"<template>
+<p>{{ form.errors }}</p>
+</template>
+<script>
+export default {
+  props: {
+    options: { type: Object, required: true }
+  },
+  data() {
+    return {
+      form: { errors: {} },
+      items: []
+    };
+  },
+  methods: {
+    fail(entry, field) {
+      this.$set(this.form.errors, field, this.message(field, { required: true }));
+      this.$set(this.items, this.items.length - 1, entry);
+      this.$set(this.options, 'last-field', field);
+      this.$delete(entry, 'error');
+      this.$axios.$delete('/api/errors');
+    },
+    message(field) {
+      return field;
+    }
+  }
+}
+</script>"#;
+
+    let result = rewrite_sfc(sfc, None).unwrap();
+
+    let expected = r#"
+<template>
+  <p>{{ form.errors }}</p>
+</template>
+<script setup>
+import { ref } from 'vue';
+import { useHttp } from '@/composables/useHttp';
+
+const http = useHttp();
+const props = defineProps({
+  options: {
+    type: Object,
+    required: true,
+  },
+});
+
+const form = ref({ errors: {} });
+const items = ref([]);
+
+const fail = (entry, field) => {
+  form.value.errors[field] = message(field, { required: true });
+  items.value[items.value.length - 1] = entry;
+  props.options['last-field'] = field;
+  delete entry.error;
+  http.delete('/api/errors').then((response) => response.data);
+};
+const message = (field) => {
+  return field;
+};
+</script>"#;
+
+    assert_eq!(trim_whitespace(&result), trim_whitespace(expected));
+  }
+
+  #[test]
+  fn test_should_split_set_and_delete_arguments_on_the_parsed_call() {
+    let sfc = r#"<template>
+<p>{{ rules }} {{ filters }}</p>
+</template>
+<script>
+import Vue from 'vue';
+
+const labels = {};
+Vue.set(labels, 'separator', /,\s*(?=\))/);
+Vue.set(labels, /* the key ) */ 'empty', `${labels.none ? `)` : ''}`);
+Vue.delete(labels, `${'a'}, ${'b'}`);
+
+export default {
+  data() {
+    return {
+      rules: Vue.observable({ separator: /[,)]/, label: `${'('}` }),
+      filters: {}
+    };
+  },
+  methods: {
+    update(key, values) {
+      this.$set(this.filters, `${key}, ${values.map((v) => `(${v})`).join(', ')}`, values);
+      this.$set(this.filters, /* the key, as typed ) */ key, values);
+      this.$delete(this.filters, /* the old key ( */ `${key}, old`);
+      this.log('this.$set(a, b)');
+    },
+    log(message) {
+      return message;
+    }
+  }
+}
+</script>"#;
+
+    let result = rewrite_sfc(sfc, None).unwrap();
+
+    let expected = r#"
+<template>
+  <p>{{ rules }} {{ filters }}</p>
+</template>
+<script setup>
+import { reactive, ref } from 'vue';
+
+const labels = {};
+labels.separator = /,\s*(?=\))/;
+labels[/* the key ) */ 'empty'] = `${labels.none ? `)` : ''}`;
+delete labels[`${'a'}, ${'b'}`];
+
+const filters = ref({});
+const rules = reactive({ separator: /[,)]/, label: `${'('}` });
+
+const update = (key, values) => {
+  filters.value[`${key}, ${values.map((v) => `(${v})`).join(', ')}`] = values;
+  filters.value[/* the key, as typed ) */ key] = values;
+  delete filters.value[/* the old key ( */ `${key}, old`];
+  log('this.$set(a, b)');
+};
+const log = (message) => {
+  return message;
+};
+</script>"#;
+
+    assert_eq!(trim_whitespace(&result), trim_whitespace(expected));
+  }
+
   #[test]
   fn test_should_handle_all_lifecycle_hooks() {
     let sfc = r#"<template><h1>{{ title }}</h1></template>
@@ -357,6 +493,123 @@ onDeactivated(() => {
     assert_eq!(trim_whitespace(&result), trim_whitespace(expected));
   }
 
+  #[test]
+  fn test_should_keep_lifecycle_hooks_async() {
+    let sfc = r#"<template><p>{{ user }}</p></template>
+<script>
+export default {
+  data() {
+    return { user: null };
+  },
+  async created() {
+    this.user = await fetchUser();
+  },
+  async mounted() {
+    await this.$nextTick();
+    console.log('ready');
+  },
+  beforeDestroy() {
+    console.log('leaving');
+  },
+  async beforeUnmount() {
+    await save(this.user);
+  },
+};
+</script>"#;
+
+    let result = rewrite_sfc(sfc, None).unwrap();
+
+    let expected = r#"<template>
+<p>{{ user }}</p>
+</template>
+<script setup>
+import { nextTick, onBeforeUnmount, onMounted, ref } from 'vue';
+
+const user = ref(null);
+
+(async () => {
+  user.value = await fetchUser();
+})();
+
+onMounted(async () => {
+  await nextTick();
+  console.log('ready');
+});
+
+onBeforeUnmount(() => {
+  console.log('leaving');
+});
+
+onBeforeUnmount(async () => {
+  await save(user.value);
+});
+</script>"#;
+
+    assert_eq!(trim_whitespace(&result), trim_whitespace(expected));
+  }
+
+  #[test]
+  fn test_should_handle_lifecycle_hooks_in_property_form() {
+    let sfc = r#"<template><p>{{ ready }}</p></template>
+<script>
+export default {
+  data() {
+    return { ready: false };
+  },
+  created: async function () {
+    await this.load();
+  },
+  mounted: () => {
+    console.log('mounted');
+  },
+  beforeDestroy: () => console.log('async leaving'),
+  updated() {
+    const asyncTask = () => 'async';
+    asyncTask();
+  },
+  methods: {
+    async load() {
+      this.ready = true;
+    },
+  },
+};
+</script>"#;
+
+    let result = rewrite_sfc(sfc, None).unwrap();
+
+    let expected = r#"<template>
+<p>{{ ready }}</p>
+</template>
+<script setup>
+import { onBeforeUnmount, onMounted, onUpdated, ref } from 'vue';
+
+const ready = ref(false);
+
+const load = async () => {
+  ready.value = true;
+};
+
+(async () => {
+  await load();
+})();
+
+onMounted(() => {
+  console.log('mounted');
+});
+
+onUpdated(() => {
+  const asyncTask = () => 'async';
+  asyncTask();
+});
+
+onBeforeUnmount(() => {
+  console.log('async leaving');
+});
+</script>"#;
+
+    assert_eq!(trim_whitespace(&result), trim_whitespace(expected));
+  }
+
   #[test]
   fn test_should_handle_this_in_methods() {
     let sfc = r#"<<template><h1 @click="$emit('send-it')">Hello</h1></template>
@@ -439,36 +692,31 @@ const notify = () => {
   }
 
   #[test]
-  fn test_should_handle_next_tick() {
-    let sfc = r#"<template><h1>{{ title }}</h1></template>
-    <script>
-    export default {
-      data() {
-        return {
-          title: 'Hello world'
-        };
-      },
-      mounted() {
-        this.$nextTick(() => {
-          console.log('Next tick executed');
-        });
-      }
-    }
-    </script>"#;
+  fn test_should_convert_array_shorthand_props() {
+    let sfc = r#"<template><input :title="title" :value="value" @input="$emit('input', $event.target.value)" /></template>
+<script>
+export default {
+  props: ['title', 'value'],
+  computed: {
+    isEmpty() {
+      return !this.value;
+    },
+  },
+};
+</script>"#;
 
-    let expected = r#"
-<template>
-  <h1>{{ title }}</h1>
+    let expected = r#"<template>
+<input :title="title" :value="value" @input="emit('update:value', $event.target.value)" />
 </template>
 <script setup>
-import { nextTick, onMounted, ref } from 'vue';
+import { computed } from 'vue';
 
-const title = ref('Hello world');
+const props = defineProps(['title', 'value']);
 
-onMounted(() => {
-  nextTick(() => {
-    console.log('Next tick executed');
-  });
+const emit = defineEmits(['update:value']);
+
+const isEmpty = computed(() => {
+  return !props.value;
 });
 </script>"#;
 
@@ -478,53 +726,1168 @@ onMounted(() => {
   }
 
   #[test]
-  fn test_should_handle_template_refs() {
-    let sfc = r##"<template>
-    <a href="#">anchor</a>
-    <h1 ref="titleRef">{{ title }}</h1>
-    <div ref="cat-row"></div>
-    </template>
-    <script>
-    export default {
-      data() {
-        return {
-          title: 'Hello world'
-        };
-      },
-      mounted() {
-        if (this.$refs?.titleRef) {
-          console.log(this.$refs.titleRef);
-        }
+  fn test_should_keep_prop_type_unions_and_prop_type_casts() {
+    let sfc = r#"<template><p>{{ item.name }} {{ id }}</p></template>
+<script lang="ts">
+import Vue from 'vue';
+import { CartItem } from '@/types/cart';
 
-        console.log('Alternative syntax', this.$refs['cat-row']);
-      }
-    }
-    </script>"##;
+export default Vue.extend({
+  props: {
+    id: [String, Number],
+    item: {
+      type: Object as PropType<CartItem>,
+      required: true,
+    },
+  },
+});
+</script>"#;
 
-    let expected = r##"
-<template>
-  <a href="#">anchor</a>
-  <h1 ref="titleRef">{{ title }}</h1>
-  <div ref="cat-row"></div>
+    let expected = r#"<template>
+<p>{{ item.name }} {{ id }}</p>
 </template>
-<script setup>
-import { onMounted, ref, useTemplateRef } from 'vue';
+<script setup lang="ts">
+import { PropType } from 'vue';
+import { CartItem } from '@/types/cart';
 
-const titleRef = useTemplateRef('titleRef');
-const catRowRef = useTemplateRef('cat-row');
-const title = ref('Hello world');
+const props = defineProps({
+  id: {
+    type: [String, Number],
+  },
+  item: {
+    type: Object as PropType<CartItem>,
+    required: true,
+  },
+});
+</script>"#;
 
-onMounted(() => {
-  if (titleRef.value) {
-    console.log(titleRef.value);
+    let result = rewrite_sfc(sfc, None).unwrap();
+
+    assert_eq!(trim_whitespace(&result), trim_whitespace(expected));
   }
 
-  console.log('Alternative syntax', catRowRef.value);
+  #[test]
+  fn test_should_keep_prop_type_casts_sharing_a_line_with_other_options() {
+    let sfc = r#"<template><p>{{ cfg.name }} {{ id }}</p></template>
+<script lang="ts">
+import Vue from 'vue';
+import { CartItem } from '@/types/cart';
+
+export default Vue.extend({
+  props: {
+    cfg: { type: Object as PropType<CartItem>, default: () => ({}) },
+    id: { type: [String, Number] as PropType<string | number>, required: true },
+    onSelect: Function as PropType<(item: CartItem) => void>,
+  },
 });
-</script>"##;
+</script>"#;
+
+    let expected = r#"<template>
+<p>{{ cfg.name }} {{ id }}</p>
+</template>
+<script setup lang="ts">
+import { PropType } from 'vue';
+import { CartItem } from '@/types/cart';
+
+const props = defineProps({
+  cfg: {
+    type: Object as PropType<CartItem>,
+    default: () => ({}),
+  },
+  id: {
+    type: [String, Number] as PropType<string | number>,
+    required: true,
+  },
+  onSelect: {
+    type: Function as PropType<(item: CartItem) => void>,
+  },
+});
+</script>"#;
 
     let result = rewrite_sfc(sfc, None).unwrap();
 
     assert_eq!(trim_whitespace(&result), trim_whitespace(expected));
   }
+
+  #[test]
+  fn test_should_keep_prop_validators_and_wrap_literal_defaults() {
+    let sfc = r#"<template><p>{{ items.length }}</p></template>
+<script>
+export default {
+  props: {
+    items: {
+      type: Array,
+      default: [],
+    },
+    options: {
+      type: Object,
+      default: { dense: false },
+    },
+    size: {
+      type: String,
+      default: 'md',
+      validator: (value) => ['sm', 'md', 'lg'].includes(value),
+    },
+    labels: {
+      type: Object,
+      default() {
+        return { empty: this.emptyLabel };
+      },
+    },
+  },
+};
+</script>"#;
+
+    let expected = r#"<template>
+<p>{{ items.length }}</p>
+</template>
+<script setup>
+const props = defineProps({
+  items: {
+    type: Array,
+    default: () => [],
+  },
+  options: {
+    type: Object,
+    default: () => ({ dense: false }),
+  },
+  size: {
+    type: String,
+    default: 'md',
+    validator: (value) => ['sm', 'md', 'lg'].includes(value),
+  },
+  labels: {
+    type: Object,
+    default: () => {
+      return { empty: this.emptyLabel };
+    },
+  },
+});
+</script>"#;
+
+    let report = rewrite_sfc_with_report(sfc, None).unwrap();
+
+    assert_eq!(trim_whitespace(&report.code), trim_whitespace(expected));
+    assert!(report
+      .diagnostics
+      .iter()
+      .any(|diagnostic| diagnostic.code == "prop-default-this"
+        && diagnostic.message.contains("`labels`")));
+  }
+
+  #[test]
+  fn test_should_rewrite_this_in_prop_defaults_and_validators() {
+    let sfc = r#"<template><p>{{ label }}</p></template>
+<script>
+export default {
+  props: {
+    size: { type: String, default: 'md' },
+    label: {
+      type: String,
+      default() {
+        return this.$t('labels.default', { size: this.size });
+      },
+    },
+    max: {
+      type: Number,
+      default: function () { return this.size === 'lg' ? 10 : 5; },
+      validator(value) {
+        return value > 0 && value < this.limit;
+      },
+    },
+    min: {
+      type: Number,
+      validator: (value) => value <= this.max,
+    },
+  },
+};
+</script>"#;
+
+    let expected = r#"<template>
+<p>{{ label }}</p>
+</template>
+<script setup>
+import { useI18n } from 'vue-i18n';
+
+const props = defineProps({
+  size: {
+    type: String,
+    default: 'md',
+  },
+  label: {
+    type: String,
+    default: (props) => {
+      return useI18n().t('labels.default', { size: props.size });
+    },
+  },
+  max: {
+    type: Number,
+    default: function (props) { return props.size === 'lg' ? 10 : 5; },
+    validator: (value) => {
+      return value > 0 && value < this.limit;
+    },
+  },
+  min: {
+    type: Number,
+    validator: (value, props) => value <= props.max,
+  },
+});
+</script>"#;
+
+    let report = rewrite_sfc_with_report(sfc, None).unwrap();
+
+    assert_eq!(trim_whitespace(&report.code), trim_whitespace(expected));
+    assert_eq!(
+      report
+        .diagnostics
+        .iter()
+        .map(|d| d.message.as_str())
+        .collect::<Vec<_>>(),
+      vec!["The validator of prop `max` references `this.limit`, which is not available in Vue 3. Only other props (read from the props argument) and i18n functions are rewritten, move the rest to a constant or the parent"]
+    );
+  }
+
+  #[test]
+  fn test_should_apply_configured_emit_mapping_per_component() {
+    let sfc = r#"<template>
+  <select :value="value" @change="$emit('change', $event.target.value)">
+    <option v-for="option in options" :key="option.value" :value="option.value">{{ option.label }}</option>
+  </select>
+</template>
+<script>
+export default {
+  name: 'DsSelect',
+  props: {
+    value: String,
+    options: Array,
+  },
+  methods: {
+    clear() {
+      if (this.value) {
+        this.$emit('input', null);
+      }
+    },
+  },
+};
+</script>"#;
+
+    let expected = r#"<template>
+<select :value="modelValue" @change="emit('update:modelValue', $event.target.value)">
+<option v-for="option in options" :key="option.value" :value="option.value">{{ option.label }}</option>
+</select>
+</template>
+<script setup>
+const props = defineProps({
+  modelValue: {
+    type: String,
+  },
+  options: {
+    type: Array,
+  },
+});
+
+const emit = defineEmits(['update:modelValue']);
+
+const clear = () => {
+  if (props.modelValue) {
+    emit('update:modelValue', null);
+  }
+};
+</script>"#;
+
+    let options = RewriteOptions {
+      emit_mapping: Some(EmitMappingConfig {
+        events: HashMap::from([("input".to_string(), "update:modelValue".to_string())]),
+        model_prop: Some("modelValue".to_string()),
+        components: HashMap::from([(
+          "DsSelect".to_string(),
+          EmitMappingConfig {
+            events: HashMap::from([("change".to_string(), "update:modelValue".to_string())]),
+            ..Default::default()
+          },
+        )]),
+      }),
+      ..Default::default()
+    };
+
+    let report = rewrite_sfc_with_report(sfc, Some(options)).unwrap();
+
+    assert_eq!(trim_whitespace(&report.code), trim_whitespace(expected));
+    assert_eq!(
+      report.event_renames,
+      vec![
+        ("change".to_string(), "update:modelValue".to_string()),
+        ("input".to_string(), "update:modelValue".to_string()),
+      ]
+    );
+  }
+
+  #[test]
+  fn test_should_fall_back_to_untyped_emits_for_dynamic_event_names() {
+    let sfc = r#"<template><button @click="$emit(action, item)">Go</button></template>
+<script>
+export default {
+  props: {
+    action: String,
+    item: Object,
+  },
+  methods: {
+    notify(eventName, payload) {
+      this.$emit(eventName, { ...payload, at: Date.now() });
+      this.$emit('notified');
+    },
+  },
+};
+</script>"#;
+
+    let expected = r#"
+<template>
+<button @click="emit(action, item)">Go</button>
+</template>
+<script setup>
+const props = defineProps({
+  action: {
+    type: String,
+  },
+  item: {
+    type: Object,
+  },
+});
+
+const emit = defineEmits();
+
+const notify = (eventName, payload) => {
+  emit(eventName, { ...payload, at: Date.now() });
+  emit('notified');
+};
+</script>"#;
+
+    let report = rewrite_sfc_with_report(sfc, None).unwrap();
+
+    assert_eq!(trim_whitespace(&report.code), trim_whitespace(expected));
+    let diagnostic = report
+      .diagnostics
+      .iter()
+      .find(|diagnostic| diagnostic.code == "dynamic-emit")
+      .unwrap();
+    assert!(diagnostic.message.contains("`action` in the template"));
+    assert!(diagnostic.message.contains("`eventName` in method `notify`"));
+  }
+
+  #[test]
+  fn test_should_handle_next_tick() {
+    let sfc = r#"<template><h1>{{ title }}</h1></template>
+    <script>
+    export default {
+      data() {
+        return {
+          title: 'Hello world'
+        };
+      },
+      mounted() {
+        this.$nextTick(() => {
+          console.log('Next tick executed');
+        });
+      }
+    }
+    </script>"#;
+
+    let expected = r#"
+<template>
+  <h1>{{ title }}</h1>
+</template>
+<script setup>
+import { nextTick, onMounted, ref } from 'vue';
+
+const title = ref('Hello world');
+
+onMounted(() => {
+  nextTick(() => {
+    console.log('Next tick executed');
+  });
+});
+</script>"#;
+
+    let result = rewrite_sfc(sfc, None).unwrap();
+
+    assert_eq!(trim_whitespace(&result), trim_whitespace(expected));
+  }
+
+  #[test]
+  fn test_should_handle_template_refs() {
+    let sfc = r##"<template>
+    <a href="#">anchor</a>
+    <h1 ref="titleRef">{{ title }}</h1>
+    <div ref="cat-row"></div>
+    </template>
+    <script>
+    export default {
+      data() {
+        return {
+          title: 'Hello world'
+        };
+      },
+      mounted() {
+        if (this.$refs?.titleRef) {
+          console.log(this.$refs.titleRef);
+        }
+
+        console.log('Alternative syntax', this.$refs['cat-row']);
+      }
+    }
+    </script>"##;
+
+    let expected = r##"
+<template>
+  <a href="#">anchor</a>
+  <h1 ref="titleRef">{{ title }}</h1>
+  <div ref="cat-row"></div>
+</template>
+<script setup>
+import { onMounted, ref, useTemplateRef } from 'vue';
+
+const titleRef = useTemplateRef('titleRef');
+const catRowRef = useTemplateRef('cat-row');
+const title = ref('Hello world');
+
+onMounted(() => {
+  if (titleRef.value) {
+    console.log(titleRef.value);
+  }
+
+  console.log('Alternative syntax', catRowRef.value);
+});
+</script>"##;
+
+    let result = rewrite_sfc(sfc, None).unwrap();
+
+    assert_eq!(trim_whitespace(&result), trim_whitespace(expected));
+  }
+
+  #[test]
+  fn test_should_declare_template_refs_as_refs_before_vue_3_5() {
+    let sfc = r#"<template>
+  <div>
+    <h1 ref="title">Hello</h1>
+    <div ref="cat-row"></div>
+  </div>
+</template>
+<script>
+export default {
+  mounted() {
+    this.$refs.title.focus();
+    console.log(this.$refs['cat-row']);
+  }
+}
+</script>"#;
+
+    let expected = r#"<template>
+  <div>
+    <h1 ref="titleRef">Hello</h1>
+    <div ref="catRowRef"></div>
+  </div>
+</template>
+<script setup>
+import { onMounted, ref } from 'vue';
+
+const titleRef = ref(null);
+const catRowRef = ref(null);
+
+onMounted(() => {
+  titleRef.value.focus();
+  console.log(catRowRef.value);
+});
+</script>"#;
+
+    let options = RewriteOptions {
+      template_ref_style: Some(TemplateRefStyle::Ref),
+      ..Default::default()
+    };
+    let result = rewrite_sfc(sfc, Some(options)).unwrap();
+    assert_eq!(trim_whitespace(&result), trim_whitespace(expected));
+  }
+
+  #[test]
+  fn test_should_convert_event_bus_usage() {
+    let sfc = r#"
+<template>
+  <button @click="$root.$emit('refresh')">Refresh</button>
+</template>
+<script>
+import EventBus from '@/event-bus';
+import { formatDate } from '@/utils/date';
+
+export default {
+  data() {
+    return {
+      items: [],
+    };
+  },
+  created() {
+    this.$root.$on('refresh', this.load);
+    EventBus.$on('item-added', this.onItemAdded);
+  },
+  beforeDestroy() {
+    this.$root.$off('refresh', this.load);
+    EventBus.$off('item-added', this.onItemAdded);
+  },
+  methods: {
+    load() {
+      this.items = [];
+    },
+    onItemAdded(item) {
+      this.items.push(item);
+      this.$bus.$emit('items-changed', formatDate(new Date()));
+      this.$emit('change', item);
+    },
+  },
+};
+</script>"#;
+
+    let result = rewrite_sfc(sfc, None).unwrap();
+
+    let expected = r#"
+<template>
+<button @click="bus.emit('refresh')">Refresh</button>
+</template>
+<script setup>
+import { onBeforeUnmount, ref } from 'vue';
+import { formatDate } from '@/utils/date';
+import { useEventBus } from '@/composables/useEventBus';
+
+const bus = useEventBus();
+const emit = defineEmits(['change']);
+
+
+const items = ref([]);
+
+const load = () => {
+  items.value = [];
+};
+const onItemAdded = (item) => {
+  items.value.push(item);
+        bus.emit('items-changed', formatDate(new Date()));
+        emit('change', item);
+};
+
+bus.on('refresh', load);
+    bus.on('item-added', onItemAdded);
+
+onBeforeUnmount(() => {
+  bus.off('refresh', load);
+      bus.off('item-added', onItemAdded);
+});
+
+</script>"#;
+
+    assert_eq!(trim_whitespace(&result), trim_whitespace(expected));
+  }
+
+  #[test]
+  fn test_should_use_configured_event_bus_composable() {
+    let sfc = r#"<template><div /></template>
+<script>
+export default {
+  mounted() {
+    this.$events.$on('saved', this.reload);
+  },
+  methods: {
+    reload() {},
+  },
+};
+</script>"#;
+
+    let options = RewriteOptions {
+      event_bus: Some(EventBusConfig {
+        import_path: "@/composables/useEmitter".to_string(),
+        composable: "useEmitter".to_string(),
+        variable: "emitter".to_string(),
+        globals: vec!["$events".to_string()],
+      }),
+      ..Default::default()
+    };
+
+    let result = rewrite_sfc(sfc, Some(options)).unwrap();
+
+    let expected = r#"<template>
+<div />
+</template>
+<script setup>
+import { onMounted } from 'vue';
+import { useEmitter } from '@/composables/useEmitter';
+
+const emitter = useEmitter();
+
+const reload = () => {
+};
+
+onMounted(() => {
+  emitter.on('saved', reload);
+});
+</script>"#;
+
+    assert_eq!(trim_whitespace(&result), trim_whitespace(expected));
+  }
+
+  #[test]
+  fn test_should_convert_vue_global_api() {
+    let sfc = r#"
+<template><div>{{ store.count }}</div></template>
+<script>
+import Vue from 'vue';
+import { formatDate } from '@/utils/date';
+
+const store = Vue.observable({ count: 0 });
+
+export default {
+  data() {
+    return { filters: {}, items: [] };
+  },
+  methods: {
+    update(item) {
+      Vue.set(this.filters, 'active', true);
+      Vue.set(item, 'label', formatDate(item.date));
+      Vue.delete(this.filters, 'old-key');
+      Vue.nextTick(() => { this.items = []; });
+    },
+  },
+};
+</script>"#;
+
+    let result = rewrite_sfc(sfc, None).unwrap();
+
+    let expected = r#"
+<template>
+<div>{{ store.count }}</div>
+</template>
+<script setup>
+import { nextTick, reactive, ref } from 'vue';
+import { formatDate } from '@/utils/date';
+
+const store = reactive({ count: 0 });
+
+
+const filters = ref({});
+const items = ref([]);
+
+const update = (item) => {
+  filters.value.active = true;
+        item.label = formatDate(item.date);
+        delete filters.value['old-key'];
+        nextTick(() => { items.value = []; });
+};
+</script>"#;
+
+    assert_eq!(trim_whitespace(&result), trim_whitespace(expected));
+  }
+
+  #[test]
+  fn test_should_declare_observable_data_as_reactive() {
+    let sfc = r#"<template><div>{{ state.count }}</div></template>
+<script>
+import Vue from 'vue';
+
+export default {
+  data() {
+    return {
+      state: Vue.observable({ count: 0 }),
+      label: '',
+    };
+  },
+  watch: {
+    'state.count'(value) {
+      this.label = `${value}`;
+    },
+  },
+  methods: {
+    reset() {
+      Vue.set(this.state, 'count', 0);
+    },
+  },
+};
+</script>"#;
+
+    let expected = r#"<template>
+<div>{{ state.count }}</div>
+</template>
+<script setup>
+import { reactive, ref, watch } from 'vue';
+
+const label = ref('');
+const state = reactive({ count: 0 });
+
+watch(() => state.count, (value, oldVal) => {
+  label.value = `${value}`;
+});
+
+const reset = () => {
+  state.count = 0;
+};
+</script>"#;
+
+    let result = rewrite_sfc(sfc, None).unwrap();
+
+    assert_eq!(trim_whitespace(&result), trim_whitespace(expected));
+  }
+
+  #[test]
+  fn test_should_keep_vue_import_when_still_used() {
+    let sfc = r#"<template><div /></template>
+<script>
+import Vue from 'vue';
+
+const Child = Vue.extend({});
+
+export default {
+  methods: {
+    reset(item) {
+      Vue.delete(item, 'draft');
+    },
+  },
+};
+</script>"#;
+
+    let result = rewrite_sfc(sfc, None).unwrap();
+
+    assert!(result.contains("import Vue from 'vue';"));
+    assert!(result.contains("delete item.draft;"));
+  }
+
+  #[test]
+  fn test_should_convert_date_plugin_to_direct_import() {
+    let sfc = r#"<template>
+  <div :title="createdAt | moment('LLL')">
+    <p>{{ createdAt | moment('LL') }}</p>
+    <p>{{ $moment(updatedAt).fromNow() }}</p>
+  </div>
+</template>
+<script>
+export default {
+  props: {
+    createdAt: String,
+    updatedAt: String,
+  },
+  computed: {
+    age() {
+      return this.$moment().diff(this.$moment(this.createdAt), 'days');
+    },
+  },
+};
+</script>"#;
+
+    let expected = r#"
+<template>
+<div :title="dayjs(createdAt).format('LLL')">
+<p>{{ dayjs(createdAt).format('LL') }}</p>
+<p>{{ dayjs(updatedAt).fromNow() }}</p>
+</div>
+</template>
+<script setup>
+import { computed } from 'vue';
+import dayjs from 'dayjs';
+
+const props = defineProps({
+  createdAt: {
+    type: String,
+  },
+  updatedAt: {
+    type: String,
+  },
+});
+
+const age = computed(() => {
+  return dayjs().diff(dayjs(props.createdAt), 'days');
+});
+</script>"#;
+
+    let options = RewriteOptions {
+      date_plugin: Some(DatePluginConfig::default()),
+      ..Default::default()
+    };
+
+    let result = rewrite_sfc(sfc, Some(options)).unwrap();
+    assert_eq!(trim_whitespace(&result), trim_whitespace(expected));
+  }
+
+  #[test]
+  fn test_should_convert_date_plugin_to_composable() {
+    let sfc = r#"<template>
+  <p>{{ createdAt | moment('add', '1 day') }}</p>
+</template>
+<script>
+export default {
+  props: ['createdAt'],
+  methods: {
+    format(value) {
+      return this.$moment(value).format('LL');
+    },
+  },
+};
+</script>"#;
+
+    let options = RewriteOptions {
+      date_plugin: Some(DatePluginConfig {
+        import_path: "@/composables/useDate".to_string(),
+        name: "date".to_string(),
+        composable: Some("useDate".to_string()),
+        ..Default::default()
+      }),
+      ..Default::default()
+    };
+
+    let report = rewrite_sfc_with_report(sfc, Some(options)).unwrap();
+    assert!(report
+      .code
+      .contains("import { useDate } from '@/composables/useDate';"));
+    assert!(report.code.contains("const date = useDate();"));
+    assert!(report.code.contains("return date(value).format('LL');"));
+
+    // Filters with non-format arguments are left for the author
+    assert!(report
+      .code
+      .contains("{{ createdAt | moment('add', '1 day') }}"));
+    assert_eq!(report.diagnostics.len(), 1);
+    assert_eq!(report.diagnostics[0].code, "date-filter");
+  }
+
+  #[test]
+  fn test_should_convert_deprecated_slot_syntax() {
+    let sfc = r#"<template>
+  <div>
+    <b-table :items="items">
+      <template slot="cell(name)" slot-scope="data">
+        <strong>{{ data.value }}</strong>
+      </template>
+      <template slot-scope="{ item }">{{ item.id }}</template>
+      <template :slot="slotName">Dynamic</template>
+    </b-table>
+    <my-card>
+      <span slot="title">Title</span>
+      <my-icon slot="icon" slot-scope="icon" :name="icon.name" />
+    </my-card>
+  </div>
+</template>
+<script>
+export default {
+  data() {
+    return { items: [], slotName: 'footer' };
+  },
+};
+</script>"#;
+
+    let expected = r#"<template>
+  <div>
+    <b-table :items="items">
+      <template #cell(name)="data">
+        <strong>{{ data.value }}</strong>
+      </template>
+      <template #default="{ item }">{{ item.id }}</template>
+      <template #[slotName]>Dynamic</template>
+    </b-table>
+    <my-card>
+      <template #title><span>Title</span></template>
+      <template #icon="icon"><my-icon :name="icon.name" /></template>
+    </my-card>
+  </div>
+</template>
+<script setup>
+import { ref } from 'vue';
+
+const items = ref([]);
+const slotName = ref('footer');
+</script>"#;
+
+    let result = rewrite_sfc(sfc, None).unwrap();
+    assert_eq!(trim_whitespace(&result), trim_whitespace(expected));
+  }
+
+  #[test]
+  fn test_should_normalize_v_for_with_v_if_and_template_keys() {
+    let sfc = r#"<template>
+  <ul>
+    <li v-for="item in items" v-if="item.active" :key="item.id" class="row">{{ item.name }}</li>
+    <template v-for="group in groups">
+      <h3 :key="group.id">{{ group.title }}</h3>
+      <p :key="group.id + '-body'">{{ group.body }}</p>
+    </template>
+    <li v-for="n in 10" :key="n">{{ n }}</li>
+  </ul>
+</template>
+<script>
+export default {
+  data() {
+    return { items: [], groups: [] };
+  },
+};
+</script>"#;
+
+    let expected = r#"<template>
+  <ul>
+    <template v-for="item in items" :key="item.id"><li v-if="item.active" class="row">{{ item.name }}</li></template>
+    <template v-for="group in groups" :key="group.id">
+      <h3>{{ group.title }}</h3>
+      <p>{{ group.body }}</p>
+    </template>
+    <li v-for="n in 10" :key="n">{{ n }}</li>
+  </ul>
+</template>
+<script setup>
+import { ref } from 'vue';
+
+const groups = ref([]);
+const items = ref([]);
+</script>"#;
+
+    let report = rewrite_sfc_with_report(sfc, None).unwrap();
+    assert_eq!(trim_whitespace(&report.code), trim_whitespace(expected));
+    assert!(report
+      .diagnostics
+      .iter()
+      .any(|diagnostic| diagnostic.code == "v-if-with-v-for"));
+  }
+
+  #[test]
+  fn test_should_rename_transition_classes_when_enabled() {
+    let sfc = r#"<template>
+  <div>
+    <transition name="fade" enter-class="hidden">
+      <p v-if="show">Hi</p>
+    </transition>
+    <transition-group name="list">
+      <li v-for="item in items" :key="item">{{ item }}</li>
+    </transition-group>
+  </div>
+</template>
+<script>
+export default {
+  data() {
+    return { show: true, items: [] };
+  },
+};
+</script>
+<style scoped>
+.fade-enter-active, .fade-leave-active { transition: opacity .5s; }
+.fade-enter, .fade-leave-to { opacity: 0; }
+.list-enter,.list-leave-to { opacity: 0; }
+.btn-enter { color: red; }
+</style>"#;
+
+    let expected = r#"<template>
+  <div>
+    <transition name="fade" enter-from-class="hidden">
+      <p v-if="show">Hi</p>
+    </transition>
+    <transition-group name="list">
+      <li v-for="item in items" :key="item">{{ item }}</li>
+    </transition-group>
+  </div>
+</template>
+<script setup>
+import { ref } from 'vue';
+
+const items = ref([]);
+const show = ref(true);
+</script>
+<style scoped>
+.fade-enter-active, .fade-leave-active { transition: opacity .5s; }
+.fade-enter-from, .fade-leave-to { opacity: 0; }
+.list-enter-from,.list-leave-to { opacity: 0; }
+.btn-enter { color: red; }
+</style>"#;
+
+    let options = RewriteOptions {
+      enable_transition_transforms: true,
+      ..Default::default()
+    };
+    let report = rewrite_sfc_with_report(sfc, Some(options)).unwrap();
+    assert_eq!(trim_whitespace(&report.code), trim_whitespace(expected));
+    assert!(report
+      .diagnostics
+      .iter()
+      .any(|diagnostic| diagnostic.code == "transition-group-tag"));
+
+    // Disabled by default
+    let result = rewrite_sfc(sfc, None).unwrap();
+    assert!(result.contains(".fade-enter, .fade-leave-to"));
+  }
+
+  #[test]
+  fn test_should_rewrite_deep_selectors() {
+    let sfc = r#"<template>
+  <div class="card"><slot /></div>
+</template>
+<script>
+export default {};
+</script>
+<style lang="scss" scoped>
+.card /deep/ .title { color: red; }
+.card >>> .body p, .card ::v-deep .footer { color: blue; }
+.card ::v-deep(.icon) { margin: 0; }
+::v-slotted(.item) { padding: 0; }
+.list {
+  ::v-deep {
+    .row { color: pink; }
+  }
+}
+</style>"#;
+
+    let expected_style = r#"<style lang="scss" scoped>
+.card :deep(.title) { color: red; }
+.card :deep(.body p), .card :deep(.footer) { color: blue; }
+.card :deep(.icon) { margin: 0; }
+:slotted(.item) { padding: 0; }
+.list {
+  ::v-deep {
+    .row { color: pink; }
+  }
+}
+</style>"#;
+
+    let report = rewrite_sfc_with_report(sfc, None).unwrap();
+    assert!(report.code.ends_with(expected_style));
+    assert!(report
+      .diagnostics
+      .iter()
+      .any(|diagnostic| diagnostic.code == "deep-selector"));
+
+    let options = RewriteOptions {
+      enable_style_transforms: Some(false),
+      ..Default::default()
+    };
+    let result = rewrite_sfc(sfc, Some(options)).unwrap();
+    assert!(result.contains(".card /deep/ .title"));
+  }
+
+  #[test]
+  fn test_should_convert_css_module_access() {
+    let sfc = r#"<template>
+  <button :class="[$style.button, activeClass]" @click="toggle">Go</button>
+</template>
+<script>
+export default {
+  data() {
+    return { active: false };
+  },
+  computed: {
+    activeClass() {
+      return this.active ? this.$style.active : '';
+    },
+  },
+  methods: {
+    toggle() {
+      this.active = !this.active;
+    },
+  },
+};
+</script>
+<style module>
+.button { color: red; }
+.active { color: blue; }
+</style>"#;
+
+    let expected = r#"<template>
+  <button :class="[$style.button, activeClass]" @click="toggle">Go</button>
+</template>
+<script setup>
+import { computed, ref, useCssModule } from 'vue';
+
+const style = useCssModule();
+
+const active = ref(false);
+
+const activeClass = computed(() => {
+  return active.value ? style.active : '';
+});
+
+const toggle = () => {
+  active.value = !active.value;
+};
+</script>
+<style module>
+.button { color: red; }
+.active { color: blue; }
+</style>"#;
+
+    let result = rewrite_sfc(sfc, None).unwrap();
+    assert_eq!(trim_whitespace(&result), trim_whitespace(expected));
+
+    let named = sfc
+      .replace("<style module>", r#"<style module="classes">"#)
+      .replace("this.$style", "this.classes");
+    let result = rewrite_sfc(&named, None).unwrap();
+    assert!(result.contains("const classes = useCssModule('classes');"));
+    assert!(result.contains("active.value ? classes.active : ''"));
+  }
+
+  #[test]
+  fn test_should_target_the_vue_2_7_runtime() {
+    let sfc = r#"<template>
+  <div>
+    <nuxt-link to="/">Home</nuxt-link>
+    <input ref="search-input" :value="value" @input="onInput" />
+    <li v-for="item in items" v-if="item.visible" :key="item.id">{{ item.label }}</li>
+  </div>
+</template>
+
+<script>
+export default {
+  inheritAttrs: false,
+  props: ['value'],
+  data() {
+    return { items: [], meta: {} };
+  },
+  methods: {
+    onInput(event) {
+      this.$emit('input', event.target.value);
+      this.$set(this.meta, 'query', event.target.value);
+    },
+    focus() {
+      this.$refs['search-input'].focus();
+      this.$router.push('/search');
+    },
+  },
+};
+</script>"#;
+
+    let expected = r#"<template>
+  <div>
+    <nuxt-link to="/">Home</nuxt-link>
+    <input ref="searchInputRef" :value="value" @input="onInput" />
+    <li v-for="item in items" v-if="item.visible" :key="item.id">{{ item.label }}</li>
+  </div>
+</template>
+<script setup>
+import { ref, set } from 'vue';
+import { useRouter } from 'vue-router/composables';
+
+const router = useRouter();
+
+const props = defineProps(['value']);
+
+const emit = defineEmits(['input']);
+
+const searchInputRef = ref(null);
+const items = ref([]);
+const meta = ref({});
+
+const onInput = (event) => {
+  emit('input', event.target.value);
+  set(meta.value, 'query', event.target.value);
+};
+const focus = () => {
+  searchInputRef.value.focus();
+  router.push('/search');
+};
+</script>
+<script>
+export default { inheritAttrs: false };
+</script>"#;
+
+    let options = RewriteOptions {
+      target: Some(Target::Vue27),
+      ..Default::default()
+    };
+    let result = rewrite_sfc(sfc, Some(options)).unwrap();
+    assert_eq!(trim_whitespace(&result), trim_whitespace(expected));
+  }
 }
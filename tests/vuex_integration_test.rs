@@ -1,4 +1,4 @@
-use vue_options_to_composition::rewrite_sfc;
+use vue_options_to_composition::{rewrite_sfc, rewrite_sfc_with_report};
 
 fn trim_whitespace(s: &str) -> String {
   s.lines()
@@ -257,4 +257,387 @@ const fetchData = async () => {
 
     assert_eq!(trim_whitespace(&result), trim_whitespace(expected));
   }
+
+  #[test]
+  fn test_should_handle_function_map_state() {
+    let sfc = r#"<template><p>{{ total }} {{ label }}</p></template>
+    <script>
+    import { mapState } from 'vuex';
+
+    export default {
+      data() {
+        return {
+          prefix: 'Items'
+        };
+      },
+      computed: {
+        ...mapState({
+          total: state => state.cart.total,
+          label(state) {
+            return `${this.prefix}: ${state.user.name}`;
+          }
+        }),
+        ...mapState('products', {
+          featured: (state) => state.list.filter((product) => product.featured)
+        })
+      },
+      methods: {
+        log() {
+          console.log(this.total, this.featured);
+        }
+      }
+    }
+    </script>"#;
+
+    let expected = r#"
+<template>
+  <p>{{ total }} {{ label }}</p>
+</template>
+<script setup>
+import { computed, ref } from 'vue';
+import { useCartStore } from '@/stores/cart';
+import { useProductsStore } from '@/stores/products';
+import { useUserStore } from '@/stores/user';
+
+const cartStore = useCartStore();
+const productsStore = useProductsStore();
+const userStore = useUserStore();
+
+const prefix = ref('Items');
+
+const total = computed(() => cartStore.total);
+const label = computed(() => {
+  return `${prefix.value}: ${userStore.name}`;
+});
+const featured = computed(() => productsStore.list.filter((product) => product.featured));
+
+const log = () => {
+  console.log(total.value, featured.value);
+};
+</script>"#;
+
+    let result = rewrite_sfc(sfc, None).unwrap();
+
+    assert_eq!(trim_whitespace(&result), trim_whitespace(expected));
+  }
+
+  #[test]
+  fn test_should_handle_getter_factories() {
+    let sfc = r#"<template>
+      <p>{{ $store.getters['products/byId'](id).name }} {{ $store.getters['cart/count'] }}</p>
+    </template>
+    <script>
+    import { mapGetters } from 'vuex';
+
+    export default {
+      props: ['id'],
+      computed: {
+        ...mapGetters({ productById: 'products/byId' }),
+        price() {
+          return this.$store.getters["products/byId"](this.id).price;
+        }
+      },
+      methods: {
+        log() {
+          console.log(this.productById(this.id), this.$store.getters['cart/count']);
+        }
+      }
+    }
+    </script>"#;
+
+    let expected = r#"
+<template>
+  <p>{{ productsStore.byId(id).name }} {{ cartStore.count }}</p>
+</template>
+<script setup>
+import { computed } from 'vue';
+import { useCartStore } from '@/stores/cart';
+import { useProductsStore } from '@/stores/products';
+
+const cartStore = useCartStore();
+const productsStore = useProductsStore();
+
+const props = defineProps(['id']);
+
+const price = computed(() => {
+  return productsStore.byId(props.id).price;
+});
+
+const log = () => {
+  console.log(productsStore.byId(props.id), cartStore.count);
+};
+</script>"#;
+
+    let result = rewrite_sfc(sfc, None).unwrap();
+
+    assert_eq!(trim_whitespace(&result), trim_whitespace(expected));
+  }
+
+  #[test]
+  fn test_should_keep_template_arguments_of_namespaced_getter_factories() {
+    let sfc = r#"<template>
+      <p>{{ byId(3).name }} {{ total }}</p>
+    </template>
+    <script>
+    import { mapGetters } from 'vuex';
+
+    export default {
+      computed: {
+        ...mapGetters('products', ['byId', 'total']),
+      },
+    }
+    </script>"#;
+
+    let expected = r#"
+<template>
+  <p>{{ byId(3).name }} {{ total }}</p>
+</template>
+<script setup>
+import { computed } from 'vue';
+import { useProductsStore } from '@/stores/products';
+
+const productsStore = useProductsStore();
+
+const byId = computed(() => productsStore.byId);
+const total = computed(() => productsStore.total());
+</script>"#;
+
+    let result = rewrite_sfc(sfc, None).unwrap();
+
+    assert_eq!(trim_whitespace(&result), trim_whitespace(expected));
+  }
+  #[test]
+  fn test_should_report_vuex_alias_collisions() {
+    let sfc = r#"<template>
+      <p>{{ count }} {{ total }}</p>
+    </template>
+    <script>
+    import { mapActions, mapGetters, mapState } from 'vuex';
+
+    export default {
+      computed: {
+        ...mapGetters('cart', ['count']),
+        ...mapGetters('user', ['count']),
+        ...mapGetters({ total: 'cart/total' }),
+        ...mapState('user', { total: 'total' })
+      },
+      methods: {
+        ...mapActions('cart', ['reset']),
+        ...mapActions('user', ['reset']),
+        ...mapActions({ clear: 'cart/clear', wipe: 'user/clear' }),
+        go() {
+          this.reset();
+          this.clear();
+          this.wipe();
+        }
+      }
+    }
+    </script>"#;
+
+    let expected = r#"
+<template>
+  <p>{{ count }} {{ total }}</p>
+</template>
+<script setup>
+import { computed } from 'vue';
+import { useCartStore } from '@/stores/cart';
+import { useUserStore } from '@/stores/user';
+
+const cartStore = useCartStore();
+const userStore = useUserStore();
+
+const count = computed(() => userStore.count());
+const total = computed(() => cartStore.total);
+
+const go = () => {
+  userStore.reset();
+  cartStore.clear();
+  userStore.clear();
+};
+</script>"#;
+
+    let report = rewrite_sfc_with_report(sfc, None).unwrap();
+
+    assert_eq!(trim_whitespace(&report.code), trim_whitespace(expected));
+    let messages: Vec<&str> = report
+      .diagnostics
+      .iter()
+      .filter(|d| d.code == "vuex-alias-collision")
+      .map(|d| d.message.as_str())
+      .collect();
+    assert_eq!(
+      messages,
+      vec![
+        "`count` is mapped by mapGetters from `cart/count`, `user/count`, only the last mapping (`user/count`) is kept as in Vue 2. Rename the alias to keep the others",
+        "`reset` is mapped by mapActions from `cart/reset`, `user/reset`, only the last mapping (`user/reset`) is kept as in Vue 2. Rename the alias to keep the others",
+        "`total` is mapped by more than one of mapState and mapGetters, only the first computed property was generated",
+      ]
+    );
+  }
+
+  #[test]
+  fn test_should_ignore_store_aliases_shadowed_by_loop_variables() {
+    let sfc = r#"<template>
+    <ul>
+      <li v-for="user in users" :key="user.id">{{ user.name }}</li>
+    </ul>
+    </template>
+    <script>
+    import { mapState } from 'vuex';
+    export default {
+      computed: {
+        ...mapState('account', ['user', 'users'])
+      }
+    }
+    </script>"#;
+
+    let expected = r#"
+<template>
+  <ul>
+    <li v-for="user in users" :key="user.id">{{ user.name }}</li>
+  </ul>
+</template>
+<script setup>
+import { computed } from 'vue';
+import { useAccountStore } from '@/stores/account';
+
+const accountStore = useAccountStore();
+
+const users = computed(() => accountStore.users);
+</script>"#;
+
+    let result = rewrite_sfc(sfc, None).unwrap();
+
+    assert_eq!(trim_whitespace(&result), trim_whitespace(expected));
+  }
+
+  #[test]
+  fn test_should_point_template_handlers_to_mapped_store_methods() {
+    let sfc = r#"<template>
+    <div>
+      <button @click="save">Save</button>
+      <button @click="remove(product)">Remove</button>
+    </div>
+    </template>
+    <script>
+    import { mapActions, mapMutations } from 'vuex';
+    export default {
+      props: ['product'],
+      methods: {
+        ...mapActions('cart', ['save']),
+        ...mapMutations('cart', { remove: 'REMOVE_ITEM' }),
+        later() {
+          setTimeout(this.save, 100);
+        },
+      }
+    }
+    </script>"#;
+
+    let expected = r#"
+<template>
+  <div>
+    <button @click="cartStore.save">Save</button>
+    <button @click="cartStore.REMOVE_ITEM(product)">Remove</button>
+  </div>
+</template>
+<script setup>
+import { useCartStore } from '@/stores/cart';
+
+const cartStore = useCartStore();
+
+const props = defineProps(['product']);
+
+const later = () => {
+  setTimeout(cartStore.save, 100);
+};
+</script>"#;
+
+    let result = rewrite_sfc(sfc, None).unwrap();
+
+    assert_eq!(trim_whitespace(&result), trim_whitespace(expected));
+  }
+
+  #[test]
+  fn test_should_rewrite_store_aliases_in_template_expressions_only_where_they_are_read() {
+    let sfc = r#"<template>
+  <div :title="$store.state.cart.total + ' $store.state.cart.total'" :data-count="$store.state.cart.totalCount">
+    <editor :handlers="{ save, reset: save }" :label="`${$store.state.cart.total}: $store.state.cart.total`" />
+  </div>
+</template>
+<script>
+import { mapActions } from 'vuex';
+export default {
+  methods: {
+    ...mapActions('cart', ['save']),
+  },
+}
+</script>"#;
+
+    let expected = r#"<template>
+  <div :title="cartStore.total + ' $store.state.cart.total'" :data-count="cartStore.totalCount">
+    <editor :handlers="{ save: cartStore.save, reset: cartStore.save }" :label="`${cartStore.total}: $store.state.cart.total`" />
+  </div>
+</template>
+<script setup>
+import { useCartStore } from '@/stores/cart';
+
+const cartStore = useCartStore();
+</script>"#;
+
+    let result = rewrite_sfc(sfc, None).unwrap();
+
+    assert_eq!(trim_whitespace(&result), trim_whitespace(expected));
+  }
+
+  #[test]
+  fn test_should_generate_mapped_computed_properties_for_watchers() {
+    let sfc = r#"<template>
+<h1>Cart</h1>
+</template>
+<script>
+import { mapState, mapGetters } from 'vuex';
+
+export default {
+  computed: {
+    ...mapState('cart', ['items']),
+    ...mapGetters({ loggedIn: 'user/isLoggedIn' })
+  },
+  watch: {
+    'items.length'(count) {
+      console.log(count);
+    },
+    loggedIn(value) {
+      console.log(value);
+    }
+  }
+}
+</script>"#;
+
+    let expected = r#"
+<template>
+  <h1>Cart</h1>
+</template>
+<script setup>
+import { computed, watch } from 'vue';
+import { useCartStore } from '@/stores/cart';
+import { useUserStore } from '@/stores/user';
+
+const cartStore = useCartStore();
+const userStore = useUserStore();
+
+const loggedIn = computed(() => userStore.isLoggedIn);
+const items = computed(() => cartStore.items);
+
+watch(() => items.value.length, (count, oldVal) => {
+  console.log(count);
+});
+watch(loggedIn, (value, oldVal) => {
+  console.log(value);
+});
+</script>"#;
+
+    let result = rewrite_sfc(sfc, None).unwrap();
+
+    assert_eq!(trim_whitespace(&result), trim_whitespace(expected));
+  }
 }